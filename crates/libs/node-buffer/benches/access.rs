@@ -0,0 +1,92 @@
+//! The accessors on a buffer no other handle shares, which work in place, and on one a clone
+//! shares, which go through word sized atomics, against a plain slice.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use node_buffer::Buffer;
+
+const LENGTH: usize = 4096;
+
+// A buffer, plus a clone keeping its memory shared for the "shared" cases.
+fn buffers() -> [(&'static str, Buffer, Option<Buffer>); 2] {
+    let unique = Buffer::alloc_unsafe_slow(LENGTH);
+    let shared = Buffer::alloc_unsafe_slow(LENGTH);
+    let clone = shared.clone();
+    [("unique", unique, None), ("shared", shared, Some(clone))]
+}
+
+fn read(c: &mut Criterion) {
+    let bytes = vec![0_u8; LENGTH];
+    let mut group = c.benchmark_group("read");
+    for (name, buffer, _clone) in buffers() {
+        group.bench_with_input(BenchmarkId::new("read_uint8", name), &buffer, |b, buffer| {
+            b.iter(|| (0..LENGTH).fold(0_u32, |sum, i| sum + buffer.read_uint8(Some(i)).unwrap() as u32))
+        });
+        group.bench_with_input(BenchmarkId::new("read_uint32le", name), &buffer, |b, buffer| {
+            b.iter(|| (0..LENGTH).step_by(4).fold(0_u32, |sum, i| sum.wrapping_add(buffer.read_uint32le(Some(i)).unwrap())))
+        });
+        group.bench_with_input(BenchmarkId::new("with_slice", name), &buffer, |b, buffer| {
+            b.iter(|| buffer.with_slice(|bytes| bytes.iter().fold(0_u32, |sum, &byte| sum + byte as u32)))
+        });
+    }
+    group.bench_function("slice", |b| {
+        b.iter(|| black_box(&bytes).iter().fold(0_u32, |sum, &byte| sum + byte as u32))
+    });
+    group.finish();
+}
+
+fn write(c: &mut Criterion) {
+    let mut bytes = vec![0_u8; LENGTH];
+    let mut group = c.benchmark_group("write");
+    for (name, mut buffer, _clone) in buffers() {
+        group.bench_function(BenchmarkId::new("write_uint8", name), |b| {
+            b.iter(|| {
+                for i in 0..LENGTH {
+                    buffer.write_uint8(i as u8, Some(i)).unwrap();
+                }
+            })
+        });
+        group.bench_function(BenchmarkId::new("with_slice_mut", name), |b| {
+            b.iter(|| {
+                buffer.with_slice_mut(|bytes| {
+                    for (i, byte) in bytes.iter_mut().enumerate() {
+                        *byte = i as u8;
+                    }
+                })
+            })
+        });
+    }
+    group.bench_function("slice", |b| {
+        b.iter(|| {
+            for (i, byte) in black_box(&mut bytes).iter_mut().enumerate() {
+                *byte = i as u8;
+            }
+        })
+    });
+    group.finish();
+}
+
+fn copy(c: &mut Criterion) {
+    let source = Buffer::alloc_unsafe_slow(LENGTH);
+    let mut target = Buffer::alloc_unsafe_slow(LENGTH);
+    let mut group = c.benchmark_group("copy");
+    group.bench_function("unique", |b| {
+        b.iter(|| source.copy(&mut target, None, None, None).unwrap())
+    });
+    let mut view = target.subarray(Some(1), None);
+    group.bench_function("shared", |b| {
+        b.iter(|| source.copy(&mut view, None, None, None).unwrap())
+    });
+    group.bench_function("same_storage", |b| {
+        b.iter(|| target.copy(&mut view, None, None, None).unwrap())
+    });
+    let (from, mut to) = (vec![0_u8; LENGTH], vec![0_u8; LENGTH]);
+    group.bench_function("slice", |b| {
+        b.iter(|| black_box(&mut to).copy_from_slice(black_box(&from)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, read, write, copy);
+criterion_main!(benches);
@@ -0,0 +1,50 @@
+//! Small allocations sliced off the pool against the unpooled path they replaced.
+
+use std::ffi::CString;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use node_buffer::{Buffer, StringEncoding};
+
+fn alloc_unsafe(c: &mut Criterion) {
+    let mut group = c.benchmark_group("alloc_unsafe");
+    for size in [16, 128, 1024] {
+        group.bench_with_input(BenchmarkId::new("pooled", size), &size, |b, &size| {
+            b.iter(|| Buffer::alloc_unsafe(black_box(size)))
+        });
+        group.bench_with_input(BenchmarkId::new("slow", size), &size, |b, &size| {
+            b.iter(|| Buffer::alloc_unsafe_slow(black_box(size)))
+        });
+    }
+    group.finish();
+}
+
+fn from_string(c: &mut Criterion) {
+    let value = CString::new("content-length: 1024").unwrap();
+    let mut group = c.benchmark_group("from_string");
+    group.bench_function("pooled", |b| {
+        b.iter(|| Buffer::from_str(black_box(&value), StringEncoding::Utf8))
+    });
+    group.bench_function("unpooled", |b| {
+        b.iter(|| Buffer::from_vec(black_box(value.as_bytes()).to_vec()))
+    });
+    group.finish();
+}
+
+fn concat(c: &mut Criterion) {
+    let parts = [&b"GET / HTTP/1.1\r\n"[..], b"host: localhost\r\n", b"\r\n"];
+    let mut group = c.benchmark_group("concat");
+    group.bench_function("pooled", |b| b.iter(|| Buffer::concat(black_box(&parts), None)));
+    group.bench_function("unpooled", |b| b.iter(|| Buffer::from_vec(black_box(&parts).concat())));
+    group.finish();
+}
+
+// Pooling off falls back to a fresh allocation per buffer, the same as allocUnsafeSlow.
+fn pool_disabled(c: &mut Criterion) {
+    node_buffer::set_pool_size(0);
+    c.bench_function("alloc_unsafe/pool_disabled/128", |b| b.iter(|| Buffer::alloc_unsafe(black_box(128))));
+    node_buffer::set_pool_size(node_buffer::DEFAULT_POOL_SIZE);
+}
+
+criterion_group!(benches, alloc_unsafe, from_string, concat, pool_disabled);
+criterion_main!(benches);
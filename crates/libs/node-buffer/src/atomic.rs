@@ -0,0 +1,168 @@
+//! Copies in and out of buffer memory that clones, views or other threads may be reading and
+//! writing at the same time. Racing accesses are unordered like on a `SharedArrayBuffer` but
+//! never a data race, and there is no lock.
+//!
+//! Memory is moved a word at a time with relaxed `AtomicUsize` operations, which compile to
+//! plain word moves. Every byte of a `Region` is always reached with the same access size,
+//! whichever handle or offset the access starts from: bytes of an aligned word that lies
+//! entirely inside the region go through that word, the few bytes at its unaligned edges one
+//! at a time. Storing part of a word merges the bytes in with a compare and swap.
+
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+
+const WORD: usize = std::mem::size_of::<usize>();
+
+/// How many bytes `copy` moves per step.
+const CHUNK: usize = 4096;
+
+/// The whole memory of one storage, `start..end`.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Region {
+    start: usize,
+    end: usize,
+}
+
+impl Region {
+    pub(crate) fn new(data: *const u8, length: usize) -> Self {
+        let start = data as usize;
+        Self { start, end: start + length }
+    }
+
+    // The aligned word holding `address` when it lies entirely inside the region.
+    #[inline(always)]
+    fn word(&self, address: usize) -> Option<usize> {
+        let word = address & !(WORD - 1);
+        (word >= self.start && word + WORD <= self.end).then_some(word)
+    }
+}
+
+#[inline(always)]
+unsafe fn byte<'a>(address: usize) -> &'a AtomicU8 {
+    unsafe { AtomicU8::from_ptr(address as *mut u8) }
+}
+
+#[inline(always)]
+unsafe fn word<'a>(address: usize) -> &'a AtomicUsize {
+    unsafe { AtomicUsize::from_ptr(address as *mut usize) }
+}
+
+/// # Safety
+///
+/// `source..source + target.len()` must lie within `region`, which must be valid for reads and
+/// only accessed through this module while it is shared.
+#[inline]
+pub(crate) unsafe fn load(region: Region, source: *const u8, target: &mut [u8]) {
+    let mut address = source as usize;
+    let end = address + target.len();
+    // the fixed width accessors, a single word
+    if let Some(aligned) = region.word(address).filter(|aligned| end <= aligned + WORD) {
+        let value = unsafe { word(aligned) }.load(Ordering::Relaxed).to_ne_bytes();
+        let from = address - aligned;
+        for (i, byte) in target.iter_mut().enumerate() {
+            *byte = value[from + i];
+        }
+        return;
+    }
+    let mut i = 0;
+    while address < end {
+        // whole words inside the range, the bulk of any long copy
+        if address.is_multiple_of(WORD) {
+            while address + WORD <= end {
+                let value = unsafe { word(address) }.load(Ordering::Relaxed);
+                unsafe { (target.as_mut_ptr().add(i) as *mut usize).write_unaligned(value) };
+                i += WORD;
+                address += WORD;
+            }
+            if address == end {
+                break;
+            }
+        }
+        match region.word(address) {
+            Some(aligned) => {
+                let value = unsafe { word(aligned) }.load(Ordering::Relaxed).to_ne_bytes();
+                let from = address - aligned;
+                let count = (WORD - from).min(end - address);
+                target[i..i + count].copy_from_slice(&value[from..from + count]);
+                i += count;
+                address += count;
+            }
+            None => {
+                target[i] = unsafe { byte(address) }.load(Ordering::Relaxed);
+                i += 1;
+                address += 1;
+            }
+        }
+    }
+}
+
+/// # Safety
+///
+/// `target..target + source.len()` must lie within `region`, which must be valid for writes
+/// and only accessed through this module while it is shared.
+#[inline]
+pub(crate) unsafe fn store(region: Region, target: *mut u8, source: &[u8]) {
+    let mut address = target as usize;
+    let end = address + source.len();
+    let mut i = 0;
+    while address < end {
+        if address.is_multiple_of(WORD) {
+            while address + WORD <= end {
+                let value = unsafe { (source.as_ptr().add(i) as *const usize).read_unaligned() };
+                unsafe { word(address) }.store(value, Ordering::Relaxed);
+                i += WORD;
+                address += WORD;
+            }
+            if address == end {
+                break;
+            }
+        }
+        match region.word(address) {
+            Some(aligned) => {
+                let from = address - aligned;
+                let count = (WORD - from).min(end - address);
+                let bytes = &source[i..i + count];
+                let _ = unsafe { word(aligned) }.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                    let mut value = current.to_ne_bytes();
+                    value[from..from + count].copy_from_slice(bytes);
+                    Some(usize::from_ne_bytes(value))
+                });
+                i += count;
+                address += count;
+            }
+            None => {
+                unsafe { byte(address) }.store(source[i], Ordering::Relaxed);
+                i += 1;
+                address += 1;
+            }
+        }
+    }
+}
+
+/// `memmove` from one shared region to another, or within one, through a bounce buffer.
+///
+/// # Safety
+///
+/// Both ranges must lie within their regions, under the same rules as `load` and `store`.
+pub(crate) unsafe fn copy(source_region: Region, source: *const u8, target_region: Region, target: *mut u8, length: usize) {
+    let mut chunk = [0_u8; CHUNK];
+    let step = |offset: usize, count: usize, chunk: &mut [u8; CHUNK]| unsafe {
+        load(source_region, source.add(offset), &mut chunk[..count]);
+        store(target_region, target.add(offset), &chunk[..count]);
+    };
+    // Chunks are copied away from the overlap, so none is overwritten before it is read.
+    if (target as usize) < (source as usize) {
+        let mut offset = 0;
+        while offset < length {
+            let count = CHUNK.min(length - offset);
+            step(offset, count, &mut chunk);
+            offset += count;
+        }
+    } else {
+        let mut end = length;
+        while end > 0 {
+            let count = CHUNK.min(end);
+            step(end - count, count, &mut chunk);
+            end -= count;
+        }
+    }
+}
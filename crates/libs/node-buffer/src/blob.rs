@@ -0,0 +1,316 @@
+use std::fmt::{Debug, Formatter};
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::Buffer;
+
+/// Chunk size `Blob::stream` uses when none is given, same as Node.
+pub const DEFAULT_STREAM_CHUNK_SIZE: usize = 65536;
+
+/// Bytes a `Blob` reads on demand instead of holding them in memory, e.g. a file.
+pub trait BlobSource: Send + Sync {
+    fn size(&self) -> usize;
+
+    /// Reads into `buf` starting at `offset`, returning how many bytes were read.
+    fn read_at(&self, offset: usize, buf: &mut [u8]) -> node_core::error::Result<usize>;
+}
+
+/// A part passed to `Blob::new`, data is copied so later changes to it don't show in the blob.
+#[derive(Copy, Clone, Debug)]
+pub enum BlobPart<'a> {
+    Bytes(&'a [u8]),
+    Buffer(&'a Buffer),
+    String(&'a str),
+    Blob(&'a Blob),
+}
+
+#[derive(Clone)]
+enum Chunk {
+    Memory(Buffer),
+    Source {
+        source: Arc<dyn BlobSource>,
+        offset: usize,
+        length: usize,
+    },
+}
+
+impl Chunk {
+    fn len(&self) -> usize {
+        match self {
+            Chunk::Memory(buffer) => buffer.length(),
+            Chunk::Source { length, .. } => *length,
+        }
+    }
+
+    fn slice(&self, start: usize, end: usize) -> Chunk {
+        match self {
+            Chunk::Memory(buffer) => Chunk::Memory(buffer.subarray(Some(start as isize), Some(end as isize))),
+            Chunk::Source { source, offset, .. } => Chunk::Source {
+                source: Arc::clone(source),
+                offset: offset + start,
+                length: end - start,
+            }
+        }
+    }
+
+    fn read_at(&self, offset: usize, buf: &mut [u8]) -> node_core::error::Result<usize> {
+        match self {
+            Chunk::Memory(buffer) => Ok(buffer.read_at(offset, buf)),
+            Chunk::Source { source, offset: start, length } => {
+                let wanted = (length - offset).min(buf.len());
+                let mut read = 0;
+                while read < wanted {
+                    match source.read_at(start + offset + read, &mut buf[read..wanted])? {
+                        0 => break,
+                        n => read += n
+                    }
+                }
+                Ok(read)
+            }
+        }
+    }
+}
+
+struct BlobInner {
+    chunks: Vec<Chunk>,
+    size: usize,
+    content_type: String,
+}
+
+/// Node's `buffer.Blob`, immutable and cheap to clone.
+#[derive(Clone)]
+pub struct Blob(Arc<BlobInner>);
+
+impl Debug for Blob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Blob")
+            .field("size", &self.0.size)
+            .field("type", &self.0.content_type)
+            .finish()
+    }
+}
+
+impl Default for Blob {
+    fn default() -> Self {
+        Blob::from_chunks(Vec::new(), "")
+    }
+}
+
+// Node only keeps a type made of printable ASCII, lowercased.
+fn normalize_type(content_type: &str) -> String {
+    if content_type.bytes().all(|c| (0x20..=0x7e).contains(&c)) {
+        content_type.to_ascii_lowercase()
+    } else {
+        String::new()
+    }
+}
+
+impl Blob {
+    /// `new Blob(sources, { type })`
+    pub fn new(parts: &[BlobPart], content_type: &str) -> Self {
+        let mut chunks = Vec::with_capacity(parts.len());
+        for part in parts {
+            match part {
+                BlobPart::Bytes(bytes) => chunks.push(Chunk::Memory(Buffer::from_slice(bytes))),
+                BlobPart::Buffer(buffer) => chunks.push(Chunk::Memory(Buffer::from_buffer(buffer))),
+                BlobPart::String(string) => chunks.push(Chunk::Memory(Buffer::from_slice(string.as_bytes()))),
+                BlobPart::Blob(blob) => chunks.extend(blob.0.chunks.iter().cloned()),
+            }
+        }
+        Blob::from_chunks(chunks, content_type)
+    }
+
+    /// A blob reading every byte of `source` lazily.
+    pub fn from_source(source: Arc<dyn BlobSource>, content_type: &str) -> Self {
+        let length = source.size();
+        Blob::from_chunks(vec![Chunk::Source { source, offset: 0, length }], content_type)
+    }
+
+    fn from_chunks(chunks: Vec<Chunk>, content_type: &str) -> Self {
+        let chunks = chunks.into_iter()
+            .filter(|chunk| chunk.len() > 0)
+            .collect::<Vec<Chunk>>();
+        let size = chunks.iter().map(Chunk::len).sum();
+        Blob(Arc::new(BlobInner {
+            chunks,
+            size,
+            content_type: normalize_type(content_type),
+        }))
+    }
+
+    pub fn size(&self) -> usize {
+        self.0.size
+    }
+
+    pub fn content_type(&self) -> &str {
+        &self.0.content_type
+    }
+
+    /// `blob.slice(start, end, contentType)`, negative indexes count from the end. Shares the
+    /// underlying data.
+    pub fn slice(&self, start: Option<isize>, end: Option<isize>, content_type: Option<&str>) -> Blob {
+        let size = self.0.size as isize;
+        let clamp = |index: isize| {
+            if index < 0 {
+                (size + index).max(0)
+            } else {
+                index.min(size)
+            }
+        };
+        let start = clamp(start.unwrap_or(0)) as usize;
+        let end = (clamp(end.unwrap_or(size)) as usize).max(start);
+
+        let mut chunks = Vec::new();
+        let mut position = 0;
+        for chunk in self.0.chunks.iter() {
+            let chunk_start = position;
+            let chunk_end = position + chunk.len();
+            position = chunk_end;
+            if chunk_end <= start {
+                continue;
+            }
+            if chunk_start >= end {
+                break;
+            }
+            let from = start.max(chunk_start) - chunk_start;
+            let to = end.min(chunk_end) - chunk_start;
+            chunks.push(chunk.slice(from, to));
+        }
+        Blob::from_chunks(chunks, content_type.unwrap_or_default())
+    }
+
+    /// Reads into `buf` starting at `offset`, returning how many bytes were read.
+    pub fn read_at(&self, offset: usize, buf: &mut [u8]) -> node_core::error::Result<usize> {
+        let mut read = 0;
+        let mut position = 0;
+        for chunk in self.0.chunks.iter() {
+            if read == buf.len() {
+                break;
+            }
+            let chunk_end = position + chunk.len();
+            let at = offset + read;
+            if at < chunk_end {
+                let n = chunk.read_at(at - position, &mut buf[read..])?;
+                read += n;
+                if n < chunk_end - at && read < buf.len() {
+                    break;
+                }
+            }
+            position = chunk_end;
+        }
+        Ok(read)
+    }
+
+    pub fn bytes(&self) -> node_core::error::Result<Vec<u8>> {
+        let mut ret = vec![0_u8; self.0.size];
+        let read = self.read_at(0, &mut ret)?;
+        ret.truncate(read);
+        Ok(ret)
+    }
+
+    /// `blob.arrayBuffer()`
+    pub fn array_buffer(&self) -> node_core::error::Result<Buffer> {
+        self.bytes().map(Buffer::from_vec)
+    }
+
+    /// `blob.text()`, decoded as UTF-8 with a leading BOM removed.
+    pub fn text(&self) -> node_core::error::Result<String> {
+        let bytes = self.bytes()?;
+        Ok(encoding_rs::UTF_8.decode_with_bom_removal(&bytes).0.into_owned())
+    }
+
+    /// `blob.stream()`, yields chunks of at most `chunk_size` bytes. Every chunk is a copy,
+    /// writing to it never changes the blob.
+    pub fn stream(&self, chunk_size: Option<usize>) -> BlobStream {
+        BlobStream {
+            blob: self.clone(),
+            chunk_size: chunk_size.filter(|size| *size > 0).unwrap_or(DEFAULT_STREAM_CHUNK_SIZE),
+            index: 0,
+            offset: 0,
+        }
+    }
+}
+
+pub struct BlobStream {
+    blob: Blob,
+    chunk_size: usize,
+    index: usize,
+    offset: usize,
+}
+
+impl Iterator for BlobStream {
+    type Item = node_core::error::Result<Buffer>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.blob.0.chunks.get(self.index)?;
+        let length = (chunk.len() - self.offset).min(self.chunk_size);
+        let start = self.offset;
+
+        self.offset += length;
+        if self.offset == chunk.len() {
+            self.index += 1;
+            self.offset = 0;
+        }
+
+        match chunk.slice(start, start + length) {
+            Chunk::Memory(buffer) => Some(Ok(Buffer::from_buffer(&buffer))),
+            source => {
+                let mut data = vec![0_u8; length];
+                Some(source.read_at(0, &mut data).map(|read| {
+                    data.truncate(read);
+                    Buffer::from_vec(data)
+                }))
+            }
+        }
+    }
+}
+
+/// Node's `buffer.File`, a `Blob` with a name and modification time in ms since the epoch.
+#[derive(Clone, Debug)]
+pub struct File {
+    blob: Blob,
+    name: String,
+    last_modified: i64,
+}
+
+impl File {
+    /// `new File(sources, name, { type, lastModified })`, `last_modified` defaults to now.
+    pub fn new(parts: &[BlobPart], name: &str, content_type: &str, last_modified: Option<i64>) -> Self {
+        File::from_blob(Blob::new(parts, content_type), name, last_modified)
+    }
+
+    pub fn from_blob(blob: Blob, name: &str, last_modified: Option<i64>) -> Self {
+        let last_modified = last_modified.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as i64)
+                .unwrap_or_default()
+        });
+        Self {
+            blob,
+            name: name.to_string(),
+            last_modified,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn last_modified(&self) -> i64 {
+        self.last_modified
+    }
+
+    pub fn blob(&self) -> &Blob {
+        &self.blob
+    }
+}
+
+impl Deref for File {
+    type Target = Blob;
+
+    fn deref(&self) -> &Self::Target {
+        &self.blob
+    }
+}
@@ -0,0 +1,72 @@
+use std::ffi::c_void;
+
+use bytes::{Bytes, BytesMut};
+
+use crate::Buffer;
+
+extern "C" fn drop_bytes_mut(_data: *mut u8, _length: usize, context: *mut c_void) {
+    drop(unsafe { Box::from_raw(context as *mut BytesMut) });
+}
+
+/// Takes over the allocation without copying, it is released when the last view is dropped.
+impl From<BytesMut> for Buffer {
+    fn from(value: BytesMut) -> Self {
+        if value.is_empty() {
+            return Buffer::default();
+        }
+        let length = value.len();
+        let value = Box::into_raw(Box::new(value));
+        unsafe {
+            let data = (*value).as_mut_ptr();
+            Buffer::from_external(data, length, Some(drop_bytes_mut), value as *mut c_void)
+        }
+    }
+}
+
+/// Zero-copy when `value` is the only handle to its memory, otherwise the bytes are copied
+/// since a `Buffer` is writable and `Bytes` may be shared or static.
+impl From<Bytes> for Buffer {
+    fn from(value: Bytes) -> Self {
+        match value.try_into_mut() {
+            Ok(value) => Buffer::from(value),
+            Err(value) => Buffer::from_slice(&value),
+        }
+    }
+}
+
+// The only handle to its memory, moved into a `Bytes` nobody can write through it again.
+struct UniqueBuffer(Buffer);
+
+impl AsRef<[u8]> for UniqueBuffer {
+    fn as_ref(&self) -> &[u8] {
+        // no clone or view exists and `Bytes` never hands the owner back out
+        unsafe { self.0.buffer() }
+    }
+}
+
+/// Zero-copy when `value` is the only handle to its memory, otherwise the bytes are copied
+/// since `Bytes` must never change while clones or views can still write to them.
+impl From<Buffer> for Bytes {
+    fn from(mut value: Buffer) -> Self {
+        if value.length() == 0 {
+            return Bytes::new();
+        }
+        if value.is_unique() {
+            return Bytes::from_owner(UniqueBuffer(value));
+        }
+        value.with_slice(Bytes::copy_from_slice)
+    }
+}
+
+/// Copies, a `BytesMut` needs memory it owns exclusively.
+impl From<Buffer> for BytesMut {
+    fn from(value: Buffer) -> Self {
+        value.with_slice(|bytes| BytesMut::from(bytes))
+    }
+}
+
+impl From<&Buffer> for BytesMut {
+    fn from(value: &Buffer) -> Self {
+        value.with_slice(|bytes| BytesMut::from(bytes))
+    }
+}
@@ -0,0 +1,148 @@
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+
+use crate::Buffer;
+
+/// A `std::io::Cursor` over a `Buffer`, so `Read`/`BufRead`/`Write`/`Seek` consumers can work
+/// on its memory directly. Writes never grow the buffer, like a cursor over `&mut [u8]` they
+/// stop at the end and report a short count.
+///
+/// The cursor owns its `Buffer`, pass a clone or `subarray` to keep using the same memory.
+/// `BufRead` hands out a copy of the next chunk, since clones and views may write to the
+/// memory between `fill_buf` and `consume`.
+#[derive(Clone, Debug, Default)]
+pub struct BufferCursor {
+    buffer: Buffer,
+    position: u64,
+    chunk: Vec<u8>,
+    chunk_position: u64,
+}
+
+/// How much `fill_buf` copies at a time.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+impl BufferCursor {
+    pub fn new(buffer: Buffer) -> Self {
+        Self {
+            buffer,
+            position: 0,
+            chunk: Vec::new(),
+            chunk_position: 0,
+        }
+    }
+
+    pub fn into_inner(self) -> Buffer {
+        self.buffer
+    }
+
+    pub fn get_ref(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    pub fn get_mut(&mut self) -> &mut Buffer {
+        self.chunk.clear();
+        &mut self.buffer
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// May be past the end, reads then return 0 bytes and writes fail to make progress.
+    pub fn set_position(&mut self, position: u64) {
+        self.position = position;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        let length = self.buffer.length();
+        self.start(length) == length
+    }
+
+    fn start(&self, length: usize) -> usize {
+        usize::try_from(self.position).map_or(length, |position| position.min(length))
+    }
+}
+
+impl From<Buffer> for BufferCursor {
+    fn from(value: Buffer) -> Self {
+        BufferCursor::new(value)
+    }
+}
+
+impl Read for BufferCursor {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.buffer.read_at(self.start(self.buffer.length()), buf);
+        self.position += read as u64;
+        Ok(read)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        let length = self.buffer.length();
+        let start = self.start(length);
+        if length - start < buf.len() {
+            self.position += (length - start) as u64;
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        self.buffer.read_at(start, buf);
+        self.position += buf.len() as u64;
+        Ok(())
+    }
+}
+
+impl BufRead for BufferCursor {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        let chunk_end = self.chunk_position + self.chunk.len() as u64;
+        if self.position < self.chunk_position || self.position >= chunk_end {
+            let length = self.buffer.length();
+            let start = self.start(length);
+            self.chunk.resize(CHUNK_SIZE.min(length - start), 0);
+            self.buffer.read_at(start, &mut self.chunk);
+            self.chunk_position = self.position;
+        }
+        Ok(&self.chunk[(self.position - self.chunk_position) as usize..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.position += amt as u64;
+    }
+}
+
+impl Write for BufferCursor {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let start = self.start(self.buffer.length());
+        let written = self.buffer.write_at(start, buf);
+        self.chunk.clear();
+        self.position += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for BufferCursor {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(position) => {
+                self.position = position;
+                return Ok(position);
+            }
+            SeekFrom::End(offset) => (self.buffer.length() as u64, offset),
+            SeekFrom::Current(offset) => (self.position, offset),
+        };
+        match base.checked_add_signed(offset) {
+            Some(position) => {
+                self.position = position;
+                Ok(position)
+            }
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+
+    fn stream_position(&mut self) -> std::io::Result<u64> {
+        Ok(self.position)
+    }
+}
@@ -1,101 +1,727 @@
-use std::ffi::{c_char, CStr, CString};
+use std::borrow::Cow;
+use std::ffi::{c_char, c_void, CStr, CString};
 use std::fmt::{Debug, Display, Formatter};
-use std::io::Write;
 use std::sync::{Arc};
+use std::sync::atomic::{fence, AtomicUsize, Ordering};
 use base64::Engine;
-use parking_lot::RwLock;
 use byteorder::{BigEndian, ByteOrder, LittleEndian};
 
+mod atomic;
+pub mod blob;
+#[cfg(feature = "bytes")]
+mod bytes_impl;
+mod cursor;
+mod memory;
+mod pool;
+#[cfg(feature = "serde")]
+mod serde_impl;
+pub mod string_decoder;
+
+pub use cursor::BufferCursor;
+pub use memory::{DEFAULT_EXTERNAL_MEMORY_THRESHOLD, ExternalMemoryCallback, MemoryUsage, memory_usage, set_external_memory_callback};
+pub use pool::{DEFAULT_POOL_SIZE, pool_size, set_pool_size};
+
+/// Encodes `value` the way Node's `Buffer.from(string, encoding)` does. Decoding never fails,
+/// invalid base64 characters are skipped and hex stops at the first invalid pair.
 pub fn get_bytes(value: &str, encoding: StringEncoding) -> Vec<u8> {
-    let string = CString::new(value).unwrap();
     match encoding {
-        StringEncoding::Ascii => {
-            let string = string.to_string_lossy();
-            string.as_bytes().to_vec()
-        }
         StringEncoding::Utf8 => {
-            string.as_bytes().to_vec()
+            value.as_bytes().to_vec()
         }
-        StringEncoding::Utf16le => {
-            string
-                .to_string_lossy()
-                .to_string()
+        StringEncoding::Utf16le | StringEncoding::Ucs2 => {
+            value
                 .encode_utf16()
-                .flat_map(|c| {
-                    let mut bytes = [0; 2];
-                    LittleEndian::write_u16(&mut bytes, c);
-                    bytes.to_vec()
-                })
-                .collect::<Vec<u8>>()
-        }
-        StringEncoding::Ucs2 => {
-            let string = string.as_bytes();
-            let length = string.len();
-            let string = unsafe { std::slice::from_raw_parts(string.as_ptr() as *const u16, length / 2) };
-            let mut buf = vec![0_u8; length];
-            let decoded = ucs2::decode(string, buf.as_mut_slice()).unwrap_or(0);
-            buf.shrink_to(decoded);
-            buf
-        }
-        StringEncoding::Base64 => {
-            // todo error
-            base64::engine::general_purpose::STANDARD.decode(string.as_bytes()).unwrap()
-        }
-        StringEncoding::Base64Url => {
-            // todo error
-            base64::engine::general_purpose::URL_SAFE.decode(string.as_bytes()).unwrap()
-        }
-        StringEncoding::Binary | StringEncoding::Latin1 => {
-            let (decoded, _) = encoding_rs::UTF_8.decode_without_bom_handling(string.as_bytes());
-            decoded.as_bytes().to_vec()
+                .flat_map(|c| c.to_le_bytes())
+                .collect()
+        }
+        StringEncoding::Base64 | StringEncoding::Base64Url => {
+            decode_base64(value.as_bytes())
+        }
+        StringEncoding::Ascii | StringEncoding::Binary | StringEncoding::Latin1 => {
+            value
+                .encode_utf16()
+                .map(|c| c as u8)
+                .collect()
         }
         StringEncoding::Hex => {
-            // todo error
-            hex::decode(string.as_bytes()).unwrap()
+            decode_hex(value.as_bytes())
+        }
+    }
+}
+
+/// Like `get_bytes` for length delimited UTF-8 that may contain NULs. Lone surrogates encoded
+/// as three byte sequences (WTF-8) are kept as code units, other invalid bytes become U+FFFD.
+pub fn get_bytes_utf8(value: &[u8], encoding: StringEncoding) -> Vec<u8> {
+    match std::str::from_utf8(value) {
+        Ok(value) => get_bytes(value, encoding),
+        Err(_) => get_bytes_utf16(&decode_wtf8(value), encoding)
+    }
+}
+
+/// Like `get_bytes` for a JS string given as UTF-16 code units. Lone surrogates become U+FFFD
+/// for utf8 and are written as is for utf16le/ucs2, as Node does.
+pub fn get_bytes_utf16(value: &[u16], encoding: StringEncoding) -> Vec<u8> {
+    match encoding {
+        StringEncoding::Utf16le | StringEncoding::Ucs2 => {
+            value
+                .iter()
+                .flat_map(|c| c.to_le_bytes())
+                .collect()
+        }
+        StringEncoding::Ascii | StringEncoding::Binary | StringEncoding::Latin1 => {
+            value
+                .iter()
+                .map(|&c| c as u8)
+                .collect()
+        }
+        _ => {
+            get_bytes(&String::from_utf16_lossy(value), encoding)
+        }
+    }
+}
+
+// Splits WTF-8 into its valid runs and the code units everything else decodes to: lone
+// surrogates as is, other invalid bytes as U+FFFD.
+fn wtf8_runs(mut input: &[u8], mut f: impl FnMut(Result<&str, u16>)) {
+    while !input.is_empty() {
+        match std::str::from_utf8(input) {
+            Ok(valid) => {
+                f(Ok(valid));
+                break;
+            }
+            Err(error) => {
+                let (valid, invalid) = input.split_at(error.valid_up_to());
+                f(Ok(unsafe { std::str::from_utf8_unchecked(valid) }));
+                if let [0xED, high @ 0xA0..=0xBF, low @ 0x80..=0xBF, ..] = *invalid {
+                    f(Err(0xD000 | (high as u16 & 0x3F) << 6 | (low as u16 & 0x3F)));
+                    input = &invalid[3..];
+                } else {
+                    f(Err(0xFFFD));
+                    input = &invalid[error.error_len().unwrap_or(invalid.len())..];
+                }
+            }
+        }
+    }
+}
+
+fn decode_wtf8(input: &[u8]) -> Vec<u16> {
+    let mut output = Vec::with_capacity(input.len());
+    wtf8_runs(input, |run| match run {
+        Ok(valid) => output.extend(valid.encode_utf16()),
+        Err(unit) => output.push(unit),
+    });
+    output
+}
+
+fn base64_value(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' | b'-' => Some(62),
+        b'/' | b'_' => Some(63),
+        _ => None
+    }
+}
+
+fn decode_base64(input: &[u8]) -> Vec<u8> {
+    let mut output = vec![0_u8; input.len() / 4 * 3 + 2];
+    let written = decode_base64_into(input.iter().map(|&c| c as u16), &mut output);
+    output.truncate(written);
+    output
+}
+
+// Accepts both alphabets, skips anything outside them and stops at the first `=`, a trailing
+// group of 2 or 3 characters yields 1 or 2 bytes. Stops once `output` is full.
+fn decode_base64_into(input: impl Iterator<Item = u16>, output: &mut [u8]) -> usize {
+    let mut written = 0;
+    let mut group = 0_u32;
+    let mut count = 0;
+    for c in input {
+        if c == b'=' as u16 || written == output.len() {
+            break;
+        }
+        let Some(value) = u8::try_from(c).ok().and_then(base64_value) else {
+            continue;
+        };
+        group = group << 6 | value as u32;
+        count += 1;
+        if count == 4 {
+            written += copy_truncated(&group.to_be_bytes()[1..], &mut output[written..]);
+            group = 0;
+            count = 0;
+        }
+    }
+    match count {
+        2 => written += copy_truncated(&[(group >> 4) as u8], &mut output[written..]),
+        3 => written += copy_truncated(&((group >> 2) as u16).to_be_bytes(), &mut output[written..]),
+        _ => {}
+    }
+    written
+}
+
+fn copy_truncated(source: &[u8], target: &mut [u8]) -> usize {
+    let length = source.len().min(target.len());
+    target[..length].copy_from_slice(&source[..length]);
+    length
+}
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None
+    }
+}
+
+fn decode_hex(input: &[u8]) -> Vec<u8> {
+    let mut output = vec![0_u8; input.len() / 2];
+    let written = decode_hex_into(input.iter().map(|&c| c as u16), &mut output);
+    output.truncate(written);
+    output
+}
+
+// Stops at the first invalid pair or once `output` is full.
+fn decode_hex_into(mut input: impl Iterator<Item = u16>, output: &mut [u8]) -> usize {
+    let digit = |c: u16| u8::try_from(c).ok().and_then(hex_value);
+    let mut written = 0;
+    for byte in output.iter_mut() {
+        let (Some(high), Some(low)) = (input.next().and_then(digit), input.next().and_then(digit)) else {
+            break;
+        };
+        *byte = high << 4 | low;
+        written += 1;
+    }
+    written
+}
+
+// Encodes into `output` the way `buf.write` does: utf8 never writes part of a character,
+// utf16le/ucs2 whole code units only, lone surrogates become U+FFFD in utf8.
+fn encode_units_into(units: impl Iterator<Item = u16>, output: &mut [u8], encoding: StringEncoding) -> usize {
+    match encoding {
+        StringEncoding::Utf8 => {
+            let mut written = 0;
+            for c in char::decode_utf16(units) {
+                let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+                if written + c.len_utf8() > output.len() {
+                    break;
+                }
+                written += c.encode_utf8(&mut output[written..]).len();
+            }
+            written
+        }
+        StringEncoding::Utf16le | StringEncoding::Ucs2 => {
+            output
+                .chunks_exact_mut(2)
+                .zip(units)
+                .map(|(target, unit)| target.copy_from_slice(&unit.to_le_bytes()))
+                .count() * 2
+        }
+        StringEncoding::Ascii | StringEncoding::Binary | StringEncoding::Latin1 => {
+            output
+                .iter_mut()
+                .zip(units)
+                .map(|(target, unit)| *target = unit as u8)
+                .count()
+        }
+        StringEncoding::Base64 | StringEncoding::Base64Url => decode_base64_into(units, output),
+        StringEncoding::Hex => decode_hex_into(units, output),
+    }
+}
+
+fn encode_str_into(value: &str, output: &mut [u8], encoding: StringEncoding) -> usize {
+    if encoding != StringEncoding::Utf8 {
+        return encode_units_into(value.encode_utf16(), output, encoding);
+    }
+    let mut length = value.len().min(output.len());
+    while !value.is_char_boundary(length) {
+        length -= 1;
+    }
+    output[..length].copy_from_slice(&value.as_bytes()[..length]);
+    length
+}
+
+/// `Buffer.byteLength(string, encoding)`, computed without encoding the string. Like Node
+/// base64 only discounts trailing `=` so the result is an upper bound for unpadded or
+/// whitespace containing input.
+pub fn byte_length(value: &str, encoding: StringEncoding) -> usize {
+    if encoding == StringEncoding::Utf8 {
+        return value.len();
+    }
+    // `=` is ASCII, so the last units are `=` exactly when the last bytes are
+    units_byte_length(utf16_length(value), encoding, |n| value.as_bytes()[value.len() - n] == b'=')
+}
+
+/// `byte_length` for a length delimited UTF-8 string, see `get_bytes_utf8`.
+pub fn byte_length_utf8(value: &[u8], encoding: StringEncoding) -> usize {
+    if let Ok(value) = std::str::from_utf8(value) {
+        return byte_length(value, encoding);
+    }
+    if encoding == StringEncoding::Utf8 {
+        // lone surrogates and U+FFFD both take 3 bytes
+        let mut length = 0;
+        wtf8_runs(value, |run| length += run.map_or(3, str::len));
+        return length;
+    }
+    let mut units = 0;
+    wtf8_runs(value, |run| units += run.map_or(1, utf16_length));
+    units_byte_length(units, encoding, |n| value[value.len() - n] == b'=')
+}
+
+/// `byte_length` for a string given as UTF-16 code units.
+pub fn byte_length_utf16(value: &[u16], encoding: StringEncoding) -> usize {
+    if encoding == StringEncoding::Utf8 {
+        return char::decode_utf16(value.iter().copied())
+            .map(|c| c.map_or(3, char::len_utf8))
+            .sum();
+    }
+    units_byte_length(value.len(), encoding, |n| value[value.len() - n] == b'=' as u16)
+}
+
+// Every character starts with one non continuation byte, 4 byte ones are surrogate pairs.
+fn utf16_length(value: &str) -> usize {
+    value.bytes()
+        .fold(0, |units, c| units + (c & 0xC0 != 0x80) as usize + (c >= 0xF0) as usize)
+}
+
+// `is_padding(n)` tells whether the n-th unit from the end is `=`, see node's base64ByteLength.
+fn units_byte_length(units: usize, encoding: StringEncoding, is_padding: impl Fn(usize) -> bool) -> usize {
+    match encoding {
+        StringEncoding::Utf16le | StringEncoding::Ucs2 => units * 2,
+        StringEncoding::Hex => units >> 1,
+        StringEncoding::Base64 | StringEncoding::Base64Url => {
+            let mut length = units;
+            if length > 0 && is_padding(1) {
+                length -= 1;
+                if length > 1 && is_padding(2) {
+                    length -= 1;
+                }
+            }
+            (length * 3) >> 2
         }
+        _ => units
+    }
+}
+
+
+// Below this simdutf8 loses to the std validator.
+const SIMD_VALIDATION_THRESHOLD: usize = 64;
+
+/// `buffer.isUtf8(input)`
+pub fn is_utf8(input: &[u8]) -> bool {
+    if input.len() < SIMD_VALIDATION_THRESHOLD {
+        return std::str::from_utf8(input).is_ok();
     }
+    simdutf8::basic::from_utf8(input).is_ok()
+}
+
+/// `buffer.isAscii(input)`
+pub fn is_ascii(input: &[u8]) -> bool {
+    encoding_rs::mem::is_ascii(input)
+}
+
+/// `buffer.transcode(source, fromEnc, toEnc)` between utf8, ucs2/utf16le, latin1/binary and
+/// ascii, any other encoding fails with `U_ILLEGAL_ARGUMENT_ERROR`. Like Node, utf8 to ucs2 and
+/// back fail with `U_INVALID_CHAR_FOUND` on invalid input, other pairs decode it to U+FFFD
+/// (ascii bytes past 0x7f too, unless going to ucs2) and characters the target can't
+/// represent become `?`.
+pub fn transcode(source: &[u8], from: StringEncoding, to: StringEncoding) -> node_core::error::Result<Buffer> {
+    let to_utf16 = matches!(to, StringEncoding::Utf16le | StringEncoding::Ucs2);
+    let decoded = match from {
+        StringEncoding::Utf8 if to_utf16 => {
+            std::str::from_utf8(source).map(Cow::Borrowed).map_err(|_| node_core::error::err_transcode_invalid_char())?
+        }
+        StringEncoding::Utf8 => encoding_rs::UTF_8.decode_without_bom_handling(source).0,
+        StringEncoding::Utf16le | StringEncoding::Ucs2 if to == StringEncoding::Utf8 => {
+            // an odd trailing byte is dropped
+            let units = source.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]]));
+            char::decode_utf16(units)
+                .collect::<Result<String, _>>()
+                .map(Cow::Owned)
+                .map_err(|_| node_core::error::err_transcode_invalid_char())?
+        }
+        StringEncoding::Utf16le | StringEncoding::Ucs2 => encoding_rs::UTF_16LE.decode_without_bom_handling(source).0,
+        StringEncoding::Ascii if !to_utf16 => {
+            source.iter().map(|&c| if c.is_ascii() { c as char } else { char::REPLACEMENT_CHARACTER }).collect()
+        }
+        StringEncoding::Latin1 | StringEncoding::Binary | StringEncoding::Ascii => encoding_rs::mem::decode_latin1(source),
+        _ => return Err(node_core::error::err_unable_to_transcode())
+    };
+
+    let encoded = match to {
+        StringEncoding::Utf8 => decoded.into_owned().into_bytes(),
+        StringEncoding::Utf16le | StringEncoding::Ucs2 => get_bytes(&decoded, to),
+        StringEncoding::Latin1 | StringEncoding::Binary => {
+            decoded.chars().map(|c| u8::try_from(c).unwrap_or(b'?')).collect()
+        }
+        StringEncoding::Ascii => {
+            decoded.chars().map(|c| if c.is_ascii() { c as u8 } else { b'?' }).collect()
+        }
+        _ => return Err(node_core::error::err_unable_to_transcode())
+    };
+
+    Ok(Buffer::from_vec(encoded))
 }
 
 
+/// Called exactly once with the original `data`, `length` and `context` when the last
+/// `Buffer` sharing an external allocation is dropped. It may run on any thread.
+pub type BufferFinalizer = extern "C" fn(data: *mut u8, length: usize, context: *mut c_void);
+
+enum StorageKind {
+    /// Owned by the storage, `data` came from `Box<[u8]>::into_raw`.
+    Allocated,
+    /// Host memory that outlives every buffer pointing at it.
+    Reference,
+    External {
+        finalizer: Option<BufferFinalizer>,
+        context: *mut c_void,
+    },
+    /// A slice of a pool slab, holding the slab keeps it alive. Each slice has its own storage
+    /// so a pooled buffer nothing else shares is still written in place.
+    Pooled { _slab: Arc<Storage> },
+}
+
+/// Fixed size memory shared by a buffer, its clones and its views, much like an
+/// `ArrayBuffer`. While it is shared the bytes are only accessed through `atomic`, a handle
+/// that holds the only reference works on them in place.
+///
+/// `readers` counts the in place reads made through `&self`. Another thread may clone that
+/// handle while such a read runs, so before writing through a shared handle `wait_for_readers`
+/// lets them finish. Nothing waits while the storage stays shared.
+struct Storage {
+    data: *mut u8,
+    length: usize,
+    kind: StorageKind,
+    readers: AtomicUsize,
+}
+
+impl Storage {
+    fn allocated(data: Vec<u8>) -> Self {
+        let length = data.len();
+        let data = Box::into_raw(data.into_boxed_slice()) as *mut u8;
+        memory::record_allocated(length);
+        Self {
+            data,
+            length,
+            kind: StorageKind::Allocated,
+            readers: AtomicUsize::new(0),
+        }
+    }
+
+    fn reference(data: *mut u8, length: usize) -> Self {
+        Self {
+            data,
+            length,
+            kind: StorageKind::Reference,
+            readers: AtomicUsize::new(0),
+        }
+    }
+
+    fn external(data: *mut u8, length: usize, finalizer: Option<BufferFinalizer>, context: *mut c_void) -> Self {
+        Self {
+            data,
+            length,
+            kind: StorageKind::External { finalizer, context },
+            readers: AtomicUsize::new(0),
+        }
+    }
+
+    // `offset + length` lies within the slab, see `pool::alloc`
+    fn pooled(slab: Arc<Storage>, offset: usize, length: usize) -> Self {
+        Self {
+            data: unsafe { slab.data.add(offset) },
+            length,
+            kind: StorageKind::Pooled { _slab: slab },
+            readers: AtomicUsize::new(0),
+        }
+    }
+
+    // null host memory reads as empty
+    fn len(&self) -> usize {
+        if self.data.is_null() {
+            return 0;
+        }
+        self.length
+    }
+
+    fn region(&self) -> atomic::Region {
+        atomic::Region::new(self.data, self.len())
+    }
+
+    // Pairs with the fence in `ReadPin::new`, either that read sees the clone this write goes
+    // through or the write sees the read.
+    fn wait_for_readers(&self) {
+        fence(Ordering::SeqCst);
+        while self.readers.load(Ordering::Acquire) != 0 {
+            std::thread::yield_now();
+        }
+    }
+
+    fn into_inner(self) -> BufferInner {
+        let length = self.len();
+        BufferInner::Shared(Arc::new(self), 0, length)
+    }
+}
+
+impl Drop for Storage {
+    fn drop(&mut self) {
+        match self.kind {
+            StorageKind::Allocated => {
+                drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(self.data, self.length)) });
+                memory::record_freed(self.length);
+            }
+            StorageKind::Reference | StorageKind::Pooled { .. } => {}
+            StorageKind::External { ref mut finalizer, context } => {
+                if let Some(finalizer) = finalizer.take() {
+                    finalizer(self.data, self.length, context);
+                }
+            }
+        }
+    }
+}
+
+impl Debug for Storage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            StorageKind::Allocated => "Allocated",
+            StorageKind::Reference => "Reference",
+            StorageKind::External { .. } => "External",
+            StorageKind::Pooled { .. } => "Pooled",
+        };
+        f.debug_struct("Storage")
+            .field("kind", &kind)
+            .field("length", &self.length)
+            .finish()
+    }
+}
+
+// Shared bytes are only accessed atomically, the external finalizer is required to be
+// callable from any thread, see `Buffer::from_external`.
+unsafe impl Send for Storage {}
+
+unsafe impl Sync for Storage {}
+
 #[derive(Clone, Debug)]
 enum BufferInner {
     Empty,
-    Allocated(Arc<RwLock<Vec<u8>>>),
-    Reference(Arc<RwLock<(*mut u8, usize)>>),
+    /// `(storage, offset, length)`, the range always lies within the storage. Clones and
+    /// views share the same storage.
+    Shared(Arc<Storage>, usize, usize),
 }
 
-
 impl PartialEq for BufferInner {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (BufferInner::Empty, BufferInner::Empty) => true,
-            (BufferInner::Allocated(a), BufferInner::Allocated(b)) => {
-                a.read().as_slice() == b.read().as_slice()
-            }
-            (BufferInner::Reference(a), BufferInner::Reference(b)) => {
-                let a = a.read();
-                let b = b.read();
-                unsafe {
-                    std::slice::from_raw_parts(
-                        a.0,
-                        a.1,
-                    )
-                        ==
-                        std::slice::from_raw_parts(
-                            b.0,
-                            b.1,
-                        )
+        compare_ranges(self, 0, self.len(), other, 0, other.len()).is_eq()
+    }
+}
+
+impl Eq for BufferInner {}
+
+/// How many bytes of shared memory `search` and `compare_between` copy out at a time, so
+/// they stop early without copying the rest.
+const SCAN_CHUNK: usize = 64 * 1024;
+
+/// An in place read of storage no other handle shares, see `Storage::readers`.
+struct ReadPin<'a>(&'a Storage);
+
+impl<'a> ReadPin<'a> {
+    fn new(storage: &'a Arc<Storage>) -> Option<Self> {
+        storage.readers.fetch_add(1, Ordering::Relaxed);
+        let pin = ReadPin(storage);
+        fence(Ordering::SeqCst);
+        if Arc::strong_count(storage) != 1 {
+            return None;
+        }
+        // synchronises with the release of the last clone, its writes are visible
+        fence(Ordering::Acquire);
+        Some(pin)
+    }
+}
+
+impl Drop for ReadPin<'_> {
+    fn drop(&mut self) {
+        self.0.readers.fetch_sub(1, Ordering::Release);
+    }
+}
+
+impl BufferInner {
+    fn len(&self) -> usize {
+        match self {
+            BufferInner::Empty => 0,
+            BufferInner::Shared(_, _, length) => *length,
+        }
+    }
+
+    fn as_ptr(&self) -> *mut u8 {
+        match self {
+            BufferInner::Shared(storage, offset, length) if *length > 0 => {
+                unsafe { storage.data.add(*offset) }
+            }
+            _ => std::ptr::NonNull::dangling().as_ptr()
+        }
+    }
+
+    /// The bytes in place, when no clone or view shares the storage nothing else can reach
+    /// them for as long as `self` is borrowed.
+    #[inline]
+    fn unique_mut(&mut self) -> Option<&mut [u8]> {
+        match self {
+            BufferInner::Empty => Some(&mut []),
+            BufferInner::Shared(storage, offset, length) => {
+                // No weak references are ever made, and nobody can clone `self` while it is
+                // borrowed mutably.
+                if Arc::strong_count(storage) != 1 {
+                    return None;
                 }
+                fence(Ordering::Acquire);
+                Some(unsafe { std::slice::from_raw_parts_mut(storage.data.add(*offset), *length) })
             }
+        }
+    }
 
-            _ => false
+    /// Copies `target.len()` bytes starting at `offset` out, callers check the range.
+    #[inline]
+    fn load(&self, offset: usize, target: &mut [u8]) {
+        debug_assert!(offset + target.len() <= self.len());
+        if let BufferInner::Shared(storage, ..) = self {
+            unsafe { atomic::load(storage.region(), self.as_ptr().add(offset), target) }
+        }
+    }
+
+    /// Copies `source` in at `offset`, callers check the range.
+    #[inline]
+    fn store(&mut self, offset: usize, source: &[u8]) {
+        debug_assert!(offset + source.len() <= self.len());
+        if let Some(bytes) = self.unique_mut() {
+            bytes[offset..offset + source.len()].copy_from_slice(source);
+            return;
+        }
+        if let BufferInner::Shared(storage, ..) = self {
+            storage.wait_for_readers();
+            unsafe { atomic::store(storage.region(), self.as_ptr().add(offset), source) }
+        }
+    }
+
+    /// `load`, but a plain copy when no clone or view shares the storage.
+    fn read_into(&self, offset: usize, target: &mut [u8]) {
+        debug_assert!(offset + target.len() <= self.len());
+        if let BufferInner::Shared(storage, ..) = self {
+            if let Some(_pin) = ReadPin::new(storage) {
+                target.copy_from_slice(unsafe { std::slice::from_raw_parts(self.as_ptr().add(offset), target.len()) });
+                return;
+            }
+        }
+        self.load(offset, target)
+    }
+
+    /// Runs `f` with `length` bytes at `offset`, in place when no clone or view shares the
+    /// storage, otherwise with a copy of just that range. Callers check the range.
+    fn read<R>(&self, offset: usize, length: usize, f: impl FnOnce(&[u8]) -> R) -> R {
+        debug_assert!(offset + length <= self.len());
+        if let BufferInner::Shared(storage, ..) = self {
+            if let Some(_pin) = ReadPin::new(storage) {
+                return f(unsafe { std::slice::from_raw_parts(self.as_ptr().add(offset), length) });
+            }
+        }
+        let mut bytes = vec![0; length];
+        self.load(offset, &mut bytes);
+        f(&bytes)
+    }
+
+    /// Runs `f` with `length` bytes at `offset`, in place when no clone or view shares the
+    /// storage. Otherwise `f` gets a copy, and only the bytes it changed are stored back so
+    /// writes through other handles to the rest of the range survive. Callers check the range.
+    fn write<R>(&mut self, offset: usize, length: usize, f: impl FnOnce(&mut [u8]) -> R) -> R {
+        if let Some(bytes) = self.unique_mut() {
+            return f(&mut bytes[offset..offset + length]);
+        }
+        let mut original = vec![0; length];
+        self.load(offset, &mut original);
+        let mut bytes = original.clone();
+        let result = f(&mut bytes);
+
+        let mut start = 0;
+        while start < length {
+            if bytes[start] == original[start] {
+                start += 1;
+                continue;
+            }
+            let end = (start..length).find(|&i| bytes[i] == original[i]).unwrap_or(length);
+            self.store(offset + start, &bytes[start..end]);
+            start = end;
+        }
+        result
+    }
+
+    /// Runs `f` with up to `length` bytes at `offset` to overwrite, it returns how many it
+    /// wrote from the start. On shared storage `f` fills a scratch copy and only those bytes
+    /// are stored. Callers check the range.
+    fn overwrite(&mut self, offset: usize, length: usize, f: impl FnOnce(&mut [u8]) -> usize) -> usize {
+        if let Some(bytes) = self.unique_mut() {
+            return f(&mut bytes[offset..offset + length]);
+        }
+        let mut bytes = vec![0; length];
+        let written = f(&mut bytes);
+        self.store(offset, &bytes[..written]);
+        written
+    }
+
+    /// `copy_between` within one buffer.
+    fn copy_within(&mut self, from: usize, to: usize, count: usize) {
+        if let Some(bytes) = self.unique_mut() {
+            bytes.copy_within(from..from + count, to);
+            return;
+        }
+        if let BufferInner::Shared(storage, ..) = self {
+            storage.wait_for_readers();
+            let region = storage.region();
+            let data = self.as_ptr();
+            unsafe { atomic::copy(region, data.add(from), region, data.add(to), count) }
         }
     }
 }
 
-impl Eq for BufferInner {}
+/// Copies `length` bytes from `source[source_start..]` into `target[target_start..]`, the
+/// ranges may overlap when both share storage. Callers check both ranges.
+fn copy_between(source: &BufferInner, source_start: usize, target: &mut BufferInner, target_start: usize, length: usize) {
+    if length == 0 {
+        return;
+    }
+    debug_assert!(source_start + length <= source.len() && target_start + length <= target.len());
 
-unsafe impl Send for BufferInner {}
+    // a target nothing else shares can't overlap the source
+    if let Some(bytes) = target.unique_mut() {
+        source.read_into(source_start, &mut bytes[target_start..target_start + length]);
+        return;
+    }
+    if let (BufferInner::Shared(from, ..), BufferInner::Shared(to, ..)) = (source, &*target) {
+        to.wait_for_readers();
+        unsafe {
+            atomic::copy(from.region(), source.as_ptr().add(source_start), to.region(), target.as_ptr().add(target_start), length)
+        }
+    }
+}
+
+/// Compares `source[source_start..source_start + source_length]` with the given range of
+/// `target`, a chunk at a time so a difference near the start is found without copying the
+/// rest of shared memory.
+fn compare_ranges(source: &BufferInner, source_start: usize, source_length: usize, target: &BufferInner, target_start: usize, target_length: usize) -> std::cmp::Ordering {
+    let common = source_length.min(target_length);
+    let mut position = 0;
+    while position < common {
+        let count = SCAN_CHUNK.min(common - position);
+        let ordering = source.read(source_start + position, count, |a| {
+            target.read(target_start + position, count, |b| a.cmp(b))
+        });
+        if ordering.is_ne() {
+            return ordering;
+        }
+        position += count;
+    }
+    source_length.cmp(&target_length)
+}
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 #[repr(C)]
@@ -132,48 +758,116 @@ impl TryFrom<i32> for StringEncoding {
     }
 }
 
+/// Parses the encoding names Node accepts, case insensitively. Anything else fails with
+/// `ERR_UNKNOWN_ENCODING`.
+impl std::str::FromStr for StringEncoding {
+    type Err = node_core::error::AnyError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "utf8" | "utf-8" => Ok(StringEncoding::Utf8),
+            "ucs2" | "ucs-2" => Ok(StringEncoding::Ucs2),
+            "utf16le" | "utf-16le" => Ok(StringEncoding::Utf16le),
+            "latin1" => Ok(StringEncoding::Latin1),
+            "binary" => Ok(StringEncoding::Binary),
+            "base64" => Ok(StringEncoding::Base64),
+            "base64url" => Ok(StringEncoding::Base64Url),
+            "hex" => Ok(StringEncoding::Hex),
+            "ascii" => Ok(StringEncoding::Ascii),
+            _ => Err(node_core::error::err_unknown_encoding(value))
+        }
+    }
+}
+
+/// What `Buffer::fill` repeats, strings are encoded with the encoding passed alongside.
+#[derive(Copy, Clone, Debug)]
+pub enum FillValue<'a> {
+    /// Only the low byte is used, `value & 255`.
+    Number(i64),
+    Bytes(&'a [u8]),
+    Buffer(&'a Buffer),
+    String(&'a str),
+    /// A length delimited UTF-8 string, see `get_bytes_utf8`.
+    Utf8String(&'a [u8]),
+    /// A string given as UTF-16 code units.
+    Utf16String(&'a [u16]),
+}
+
+impl FillValue<'_> {
+    fn pattern(&self, encoding: StringEncoding) -> Vec<u8> {
+        match *self {
+            FillValue::Number(value) => vec![(value & 255) as u8],
+            FillValue::Bytes(bytes) => bytes.to_vec(),
+            // copied since it may share storage with the buffer being filled
+            FillValue::Buffer(buffer) => buffer.with_slice(|bytes| bytes.to_vec()),
+            FillValue::String("") | FillValue::Utf8String(&[]) | FillValue::Utf16String(&[]) => vec![0],
+            FillValue::String(value) => get_bytes_utf8(value.as_bytes(), encoding),
+            FillValue::Utf8String(value) => get_bytes_utf8(value, encoding),
+            FillValue::Utf16String(value) => get_bytes_utf16(value, encoding),
+        }
+    }
+
+    // how node's inspect shows the value in ERR_INVALID_ARG_VALUE
+    fn describe(&self) -> String {
+        match *self {
+            FillValue::Number(value) => value.to_string(),
+            FillValue::Bytes(bytes) => format!("Uint8Array({}) []", bytes.len()),
+            FillValue::Buffer(buffer) => buffer.to_string(),
+            FillValue::String(value) => format!("'{}'", value),
+            FillValue::Utf8String(value) => format!("'{}'", String::from_utf8_lossy(value)),
+            FillValue::Utf16String(value) => format!("'{}'", String::from_utf16_lossy(value)),
+        }
+    }
+}
+
+// Writes `pattern` once then doubles the filled prefix, so long fills are a few memcpys.
+fn fill_pattern(target: &mut [u8], pattern: &[u8]) {
+    let first = pattern.len().min(target.len());
+    target[..first].copy_from_slice(&pattern[..first]);
+    let mut filled = first;
+    while filled > 0 && filled < target.len() {
+        let count = filled.min(target.len() - filled);
+        target.copy_within(..count, filled);
+        filled += count;
+    }
+}
+
 pub struct BufferBuilder {
     size: usize,
     fill: Option<Vec<u8>>,
     string: Option<CString>,
     fill_encoding: StringEncoding,
     reference: Option<(*mut u8, usize)>,
+    external: Option<(*mut u8, usize, Option<BufferFinalizer>, *mut c_void)>,
 }
 
 impl BufferBuilder {
     pub fn build(&self) -> Buffer {
-        if self.size == 0 && self.reference.is_none() {
+        if self.size == 0 && self.reference.is_none() && self.external.is_none() {
             return Buffer::default();
         }
 
-        let mut buffer = Buffer(match self.reference {
-            None => {
-                BufferInner::Allocated(
-                    Arc::new(
-                        RwLock::new(
-                            vec![0_u8; self.size]
-                        )
-                    )
-                )
-            }
-            Some((data, size)) => {
-                BufferInner::Reference(Arc::new(
-                    RwLock::new((data, size))
-                ))
+        let mut buffer = Buffer(match (self.reference, self.external) {
+            (_, Some((data, length, finalizer, context))) => {
+                Storage::external(data, length, finalizer, context).into_inner()
+            }
+            (Some((data, size)), None) => {
+                Storage::reference(data, size).into_inner()
+            }
+            (None, None) => {
+                let _span = node_core::trace::allocation_span(self.size);
+                Storage::allocated(vec![0_u8; self.size]).into_inner()
             }
         });
 
-        let buf = buffer.buffer_mut();
-
+        // A pattern that encodes to nothing leaves the buffer zeroed, `Buffer::alloc` reports it.
         if let Some(string) = self.string.as_ref() {
-            let ret = Buffer::encode_string(string, self.fill_encoding);
-            let to_copy = &ret[0..self.size];
-            buf.copy_from_slice(to_copy);
+            let pattern = Buffer::encode_string(string, self.fill_encoding);
+            buffer.with_slice_mut(|buf| fill_pattern(buf, &pattern));
         }
 
         if let Some(fill) = self.fill.as_ref() {
-            let to_copy = &fill[0..self.size];
-            buf.copy_from_slice(to_copy);
+            buffer.with_slice_mut(|buf| fill_pattern(buf, fill));
         }
 
         buffer
@@ -190,6 +884,16 @@ impl BufferBuilder {
         self
     }
 
+    /// `fill_text` for a length delimited UTF-8 string, see `get_bytes_utf8`.
+    pub fn fill_text_utf8(&mut self, value: &[u8], encoding: StringEncoding) -> &mut Self {
+        self.fill(get_bytes_utf8(value, encoding))
+    }
+
+    /// `fill_text` for a string given as UTF-16 code units, see `get_bytes_utf16`.
+    pub fn fill_text_utf16(&mut self, value: &[u16], encoding: StringEncoding) -> &mut Self {
+        self.fill(get_bytes_utf16(value, encoding))
+    }
+
     pub fn fill(&mut self, value: Vec<u8>) -> &mut Self {
         self.fill_encoding = StringEncoding::Utf8;
         self.string = None;
@@ -201,8 +905,22 @@ impl BufferBuilder {
         self.reference = Some((data, size));
         self
     }
+
+    /// Wraps memory owned by the host, `finalizer` is invoked with `context` once the last
+    /// `Buffer` built from it is dropped. Takes precedence over `reference`.
+    pub fn external(&mut self, data: *mut u8, size: usize, finalizer: Option<BufferFinalizer>, context: *mut c_void) -> &mut Self {
+        self.external = Some((data, size, finalizer, context));
+        self
+    }
 }
 
+/// Node's `Buffer`. Clones, views and the memory passed to `from_reference`/`from_external`
+/// share the same bytes, like typed arrays over one `ArrayBuffer`.
+///
+/// There is no lock. While a clone or view shares the memory every method copies in and out
+/// of it with relaxed atomic byte accesses, so racing handles see unordered bytes like on a
+/// `SharedArrayBuffer` but never cause a data race. A handle that owns the only reference
+/// works on the memory in place.
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[repr(C)]
 pub struct Buffer(BufferInner);
@@ -227,356 +945,370 @@ impl Display for Buffer {
 }
 
 impl Buffer {
+    /// # Safety
+    ///
+    /// The returned pointer is only valid if `value` came from a `Box<Buffer>` pointer that
+    /// is still alive.
     pub unsafe fn get_ptr(value: i64) -> *mut Buffer {
         value as *mut Buffer
     }
 
 
     fn encode_string(string: &CString, encoding: StringEncoding) -> Vec<u8> {
-        let string = string.to_string_lossy();
-        get_bytes(string.as_ref(), encoding)
+        get_bytes_utf8(string.to_bytes(), encoding)
     }
 
     fn encode_str(string: &CStr, encoding: StringEncoding) -> Vec<u8> {
-        let string = string.to_string_lossy();
-        get_bytes(string.as_ref(), encoding)
+        get_bytes_utf8(string.to_bytes(), encoding)
     }
 
 
-    pub fn write_int8(&mut self, value: i8, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-
-        unsafe { std::ptr::write(buffer.as_mut_ptr(), value as u8) }
+    pub fn write_int8(&mut self, value: i8, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 1, |buffer| buffer[0] = value as u8)?;
+        Ok(offset + 1)
     }
 
-    pub fn write_uint8(&mut self, value: u8, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let ptr = unsafe { buffer.as_mut_ptr().offset(offset.unwrap_or(0) as isize) };
-
-        unsafe { std::ptr::write(ptr, value) }
+    pub fn write_uint8(&mut self, value: u8, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 1, |buffer| buffer[0] = value)?;
+        Ok(offset + 1)
     }
 
-    pub fn write_uint16be(&mut self, value: u16, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        BigEndian::write_u16(buffer, value);
+    pub fn write_uint16be(&mut self, value: u16, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 2, |buffer| BigEndian::write_u16(buffer, value))?;
+        Ok(offset + 2)
     }
 
-    pub fn write_uint16le(&mut self, value: u16, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        LittleEndian::write_u16(buffer, value);
+    pub fn write_uint16le(&mut self, value: u16, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 2, |buffer| LittleEndian::write_u16(buffer, value))?;
+        Ok(offset + 2)
     }
 
-    pub fn write_int16be(&mut self, value: i16, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        BigEndian::write_i16(buffer, value);
+    pub fn write_int16be(&mut self, value: i16, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 2, |buffer| BigEndian::write_i16(buffer, value))?;
+        Ok(offset + 2)
     }
 
-    pub fn write_int16le(&mut self, value: i16, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        LittleEndian::write_i16(buffer, value);
+    pub fn write_int16le(&mut self, value: i16, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 2, |buffer| LittleEndian::write_i16(buffer, value))?;
+        Ok(offset + 2)
     }
 
-    pub fn write_uint32be(&mut self, value: u32, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        BigEndian::write_u32(buffer, value);
+    pub fn write_uint32be(&mut self, value: u32, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 4, |buffer| BigEndian::write_u32(buffer, value))?;
+        Ok(offset + 4)
     }
 
-    pub fn write_uint32le(&mut self, value: u32, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        LittleEndian::write_u32(buffer, value);
+    pub fn write_uint32le(&mut self, value: u32, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 4, |buffer| LittleEndian::write_u32(buffer, value))?;
+        Ok(offset + 4)
     }
 
-    pub fn write_int32be(&mut self, value: i32, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        BigEndian::write_i32(buffer, value);
+    pub fn write_int32be(&mut self, value: i32, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 4, |buffer| BigEndian::write_i32(buffer, value))?;
+        Ok(offset + 4)
     }
 
-    pub fn write_int32le(&mut self, value: i32, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        LittleEndian::write_i32(buffer, value);
+    pub fn write_int32le(&mut self, value: i32, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 4, |buffer| LittleEndian::write_i32(buffer, value))?;
+        Ok(offset + 4)
     }
 
-    pub fn write_float_be(&mut self, value: f32, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        BigEndian::write_f32(buffer, value);
+    pub fn write_float_be(&mut self, value: f32, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 4, |buffer| BigEndian::write_f32(buffer, value))?;
+        Ok(offset + 4)
     }
 
-    pub fn write_float_le(&mut self, value: f32, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        LittleEndian::write_f32(buffer, value);
+    pub fn write_float_le(&mut self, value: f32, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 4, |buffer| LittleEndian::write_f32(buffer, value))?;
+        Ok(offset + 4)
     }
 
-    pub fn write_double_be(&mut self, value: f64, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        BigEndian::write_f64(buffer, value);
+    pub fn write_double_be(&mut self, value: f64, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 8, |buffer| BigEndian::write_f64(buffer, value))?;
+        Ok(offset + 8)
     }
 
-    pub fn write_double_le(&mut self, value: f64, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        LittleEndian::write_f64(buffer, value);
+    pub fn write_double_le(&mut self, value: f64, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 8, |buffer| LittleEndian::write_f64(buffer, value))?;
+        Ok(offset + 8)
     }
 
-    pub fn write_big_int64be(&mut self, value: i64, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        BigEndian::write_i64(buffer, value);
+    pub fn write_big_int64be(&mut self, value: i64, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 8, |buffer| BigEndian::write_i64(buffer, value))?;
+        Ok(offset + 8)
     }
 
-    pub fn write_big_int64be_bytes(&mut self, value: &[u8], offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
+    pub fn write_big_int64be_bytes(&mut self, value: &[u8], offset: Option<usize>) -> node_core::error::Result<usize> {
+        if value.len() < 8 {
+            return Err(node_core::error::err_invalid_arg_value_with_reason("value", value.len(), "must be 8 bytes"));
+        }
         let value = BigEndian::read_i64(value);
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        BigEndian::write_i64(buffer, value);
+        self.write_big_int64be(value, offset)
     }
 
-    pub fn write_big_int64le(&mut self, value: i64, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        LittleEndian::write_i64(buffer, value);
+    pub fn write_big_int64le(&mut self, value: i64, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 8, |buffer| LittleEndian::write_i64(buffer, value))?;
+        Ok(offset + 8)
     }
 
-    pub fn write_big_int64le_bytes(&mut self, value: &[u8], offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
+    pub fn write_big_int64le_bytes(&mut self, value: &[u8], offset: Option<usize>) -> node_core::error::Result<usize> {
+        if value.len() < 8 {
+            return Err(node_core::error::err_invalid_arg_value_with_reason("value", value.len(), "must be 8 bytes"));
+        }
         let value = LittleEndian::read_i64(value);
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        LittleEndian::write_i64(buffer, value);
+        self.write_big_int64le(value, offset)
     }
 
-    pub fn write_big_uint64be(&mut self, value: u64, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        BigEndian::write_u64(buffer, value);
+    pub fn write_big_uint64be(&mut self, value: u64, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 8, |buffer| BigEndian::write_u64(buffer, value))?;
+        Ok(offset + 8)
     }
 
-    pub fn write_big_uint64be_bytes(&mut self, value: &[u8], offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
+    pub fn write_big_uint64be_bytes(&mut self, value: &[u8], offset: Option<usize>) -> node_core::error::Result<usize> {
+        if value.len() < 8 {
+            return Err(node_core::error::err_invalid_arg_value_with_reason("value", value.len(), "must be 8 bytes"));
+        }
         let value = BigEndian::read_u64(value);
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        BigEndian::write_u64(buffer, value);
+        self.write_big_uint64be(value, offset)
     }
 
-    pub fn write_big_uint64le(&mut self, value: u64, offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        LittleEndian::write_u64(buffer, value);
+    pub fn write_big_uint64le(&mut self, value: u64, offset: Option<usize>) -> node_core::error::Result<usize> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range_mut(offset, 8, |buffer| LittleEndian::write_u64(buffer, value))?;
+        Ok(offset + 8)
     }
 
-    pub fn write_big_uint64le_bytes(&mut self, value: &[u8], offset: Option<usize>) {
-        let buffer = self.buffer_mut();
-        let length = buffer.len();
+    pub fn write_big_uint64le_bytes(&mut self, value: &[u8], offset: Option<usize>) -> node_core::error::Result<usize> {
+        if value.len() < 8 {
+            return Err(node_core::error::err_invalid_arg_value_with_reason("value", value.len(), "must be 8 bytes"));
+        }
         let value = LittleEndian::read_u64(value);
-        let buffer = &mut buffer[offset.unwrap_or(0)..length];
-        LittleEndian::write_u64(buffer, value);
+        self.write_big_uint64le(value, offset)
+    }
+
+    pub fn read_int8(&self, offset: Option<usize>) -> node_core::error::Result<i8> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 1, |buffer| buffer[0] as i8)
+    }
+
+    pub fn read_uint8(&self, offset: Option<usize>) -> node_core::error::Result<u8> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 1, |buffer| buffer[0])
+    }
+
+    pub fn read_uint16be(&self, offset: Option<usize>) -> node_core::error::Result<u16> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 2, BigEndian::read_u16)
+    }
+
+    pub fn read_uint16le(&self, offset: Option<usize>) -> node_core::error::Result<u16> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 2, LittleEndian::read_u16)
     }
 
-    pub fn read_int8(&self, offset: Option<usize>) -> i8 {
-        unsafe { *(self.buffer().get(offset.unwrap_or(0)).unwrap() as *const _ as *const i8) }
+    pub fn read_int16be(&self, offset: Option<usize>) -> node_core::error::Result<i16> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 2, BigEndian::read_i16)
     }
 
-    pub fn read_uint8(&self, offset: Option<usize>) -> u8 {
-        unsafe { *(self.buffer().get(offset.unwrap_or(0)).unwrap()) }
+    pub fn read_int16le(&self, offset: Option<usize>) -> node_core::error::Result<i16> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 2, LittleEndian::read_i16)
     }
 
-    pub fn read_uint16be(&self, offset: Option<usize>) -> u16 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        BigEndian::read_u16(buffer)
+    pub fn read_uint32be(&self, offset: Option<usize>) -> node_core::error::Result<u32> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 4, BigEndian::read_u32)
     }
 
-    pub fn read_uint16le(&self, offset: Option<usize>) -> u16 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        LittleEndian::read_u16(buffer)
+    pub fn read_uint32le(&self, offset: Option<usize>) -> node_core::error::Result<u32> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 4, LittleEndian::read_u32)
     }
 
-    pub fn read_int16be(&self, offset: Option<usize>) -> i16 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        BigEndian::read_i16(buffer)
+    pub fn read_int32be(&self, offset: Option<usize>) -> node_core::error::Result<i32> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 4, BigEndian::read_i32)
     }
 
-    pub fn read_int16le(&self, offset: Option<usize>) -> i16 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        LittleEndian::read_i16(buffer)
+    pub fn read_int32le(&self, offset: Option<usize>) -> node_core::error::Result<i32> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 4, LittleEndian::read_i32)
     }
 
-    pub fn read_uint32be(&self, offset: Option<usize>) -> u32 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        BigEndian::read_u32(buffer)
+    pub fn read_float_be(&self, offset: Option<usize>) -> node_core::error::Result<f32> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 4, BigEndian::read_f32)
     }
 
-    pub fn read_uint32le(&self, offset: Option<usize>) -> u32 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        LittleEndian::read_u32(buffer)
+    pub fn read_float_le(&self, offset: Option<usize>) -> node_core::error::Result<f32> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 4, LittleEndian::read_f32)
     }
 
-    pub fn read_int32be(&self, offset: Option<usize>) -> i32 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        BigEndian::read_i32(buffer)
+    pub fn read_double_be(&self, offset: Option<usize>) -> node_core::error::Result<f64> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 8, BigEndian::read_f64)
     }
 
-    pub fn read_int32le(&self, offset: Option<usize>) -> i32 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        LittleEndian::read_i32(buffer)
+    pub fn read_double_le(&self, offset: Option<usize>) -> node_core::error::Result<f64> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 8, LittleEndian::read_f64)
     }
 
-    pub fn read_float_be(&self, offset: Option<usize>) -> f32 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        BigEndian::read_f32(buffer)
+    pub fn read_big_int64be(&self, offset: Option<usize>) -> node_core::error::Result<i64> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 8, BigEndian::read_i64)
     }
 
-    pub fn read_float_le(&self, offset: Option<usize>) -> f32 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        LittleEndian::read_f32(buffer)
+    pub fn read_big_int64be_bytes(&self, offset: Option<usize>) -> node_core::error::Result<[u8; 8]> {
+        self.read_big_int64be(offset).map(|value| value.to_ne_bytes())
     }
 
-    pub fn read_double_be(&self, offset: Option<usize>) -> f64 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        BigEndian::read_f64(buffer)
+    pub fn read_big_int64le(&self, offset: Option<usize>) -> node_core::error::Result<i64> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 8, LittleEndian::read_i64)
     }
 
-    pub fn read_double_le(&self, offset: Option<usize>) -> f64 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        LittleEndian::read_f64(buffer)
+    pub fn read_big_int64le_bytes(&self, offset: Option<usize>) -> node_core::error::Result<[u8; 8]> {
+        self.read_big_int64le(offset).map(|value| value.to_ne_bytes())
     }
 
-    pub fn read_big_int64be(&self, offset: Option<usize>) -> i64 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        BigEndian::read_i64(buffer)
+    pub fn read_big_uint64be(&self, offset: Option<usize>) -> node_core::error::Result<u64> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 8, BigEndian::read_u64)
     }
 
-    pub fn read_big_int64be_bytes(&self, offset: Option<usize>) -> [u8; 8] {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        let mut ret = [0_u8; 8];
-        let store = unsafe { std::slice::from_raw_parts_mut(ret.as_mut_ptr() as *mut i64, 1) };
-        BigEndian::read_i64_into(buffer, store);
-        ret
+    pub fn read_big_uint64be_bytes(&self, offset: Option<usize>) -> node_core::error::Result<[u8; 8]> {
+        self.read_big_uint64be(offset).map(|value| value.to_ne_bytes())
     }
 
-    pub fn read_big_int64le(&self, offset: Option<usize>) -> i64 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        LittleEndian::read_i64(buffer)
+    pub fn read_big_uint64le(&self, offset: Option<usize>) -> node_core::error::Result<u64> {
+        let offset = offset.unwrap_or(0);
+        self.checked_range(offset, 8, LittleEndian::read_u64)
     }
 
-    pub fn read_big_int64le_bytes(&self, offset: Option<usize>) -> [u8; 8] {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
+    pub fn read_big_uint64le_bytes(&self, offset: Option<usize>) -> node_core::error::Result<[u8; 8]> {
+        self.read_big_uint64le(offset).map(|value| value.to_ne_bytes())
+    }
 
-        let mut ret = [0_u8; 8];
-        let store = unsafe { std::slice::from_raw_parts_mut(ret.as_mut_ptr() as *mut i64, 1) };
+    // Runs `f` with the `size` bytes at `offset`, once they are known to be in bounds.
+    fn checked_range<R>(&self, offset: usize, size: usize, f: impl FnOnce(&[u8]) -> R) -> node_core::error::Result<R> {
+        check_offset(self.length(), offset, size)?;
+        let mut bytes = [0_u8; 8];
+        self.0.load(offset, &mut bytes[..size]);
+        Ok(f(&bytes[..size]))
+    }
+
+    fn checked_range_mut<R>(&mut self, offset: usize, size: usize, f: impl FnOnce(&mut [u8]) -> R) -> node_core::error::Result<R> {
+        check_offset(self.length(), offset, size)?;
+        let mut bytes = [0_u8; 8];
+        let result = f(&mut bytes[..size]);
+        self.0.store(offset, &bytes[..size]);
+        Ok(result)
+    }
 
-        LittleEndian::read_i64_into(buffer, store);
+    /// `buf.readIntBE(offset, byteLength)` for 1 to 6 byte wide signed integers.
+    pub fn read_int_be(&self, offset: usize, byte_length: usize) -> node_core::error::Result<i64> {
+        self.checked_int_range(offset, byte_length, |buffer| BigEndian::read_int(buffer, byte_length))
+    }
 
-        ret
+    pub fn read_int_le(&self, offset: usize, byte_length: usize) -> node_core::error::Result<i64> {
+        self.checked_int_range(offset, byte_length, |buffer| LittleEndian::read_int(buffer, byte_length))
     }
 
-    pub fn read_big_uint64be(&self, offset: Option<usize>) -> u64 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        BigEndian::read_u64(buffer)
+    /// `buf.readUIntBE(offset, byteLength)` for 1 to 6 byte wide unsigned integers.
+    pub fn read_uint_be(&self, offset: usize, byte_length: usize) -> node_core::error::Result<u64> {
+        self.checked_int_range(offset, byte_length, |buffer| BigEndian::read_uint(buffer, byte_length))
     }
 
-    pub fn read_big_uint64be_bytes(&self, offset: Option<usize>) -> [u8; 8] {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
+    pub fn read_uint_le(&self, offset: usize, byte_length: usize) -> node_core::error::Result<u64> {
+        self.checked_int_range(offset, byte_length, |buffer| LittleEndian::read_uint(buffer, byte_length))
+    }
 
-        let mut ret = [0_u8; 8];
-        let store = unsafe { std::slice::from_raw_parts_mut(ret.as_mut_ptr() as *mut u64, 1) };
+    /// `buf.writeIntBE(value, offset, byteLength)`, returns `offset + byteLength`.
+    pub fn write_int_be(&mut self, value: i64, offset: usize, byte_length: usize) -> node_core::error::Result<usize> {
+        check_int_value(value, byte_length, true)?;
+        self.checked_int_range_mut(offset, byte_length, |buffer| BigEndian::write_int(buffer, value, byte_length))?;
+        Ok(offset + byte_length)
+    }
 
+    pub fn write_int_le(&mut self, value: i64, offset: usize, byte_length: usize) -> node_core::error::Result<usize> {
+        check_int_value(value, byte_length, true)?;
+        self.checked_int_range_mut(offset, byte_length, |buffer| LittleEndian::write_int(buffer, value, byte_length))?;
+        Ok(offset + byte_length)
+    }
 
-        BigEndian::read_u64_into(buffer, store);
+    /// `buf.writeUIntBE(value, offset, byteLength)`, returns `offset + byteLength`.
+    pub fn write_uint_be(&mut self, value: i64, offset: usize, byte_length: usize) -> node_core::error::Result<usize> {
+        check_int_value(value, byte_length, false)?;
+        self.checked_int_range_mut(offset, byte_length, |buffer| BigEndian::write_uint(buffer, value as u64, byte_length))?;
+        Ok(offset + byte_length)
+    }
 
-        ret
+    pub fn write_uint_le(&mut self, value: i64, offset: usize, byte_length: usize) -> node_core::error::Result<usize> {
+        check_int_value(value, byte_length, false)?;
+        self.checked_int_range_mut(offset, byte_length, |buffer| LittleEndian::write_uint(buffer, value as u64, byte_length))?;
+        Ok(offset + byte_length)
     }
 
-    pub fn read_big_uint64le(&self, offset: Option<usize>) -> u64 {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
-        LittleEndian::read_u64(buffer)
+    fn checked_int_range<R>(&self, offset: usize, byte_length: usize, f: impl FnOnce(&[u8]) -> R) -> node_core::error::Result<R> {
+        check_byte_length(byte_length)?;
+        self.checked_range(offset, byte_length, f)
     }
 
-    pub fn read_big_uint64le_bytes(&self, offset: Option<usize>) -> [u8; 8] {
-        let buffer = self.buffer();
-        let length = buffer.len();
-        let buffer = &buffer[offset.unwrap_or(0)..length];
+    fn checked_int_range_mut<R>(&mut self, offset: usize, byte_length: usize, f: impl FnOnce(&mut [u8]) -> R) -> node_core::error::Result<R> {
+        check_byte_length(byte_length)?;
+        self.checked_range_mut(offset, byte_length, f)
+    }
 
-        let mut ret = [0_u8; 8];
-        let store = unsafe { std::slice::from_raw_parts_mut(ret.as_mut_ptr() as *mut u64, 1) };
+    /// `buf.swap16()`, swaps the byte order in place, the length must be a multiple of 2.
+    pub fn swap16(&mut self) -> node_core::error::Result<()> {
+        self.swap(2, "16-bits")
+    }
 
+    pub fn swap32(&mut self) -> node_core::error::Result<()> {
+        self.swap(4, "32-bits")
+    }
 
-        LittleEndian::read_u64_into(buffer, store);
+    pub fn swap64(&mut self) -> node_core::error::Result<()> {
+        self.swap(8, "64-bits")
+    }
 
-        ret
+    fn swap(&mut self, size: usize, bits: &str) -> node_core::error::Result<()> {
+        if !self.length().is_multiple_of(size) {
+            return Err(node_core::error::err_invalid_buffer_size(bits));
+        }
+        let length = self.length();
+        self.0.write(0, length, |buffer| buffer.chunks_exact_mut(size).for_each(|chunk| chunk.reverse()));
+        Ok(())
     }
 
     pub fn atob(value: CString) -> String {
         Buffer::from_string(value, StringEncoding::Base64).as_string(Some(StringEncoding::Binary), None, None)
     }
 
+    /// # Safety
+    ///
+    /// `value` must be null or a valid nul-terminated string. The result is freed with
+    /// `CString::from_raw`.
     pub unsafe fn atob_raw(value: *const c_char) -> *const c_char {
         if value.is_null() {
             return std::ptr::null();
@@ -594,6 +1326,9 @@ impl Buffer {
         Buffer::from_string(value, StringEncoding::Utf8).as_string(Some(StringEncoding::Base64), None, None)
     }
 
+    /// # Safety
+    ///
+    /// Same as `atob_raw`.
     pub unsafe fn btoa_raw(value: *const c_char) -> *const c_char {
         if value.is_null() {
             return std::ptr::null();
@@ -607,127 +1342,187 @@ impl Buffer {
         }
     }
 
+    /// `Buffer.concat(list, totalLength)`, small results come from the pool.
     pub fn concat(buffers: &[&[u8]], length: Option<usize>) -> Self {
         let len: usize = match length {
             Some(len) => len.min(buffers.iter().map(|buf| buf.len()).sum()),
             None => buffers.iter().map(|buf| buf.len()).sum(),
         };
 
-        let mut result = vec![0_u8; len];
-
-        let mut cursor = std::io::Cursor::new(&mut result);
-
-        for buf in buffers {
-            let remaining_length = match length {
-                Some(len) => len - cursor.position() as usize,
-                None => usize::MAX - cursor.position() as usize,
-            };
-
-            if remaining_length == 0 {
-                break;
+        let mut result = Buffer::alloc_unsafe(len);
+        result.with_slice_mut(|target| {
+            let mut position = 0;
+            for buf in buffers {
+                if position == len {
+                    break;
+                }
+                let bytes_to_write = (len - position).min(buf.len());
+                target[position..position + bytes_to_write].copy_from_slice(&buf[..bytes_to_write]);
+                position += bytes_to_write;
             }
+        });
 
-            let bytes_to_write = remaining_length.min(buf.len());
-            cursor.write_all(&buf[..bytes_to_write]).unwrap();
-        }
-
-        Buffer(BufferInner::Allocated(
-            Arc::new(
-                RwLock::new(
-                    result
-                )
-            )
-        ))
+        result
     }
 
+    /// # Safety
+    ///
+    /// `buffers` and `buffers_length` must both point to `count` elements, and every
+    /// `buffers[i]` must be valid for reads of `buffers_length[i]` bytes.
     pub unsafe fn concat_raw(buffers: *const *const u8, buffers_length: *const usize, count: usize, length: Option<usize>) -> Self {
         unsafe {
             let array = std::slice::from_raw_parts(buffers, count);
             let array_lengths = std::slice::from_raw_parts(buffers_length, count);
+            let buffers = array.iter()
+                .zip(array_lengths)
+                .map(|(ptr, len)| std::slice::from_raw_parts(*ptr, *len))
+                .collect::<Vec<&[u8]>>();
 
-            let len: usize = match length {
-                Some(len) => len.min(array_lengths.iter().sum()),
-                None => array_lengths.iter().sum(),
-            };
-
-            let mut result = vec![0_u8; len];
-
-            let mut cursor = std::io::Cursor::new(&mut result);
+            Buffer::concat(&buffers, length)
+        }
+    }
 
-            for (ptr, len) in array.iter().zip(array_lengths) {
-                let buf = std::slice::from_raw_parts(*ptr, *len);
+    pub fn from_buffer(value: &Buffer) -> Self {
+        Self(Storage::allocated(value.with_slice(|bytes| bytes.to_vec())).into_inner())
+    }
 
+    pub fn from_slice(value: &[u8]) -> Self {
+        Self(Storage::allocated(value.to_vec()).into_inner())
+    }
 
-                let remaining_length = match length {
-                    Some(len) => len - cursor.position() as usize,
-                    None => usize::MAX - cursor.position() as usize,
-                };
+    /// `Buffer.allocUnsafe(size)`, sizes below half of `pool_size()` are sliced from a shared
+    /// slab the way Node does. The memory is zeroed but callers should not rely on it.
+    pub fn alloc_unsafe(size: usize) -> Self {
+        pool::alloc(size).unwrap_or_else(|| Buffer::alloc_unsafe_slow(size))
+    }
 
-                if remaining_length == 0 {
-                    break;
-                }
+    /// `Buffer.allocUnsafeSlow(size)`, always a dedicated allocation that never retains a slab.
+    pub fn alloc_unsafe_slow(size: usize) -> Self {
+        Buffer::builder().size(size).build()
+    }
 
-                let bytes_to_write = remaining_length.min(buf.len());
-                cursor.write_all(&buf[..bytes_to_write]).unwrap();
+    fn pooled_from_slice(value: &[u8]) -> Self {
+        match pool::alloc(value.len()) {
+            Some(mut buffer) => {
+                buffer.with_slice_mut(|buf| buf.copy_from_slice(value));
+                buffer
             }
-
-            Buffer(BufferInner::Allocated(
-                Arc::new(
-                    RwLock::new(
-                        result
-                    )
-                )
-            ))
+            None => Buffer::from_slice(value)
         }
     }
 
-    pub fn from_buffer(value: &Buffer) -> Self {
-        let buf = value.buffer().to_vec();
-        Self(
-            BufferInner::Allocated(
-                Arc::new(
-                    RwLock::new(
-                        buf
-                    )
-                )
-            )
-        )
+    fn pooled_from_vec(value: Vec<u8>) -> Self {
+        if value.len() >= pool_size() >> 1 {
+            return Buffer::from_vec(value);
+        }
+        Buffer::pooled_from_slice(&value)
     }
 
-    pub fn from_slice(value: &[u8]) -> Self {
-        Self(
-            BufferInner::Allocated(
-                Arc::new(
-                    RwLock::new(
-                        value.to_vec()
-                    )
-                )
-            )
-        )
+    // Valid UTF-8 is copied straight into the pool, skipping the intermediate `Vec`.
+    fn from_string_bytes(value: &[u8], encoding: StringEncoding) -> Self {
+        if encoding == StringEncoding::Utf8 && std::str::from_utf8(value).is_ok() {
+            return Buffer::pooled_from_slice(value);
+        }
+        Buffer::pooled_from_vec(get_bytes_utf8(value, encoding))
     }
 
     pub fn from_vec(value: Vec<u8>) -> Self {
-        Self(
-            BufferInner::Allocated(
-                Arc::new(
-                    RwLock::new(
-                        value
-                    )
-                )
-            )
-        )
+        Self(Storage::allocated(value).into_inner())
     }
 
+    /// Wraps `size` bytes at `data` without copying or ever releasing them.
+    ///
+    /// # Safety
+    ///
+    /// Same as `from_external`, except that the memory must outlive every handle since
+    /// nothing tells the owner when the last one is dropped.
     pub unsafe fn from_reference(data: *mut u8, size: usize) -> Self {
-        Self(
-            BufferInner::Reference(
-                Arc::new(
-                    RwLock::new(
-                        (data, size)
-                    )
-                )
-            )
-        )
+        Self(Storage::reference(data, size).into_inner())
+    }
+
+    /// Wraps `size` bytes at `data` without copying. Unlike `from_reference` the memory is
+    /// released through `finalizer`, which runs exactly once when the last clone is dropped.
+    ///
+    /// # Safety
+    ///
+    /// - `data` must be null or valid for reads and writes of `size` bytes until `finalizer`
+    ///   runs, or for as long as any handle exists when there is none.
+    /// - While any handle is alive the memory must only be accessed through the buffer, its
+    ///   clones and its views. The host must not read or write it behind their back.
+    /// - `finalizer` and `context` must be safe to call and use from any thread.
+    pub unsafe fn from_external(data: *mut u8, size: usize, finalizer: Option<BufferFinalizer>, context: *mut c_void) -> Self {
+        Self(Storage::external(data, size, finalizer, context).into_inner())
+    }
+
+    // No clone or view shares the memory, so nothing else can read or write it.
+    #[cfg(feature = "bytes")]
+    pub(crate) fn is_unique(&mut self) -> bool {
+        match self.0 {
+            BufferInner::Empty => true,
+            BufferInner::Shared(ref mut storage, ..) => Arc::get_mut(storage).is_some(),
+        }
+    }
+
+    /// Copies the bytes at `offset` into `target`, up to the end of the buffer, and returns
+    /// how many were copied. Only that range is read, however the memory is shared.
+    pub(crate) fn read_at(&self, offset: usize, target: &mut [u8]) -> usize {
+        let count = target.len().min(self.length().saturating_sub(offset));
+        self.0.read_into(offset, &mut target[..count]);
+        count
+    }
+
+    /// Copies as much of `source` as fits at `offset`, returns how many bytes were written.
+    pub(crate) fn write_at(&mut self, offset: usize, source: &[u8]) -> usize {
+        let count = source.len().min(self.length().saturating_sub(offset));
+        self.0.store(offset, &source[..count]);
+        count
+    }
+
+    fn view(&self, offset: usize, length: usize) -> Self {
+        match self.0 {
+            BufferInner::Empty => Buffer::default(),
+            BufferInner::Shared(ref storage, parent_offset, _) => {
+                Self(BufferInner::Shared(Arc::clone(storage), parent_offset + offset, length))
+            }
+        }
+    }
+
+    /// `Buffer.from(arrayBuffer, byteOffset, length)`, the result shares `value`'s memory.
+    /// Fails with `ERR_BUFFER_OUT_OF_BOUNDS` when the range does not fit.
+    pub fn from_buffer_view(value: &Buffer, offset: usize, length: Option<usize>) -> node_core::error::Result<Self> {
+        let max = value.length();
+        if offset > max {
+            return Err(node_core::error::err_buffer_out_of_bounds(Some("offset")));
+        }
+        let length = match length {
+            Some(length) if length > max - offset => {
+                return Err(node_core::error::err_buffer_out_of_bounds(Some("length")));
+            }
+            Some(length) => length,
+            None => max - offset
+        };
+        Ok(value.view(offset, length))
+    }
+
+    /// `buf.subarray(start, end)`, negative indices count from the end and both are
+    /// clamped to the buffer, writes through the result are visible in `self`.
+    pub fn subarray(&self, start: Option<isize>, end: Option<isize>) -> Self {
+        let length = self.length() as isize;
+        let clamp = |index: isize| {
+            if index < 0 {
+                (length + index).max(0)
+            } else {
+                index.min(length)
+            }
+        };
+        let start = clamp(start.unwrap_or(0));
+        let end = clamp(end.unwrap_or(length)).max(start);
+        self.view(start as usize, (end - start) as usize)
+    }
+
+    /// Same as `subarray`, Node deprecated `buf.slice` in its favour.
+    pub fn slice(&self, start: Option<isize>, end: Option<isize>) -> Self {
+        self.subarray(start, end)
     }
 
     pub fn builder() -> BufferBuilder {
@@ -737,169 +1532,177 @@ impl Buffer {
             string: None,
             fill_encoding: StringEncoding::Utf8,
             reference: None,
+            external: None,
         }
     }
 
     pub fn from_string(value: CString, encoding: StringEncoding) -> Self {
-        let buf = Buffer::encode_string(&value, encoding);
-        Self(
-            BufferInner::Allocated(
-                Arc::new(
-                    RwLock::new(
-                        buf
-                    )
-                )
-            )
-        )
+        Buffer::from_string_bytes(value.as_bytes(), encoding)
+    }
+
+    /// `Buffer.from(string, encoding)` for a length delimited UTF-8 string, embedded NULs are kept.
+    pub fn from_utf8_string(value: &[u8], encoding: StringEncoding) -> Self {
+        Buffer::from_string_bytes(value, encoding)
+    }
+
+    /// `Buffer.from(string, encoding)` for a string given as UTF-16 code units.
+    pub fn from_utf16_string(value: &[u16], encoding: StringEncoding) -> Self {
+        Buffer::pooled_from_vec(get_bytes_utf16(value, encoding))
     }
 
     pub fn from_str(value: &CStr, encoding: StringEncoding) -> Self {
-        let buf = Buffer::encode_str(value, encoding);
-        Self(
-            BufferInner::Allocated(
-                Arc::new(
-                    RwLock::new(
-                        buf
-                    )
-                )
-            )
-        )
-    }
-
-    fn fill_bytes(&mut self, bytes: &[u8], encoding: Option<StringEncoding>) -> &mut Self {
-        let ret = match encoding.unwrap_or(StringEncoding::Utf8) {
-            StringEncoding::Ascii => {
-                bytes.to_vec()
-            }
-            StringEncoding::Utf8 => {
-                bytes.to_vec()
-            }
-            StringEncoding::Utf16le => {
-                let (decoded, _) = encoding_rs::UTF_8.decode_without_bom_handling(bytes);
-                decoded.as_bytes().to_vec()
-            }
-            StringEncoding::Ucs2 => {
-                let string = bytes;
-                let length = string.len();
-                let string = unsafe { std::slice::from_raw_parts(string.as_ptr() as *const u16, length / 2) };
-                let mut buf = vec![0_u8; length];
-                let decoded = ucs2::decode(string, buf.as_mut_slice()).unwrap_or(0);
-                buf.shrink_to(decoded);
-                buf
-            }
-            StringEncoding::Base64 => {
-                // todo error
-                base64::engine::general_purpose::STANDARD.decode(bytes).unwrap()
-            }
-            StringEncoding::Base64Url => {
-                // todo error
-                base64::engine::general_purpose::URL_SAFE.decode(bytes).unwrap()
-            }
-            StringEncoding::Binary | StringEncoding::Latin1 => {
-                let (decoded, _) = encoding_rs::UTF_8.decode_without_bom_handling(bytes);
-                decoded.as_bytes().to_vec()
-            }
-            StringEncoding::Hex => {
-                // todo error
-                hex::decode(bytes).unwrap()
+        Buffer::from_string_bytes(value.to_bytes(), encoding)
+    }
+
+    /// `buf.fill(value, offset, end, encoding)`, repeats `value` over `offset..end`. An `end`
+    /// past the buffer fails with `ERR_OUT_OF_RANGE`, a pattern that encodes to nothing (e.g.
+    /// invalid hex) with `ERR_INVALID_ARG_VALUE`. Empty strings fill with zeros.
+    pub fn fill(&mut self, value: FillValue, offset: Option<usize>, end: Option<usize>, encoding: Option<StringEncoding>) -> node_core::error::Result<&mut Self> {
+        let length = self.length();
+        let offset = offset.unwrap_or(0);
+        let end = match end {
+            Some(end) if end > length => {
+                return Err(node_core::error::err_out_of_range("end", &format!(">= 0 && <= {}", length), end));
             }
+            Some(end) => end,
+            None => length
         };
-        match self.0 {
-            BufferInner::Allocated(ref buf) => {
-                let mut buf = buf.write();
-                let _ = buf.write(ret.as_slice());
-            }
-            BufferInner::Reference(ref buf) => {
-                let (data, size) = *buf.read();
-
-                if data.is_null() || size == 0 {
-                    return self;
-                }
-                let mut buf = unsafe { std::slice::from_raw_parts_mut(data, size) };
-                let _ = buf.write(ret.as_slice());
-            }
+        if offset >= end {
+            return Ok(self);
+        }
 
-            _ => {}
+        let pattern = value.pattern(encoding.unwrap_or(StringEncoding::Utf8));
+        if pattern.is_empty() {
+            return Err(node_core::error::err_invalid_arg_value("value", value.describe()));
         }
-        self
+        self.0.overwrite(offset, end - offset, |buffer| {
+            fill_pattern(buffer, &pattern);
+            buffer.len()
+        });
+        Ok(self)
     }
 
-    pub fn fill(&mut self, string: CString, encoding: Option<StringEncoding>) -> &mut Self {
-        self.fill_bytes(string.as_bytes(), encoding)
+    pub fn fill_str(&mut self, string: &CStr, encoding: Option<StringEncoding>) -> node_core::error::Result<&mut Self> {
+        self.fill(FillValue::Utf8String(string.to_bytes()), None, None, encoding)
     }
 
-    pub fn fill_str(&mut self, string: &CStr, encoding: Option<StringEncoding>) -> &mut Self {
-        let string = string.to_string_lossy();
-        self.fill_bytes(string.as_bytes(), encoding)
+    pub fn fill_utf8_string(&mut self, string: &[u8], encoding: Option<StringEncoding>) -> node_core::error::Result<&mut Self> {
+        self.fill(FillValue::Utf8String(string), None, None, encoding)
     }
 
-    pub fn length(&self) -> usize {
-        match self.0 {
-            BufferInner::Allocated(ref allocated) => {
-                allocated.read().len()
-            }
-            BufferInner::Reference(ref buf) => {
-                let (data, size) = *buf.read();
-                if data.is_null() {
-                    return 0;
-                }
-                size
-            }
-            BufferInner::Empty => 0
-        }
+    pub fn fill_utf16_string(&mut self, string: &[u16], encoding: Option<StringEncoding>) -> node_core::error::Result<&mut Self> {
+        self.fill(FillValue::Utf16String(string), None, None, encoding)
     }
 
-    pub fn buffer(&self) -> &[u8] {
-        match self.0 {
-            BufferInner::Allocated(ref buf) => {
-                let buf = buf.read();
-                unsafe {
-                    std::slice::from_raw_parts(buf.as_ptr(), buf.len())
-                }
-            }
-            BufferInner::Reference(ref buf) => {
-                let (data, size) = *buf.read();
-                if data.is_null() || size == 0 {
-                    return &[];
-                }
-                return unsafe { std::slice::from_raw_parts(data, size) };
-            }
+    /// `buf.write(string, offset, length, encoding)`, returns the number of bytes written.
+    /// Characters that don't fit whole are left out, `offset` or `length` past the buffer
+    /// fail with `ERR_OUT_OF_RANGE`.
+    pub fn write(&mut self, string: &str, offset: Option<usize>, length: Option<usize>, encoding: Option<StringEncoding>) -> node_core::error::Result<usize> {
+        // no encoding produces more than two bytes per UTF-8 byte
+        let most = string.len().saturating_mul(2);
+        self.write_range(offset, length, most, |target| encode_str_into(string, target, encoding.unwrap_or(StringEncoding::Utf8)))
+    }
 
-            BufferInner::Empty => {
-                &[]
-            }
+    /// `write` for a length delimited UTF-8 string, see `get_bytes_utf8`.
+    pub fn write_utf8_string(&mut self, string: &[u8], offset: Option<usize>, length: Option<usize>, encoding: Option<StringEncoding>) -> node_core::error::Result<usize> {
+        match std::str::from_utf8(string) {
+            Ok(string) => self.write(string, offset, length, encoding),
+            Err(_) => self.write_utf16_string(&decode_wtf8(string), offset, length, encoding)
         }
     }
 
-    pub fn buffer_mut(&mut self) -> &mut [u8] {
-        match self.0 {
-            BufferInner::Allocated(ref buf) => {
-                let mut buf = buf.write();
-                unsafe {
-                    std::slice::from_raw_parts_mut(buf.as_mut_ptr(), buf.len())
-                }
-            }
-            BufferInner::Reference(ref buf) => {
-                let (data, size) = *buf.write();
-                if data.is_null() || size == 0 {
-                    return &mut [];
-                }
-                return unsafe { std::slice::from_raw_parts_mut(data, size) };
-            }
+    /// `write` for a string given as UTF-16 code units.
+    pub fn write_utf16_string(&mut self, string: &[u16], offset: Option<usize>, length: Option<usize>, encoding: Option<StringEncoding>) -> node_core::error::Result<usize> {
+        // no encoding produces more than three bytes per code unit
+        let most = string.len().saturating_mul(3);
+        self.write_range(offset, length, most, |target| encode_units_into(string.iter().copied(), target, encoding.unwrap_or(StringEncoding::Utf8)))
+    }
 
-            BufferInner::Empty => {
-                &mut []
+    // `most` bounds what `f` can write, so a buffer sharing its memory only copies that much.
+    fn write_range(&mut self, offset: Option<usize>, length: Option<usize>, most: usize, f: impl FnOnce(&mut [u8]) -> usize) -> node_core::error::Result<usize> {
+        let buffer_length = self.length();
+        let offset = offset.unwrap_or(0);
+        if offset > buffer_length {
+            return Err(node_core::error::err_out_of_range("offset", &format!(">= 0 && <= {}", buffer_length), offset));
+        }
+        let remaining = buffer_length - offset;
+        let length = match length {
+            Some(length) if length > buffer_length => {
+                return Err(node_core::error::err_out_of_range("length", &format!(">= 0 && <= {}", buffer_length), length));
             }
+            Some(length) => length.min(remaining),
+            None => remaining
+        };
+        Ok(self.0.overwrite(offset, length.min(most), f))
+    }
+
+    /// `Buffer.alloc(size, fill, encoding)`, never pooled. Without `fill` the memory is zeroed.
+    pub fn alloc(size: usize, fill: Option<FillValue>, encoding: Option<StringEncoding>) -> node_core::error::Result<Self> {
+        let mut buffer = Buffer::builder().size(size).build();
+        if let Some(fill) = fill {
+            buffer.fill(fill, None, None, encoding)?;
         }
+        Ok(buffer)
     }
 
-    pub fn as_string(&self, encoding: Option<StringEncoding>, start: Option<usize>, end: Option<usize>) -> String {
-        let buf = self.buffer();
-        let start = start.unwrap_or(0);
-        let end = end.unwrap_or(buf.len());
+    pub fn length(&self) -> usize {
+        self.0.len()
+    }
+
+    /// The buffer's bytes in place, for handing them to code that can't run inside
+    /// `with_slice`.
+    ///
+    /// # Safety
+    ///
+    /// No clone or view sharing this buffer's memory, on any thread, may write to it while the
+    /// returned slice is alive.
+    pub unsafe fn buffer(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.0.as_ptr(), self.length()) }
+    }
+
+    /// # Safety
+    ///
+    /// No clone or view sharing this buffer's memory, on any thread, may read or write it
+    /// while the returned slice is alive.
+    pub unsafe fn buffer_mut(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.0.as_ptr(), self.length()) }
+    }
+
+    /// Pointer to the first byte, dangling when empty. Reading or writing through it is
+    /// subject to the same rules as `buffer`/`buffer_mut`.
+    pub fn as_ptr(&self) -> *const u8 {
+        self.0.as_ptr()
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.0.as_ptr()
+    }
+
+    /// Runs `f` with the buffer's bytes, in place when no clone or view shares them. Otherwise
+    /// `f` gets a snapshot, so writes through clones and views while it runs don't show up.
+    ///
+    /// Writing through a clone made while `f` runs waits for it to return, so doing that from
+    /// inside `f` never returns.
+    pub fn with_slice<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+        self.0.read(0, self.length(), f)
+    }
+
+    /// Runs `f` with the buffer's bytes, in place when no clone or view shares them. Otherwise
+    /// `f` works on a copy and the bytes it changed are written back once it returns.
+    pub fn with_slice_mut<R>(&mut self, f: impl FnOnce(&mut [u8]) -> R) -> R {
+        let length = self.length();
+        self.0.write(0, length, f)
+    }
 
-        let buffer = &buf[start..end];
+    /// `buf.toString(encoding, start, end)`, like Node `end` is clamped to the length and an
+    /// empty range decodes to an empty string.
+    pub fn as_string(&self, encoding: Option<StringEncoding>, start: Option<usize>, end: Option<usize>) -> String {
+        let end = end.unwrap_or(usize::MAX).min(self.length());
+        let start = start.unwrap_or(0).min(end);
+        self.0.read(start, end - start, |buffer| Buffer::decode(buffer, encoding))
+    }
 
+    fn decode(buffer: &[u8], encoding: Option<StringEncoding>) -> String {
         match encoding {
             None => {
                 encoding_rs::UTF_8.decode(buffer).0.to_string()
@@ -907,31 +1710,26 @@ impl Buffer {
             Some(encoding) => {
                 match encoding {
                     StringEncoding::Ascii => {
-                        match std::str::from_utf8(buffer) {
-                            Ok(string) => {
-                                string.to_string()
-                            }
-                            Err(_) => {
-                                String::default()
-                            }
-                        }
+                        buffer.iter().map(|&c| (c & 0x7f) as char).collect()
                     }
                     StringEncoding::Utf8 => {
                         String::from_utf8_lossy(buffer).to_string()
                     }
                     StringEncoding::Utf16le | StringEncoding::Ucs2 => {
-                        let buffer = unsafe { std::slice::from_raw_parts(buffer.as_ptr() as *const u16, buffer.len()) };
-                        String::from_utf16_lossy(buffer)
+                        let buffer = buffer
+                            .chunks_exact(2)
+                            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                            .collect::<Vec<u16>>();
+                        String::from_utf16_lossy(&buffer)
                     }
                     StringEncoding::Base64 => {
                         base64::engine::general_purpose::STANDARD.encode(buffer)
                     }
                     StringEncoding::Base64Url => {
-                        base64::engine::general_purpose::URL_SAFE.encode(buffer)
+                        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(buffer)
                     }
                     StringEncoding::Latin1 | StringEncoding::Binary => {
-                        let encoding = encoding_rs::Encoding::for_label(b"latin1").unwrap();
-                        encoding.decode(buffer).0.to_string()
+                        buffer.iter().map(|&c| c as char).collect()
                     }
                     StringEncoding::Hex => {
                         hex::encode(buffer)
@@ -940,6 +1738,350 @@ impl Buffer {
             }
         }
     }
+
+    /// `buf.copy(target, targetStart, sourceStart, sourceEnd)`, returns the number of bytes
+    /// copied after clamping to both buffers. `sourceStart` past the end fails with
+    /// `ERR_OUT_OF_RANGE`.
+    pub fn copy(
+        &self,
+        target: &mut Buffer,
+        target_start: Option<usize>,
+        source_start: Option<usize>,
+        source_end: Option<usize>,
+    ) -> node_core::error::Result<usize> {
+        let source_length = self.length();
+        let target_length = target.length();
+        let target_start = target_start.unwrap_or(0);
+        let source_start = source_start.unwrap_or(0);
+        if source_start > source_length {
+            return Err(node_core::error::err_out_of_range("sourceStart", &format!(">= 0 && <= {}", source_length), source_start));
+        }
+        let source_end = source_end.unwrap_or(source_length);
+
+        if target_start >= target_length || source_start >= source_end {
+            return Ok(0);
+        }
+
+        let length = (source_end - source_start)
+            .min(target_length - target_start)
+            .min(source_length - source_start);
+
+        copy_between(&self.0, source_start, &mut target.0, target_start, length);
+        Ok(length)
+    }
+
+    /// `buf.copyWithin(target, start, end)`, negative indices count from the end and the
+    /// ranges may overlap. Returns the number of bytes moved.
+    pub fn copy_within(&mut self, target: isize, start: isize, end: Option<isize>) -> usize {
+        let length = self.length() as isize;
+        let clamp = |index: isize| {
+            if index < 0 {
+                (length + index).max(0)
+            } else {
+                index.min(length)
+            }
+        };
+        let to = clamp(target);
+        let from = clamp(start);
+        let end = clamp(end.unwrap_or(length));
+        let count = (end - from).min(length - to);
+        if count <= 0 {
+            return 0;
+        }
+
+        let (to, from, count) = (to as usize, from as usize, count as usize);
+        self.0.copy_within(from, to, count);
+        count
+    }
+
+    /// `buf.set(source, offset)`, fails with a RangeError when `source` does not fit.
+    pub fn set(&mut self, source: &Buffer, offset: usize) -> node_core::error::Result<()> {
+        let length = source.length();
+        if offset > self.length() || length > self.length() - offset {
+            return Err(node_core::error::range_error("offset is out of bounds"));
+        }
+        copy_between(&source.0, 0, &mut self.0, offset, length);
+        Ok(())
+    }
+
+    /// `Buffer.compare(a, b)` or `a.compare(b)`, returns -1, 0 or 1.
+    pub fn compare(&self, target: &Buffer) -> i32 {
+        normalize_compare(compare_ranges(&self.0, 0, self.length(), &target.0, 0, target.length()))
+    }
+
+    /// `buf.compare(target, targetStart, targetEnd, sourceStart, sourceEnd)`, ends default to
+    /// each buffer's length and starts past the end fail with `ERR_OUT_OF_RANGE`.
+    pub fn compare_range(
+        &self,
+        target: &Buffer,
+        target_start: Option<usize>,
+        target_end: Option<usize>,
+        source_start: Option<usize>,
+        source_end: Option<usize>,
+    ) -> node_core::error::Result<i32> {
+        let (source_length, target_length) = (self.length(), target.length());
+        let target_start = target_start.unwrap_or(0);
+        let target_end = match target_end {
+            Some(end) if end > target_length => {
+                return Err(node_core::error::err_out_of_range("targetEnd", &format!(">= 0 && <= {}", target_length), end));
+            }
+            Some(end) => end,
+            None => target_length
+        };
+        let source_start = source_start.unwrap_or(0);
+        let source_end = match source_end {
+            Some(end) if end > source_length => {
+                return Err(node_core::error::err_out_of_range("sourceEnd", &format!(">= 0 && <= {}", source_length), end));
+            }
+            Some(end) => end,
+            None => source_length
+        };
+
+        if source_start >= source_end {
+            return Ok(if target_start >= target_end { 0 } else { -1 });
+        }
+        if target_start >= target_end {
+            return Ok(1);
+        }
+        if source_start > source_length {
+            return Err(node_core::error::err_out_of_range("sourceStart", &format!(">= 0 && <= {}", source_length), source_start));
+        }
+        if target_start > target_length {
+            return Err(node_core::error::err_out_of_range("targetStart", &format!(">= 0 && <= {}", target_length), target_start));
+        }
+
+        Ok(normalize_compare(compare_ranges(
+            &self.0,
+            source_start,
+            source_end - source_start,
+            &target.0,
+            target_start,
+            target_end - target_start,
+        )))
+    }
+
+    pub fn equals(&self, other: &Buffer) -> bool {
+        self.0 == other.0
+    }
+
+    /// `buf.indexOf(value, byteOffset, encoding)`, returns -1 when not found.
+    pub fn index_of(&self, value: IndexOfValue, byte_offset: Option<i64>, encoding: Option<StringEncoding>) -> i64 {
+        self.search(value, byte_offset, encoding, true)
+    }
+
+    /// `buf.lastIndexOf(value, byteOffset, encoding)`, returns -1 when not found.
+    pub fn last_index_of(&self, value: IndexOfValue, byte_offset: Option<i64>, encoding: Option<StringEncoding>) -> i64 {
+        self.search(value, byte_offset, encoding, false)
+    }
+
+    pub fn includes(&self, value: IndexOfValue, byte_offset: Option<i64>, encoding: Option<StringEncoding>) -> bool {
+        self.index_of(value, byte_offset, encoding) != -1
+    }
+
+    fn search(&self, value: IndexOfValue, byte_offset: Option<i64>, encoding: Option<StringEncoding>, forward: bool) -> i64 {
+        let encoding = encoding.unwrap_or(StringEncoding::Utf8);
+        let ucs2 = matches!(encoding, StringEncoding::Ucs2 | StringEncoding::Utf16le);
+        match value {
+            IndexOfValue::Byte(byte) => self.search_bytes(&[byte], byte_offset, false, forward),
+            IndexOfValue::Bytes(needle) => self.search_bytes(needle, byte_offset, ucs2, forward),
+            IndexOfValue::Buffer(needle) => needle.with_slice(|needle| self.search_bytes(needle, byte_offset, ucs2, forward)),
+            IndexOfValue::String(needle) => {
+                let needle = Buffer::encode_str(needle, encoding);
+                self.search_bytes(&needle, byte_offset, ucs2, forward)
+            }
+            IndexOfValue::Utf8String(needle) => {
+                self.search_bytes(&get_bytes_utf8(needle, encoding), byte_offset, ucs2, forward)
+            }
+            IndexOfValue::Utf16String(needle) => {
+                self.search_bytes(&get_bytes_utf16(needle, encoding), byte_offset, ucs2, forward)
+            }
+        }
+    }
+
+    fn search_bytes(&self, needle: &[u8], byte_offset: Option<i64>, ucs2: bool, forward: bool) -> i64 {
+        let (start, end, needle) = match search_window(self.length(), needle, byte_offset, ucs2, forward) {
+            Ok(window) => window,
+            Err(result) => return result,
+        };
+        // A shared haystack is copied a chunk at a time, consecutive chunks overlap by one
+        // byte less than the needle so no match straddles them unseen. Chunks start on even
+        // offsets to keep UTF-16 matches aligned.
+        let chunk = SCAN_CHUNK.max(needle.len() * 2);
+        let overlap = needle.len() - 1;
+        if forward {
+            let mut position = start;
+            loop {
+                let chunk_end = end.min(position + chunk);
+                let found = self.0.read(position, chunk_end - position, |haystack| find_bytes(haystack, needle, ucs2, true));
+                if let Some(found) = found {
+                    return (position + found) as i64;
+                }
+                if chunk_end == end {
+                    return -1;
+                }
+                position = (chunk_end - overlap) & !(ucs2 as usize);
+            }
+        } else {
+            let mut position = end;
+            loop {
+                let chunk_start = position.saturating_sub(chunk).max(start) & !(ucs2 as usize);
+                let found = self.0.read(chunk_start, position - chunk_start, |haystack| find_bytes(haystack, needle, ucs2, false));
+                if let Some(found) = found {
+                    return (chunk_start + found) as i64;
+                }
+                if chunk_start == start {
+                    return -1;
+                }
+                position = chunk_start + overlap;
+            }
+        }
+    }
+}
+
+/// Needle accepted by `Buffer::index_of` and friends, a string is encoded with the
+/// search encoding first.
+#[derive(Copy, Clone, Debug)]
+pub enum IndexOfValue<'a> {
+    Byte(u8),
+    Bytes(&'a [u8]),
+    Buffer(&'a Buffer),
+    String(&'a CStr),
+    /// A length delimited UTF-8 string, see `get_bytes_utf8`.
+    Utf8String(&'a [u8]),
+    /// A string given as UTF-16 code units.
+    Utf16String(&'a [u16]),
+}
+
+fn check_byte_length(byte_length: usize) -> node_core::error::Result<()> {
+    if !(1..=6).contains(&byte_length) {
+        return Err(node_core::error::err_out_of_range("byteLength", ">= 1 and <= 6", byte_length));
+    }
+    Ok(())
+}
+
+// Inlined so accessor loops only pay for the comparison, building the error is out of line.
+#[inline]
+fn check_offset(length: usize, offset: usize, byte_length: usize) -> node_core::error::Result<()> {
+    if byte_length <= length && offset <= length - byte_length {
+        return Ok(());
+    }
+    Err(offset_error(length, offset, byte_length))
+}
+
+#[cold]
+fn offset_error(length: usize, offset: usize, byte_length: usize) -> node_core::error::AnyError {
+    if byte_length > length {
+        return node_core::error::err_buffer_out_of_bounds(None);
+    }
+    node_core::error::err_out_of_range("offset", &format!(">= 0 and <= {}", length - byte_length), offset)
+}
+
+// Same bounds and messages as node's checkInt, widths over 4 bytes are described as powers of two.
+fn check_int_value(value: i64, byte_length: usize, signed: bool) -> node_core::error::Result<()> {
+    check_byte_length(byte_length)?;
+    let bits = byte_length as u32 * 8;
+    let (min, max) = if signed {
+        (-(1_i64 << (bits - 1)), (1_i64 << (bits - 1)) - 1)
+    } else {
+        (0, (1_i64 << bits) - 1)
+    };
+    if value < min || value > max {
+        let range = match (byte_length > 4, signed) {
+            (true, true) => format!(">= -(2 ** {}) and < 2 ** {}", bits - 1, bits - 1),
+            (true, false) => format!(">= 0 and < 2 ** {}", bits),
+            (false, _) => format!(">= {} and <= {}", min, max),
+        };
+        return Err(node_core::error::err_out_of_range("value", &range, value));
+    }
+    Ok(())
 }
 
-unsafe impl Send for Buffer {}
\ No newline at end of file
+fn normalize_compare(ordering: std::cmp::Ordering) -> i32 {
+    match ordering {
+        std::cmp::Ordering::Less => -1,
+        std::cmp::Ordering::Equal => 0,
+        std::cmp::Ordering::Greater => 1,
+    }
+}
+
+// Mirrors node's IndexOfOffset, the start of the search or -1 when nothing can match.
+fn index_of_offset(length: i64, offset: i64, needle_length: i64, forward: bool) -> i64 {
+    if offset < 0 {
+        if offset + length >= 0 {
+            length + offset
+        } else if forward || needle_length == 0 {
+            0
+        } else {
+            -1
+        }
+    } else if offset + needle_length <= length {
+        offset
+    } else if needle_length == 0 {
+        length
+    } else if forward {
+        -1
+    } else {
+        length - 1
+    }
+}
+
+/// Where `buf.indexOf`/`lastIndexOf` look for `needle` in a haystack of `length` bytes: the
+/// first (or last) match inside `start..end` is the answer, with the needle cut to whole UTF-16
+/// units for `ucs2`. `Err` holds the result when no search is needed.
+fn search_window(length: usize, needle: &[u8], byte_offset: Option<i64>, ucs2: bool, forward: bool) -> Result<(usize, usize, &[u8]), i64> {
+    let haystack_length = length as i64;
+    let byte_offset = byte_offset.unwrap_or(if forward { 0 } else { haystack_length });
+    let offset = index_of_offset(haystack_length, byte_offset, needle.len() as i64, forward);
+
+    if needle.is_empty() {
+        return Err(offset);
+    }
+    if length == 0 || offset < 0 {
+        return Err(-1);
+    }
+    let offset = offset as usize;
+    if (forward && needle.len() + offset > length) || needle.len() > length {
+        return Err(-1);
+    }
+
+    if ucs2 {
+        // utf-16 code units are only matched on even offsets, a trailing odd byte is ignored
+        if length < 2 || needle.len() < 2 {
+            return Err(-1);
+        }
+        let length = length & !1;
+        let needle = &needle[..needle.len() & !1];
+        let offset = offset & !1;
+        return if forward {
+            Ok((offset.min(length), length, needle))
+        } else {
+            Ok((0, (offset + needle.len()).min(length), needle))
+        };
+    }
+
+    if forward {
+        Ok((offset, length, needle))
+    } else {
+        Ok((0, (offset + needle.len()).min(length), needle))
+    }
+}
+
+// The first or last match of `needle`, only at even positions for `ucs2`.
+fn find_bytes(haystack: &[u8], needle: &[u8], ucs2: bool, forward: bool) -> Option<usize> {
+    if needle.len() > haystack.len() {
+        return None;
+    }
+    if ucs2 {
+        return if forward {
+            memchr::memmem::find_iter(haystack, needle).find(|position| position % 2 == 0)
+        } else {
+            memchr::memmem::rfind_iter(haystack, needle).find(|position| position % 2 == 0)
+        };
+    }
+    match (needle.len(), forward) {
+        (1, true) => memchr::memchr(needle[0], haystack),
+        (1, false) => memchr::memrchr(needle[0], haystack),
+        (_, true) => memchr::memmem::find(haystack, needle),
+        (_, false) => memchr::memmem::rfind(haystack, needle),
+    }
+}
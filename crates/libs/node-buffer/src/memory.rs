@@ -0,0 +1,105 @@
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Receives the net change in bytes held by buffers since its last call, negative when more
+/// was freed than allocated. Meant for V8's `AdjustAmountOfExternalAllocatedMemory` and the
+/// like. It runs on whichever thread crossed the threshold, possibly one dropping a buffer.
+pub type ExternalMemoryCallback = extern "C" fn(change: i64, context: *mut c_void);
+
+/// Threshold hosts can pass to `set_external_memory_callback` when they have no better value.
+pub const DEFAULT_EXTERNAL_MEMORY_THRESHOLD: usize = 64 * 1024;
+
+#[derive(Copy, Clone)]
+struct Callback {
+    callback: ExternalMemoryCallback,
+    context: *mut c_void,
+}
+
+unsafe impl Send for Callback {}
+
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static FREED: AtomicU64 = AtomicU64::new(0);
+static PENDING: AtomicI64 = AtomicI64::new(0);
+static THRESHOLD: AtomicUsize = AtomicUsize::new(DEFAULT_EXTERNAL_MEMORY_THRESHOLD);
+// Checked first so allocations skip `PENDING` and the lock while nothing is installed.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static CALLBACK: Mutex<Option<Callback>> = Mutex::new(None);
+
+/// Bytes allocated and freed by buffers since startup. Only memory owned by node-buffer is
+/// counted, pool slabs included, while external and referenced host memory is not.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct MemoryUsage {
+    pub allocated: u64,
+    pub freed: u64,
+}
+
+impl MemoryUsage {
+    /// Bytes currently held.
+    pub fn current(&self) -> u64 {
+        self.allocated.saturating_sub(self.freed)
+    }
+}
+
+pub fn memory_usage() -> MemoryUsage {
+    MemoryUsage {
+        allocated: ALLOCATED.load(Ordering::Relaxed),
+        freed: FREED.load(Ordering::Relaxed),
+    }
+}
+
+/// Installs `callback`, replacing any previous one, or removes it when `None`. Changes are
+/// coalesced until they add up to `threshold` bytes either way, 0 reports every allocation.
+///
+/// Only changes made after the first install are reported, hosts seed their count from
+/// `memory_usage().current()`. Replacing the callback, e.g. to change the threshold, keeps
+/// the changes not reported yet.
+pub fn set_external_memory_callback(callback: Option<ExternalMemoryCallback>, context: *mut c_void, threshold: usize) {
+    let Ok(mut installed) = CALLBACK.lock() else {
+        return;
+    };
+    if installed.is_none() {
+        PENDING.store(0, Ordering::SeqCst);
+    }
+    THRESHOLD.store(threshold, Ordering::Relaxed);
+    *installed = callback.map(|callback| Callback { callback, context });
+    ENABLED.store(installed.is_some(), Ordering::SeqCst);
+}
+
+pub(crate) fn record_allocated(bytes: usize) {
+    if bytes == 0 {
+        return;
+    }
+    ALLOCATED.fetch_add(bytes as u64, Ordering::Relaxed);
+    report(bytes as i64);
+}
+
+pub(crate) fn record_freed(bytes: usize) {
+    if bytes == 0 {
+        return;
+    }
+    FREED.fetch_add(bytes as u64, Ordering::Relaxed);
+    report(-(bytes as i64));
+}
+
+fn report(change: i64) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let pending = PENDING.fetch_add(change, Ordering::AcqRel) + change;
+    if pending.unsigned_abs() < THRESHOLD.load(Ordering::Relaxed) as u64 {
+        return;
+    }
+
+    // Copied out so the callback may itself allocate, free or replace the callback.
+    let installed = match CALLBACK.lock() {
+        Ok(installed) => *installed,
+        Err(_) => return,
+    };
+    if let Some(Callback { callback, context }) = installed {
+        let change = PENDING.swap(0, Ordering::AcqRel);
+        if change != 0 {
+            callback(change, context);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::{Buffer, Storage};
+
+/// Node's default `Buffer.poolSize`.
+pub const DEFAULT_POOL_SIZE: usize = 8 * 1024;
+
+static POOL_SIZE: AtomicUsize = AtomicUsize::new(DEFAULT_POOL_SIZE);
+
+struct Pool {
+    slab: Arc<Storage>,
+    size: usize,
+    offset: usize,
+}
+
+impl Pool {
+    fn new(size: usize) -> Self {
+        Self {
+            slab: Arc::new(Storage::allocated(vec![0_u8; size])),
+            size,
+            offset: 0,
+        }
+    }
+}
+
+thread_local! {
+    // Per thread so slicing a buffer off the slab never contends, pooled buffers are
+    // still free to move between threads afterwards.
+    static POOL: RefCell<Option<Pool>> = const { RefCell::new(None) };
+}
+
+/// `Buffer.poolSize`
+pub fn pool_size() -> usize {
+    POOL_SIZE.load(Ordering::Relaxed)
+}
+
+/// Sets `Buffer.poolSize`, slabs created from now on use the new size. `0` disables pooling.
+pub fn set_pool_size(size: usize) {
+    POOL_SIZE.store(size, Ordering::Relaxed);
+}
+
+/// Slices `size` bytes off the calling thread's slab. Like Node only sizes below half the pool
+/// size are pooled, `None` means the caller should allocate on its own.
+pub(crate) fn alloc(size: usize) -> Option<Buffer> {
+    let pool_size = pool_size();
+    if size == 0 || size >= pool_size >> 1 {
+        return None;
+    }
+
+    POOL.with(|pool| {
+        let mut pool = pool.borrow_mut();
+        let pool = match pool.as_mut() {
+            Some(current) if current.size.saturating_sub(current.offset) >= size => current,
+            _ => pool.insert(Pool::new(pool_size)),
+        };
+
+        let offset = pool.offset;
+        // Keep the next slice 8 byte aligned, as Node's alignPool does.
+        pool.offset = (offset + size + 7) & !7;
+
+        Some(Buffer(Storage::pooled(Arc::clone(&pool.slab), offset, size).into_inner()))
+    })
+}
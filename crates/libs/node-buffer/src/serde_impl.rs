@@ -0,0 +1,62 @@
+use std::fmt::Formatter;
+
+use base64::Engine;
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Buffer;
+
+/// Human-readable formats such as JSON get a standard base64 string, binary formats get the
+/// raw bytes.
+impl Serialize for Buffer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.with_slice(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes)))
+        } else {
+            self.with_slice(|bytes| serializer.serialize_bytes(bytes))
+        }
+    }
+}
+
+struct BufferVisitor;
+
+impl<'de> Visitor<'de> for BufferVisitor {
+    type Value = Buffer;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a base64 string, bytes or a sequence of bytes")
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        base64::engine::general_purpose::STANDARD
+            .decode(v)
+            .map(Buffer::from_vec)
+            .map_err(E::custom)
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Buffer::from_slice(v))
+    }
+
+    fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Buffer::from_vec(v))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        Ok(Buffer::from_vec(bytes))
+    }
+}
+
+impl<'de> Deserialize<'de> for Buffer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BufferVisitor)
+        } else {
+            deserializer.deserialize_byte_buf(BufferVisitor)
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use base64::Engine;
+
+use crate::StringEncoding;
+
+enum State {
+    Utf8(encoding_rs::Decoder),
+    // An odd trailing byte and/or a high surrogate waiting for its pair.
+    Utf16(Vec<u8>),
+    // Up to 2 bytes short of a 3 byte group.
+    Base64(Vec<u8>),
+    None,
+}
+
+impl State {
+    fn new(encoding: StringEncoding) -> Self {
+        match encoding {
+            StringEncoding::Utf8 => State::Utf8(encoding_rs::UTF_8.new_decoder_without_bom_handling()),
+            StringEncoding::Utf16le | StringEncoding::Ucs2 => State::Utf16(Vec::with_capacity(4)),
+            StringEncoding::Base64 | StringEncoding::Base64Url => State::Base64(Vec::with_capacity(3)),
+            _ => State::None,
+        }
+    }
+}
+
+/// Node's `StringDecoder`, decodes a stream of chunks keeping incomplete multi-byte characters
+/// (and partial base64 groups) until the next `write` instead of emitting U+FFFD.
+pub struct StringDecoder {
+    encoding: StringEncoding,
+    state: State,
+}
+
+impl StringDecoder {
+    pub fn new(encoding: StringEncoding) -> Self {
+        Self {
+            encoding,
+            state: State::new(encoding),
+        }
+    }
+
+    pub fn encoding(&self) -> StringEncoding {
+        self.encoding
+    }
+
+    /// `decoder.write(buffer)`, returns everything that can be decoded so far.
+    pub fn write(&mut self, bytes: &[u8]) -> String {
+        self.decode(bytes, false)
+    }
+
+    /// `decoder.end([buffer])`, flushes what is left (incomplete characters become U+FFFD)
+    /// and resets the decoder so it can be reused.
+    pub fn end(&mut self, bytes: Option<&[u8]>) -> String {
+        let ret = self.decode(bytes.unwrap_or_default(), true);
+        self.state = State::new(self.encoding);
+        ret
+    }
+
+    fn decode(&mut self, bytes: &[u8], last: bool) -> String {
+        match &mut self.state {
+            State::Utf8(decoder) => {
+                let mut ret = String::with_capacity(decoder.max_utf8_buffer_length(bytes.len()).unwrap_or(bytes.len()));
+                let _ = decoder.decode_to_string(bytes, &mut ret, last);
+                ret
+            }
+            State::Utf16(pending) => {
+                pending.extend_from_slice(bytes);
+                let mut units = pending
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect::<Vec<u16>>();
+                let mut rest = pending.len() - units.len() * 2;
+                if !last && matches!(units.last(), Some(0xD800..=0xDBFF)) {
+                    units.pop();
+                    rest += 2;
+                }
+                pending.drain(..pending.len() - rest);
+                if last {
+                    pending.clear();
+                }
+                String::from_utf16_lossy(&units)
+            }
+            State::Base64(pending) => {
+                pending.extend_from_slice(bytes);
+                let complete = if last { pending.len() } else { pending.len() - pending.len() % 3 };
+                let ret = if self.encoding == StringEncoding::Base64Url {
+                    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&pending[..complete])
+                } else {
+                    base64::engine::general_purpose::STANDARD.encode(&pending[..complete])
+                };
+                pending.drain(..complete);
+                ret
+            }
+            State::None => {
+                match self.encoding {
+                    StringEncoding::Hex => hex::encode(bytes),
+                    StringEncoding::Ascii => bytes.iter().map(|&c| (c & 0x7f) as char).collect(),
+                    _ => bytes.iter().map(|&c| c as char).collect(),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! Table driven checks of the fixed and variable width accessors, including the errors Node
+//! throws for offsets and values out of range.
+
+mod common;
+
+use node_buffer::Buffer;
+
+use common::{bytes, check, to_hex, Expected};
+
+const READS: &[(&str, usize, usize, Expected)] = &[
+    ("read_int8", 10, 0, Ok("18")),
+    ("read_int8", 10, 1, Ok("52")),
+    ("read_int8", 10, 9, Ok("-1")),
+    ("read_int8", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 9. Received 10"))),
+    ("read_int8", 0, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_uint8", 10, 0, Ok("18")),
+    ("read_uint8", 10, 1, Ok("52")),
+    ("read_uint8", 10, 9, Ok("255")),
+    ("read_uint8", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 9. Received 10"))),
+    ("read_uint8", 0, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_int16le", 10, 0, Ok("13330")),
+    ("read_int16le", 10, 1, Ok("22068")),
+    ("read_int16le", 10, 8, Ok("-128")),
+    ("read_int16le", 10, 9, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 8. Received 9"))),
+    ("read_int16le", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 8. Received 10"))),
+    ("read_int16le", 1, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_int16be", 10, 0, Ok("4660")),
+    ("read_int16be", 10, 1, Ok("13398")),
+    ("read_int16be", 10, 8, Ok("-32513")),
+    ("read_int16be", 10, 9, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 8. Received 9"))),
+    ("read_int16be", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 8. Received 10"))),
+    ("read_int16be", 1, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_uint16le", 10, 0, Ok("13330")),
+    ("read_uint16le", 10, 1, Ok("22068")),
+    ("read_uint16le", 10, 8, Ok("65408")),
+    ("read_uint16le", 10, 9, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 8. Received 9"))),
+    ("read_uint16le", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 8. Received 10"))),
+    ("read_uint16le", 1, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_uint16be", 10, 0, Ok("4660")),
+    ("read_uint16be", 10, 1, Ok("13398")),
+    ("read_uint16be", 10, 8, Ok("33023")),
+    ("read_uint16be", 10, 9, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 8. Received 9"))),
+    ("read_uint16be", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 8. Received 10"))),
+    ("read_uint16be", 1, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_int32le", 10, 0, Ok("2018915346")),
+    ("read_int32le", 10, 1, Ok("-1703389644")),
+    ("read_int32le", 10, 6, Ok("-8326946")),
+    ("read_int32le", 10, 7, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 7"))),
+    ("read_int32le", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 10"))),
+    ("read_int32le", 3, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_int32be", 10, 0, Ok("305419896")),
+    ("read_int32be", 10, 1, Ok("878082202")),
+    ("read_int32be", 10, 6, Ok("-554663681")),
+    ("read_int32be", 10, 7, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 7"))),
+    ("read_int32be", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 10"))),
+    ("read_int32be", 3, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_uint32le", 10, 0, Ok("2018915346")),
+    ("read_uint32le", 10, 1, Ok("2591577652")),
+    ("read_uint32le", 10, 6, Ok("4286640350")),
+    ("read_uint32le", 10, 7, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 7"))),
+    ("read_uint32le", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 10"))),
+    ("read_uint32le", 3, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_uint32be", 10, 0, Ok("305419896")),
+    ("read_uint32be", 10, 1, Ok("878082202")),
+    ("read_uint32be", 10, 6, Ok("3740303615")),
+    ("read_uint32be", 10, 7, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 7"))),
+    ("read_uint32be", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 10"))),
+    ("read_uint32be", 3, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_float_le", 10, 0, Ok("f64:470ac68240000000")),
+    ("read_float_le", 10, 1, Ok("f64:bb4f0ac680000000")),
+    ("read_float_le", 10, 6, Ok("f64:fff81e1bc0000000")),
+    ("read_float_le", 10, 7, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 7"))),
+    ("read_float_le", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 10"))),
+    ("read_float_le", 3, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_float_be", 10, 0, Ok("f64:3a468acf00000000")),
+    ("read_float_be", 10, 1, Ok("f64:3e8acf1340000000")),
+    ("read_float_be", 10, 6, Ok("f64:c3de101fe0000000")),
+    ("read_float_be", 10, 7, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 7"))),
+    ("read_float_be", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 10"))),
+    ("read_float_be", 3, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_double_le", 10, 0, Ok("f64:f0debc9a78563412")),
+    ("read_double_le", 10, 1, Ok("f64:80f0debc9a785634")),
+    ("read_double_le", 10, 2, Ok("f64:ff80f0debc9a7856")),
+    ("read_double_le", 10, 3, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 3"))),
+    ("read_double_le", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 10"))),
+    ("read_double_le", 7, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_double_be", 10, 0, Ok("f64:123456789abcdef0")),
+    ("read_double_be", 10, 1, Ok("f64:3456789abcdef080")),
+    ("read_double_be", 10, 2, Ok("f64:56789abcdef080ff")),
+    ("read_double_be", 10, 3, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 3"))),
+    ("read_double_be", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 10"))),
+    ("read_double_be", 7, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_big_int64le", 10, 0, Ok("-1090226688147180526")),
+    ("read_big_int64le", 10, 1, Ok("-9155573140817422796")),
+    ("read_big_int64le", 10, 2, Ok("-35763957581318058")),
+    ("read_big_int64le", 10, 3, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 3"))),
+    ("read_big_int64le", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 10"))),
+    ("read_big_int64le", 7, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_big_int64be", 10, 0, Ok("1311768467463790320")),
+    ("read_big_int64be", 10, 1, Ok("3771334343958392960")),
+    ("read_big_int64be", 10, 2, Ok("6230900220451913983")),
+    ("read_big_int64be", 10, 3, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 3"))),
+    ("read_big_int64be", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 10"))),
+    ("read_big_int64be", 7, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_big_uint64le", 10, 0, Ok("17356517385562371090")),
+    ("read_big_uint64le", 10, 1, Ok("9291170932892128820")),
+    ("read_big_uint64le", 10, 2, Ok("18410980116128233558")),
+    ("read_big_uint64le", 10, 3, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 3"))),
+    ("read_big_uint64le", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 10"))),
+    ("read_big_uint64le", 7, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_big_uint64be", 10, 0, Ok("1311768467463790320")),
+    ("read_big_uint64be", 10, 1, Ok("3771334343958392960")),
+    ("read_big_uint64be", 10, 2, Ok("6230900220451913983")),
+    ("read_big_uint64be", 10, 3, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 3"))),
+    ("read_big_uint64be", 10, 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 10"))),
+    ("read_big_uint64be", 7, 0, Err(("ERR_BUFFER_OUT_OF_BOUNDS", "Attempt to access memory outside buffer bounds"))),
+    ("read_int_le:1", 10, 0, Ok("18")),
+    ("read_int_le:3", 10, 0, Ok("5649426")),
+    ("read_int_le:6", 10, 4, Ok("-545714684774")),
+    ("read_int_le:6", 10, 5, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 4. Received 5"))),
+    ("read_int_le:7", 10, 0, Err(("ERR_OUT_OF_RANGE", "The value of \"byteLength\" is out of range. It must be >= 1 and <= 6. Received 7"))),
+    ("read_int_le:0", 10, 0, Err(("ERR_OUT_OF_RANGE", "The value of \"byteLength\" is out of range. It must be >= 1 and <= 6. Received 0"))),
+    ("read_int_be:1", 10, 0, Ok("18")),
+    ("read_int_be:3", 10, 0, Ok("1193046")),
+    ("read_int_be:6", 10, 4, Ok("-111338991877889")),
+    ("read_int_be:6", 10, 5, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 4. Received 5"))),
+    ("read_int_be:7", 10, 0, Err(("ERR_OUT_OF_RANGE", "The value of \"byteLength\" is out of range. It must be >= 1 and <= 6. Received 7"))),
+    ("read_int_be:0", 10, 0, Err(("ERR_OUT_OF_RANGE", "The value of \"byteLength\" is out of range. It must be >= 1 and <= 6. Received 0"))),
+    ("read_uint_le:1", 10, 0, Ok("18")),
+    ("read_uint_le:3", 10, 0, Ok("5649426")),
+    ("read_uint_le:6", 10, 4, Ok("280929262025882")),
+    ("read_uint_le:6", 10, 5, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 4. Received 5"))),
+    ("read_uint_le:7", 10, 0, Err(("ERR_OUT_OF_RANGE", "The value of \"byteLength\" is out of range. It must be >= 1 and <= 6. Received 7"))),
+    ("read_uint_le:0", 10, 0, Err(("ERR_OUT_OF_RANGE", "The value of \"byteLength\" is out of range. It must be >= 1 and <= 6. Received 0"))),
+    ("read_uint_be:1", 10, 0, Ok("18")),
+    ("read_uint_be:3", 10, 0, Ok("1193046")),
+    ("read_uint_be:6", 10, 4, Ok("170135984832767")),
+    ("read_uint_be:6", 10, 5, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 4. Received 5"))),
+    ("read_uint_be:7", 10, 0, Err(("ERR_OUT_OF_RANGE", "The value of \"byteLength\" is out of range. It must be >= 1 and <= 6. Received 7"))),
+    ("read_uint_be:0", 10, 0, Err(("ERR_OUT_OF_RANGE", "The value of \"byteLength\" is out of range. It must be >= 1 and <= 6. Received 0"))),
+];
+
+const WRITES: &[(&str, &str, usize, Expected)] = &[
+    ("write_int8", "-1", 0, Ok("1:ff000000000000000000")),
+    ("write_int8", "127", 9, Ok("10:0000000000000000007f")),
+    ("write_int8", "1", 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 9. Received 10"))),
+    ("write_uint8", "255", 3, Ok("4:000000ff000000000000")),
+    ("write_uint8", "1", 10, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 9. Received 10"))),
+    ("write_int16le", "-2", 0, Ok("2:feff0000000000000000")),
+    ("write_int16le", "1", 8, Ok("10:00000000000000000100")),
+    ("write_int16le", "1", 9, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 8. Received 9"))),
+    ("write_uint16be", "48879", 1, Ok("3:00beef00000000000000")),
+    ("write_uint16be", "1", 9, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 8. Received 9"))),
+    ("write_int32be", "-123456", 2, Ok("6:0000fffe1dc000000000")),
+    ("write_int32be", "1", 7, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 7"))),
+    ("write_uint32le", "3735928559", 6, Ok("10:000000000000efbeadde")),
+    ("write_uint32le", "1", 7, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 7"))),
+    ("write_float_le", "1.5", 0, Ok("4:0000c03f000000000000")),
+    ("write_float_le", "1", 7, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 6. Received 7"))),
+    ("write_double_be", "-0.1", 2, Ok("10:0000bfb999999999999a")),
+    ("write_double_be", "1", 3, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 3"))),
+    ("write_big_int64le", "-2", 2, Ok("10:0000feffffffffffffff")),
+    ("write_big_int64le", "1", 3, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 2. Received 3"))),
+    ("write_big_uint64be", "72623859790382856", 0, Ok("8:01020304050607080000")),
+    ("write_int_le:1", "-1", 0, Ok("1:ff000000000000000000")),
+    ("write_int_le:3", "1193046", 2, Ok("5:00005634120000000000")),
+    ("write_int_le:1", "128", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= -128 and <= 127. Received 128"))),
+    ("write_int_le:1", "-129", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= -128 and <= 127. Received -129"))),
+    ("write_int_le:1", "256", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= -128 and <= 127. Received 256"))),
+    ("write_int_le:6", "1099511627776", 4, Ok("10:00000000000000000001")),
+    ("write_int_le:6", "140737488355328", 4, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= -(2 ** 47) and < 2 ** 47. Received 140_737_488_355_328"))),
+    ("write_int_le:6", "-140737488355328", 4, Ok("10:00000000000000000080")),
+    ("write_int_le:6", "1", 5, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 4. Received 5"))),
+    ("write_int_le:7", "1", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"byteLength\" is out of range. It must be >= 1 and <= 6. Received 7"))),
+    ("write_int_be:1", "-1", 0, Ok("1:ff000000000000000000")),
+    ("write_int_be:3", "1193046", 2, Ok("5:00001234560000000000")),
+    ("write_int_be:1", "128", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= -128 and <= 127. Received 128"))),
+    ("write_int_be:1", "-129", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= -128 and <= 127. Received -129"))),
+    ("write_int_be:1", "256", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= -128 and <= 127. Received 256"))),
+    ("write_int_be:6", "1099511627776", 4, Ok("10:00000000010000000000")),
+    ("write_int_be:6", "140737488355328", 4, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= -(2 ** 47) and < 2 ** 47. Received 140_737_488_355_328"))),
+    ("write_int_be:6", "-140737488355328", 4, Ok("10:00000000800000000000")),
+    ("write_int_be:6", "1", 5, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 4. Received 5"))),
+    ("write_int_be:7", "1", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"byteLength\" is out of range. It must be >= 1 and <= 6. Received 7"))),
+    ("write_uint_le:1", "-1", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= 0 and <= 255. Received -1"))),
+    ("write_uint_le:3", "1193046", 2, Ok("5:00005634120000000000")),
+    ("write_uint_le:1", "128", 0, Ok("1:80000000000000000000")),
+    ("write_uint_le:1", "-129", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= 0 and <= 255. Received -129"))),
+    ("write_uint_le:1", "256", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= 0 and <= 255. Received 256"))),
+    ("write_uint_le:6", "1099511627776", 4, Ok("10:00000000000000000001")),
+    ("write_uint_le:6", "140737488355328", 4, Ok("10:00000000000000000080")),
+    ("write_uint_le:6", "-140737488355328", 4, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= 0 and < 2 ** 48. Received -140_737_488_355_328"))),
+    ("write_uint_le:6", "1", 5, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 4. Received 5"))),
+    ("write_uint_le:7", "1", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"byteLength\" is out of range. It must be >= 1 and <= 6. Received 7"))),
+    ("write_uint_be:1", "-1", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= 0 and <= 255. Received -1"))),
+    ("write_uint_be:3", "1193046", 2, Ok("5:00001234560000000000")),
+    ("write_uint_be:1", "128", 0, Ok("1:80000000000000000000")),
+    ("write_uint_be:1", "-129", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= 0 and <= 255. Received -129"))),
+    ("write_uint_be:1", "256", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= 0 and <= 255. Received 256"))),
+    ("write_uint_be:6", "1099511627776", 4, Ok("10:00000000010000000000")),
+    ("write_uint_be:6", "140737488355328", 4, Ok("10:00000000800000000000")),
+    ("write_uint_be:6", "-140737488355328", 4, Err(("ERR_OUT_OF_RANGE", "The value of \"value\" is out of range. It must be >= 0 and < 2 ** 48. Received -140_737_488_355_328"))),
+    ("write_uint_be:6", "1", 5, Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 and <= 4. Received 5"))),
+    ("write_uint_be:7", "1", 0, Err(("ERR_OUT_OF_RANGE", "The value of \"byteLength\" is out of range. It must be >= 1 and <= 6. Received 7"))),
+];
+
+const DATA: &str = "123456789abcdef080ff";
+
+// Integral numbers print like JS, anything else as the bits of the f64 JS would hold.
+fn format_float(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() <= 2f64.powi(53) && !(value == 0.0 && value.is_sign_negative()) {
+        return format!("{}", value as i64);
+    }
+    format!("f64:{:016x}", value.to_bits())
+}
+
+// `name:byteLength` selects the variable width accessors.
+fn split_name(name: &str) -> (&str, usize) {
+    match name.split_once(':') {
+        Some((name, byte_length)) => (name, byte_length.parse().unwrap()),
+        None => (name, 0),
+    }
+}
+
+fn read(buffer: &Buffer, name: &str, offset: usize) -> node_core::error::Result<String> {
+    let (name, byte_length) = split_name(name);
+    let at = Some(offset);
+    Ok(match name {
+        "read_int8" => buffer.read_int8(at)?.to_string(),
+        "read_uint8" => buffer.read_uint8(at)?.to_string(),
+        "read_int16le" => buffer.read_int16le(at)?.to_string(),
+        "read_int16be" => buffer.read_int16be(at)?.to_string(),
+        "read_uint16le" => buffer.read_uint16le(at)?.to_string(),
+        "read_uint16be" => buffer.read_uint16be(at)?.to_string(),
+        "read_int32le" => buffer.read_int32le(at)?.to_string(),
+        "read_int32be" => buffer.read_int32be(at)?.to_string(),
+        "read_uint32le" => buffer.read_uint32le(at)?.to_string(),
+        "read_uint32be" => buffer.read_uint32be(at)?.to_string(),
+        "read_float_le" => format_float(buffer.read_float_le(at)? as f64),
+        "read_float_be" => format_float(buffer.read_float_be(at)? as f64),
+        "read_double_le" => format_float(buffer.read_double_le(at)?),
+        "read_double_be" => format_float(buffer.read_double_be(at)?),
+        "read_big_int64le" => buffer.read_big_int64le(at)?.to_string(),
+        "read_big_int64be" => buffer.read_big_int64be(at)?.to_string(),
+        "read_big_uint64le" => buffer.read_big_uint64le(at)?.to_string(),
+        "read_big_uint64be" => buffer.read_big_uint64be(at)?.to_string(),
+        "read_int_le" => buffer.read_int_le(offset, byte_length)?.to_string(),
+        "read_int_be" => buffer.read_int_be(offset, byte_length)?.to_string(),
+        "read_uint_le" => buffer.read_uint_le(offset, byte_length)?.to_string(),
+        "read_uint_be" => buffer.read_uint_be(offset, byte_length)?.to_string(),
+        _ => unreachable!("{name}"),
+    })
+}
+
+fn write(buffer: &mut Buffer, name: &str, value: &str, offset: usize) -> node_core::error::Result<usize> {
+    let (name, byte_length) = split_name(name);
+    let at = Some(offset);
+    match name {
+        "write_int8" => buffer.write_int8(value.parse().unwrap(), at),
+        "write_uint8" => buffer.write_uint8(value.parse().unwrap(), at),
+        "write_int16le" => buffer.write_int16le(value.parse().unwrap(), at),
+        "write_uint16be" => buffer.write_uint16be(value.parse().unwrap(), at),
+        "write_int32be" => buffer.write_int32be(value.parse().unwrap(), at),
+        "write_uint32le" => buffer.write_uint32le(value.parse().unwrap(), at),
+        "write_float_le" => buffer.write_float_le(value.parse().unwrap(), at),
+        "write_double_be" => buffer.write_double_be(value.parse().unwrap(), at),
+        "write_big_int64le" => buffer.write_big_int64le(value.parse().unwrap(), at),
+        "write_big_uint64be" => buffer.write_big_uint64be(value.parse().unwrap(), at),
+        "write_int_le" => buffer.write_int_le(value.parse().unwrap(), offset, byte_length),
+        "write_int_be" => buffer.write_int_be(value.parse().unwrap(), offset, byte_length),
+        "write_uint_le" => buffer.write_uint_le(value.parse().unwrap(), offset, byte_length),
+        "write_uint_be" => buffer.write_uint_be(value.parse().unwrap(), offset, byte_length),
+        _ => unreachable!("{name}"),
+    }
+}
+
+#[test]
+fn reads() {
+    let data = bytes(DATA);
+    for (name, length, offset, expected) in READS {
+        let buffer = Buffer::from_slice(&data[..*length]);
+        check(&format!("{name}({offset}) on {length} bytes"), read(&buffer, name, *offset), expected);
+    }
+}
+
+#[test]
+fn writes() {
+    for (name, value, offset, expected) in WRITES {
+        let mut buffer = Buffer::from_vec(vec![0; 10]);
+        let actual = write(&mut buffer, name, value, *offset).map(|offset| format!("{offset}:{}", to_hex(&buffer)));
+        check(&format!("{name}({value}, {offset})"), actual, expected);
+    }
+}
@@ -0,0 +1,92 @@
+//! `Blob` and `File` against what Node returns for the same parts.
+
+use std::sync::Arc;
+
+use node_buffer::blob::{Blob, BlobPart, BlobSource, File};
+use node_buffer::Buffer;
+
+// A source reading `length` bytes whose values are their offsets.
+struct Counting(usize);
+
+impl BlobSource for Counting {
+    fn size(&self) -> usize {
+        self.0
+    }
+
+    fn read_at(&self, offset: usize, buf: &mut [u8]) -> node_core::error::Result<usize> {
+        let read = buf.len().min(self.0.saturating_sub(offset));
+        for (i, byte) in buf[..read].iter_mut().enumerate() {
+            *byte = (offset + i) as u8;
+        }
+        Ok(read)
+    }
+}
+
+#[test]
+fn parts_are_copied_and_joined() {
+    let mut buffer = Buffer::from_slice(b"abc");
+    let inner = Blob::new(&[BlobPart::String("de")], "");
+    let blob = Blob::new(&[BlobPart::Buffer(&buffer), BlobPart::Blob(&inner), BlobPart::Bytes(b"f")], "Text/Plain");
+    buffer.write_uint8(b'x', Some(0)).unwrap();
+
+    assert_eq!(blob.size(), 6);
+    assert_eq!(blob.content_type(), "text/plain");
+    assert_eq!(blob.text().unwrap(), "abcdef");
+    assert_eq!(Blob::new(&[], "\u{e9}").content_type(), "");
+}
+
+#[test]
+fn slice_across_parts() {
+    let blob = Blob::new(&[BlobPart::String("hello "), BlobPart::String("world")], "text/plain");
+    assert_eq!(blob.slice(Some(4), Some(8), None).text().unwrap(), "o wo");
+    assert_eq!(blob.slice(Some(-5), None, Some("a/b")).text().unwrap(), "world");
+    assert_eq!(blob.slice(Some(-5), None, Some("a/b")).content_type(), "a/b");
+    assert_eq!(blob.slice(Some(8), Some(2), None).size(), 0);
+    assert_eq!(blob.slice(None, Some(100), None).size(), 11);
+}
+
+#[test]
+fn read_at_within_a_large_part() {
+    let data = (0..200_000).map(|i| i as u8).collect::<Vec<u8>>();
+    let blob = Blob::new(&[BlobPart::Bytes(&data), BlobPart::String("tail")], "");
+    let mut buf = [0_u8; 6];
+    assert_eq!(blob.read_at(199_998, &mut buf).unwrap(), 6);
+    assert_eq!(buf, [data[199_998], data[199_999], b't', b'a', b'i', b'l']);
+    assert_eq!(blob.read_at(200_004, &mut buf).unwrap(), 0);
+}
+
+#[test]
+fn text_drops_a_leading_bom() {
+    let blob = Blob::new(&[BlobPart::Bytes(b"\xef\xbb\xbfhi")], "");
+    assert_eq!(blob.text().unwrap(), "hi");
+    assert_eq!(blob.bytes().unwrap().len(), 5);
+}
+
+#[test]
+fn stream_chunks_are_copies() {
+    let blob = Blob::new(&[BlobPart::String("abcde"), BlobPart::String("fg")], "");
+    let mut chunks = blob.stream(Some(2)).collect::<node_core::error::Result<Vec<Buffer>>>().unwrap();
+    let text = chunks.iter().map(|chunk| chunk.as_string(None, None, None)).collect::<Vec<String>>();
+    assert_eq!(text, ["ab", "cd", "e", "fg"]);
+
+    chunks[0].write_uint8(b'z', Some(0)).unwrap();
+    assert_eq!(blob.text().unwrap(), "abcdefg");
+}
+
+#[test]
+fn source_parts_are_read_lazily() {
+    let blob = Blob::from_source(Arc::new(Counting(1000)), "application/octet-stream");
+    let slice = blob.slice(Some(300), Some(304), None);
+    assert_eq!(slice.bytes().unwrap(), [44, 45, 46, 47]);
+    assert_eq!(blob.array_buffer().unwrap().length(), 1000);
+}
+
+#[test]
+fn file_keeps_name_and_last_modified() {
+    let file = File::new(&[BlobPart::String("content")], "notes.txt", "text/plain", Some(42));
+    assert_eq!(file.name(), "notes.txt");
+    assert_eq!(file.last_modified(), 42);
+    assert_eq!(file.size(), 7);
+    assert_eq!(file.text().unwrap(), "content");
+    assert!(File::new(&[], "now", "", None).last_modified() > 0);
+}
@@ -0,0 +1,31 @@
+//! Helpers shared by the table driven tests. Expected values in those tables are what Node 20
+//! returns or throws for the same call.
+
+#![allow(dead_code)]
+
+use node_buffer::{Buffer, StringEncoding};
+
+/// The value as a string, or Node's error `code` and `message`.
+pub type Expected = Result<&'static str, (&'static str, &'static str)>;
+
+pub fn bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+pub fn to_hex(buffer: &Buffer) -> String {
+    buffer.as_string(Some(StringEncoding::Hex), None, None)
+}
+
+pub fn check(context: &str, actual: node_core::error::Result<String>, expected: &Expected) {
+    match (actual, expected) {
+        (Ok(actual), Ok(expected)) => assert_eq!(actual, *expected, "{context}"),
+        (Err(error), Err((code, message))) => {
+            assert_eq!(node_core::error::get_custom_error_code(&error), Some(*code), "{context}");
+            assert_eq!(error.to_string(), *message, "{context}");
+        }
+        (actual, expected) => panic!("{context}: got {actual:?}, expected {expected:?}"),
+    }
+}
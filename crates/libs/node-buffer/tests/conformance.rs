@@ -0,0 +1,95 @@
+//! Table driven checks of encoding and decoding strings against Node's `Buffer`.
+
+mod common;
+
+use node_buffer::{Buffer, StringEncoding};
+
+use common::{bytes, to_hex};
+
+const ENCODE: &[(&str, &str, &str)] = &[
+    ("utf8", "hello", "68656c6c6f"),
+    ("utf8", "h\u{e9}llo w\u{f6}rld", "68c3a96c6c6f2077c3b6726c64"),
+    ("utf8", "\u{20ac} \u{1d11e} \u{1f600}", "e282ac20f09d849e20f09f9880"),
+    ("utf-8", "", ""),
+    ("ucs2", "abc", "610062006300"),
+    ("utf16le", "\u{20ac}\u{1d11e}", "ac2034d81edd"),
+    ("utf-16le", "h\u{e9}llo", "6800e9006c006c006f00"),
+    ("latin1", "h\u{e9}llo", "68e96c6c6f"),
+    ("latin1", "\u{20ac} overflow", "ac206f766572666c6f77"),
+    ("binary", "\u{ff}\u{100}", "ff00"),
+    ("ascii", "hello", "68656c6c6f"),
+    ("ascii", "h\u{e9}llo", "68e96c6c6f"),
+    ("hex", "00ff10", "00ff10"),
+    ("hex", "DEADbeef", "deadbeef"),
+    ("hex", "abc", "ab"),
+    ("hex", "abzz12", "ab"),
+    ("hex", "zz", ""),
+    ("hex", "0", ""),
+    ("base64", "aGVsbG8=", "68656c6c6f"),
+    ("base64", "aGVsbG8", "68656c6c6f"),
+    ("base64", "aGV sbG8=\u{a}", "68656c6c6f"),
+    ("base64", "aGVs=bG8", "68656c"),
+    ("base64", "_-8=", "ffef"),
+    ("base64", "YQ", "61"),
+    ("base64", "Y", ""),
+    ("base64url", "aGVsbG8", "68656c6c6f"),
+    ("base64url", "_-8", "ffef"),
+    ("base64url", "+/8=", "fbff"),
+    ("UTF8", "case", "63617365"),
+    ("Latin1", "case", "63617365"),
+];
+
+const DECODE: &[(&str, &str, &str)] = &[
+    ("utf8", "68656c6c6f", "hello"),
+    ("utf8", "c3a9e282ac", "\u{e9}\u{20ac}"),
+    ("utf8", "f09f9880", "\u{1f600}"),
+    ("utf8", "ff", "\u{fffd}"),
+    ("utf8", "c3", "\u{fffd}"),
+    ("utf8", "e282", "\u{fffd}"),
+    ("utf8", "f09f98", "\u{fffd}"),
+    ("utf8", "eda080", "\u{fffd}\u{fffd}\u{fffd}"),
+    ("utf8", "c0af", "\u{fffd}\u{fffd}"),
+    ("utf8", "61ff62", "a\u{fffd}b"),
+    ("ucs2", "610062006300", "abc"),
+    ("utf16le", "ac203dd81edd", "\u{20ac}\u{1f51e}"),
+    ("utf16le", "610062", "a"),
+    ("latin1", "68e9ff00", "h\u{e9}\u{ff}\u{0}"),
+    ("binary", "80ff", "\u{80}\u{ff}"),
+    ("ascii", "68656c6c6f", "hello"),
+    ("ascii", "e9ff80", "i\u{7f}\u{0}"),
+    ("hex", "00ff10", "00ff10"),
+    ("hex", "", ""),
+    ("base64", "68656c6c6f", "aGVsbG8="),
+    ("base64", "fbff", "+/8="),
+    ("base64", "00", "AA=="),
+    ("base64url", "68656c6c6f", "aGVsbG8"),
+    ("base64url", "fbff", "-_8"),
+];
+
+fn encoding(name: &str) -> StringEncoding {
+    name.parse().unwrap()
+}
+
+#[test]
+fn encode() {
+    for (name, input, expected) in ENCODE {
+        let buffer = Buffer::from_utf8_string(input.as_bytes(), encoding(name));
+        assert_eq!(to_hex(&buffer), *expected, "Buffer.from({input:?}, {name:?})");
+    }
+}
+
+#[test]
+fn decode() {
+    for (name, input, expected) in DECODE {
+        let buffer = Buffer::from_vec(bytes(input));
+        let actual = buffer.as_string(Some(encoding(name)), None, None);
+        assert_eq!(actual, *expected, "Buffer.from({input:?}, \"hex\").toString({name:?})");
+    }
+}
+
+#[test]
+fn unknown_encoding() {
+    let error = "utf9".parse::<StringEncoding>().unwrap_err();
+    assert_eq!(node_core::error::get_custom_error_code(&error), Some("ERR_UNKNOWN_ENCODING"));
+    assert_eq!(error.to_string(), "Unknown encoding: utf9");
+}
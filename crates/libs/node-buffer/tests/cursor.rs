@@ -0,0 +1,48 @@
+//! `BufferCursor` over buffers whose memory may be shared with clones.
+
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+
+use node_buffer::{Buffer, BufferCursor};
+
+#[test]
+fn reads_a_shared_buffer_in_order() {
+    let source = Buffer::from_slice(&(0..100_000).map(|i| i as u8).collect::<Vec<u8>>());
+    let mut cursor = BufferCursor::new(source.clone());
+    let mut read = Vec::new();
+    let mut chunk = [0_u8; 8192];
+    loop {
+        match cursor.read(&mut chunk).unwrap() {
+            0 => break,
+            n => read.extend_from_slice(&chunk[..n]),
+        }
+    }
+    assert_eq!(read, source.with_slice(|bytes| bytes.to_vec()));
+    assert_eq!(cursor.position(), 100_000);
+}
+
+#[test]
+fn read_exact_fails_past_the_end() {
+    let mut cursor = BufferCursor::new(Buffer::from_slice(b"abc"));
+    let mut buf = [0_u8; 2];
+    cursor.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"ab");
+    assert_eq!(cursor.read_exact(&mut buf).unwrap_err().kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn buf_read_lines() {
+    let mut cursor = BufferCursor::new(Buffer::from_slice(b"one\ntwo\nthree"));
+    let lines = (&mut cursor).lines().collect::<std::io::Result<Vec<String>>>().unwrap();
+    assert_eq!(lines, ["one", "two", "three"]);
+    assert!(cursor.fill_buf().unwrap().is_empty());
+}
+
+#[test]
+fn writes_show_through_clones_and_stop_at_the_end() {
+    let buffer = Buffer::alloc_unsafe_slow(8);
+    let mut cursor = BufferCursor::new(buffer.clone());
+    cursor.seek(SeekFrom::Start(5)).unwrap();
+    assert_eq!(cursor.write(b"hello").unwrap(), 3);
+    assert_eq!(cursor.write(b"!").unwrap(), 0);
+    assert_eq!(buffer.as_string(None, Some(5), None), "hel");
+}
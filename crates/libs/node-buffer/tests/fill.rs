@@ -0,0 +1,73 @@
+//! Table driven checks of `buf.fill` and `Buffer.alloc(size, fill)` against Node's `Buffer`.
+
+mod common;
+
+use node_buffer::{Buffer, FillValue, StringEncoding};
+
+use common::{check, to_hex, Expected};
+
+const INVALID_ZZ: Expected = Err(("ERR_INVALID_ARG_VALUE", "The argument 'value' is invalid. Received 'zz'"));
+
+// buffer size, value, offset, end, encoding, then the buffer's hex
+type FillCase = (usize, FillValue<'static>, Option<usize>, Option<usize>, Option<&'static str>, Expected);
+
+const FILL: &[FillCase] = &[
+    (5, FillValue::String("abc"), None, None, None, Ok("6162636162")),
+    (5, FillValue::String("ab"), Some(1), Some(4), None, Ok("0061626100")),
+    (5, FillValue::String("\u{20ac}"), None, None, None, Ok("e282ace282")),
+    (4, FillValue::String(""), None, None, None, Ok("00000000")),
+    (4, FillValue::String("abzz"), None, None, Some("hex"), Ok("abababab")),
+    (4, FillValue::String("zz"), None, None, Some("hex"), INVALID_ZZ),
+    (4, FillValue::String("0"), None, None, Some("hex"), Err(("ERR_INVALID_ARG_VALUE", "The argument 'value' is invalid. Received '0'"))),
+    (6, FillValue::String("aGk="), None, None, Some("base64"), Ok("686968696869")),
+    (4, FillValue::String("\u{20ac}"), None, None, Some("ucs2"), Ok("ac20ac20")),
+    (4, FillValue::String("x"), Some(0), Some(5), None, Err(("ERR_OUT_OF_RANGE", "The value of \"end\" is out of range. It must be >= 0 && <= 4. Received 5"))),
+    (4, FillValue::String("x"), Some(3), Some(1), None, Ok("00000000")),
+    (4, FillValue::String("x"), Some(4), Some(4), None, Ok("00000000")),
+    (3, FillValue::Number(257), None, None, None, Ok("010101")),
+    (3, FillValue::Number(-1), None, None, None, Ok("ffffff")),
+    (5, FillValue::Bytes(&[1, 2]), Some(1), None, None, Ok("0001020102")),
+    (5, FillValue::Bytes(&[]), None, None, None, Err(("ERR_INVALID_ARG_VALUE", "The argument 'value' is invalid. Received Uint8Array(0) []"))),
+];
+
+fn filled(buffer: &Buffer, result: node_core::error::Result<()>) -> node_core::error::Result<String> {
+    result.map(|_| to_hex(buffer))
+}
+
+#[test]
+fn fill() {
+    for (size, value, offset, end, encoding, expected) in FILL {
+        let encoding = encoding.map(|name| name.parse::<StringEncoding>().unwrap());
+        let mut buffer = Buffer::from_vec(vec![0; *size]);
+        let result = buffer.fill(*value, *offset, *end, encoding).map(|_| ());
+        check(&format!("Buffer.alloc({size}).fill({value:?}, {offset:?}, {end:?}, {encoding:?})"), filled(&buffer, result), expected);
+    }
+}
+
+#[test]
+fn fill_with_a_buffer() {
+    let pattern = Buffer::from_slice(&[7, 8]);
+    let mut buffer = Buffer::from_vec(vec![0; 5]);
+    buffer.fill(FillValue::Buffer(&pattern), Some(1), None, None).unwrap();
+    assert_eq!(to_hex(&buffer), "0007080708");
+
+    // the pattern is read before the fill overwrites it
+    let mut shared = Buffer::from_slice(b"ab");
+    let source = shared.clone();
+    shared.fill(FillValue::Buffer(&source), None, None, None).unwrap();
+    assert_eq!(to_hex(&shared), "6162");
+
+    let empty = Buffer::from_vec(Vec::new());
+    let result = buffer.fill(FillValue::Buffer(&empty), None, None, None).map(|_| ());
+    check("fill(<Buffer >)", filled(&buffer, result), &Err(("ERR_INVALID_ARG_VALUE", "The argument 'value' is invalid. Received <Buffer >")));
+}
+
+#[test]
+fn alloc_with_a_fill() {
+    let filled = |result: node_core::error::Result<Buffer>| result.map(|buffer| to_hex(&buffer));
+    check("Buffer.alloc(5, 'ab')", filled(Buffer::alloc(5, Some(FillValue::String("ab")), None)), &Ok("6162616261"));
+    check("Buffer.alloc(4, '')", filled(Buffer::alloc(4, Some(FillValue::String("")), None)), &Ok("00000000"));
+    check("Buffer.alloc(4, 'zz', 'hex')", filled(Buffer::alloc(4, Some(FillValue::String("zz")), Some(StringEncoding::Hex))), &INVALID_ZZ);
+    let utf16 = "\u{20ac}".encode_utf16().collect::<Vec<u16>>();
+    check("Buffer.alloc(3, '€') utf16", filled(Buffer::alloc(3, Some(FillValue::Utf16String(&utf16)), None)), &Ok("e282ac"));
+}
@@ -0,0 +1,184 @@
+//! Table driven checks of compare, equals and indexOf/lastIndexOf/includes.
+
+mod common;
+
+use std::ffi::CString;
+
+use node_buffer::{Buffer, IndexOfValue, StringEncoding};
+
+use common::{bytes, check, Expected};
+
+const COMPARE: &[(&str, &str, i32)] = &[
+    ("", "", 0),
+    ("00", "", 1),
+    ("", "00", -1),
+    ("0102", "0102", 0),
+    ("0102", "0103", -1),
+    ("02", "0103", 1),
+    ("010203", "0102", 1),
+    ("ff", "00", 1),
+];
+
+const COMPARE_RANGE: &[([Option<usize>; 4], Expected)] = &[
+    ([None, None, None, None], Ok("-1")),
+    ([Some(0), Some(3), Some(2), Some(5)], Ok("0")),
+    ([Some(3), Some(5), Some(0), Some(2)], Ok("-1")),
+    ([Some(0), Some(0), Some(0), Some(0)], Ok("0")),
+    ([Some(0), Some(1), Some(1), Some(1)], Ok("-1")),
+    ([Some(1), Some(1), Some(0), Some(1)], Ok("1")),
+    ([Some(0), Some(6), None, None], Err(("ERR_OUT_OF_RANGE", "The value of \"targetEnd\" is out of range. It must be >= 0 && <= 5. Received 6"))),
+    ([None, None, Some(0), Some(6)], Err(("ERR_OUT_OF_RANGE", "The value of \"sourceEnd\" is out of range. It must be >= 0 && <= 5. Received 6"))),
+    ([Some(6), None, None, None], Ok("1")),
+    ([None, None, Some(6), None], Ok("-1")),
+    ([Some(2), Some(4), Some(4), Some(5)], Ok("-1")),
+];
+
+const INDEX_OF: &[(&str, &str, Option<i64>, i64, i64)] = &[
+    ("s", "utf8", None, 3, 6),
+    ("s", "utf8", Some(0), 3, -1),
+    ("s", "utf8", Some(4), 6, 3),
+    ("s", "utf8", Some(12), -1, 6),
+    ("s", "utf8", Some(-5), -1, 6),
+    ("s", "utf8", Some(-100), 3, -1),
+    ("s", "utf8", Some(100), -1, 6),
+    ("buffer", "utf8", None, 10, 20),
+    ("buffer", "utf8", Some(0), 10, -1),
+    ("buffer", "utf8", Some(4), 10, -1),
+    ("buffer", "utf8", Some(12), 20, 10),
+    ("buffer", "utf8", Some(-5), -1, 20),
+    ("buffer", "utf8", Some(-100), 10, -1),
+    ("buffer", "utf8", Some(100), -1, 20),
+    ("a buffer", "utf8", None, 8, 18),
+    ("a buffer", "utf8", Some(0), 8, -1),
+    ("a buffer", "utf8", Some(4), 8, -1),
+    ("a buffer", "utf8", Some(12), 18, 8),
+    ("a buffer", "utf8", Some(-5), -1, 18),
+    ("a buffer", "utf8", Some(-100), 8, -1),
+    ("a buffer", "utf8", Some(100), -1, 18),
+    ("", "utf8", None, 0, 26),
+    ("", "utf8", Some(0), 0, 0),
+    ("", "utf8", Some(4), 4, 4),
+    ("", "utf8", Some(12), 12, 12),
+    ("", "utf8", Some(-5), 21, 21),
+    ("", "utf8", Some(-100), 0, 0),
+    ("", "utf8", Some(100), 26, 26),
+    ("zz", "utf8", None, -1, -1),
+    ("zz", "utf8", Some(0), -1, -1),
+    ("zz", "utf8", Some(4), -1, -1),
+    ("zz", "utf8", Some(12), -1, -1),
+    ("zz", "utf8", Some(-5), -1, -1),
+    ("zz", "utf8", Some(-100), -1, -1),
+    ("zz", "utf8", Some(100), -1, -1),
+    ("6275", "hex", None, 10, 20),
+    ("6275", "hex", Some(0), 10, -1),
+    ("6275", "hex", Some(4), 10, -1),
+    ("6275", "hex", Some(12), 20, 10),
+    ("6275", "hex", Some(-5), -1, 20),
+    ("6275", "hex", Some(-100), 10, -1),
+    ("6275", "hex", Some(100), -1, 20),
+    ("YSBi", "base64", None, 8, 18),
+    ("YSBi", "base64", Some(0), 8, -1),
+    ("YSBi", "base64", Some(4), 8, -1),
+    ("YSBi", "base64", Some(12), 18, 8),
+    ("YSBi", "base64", Some(-5), -1, 18),
+    ("YSBi", "base64", Some(-100), 8, -1),
+    ("YSBi", "base64", Some(100), -1, 18),
+];
+
+const INDEX_OF_UCS2: &[(&str, Option<i64>, i64, i64)] = &[
+    ("b", None, 2, 8),
+    ("b", Some(0), 2, -1),
+    ("b", Some(3), 2, 2),
+    ("b", Some(4), 8, 2),
+    ("b", Some(-4), 8, 8),
+    ("ca", None, 4, 4),
+    ("ca", Some(0), 4, -1),
+    ("ca", Some(3), 4, -1),
+    ("ca", Some(4), 4, 4),
+    ("ca", Some(-4), -1, 4),
+    ("abc", None, 0, 6),
+    ("abc", Some(0), 0, 0),
+    ("abc", Some(3), 6, 0),
+    ("abc", Some(4), 6, 0),
+    ("abc", Some(-4), -1, 6),
+    ("d", None, -1, -1),
+    ("d", Some(0), -1, -1),
+    ("d", Some(3), -1, -1),
+    ("d", Some(4), -1, -1),
+    ("d", Some(-4), -1, -1),
+];
+
+fn encoding(name: &str) -> StringEncoding {
+    name.parse().unwrap()
+}
+
+#[test]
+fn compare() {
+    for (a, b, expected) in COMPARE {
+        let a = Buffer::from_vec(bytes(a));
+        let b = Buffer::from_vec(bytes(b));
+        assert_eq!(a.compare(&b), *expected, "Buffer.compare({a:?}, {b:?})");
+        assert_eq!(a.equals(&b), *expected == 0, "{a:?}.equals({b:?})");
+    }
+}
+
+#[test]
+fn compare_range() {
+    let source = Buffer::from_vec(bytes("0102030405"));
+    let target = Buffer::from_vec(bytes("0304050607"));
+    for ([target_start, target_end, source_start, source_end], expected) in COMPARE_RANGE {
+        let actual = source
+            .compare_range(&target, *target_start, *target_end, *source_start, *source_end)
+            .map(|result| result.to_string());
+        check(&format!("compare({target_start:?}, {target_end:?}, {source_start:?}, {source_end:?})"), actual, expected);
+    }
+}
+
+#[test]
+fn index_of() {
+    let buffer = Buffer::from_slice(b"this is a buffer, a buffer");
+    for (needle, name, offset, first, last) in INDEX_OF {
+        let value = CString::new(*needle).unwrap();
+        let encoding = Some(encoding(name));
+        let context = format!("{needle:?}, {offset:?}, {name:?}");
+        assert_eq!(buffer.index_of(IndexOfValue::String(&value), *offset, encoding), *first, "indexOf({context})");
+        assert_eq!(buffer.last_index_of(IndexOfValue::String(&value), *offset, encoding), *last, "lastIndexOf({context})");
+        assert_eq!(buffer.includes(IndexOfValue::String(&value), *offset, encoding), *first != -1, "includes({context})");
+    }
+}
+
+#[test]
+fn index_of_ucs2() {
+    let buffer = Buffer::from_utf8_string(b"abcabc", StringEncoding::Ucs2);
+    for (needle, offset, first, last) in INDEX_OF_UCS2 {
+        let value = CString::new(*needle).unwrap();
+        let encoding = Some(StringEncoding::Ucs2);
+        let context = format!("{needle:?}, {offset:?}, \"ucs2\"");
+        assert_eq!(buffer.index_of(IndexOfValue::String(&value), *offset, encoding), *first, "indexOf({context})");
+        assert_eq!(buffer.last_index_of(IndexOfValue::String(&value), *offset, encoding), *last, "lastIndexOf({context})");
+    }
+}
+
+#[test]
+fn index_of_byte() {
+    let buffer = Buffer::from_slice(b"abcabc");
+    assert_eq!(buffer.index_of(IndexOfValue::Byte(b'c'), None, None), 2);
+    assert_eq!(buffer.index_of(IndexOfValue::Byte(b'c'), Some(3), None), 5);
+    assert_eq!(buffer.last_index_of(IndexOfValue::Byte(b'a'), None, None), 3);
+    assert_eq!(buffer.last_index_of(IndexOfValue::Byte(b'a'), Some(-4), None), 0);
+    assert_eq!(buffer.index_of(IndexOfValue::Byte(b'z'), None, None), -1);
+}
+
+#[test]
+fn index_of_length_delimited_strings() {
+    let buffer = Buffer::from_slice(b"a\0b, a\0b");
+    assert_eq!(buffer.index_of(IndexOfValue::Utf8String(b"a\0b"), None, None), 0);
+    assert_eq!(buffer.last_index_of(IndexOfValue::Utf8String(b"a\0b"), None, None), 5);
+    assert_eq!(buffer.index_of(IndexOfValue::Utf16String(&[0x61, 0, 0x62]), Some(1), None), 5);
+    assert!(!buffer.includes(IndexOfValue::Utf8String(b"b\0"), None, None));
+
+    // a lone surrogate is searched for as U+FFFD in utf8 and as the code unit in ucs2
+    let buffer = Buffer::from_slice(b"x\xef\xbf\xbd\x00\xd8");
+    assert_eq!(buffer.index_of(IndexOfValue::Utf16String(&[0xd800]), None, None), 1);
+    assert_eq!(buffer.index_of(IndexOfValue::Utf16String(&[0xd800]), None, Some(StringEncoding::Ucs2)), 4);
+}
@@ -0,0 +1,84 @@
+//! Buffers whose memory is shared with clones and views, which is read and written atomically
+//! instead of in place.
+
+use std::thread;
+
+use node_buffer::{Buffer, IndexOfValue, StringEncoding};
+
+// Larger than the chunks shared memory is searched and compared in.
+const LENGTH: usize = 200 * 1024;
+
+fn shared(length: usize) -> (Buffer, Buffer) {
+    let buffer = Buffer::alloc_unsafe_slow(length);
+    let clone = buffer.clone();
+    (buffer, clone)
+}
+
+#[test]
+fn writes_through_clones_are_visible() {
+    let (mut buffer, clone) = shared(16);
+    buffer.write_uint32le(0xdeadbeef, Some(3)).unwrap();
+    assert_eq!(clone.read_uint32le(Some(3)).unwrap(), 0xdeadbeef);
+
+    let mut view = clone.subarray(Some(8), None);
+    view.fill(node_buffer::FillValue::Number(7), None, None, None).unwrap();
+    assert_eq!(buffer.as_string(Some(StringEncoding::Hex), Some(6), Some(10)), "de000707");
+}
+
+#[test]
+fn write_keeps_concurrent_writes_outside_what_it_wrote() {
+    const LENGTH: usize = 4096;
+    let (mut buffer, mut clone) = shared(LENGTH);
+    let writer = thread::spawn(move || {
+        for i in 0..2000 {
+            clone.write_uint8((i % 255) as u8 + 1, Some(LENGTH - 100 + i % 50)).unwrap();
+        }
+        clone
+    });
+    for _ in 0..2000 {
+        assert_eq!(buffer.write("x", Some(0), None, None).unwrap(), 1);
+        buffer.with_slice_mut(|bytes| bytes[1] = b'y');
+    }
+    let clone = writer.join().unwrap();
+    for i in 1950..2000 {
+        assert_eq!(clone.read_uint8(Some(LENGTH - 100 + i % 50)).unwrap(), (i % 255) as u8 + 1);
+    }
+    assert_eq!(buffer.as_string(None, Some(0), Some(2)), "xy");
+}
+
+#[test]
+fn search_and_compare_shared_memory_across_chunks() {
+    let (mut buffer, _clone) = shared(LENGTH);
+    let needle = b"needle";
+    for position in [0, 64 * 1024 - 3, 128 * 1024 - 1, LENGTH - needle.len()] {
+        buffer.fill(node_buffer::FillValue::Number(0), None, None, None).unwrap();
+        buffer.set(&Buffer::from_slice(needle), position).unwrap();
+        assert_eq!(buffer.index_of(IndexOfValue::Bytes(needle), None, None), position as i64);
+        assert_eq!(buffer.last_index_of(IndexOfValue::Bytes(needle), None, None), position as i64);
+
+        let copy = Buffer::from_buffer(&buffer);
+        assert!(buffer.equals(&copy));
+        let mut other = copy.clone();
+        other.write_uint8(b'm', Some(position)).unwrap();
+        assert_eq!(buffer.compare(&other), 1);
+        assert_eq!(other.compare(&buffer), -1);
+    }
+
+    // UTF-16 matches stay on even offsets
+    buffer.fill(node_buffer::FillValue::Number(0), None, None, None).unwrap();
+    buffer.write_uint16le(0x61, Some(64 * 1024 + 1)).unwrap();
+    buffer.write_uint16le(0x61, Some(64 * 1024 + 4)).unwrap();
+    let ucs2 = Some(StringEncoding::Ucs2);
+    assert_eq!(buffer.index_of(IndexOfValue::Bytes(&[0x61, 0]), None, ucs2), 64 * 1024 + 4);
+}
+
+#[test]
+fn copy_within_shared_memory_moves_only_the_target_range() {
+    let (mut buffer, _clone) = shared(LENGTH);
+    buffer.with_slice_mut(|bytes| bytes.iter_mut().enumerate().for_each(|(i, byte)| *byte = i as u8));
+    assert_eq!(buffer.copy_within(1, 0, Some(10_000)), 10_000);
+    assert_eq!(buffer.read_uint8(Some(0)).unwrap(), 0);
+    assert_eq!(buffer.read_uint8(Some(1)).unwrap(), 0);
+    assert_eq!(buffer.read_uint8(Some(10_000)).unwrap(), (9_999 % 256) as u8);
+    assert_eq!(buffer.read_uint8(Some(10_001)).unwrap(), (10_001 % 256) as u8);
+}
@@ -0,0 +1,55 @@
+//! `StringDecoder` fed one chunk at a time, the expected strings are what Node 20's
+//! `string_decoder` returns for the same writes.
+
+use node_buffer::string_decoder::StringDecoder;
+use node_buffer::StringEncoding;
+
+fn decode(encoding: StringEncoding, chunks: &[&[u8]], end: &[u8]) -> Vec<String> {
+    let mut decoder = StringDecoder::new(encoding);
+    let mut ret = chunks.iter().map(|chunk| decoder.write(chunk)).collect::<Vec<String>>();
+    ret.push(decoder.end(Some(end)));
+    ret
+}
+
+#[test]
+fn utf8_holds_split_characters() {
+    // € is e2 82 ac, 😍 is f0 9f 98 8d
+    assert_eq!(decode(StringEncoding::Utf8, &[b"a\xe2", b"\x82", b"\xac\xf0\x9f", b"\x98\x8db"], b""), ["a", "", "€", "😍b", ""]);
+    assert_eq!(decode(StringEncoding::Utf8, &[b"\xe2\x82"], b""), ["", "\u{FFFD}"]);
+    assert_eq!(decode(StringEncoding::Utf8, &[b"\xe2"], b"\x82\xac"), ["", "€"]);
+    assert_eq!(decode(StringEncoding::Utf8, &[b"\xff"], b""), ["\u{FFFD}", ""]);
+}
+
+#[test]
+fn utf16le_holds_odd_bytes_and_high_surrogates() {
+    // 😍 is d83d de0d
+    assert_eq!(decode(StringEncoding::Utf16le, &[b"a\x00\x3d", b"\xd8", b"\x0d", b"\xde"], b""), ["a", "", "", "😍", ""]);
+    assert_eq!(decode(StringEncoding::Ucs2, &[b"a\x00b"], b""), ["a", ""]);
+    assert_eq!(decode(StringEncoding::Utf16le, &[b"\x3d\xd8"], b""), ["", "\u{FFFD}"]);
+}
+
+#[test]
+fn base64_carries_partial_groups() {
+    assert_eq!(decode(StringEncoding::Base64, &[b"ab", b"cd", b"ef"], b""), ["", "YWJj", "ZGVm", ""]);
+    assert_eq!(decode(StringEncoding::Base64, &[b"abcd"], b""), ["YWJj", "ZA=="]);
+    assert_eq!(decode(StringEncoding::Base64, &[b"a"], b"b"), ["", "YWI="]);
+    assert_eq!(decode(StringEncoding::Base64Url, &[b"\xfb\xff", b"\xfe"], b"a"), ["", "-__-", "YQ"]);
+}
+
+#[test]
+fn single_byte_encodings_decode_each_chunk_as_is() {
+    assert_eq!(decode(StringEncoding::Latin1, &[b"a\x00\xff"], b"\xe9"), ["a\0ÿ", "é"]);
+    assert_eq!(decode(StringEncoding::Utf8, &[b"a\x00b"], b""), ["a\0b", ""]);
+    assert_eq!(decode(StringEncoding::Hex, &[b"\x00\xff"], b"\x10"), ["00ff", "10"]);
+    assert_eq!(decode(StringEncoding::Ascii, &[b"abc"], b""), ["abc", ""]);
+}
+
+#[test]
+fn end_resets_the_decoder() {
+    let mut decoder = StringDecoder::new(StringEncoding::Utf8);
+    assert_eq!(decoder.write(b"\xe2\x82"), "");
+    assert_eq!(decoder.end(None), "\u{FFFD}");
+    assert_eq!(decoder.write(b"\xac"), "\u{FFFD}");
+    assert_eq!(decoder.write(b"\xe2\x82\xac"), "€");
+    assert_eq!(decoder.encoding(), StringEncoding::Utf8);
+}
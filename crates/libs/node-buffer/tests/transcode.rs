@@ -0,0 +1,91 @@
+//! Table driven checks of `buffer.transcode`, `isUtf8` and `isAscii` against Node's `buffer`.
+
+mod common;
+
+use node_buffer::{is_ascii, is_utf8, transcode, StringEncoding};
+
+use common::{bytes, check, to_hex, Expected};
+
+const INVALID_CHAR: Expected = Err(("U_INVALID_CHAR_FOUND", "Unable to transcode Buffer [U_INVALID_CHAR_FOUND]"));
+const ILLEGAL_ARGUMENT: Expected = Err(("U_ILLEGAL_ARGUMENT_ERROR", "Unable to transcode Buffer [U_ILLEGAL_ARGUMENT_ERROR]"));
+
+// source hex, from, to, then the result's hex
+const TRANSCODE: &[(&str, &str, &str, Expected)] = &[
+    ("68c3a9e282ac", "utf8", "latin1", Ok("68e93f")),
+    ("68c3a9e282ac", "utf8", "ascii", Ok("683f3f")),
+    ("68c3a9e282acf09f9880", "utf8", "ucs2", Ok("6800e900ac203dd800de")),
+    ("ff61", "utf8", "latin1", Ok("3f61")),
+    ("ff61", "utf8", "utf8", Ok("efbfbd61")),
+    ("f09f9880", "utf8", "latin1", Ok("3f")),
+    ("", "utf8", "ucs2", Ok("")),
+    ("ff61", "utf8", "utf16le", INVALID_CHAR),
+    ("c3", "utf8", "ucs2", INVALID_CHAR),
+    ("eda080", "utf8", "ucs2", INVALID_CHAR),
+    ("ac203dd800de", "ucs2", "utf8", Ok("e282acf09f9880")),
+    ("610062", "ucs2", "utf8", Ok("61")),
+    ("3dd8", "utf16le", "utf8", INVALID_CHAR),
+    ("3dd861", "ucs2", "utf8", INVALID_CHAR),
+    ("3dd8", "utf16le", "latin1", Ok("3f")),
+    ("3dd8", "ucs2", "ucs2", Ok("fdff")),
+    ("3dd800de", "ucs2", "latin1", Ok("3f")),
+    ("68e9", "latin1", "utf8", Ok("68c3a9")),
+    ("e9", "binary", "utf8", Ok("c3a9")),
+    ("e9", "latin1", "ucs2", Ok("e900")),
+    ("e9", "latin1", "ascii", Ok("3f")),
+    ("ff", "latin1", "ascii", Ok("3f")),
+    ("e9", "ascii", "utf8", Ok("efbfbd")),
+    ("e9", "ascii", "latin1", Ok("3f")),
+    ("e9", "ascii", "ascii", Ok("3f")),
+    ("e9", "ascii", "ucs2", Ok("e900")),
+    ("61", "utf8", "hex", ILLEGAL_ARGUMENT),
+    ("61", "base64", "utf8", ILLEGAL_ARGUMENT),
+];
+
+// hex, then isUtf8 and isAscii
+const VALIDATE: &[(&str, bool, bool)] = &[
+    ("", true, true),
+    ("68656c6c6f", true, true),
+    ("7f", true, true),
+    ("c3a9", true, false),
+    ("f09f9880", true, false),
+    ("ff", false, false),
+    ("80", false, false),
+    ("c3", false, false),
+    ("eda080", false, false),
+    ("c0af", false, false),
+];
+
+fn encoding(name: &str) -> StringEncoding {
+    name.parse().unwrap()
+}
+
+#[test]
+fn transcode_matches_node() {
+    for (source, from, to, expected) in TRANSCODE {
+        let actual = transcode(&bytes(source), encoding(from), encoding(to)).map(|buffer| to_hex(&buffer));
+        check(&format!("transcode({source}, {from:?}, {to:?})"), actual, expected);
+    }
+}
+
+#[test]
+fn validators_match_node() {
+    for (input, utf8, ascii) in VALIDATE {
+        assert_eq!(is_utf8(&bytes(input)), *utf8, "isUtf8({input})");
+        assert_eq!(is_ascii(&bytes(input)), *ascii, "isAscii({input})");
+    }
+}
+
+// Inputs from 64 bytes up take the SIMD validator.
+#[test]
+fn validators_on_large_inputs() {
+    let mut text = "h\u{e9}llo \u{20ac} w\u{f6}rld \u{1f600} ".repeat(100).into_bytes();
+    assert!(is_utf8(&text));
+    assert!(!is_ascii(&text));
+    text.push(0xc3);
+    assert!(!is_utf8(&text));
+
+    let mut ascii = vec![b'a'; 200];
+    assert!(is_utf8(&ascii) && is_ascii(&ascii));
+    ascii[150] = 0x80;
+    assert!(!is_utf8(&ascii) && !is_ascii(&ascii));
+}
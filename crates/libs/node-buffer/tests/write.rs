@@ -0,0 +1,106 @@
+//! Table driven checks of `buf.write` and `Buffer.byteLength` against Node's `Buffer`.
+
+mod common;
+
+use node_buffer::{byte_length, byte_length_utf16, byte_length_utf8, Buffer, StringEncoding};
+
+use common::{check, to_hex, Expected};
+
+// buffer size, string, offset, length, encoding, then the bytes written and the buffer's hex
+type WriteCase = (usize, &'static str, Option<usize>, Option<usize>, &'static str, Expected);
+
+const WRITE: &[WriteCase] = &[
+    (2, "\u{20ac}", None, None, "utf8", Ok("0 0000")),
+    (3, "\u{20ac}", None, None, "utf8", Ok("3 e282ac")),
+    (4, "a\u{20ac}", None, None, "utf8", Ok("4 61e282ac")),
+    (4, "\u{1f600}", Some(1), None, "utf8", Ok("0 00000000")),
+    (5, "\u{1f600}", Some(1), None, "utf8", Ok("4 00f09f9880")),
+    (8, "hello", Some(2), Some(2), "utf8", Ok("2 0000686500000000")),
+    (4, "hello", Some(2), Some(4), "utf8", Ok("2 00006865")),
+    (4, "hello", Some(4), None, "utf8", Ok("0 00000000")),
+    (4, "hello", Some(5), None, "utf8", Err(("ERR_OUT_OF_RANGE", "The value of \"offset\" is out of range. It must be >= 0 && <= 4. Received 5"))),
+    (4, "hello", Some(0), Some(5), "utf8", Err(("ERR_OUT_OF_RANGE", "The value of \"length\" is out of range. It must be >= 0 && <= 4. Received 5"))),
+    (3, "abc", None, None, "utf16le", Ok("2 610000")),
+    (4, "\u{20ac}\u{1f600}", None, None, "ucs2", Ok("4 ac203dd8")),
+    (3, "h\u{e9}llo", None, None, "latin1", Ok("3 68e96c")),
+    (4, "aGVsbG8=", None, None, "base64", Ok("4 68656c6c")),
+    (8, "aGVsbG8", None, None, "base64", Ok("5 68656c6c6f000000")),
+    (4, "_-8", None, None, "base64url", Ok("2 ffef0000")),
+    (4, "abzz12", None, None, "hex", Ok("1 ab000000")),
+    (4, "00ff10", Some(1), None, "hex", Ok("3 0000ff10")),
+];
+
+const BYTE_LENGTH: &[(&str, &str, usize)] = &[
+    ("aGk=", "base64", 2),
+    ("aGk", "base64", 2),
+    ("aGk==", "base64", 2),
+    ("aGVsbG8=", "base64", 5),
+    ("aG k=", "base64", 3),
+    ("a", "base64", 0),
+    ("", "base64", 0),
+    ("=", "base64", 0),
+    ("==", "base64", 0),
+    ("_-8", "base64url", 2),
+    ("\u{20ac}", "utf8", 3),
+    ("\u{1f600}", "utf16le", 4),
+    ("\u{1f600}", "latin1", 2),
+    ("h\u{e9}llo", "ascii", 5),
+    ("abc", "hex", 1),
+];
+
+fn encoding(name: &str) -> StringEncoding {
+    name.parse().unwrap()
+}
+
+fn written(buffer: &Buffer, result: node_core::error::Result<usize>) -> node_core::error::Result<String> {
+    result.map(|written| format!("{} {}", written, to_hex(buffer)))
+}
+
+#[test]
+fn write() {
+    for (size, string, offset, length, name, expected) in WRITE {
+        let context = format!("Buffer.alloc({size}).write({string:?}, {offset:?}, {length:?}, {name:?})");
+        let encoding = Some(encoding(name));
+
+        let mut buffer = Buffer::from_vec(vec![0; *size]);
+        let result = buffer.write(string, *offset, *length, encoding);
+        check(&context, written(&buffer, result), expected);
+
+        let mut buffer = Buffer::from_vec(vec![0; *size]);
+        let result = buffer.write_utf8_string(string.as_bytes(), *offset, *length, encoding);
+        check(&format!("{context} utf8"), written(&buffer, result), expected);
+
+        let units = string.encode_utf16().collect::<Vec<u16>>();
+        let mut buffer = Buffer::from_vec(vec![0; *size]);
+        let result = buffer.write_utf16_string(&units, *offset, *length, encoding);
+        check(&format!("{context} utf16"), written(&buffer, result), expected);
+    }
+}
+
+#[test]
+fn write_lone_surrogates() {
+    // Buffer.alloc(4).write('\ud83d') is 3 with efbfbd00
+    let mut buffer = Buffer::from_vec(vec![0; 4]);
+    assert_eq!(buffer.write_utf16_string(&[0xD83D], None, None, None).unwrap(), 3);
+    assert_eq!(to_hex(&buffer), "efbfbd00");
+
+    let mut buffer = Buffer::from_vec(vec![0; 4]);
+    assert_eq!(buffer.write_utf8_string(b"\xed\xa0\xbd", None, None, None).unwrap(), 3);
+    assert_eq!(to_hex(&buffer), "efbfbd00");
+
+    let mut buffer = Buffer::from_vec(vec![0; 2]);
+    assert_eq!(buffer.write_utf16_string(&[0xD83D], None, None, None).unwrap(), 0);
+}
+
+#[test]
+fn byte_length_matches_node() {
+    for (string, name, expected) in BYTE_LENGTH {
+        let encoding = encoding(name);
+        let units = string.encode_utf16().collect::<Vec<u16>>();
+        assert_eq!(byte_length(string, encoding), *expected, "byteLength({string:?}, {name:?})");
+        assert_eq!(byte_length_utf8(string.as_bytes(), encoding), *expected, "byteLength({string:?}, {name:?}) utf8");
+        assert_eq!(byte_length_utf16(&units, encoding), *expected, "byteLength({string:?}, {name:?}) utf16");
+    }
+    assert_eq!(byte_length_utf16(&[0xD83D], StringEncoding::Utf8), 3);
+    assert_eq!(byte_length_utf8(b"\xed\xa0\xbd", StringEncoding::Utf8), 3);
+}
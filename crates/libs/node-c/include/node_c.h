@@ -8,6 +8,75 @@
 #include <stdint.h>
 #include <stdlib.h>
 
+/**
+ * Bumped whenever an existing export changes signature or is removed, additions only
+ * add a capability bit.
+ */
+#define NODE_C_ABI_VERSION 1
+
+#define NODE_C_CAPABILITY_FS_CP (1 << 0)
+
+#define NODE_C_CAPABILITY_FS_BIGINT_STATS (1 << 1)
+
+#define NODE_C_CAPABILITY_FS_WATCH (1 << 2)
+
+#define NODE_C_CAPABILITY_FS_BATCH (1 << 3)
+
+#define NODE_C_CAPABILITY_BUFFER_EXTERNAL (1 << 4)
+
+#define NODE_C_CAPABILITY_CRYPTO (1 << 5)
+
+#define NODE_C_CAPABILITY_TRACE (1 << 6)
+
+#define NODE_C_CAPABILITY_BUFFER_BLOB (1 << 7)
+
+#define NODE_C_CAPABILITY_FS_OPEN_AS_BLOB (1 << 8)
+
+#define NODE_C_CAPABILITY_BUFFER_MEMORY_ACCOUNTING (1 << 9)
+
+#define NODE_C_CAPABILITY_BUFFER_FILL (1 << 10)
+
+#define NODE_C_CAPABILITY_BUFFER_WRITE (1 << 11)
+
+#define NODE_C_CAPABILITY_FS_MAP_FILE (1 << 12)
+
+#define NODE_C_CAPABILITY_FS_READ_FILE_LIMITS (1 << 13)
+
+#define NODE_C_CAPABILITY_FS_FD_TRACKING (1 << 14)
+
+typedef enum BlobPartType {
+  BlobPartTypeBytes,
+  BlobPartTypeBuffer,
+  BlobPartTypeString,
+  BlobPartTypeBlob,
+} BlobPartType;
+
+typedef enum BufferFillType {
+  BufferFillTypeNumber,
+  BufferFillTypeBytes,
+  BufferFillTypeBuffer,
+  BufferFillTypeUtf8String,
+  BufferFillTypeUtf16String,
+} BufferFillType;
+
+typedef enum FsBatchOperationType {
+  FsBatchOperationTypeStat,
+  FsBatchOperationTypeLstat,
+  FsBatchOperationTypeReadFile,
+  FsBatchOperationTypeAccess,
+  FsBatchOperationTypeExists,
+  FsBatchOperationTypeReaddir,
+} FsBatchOperationType;
+
+typedef enum FsBatchResultType {
+  FsBatchResultTypeStat,
+  FsBatchResultTypeReadFile,
+  FsBatchResultTypeAccess,
+  FsBatchResultTypeExists,
+  FsBatchResultTypeReaddir,
+  FsBatchResultTypeError,
+} FsBatchResultType;
+
 typedef enum FsEncodingType {
   FsEncodingTypeAscii,
   FsEncodingTypeUtf8,
@@ -17,6 +86,13 @@ typedef enum FsEncodingType {
   FsEncodingTypeBuffer,
 } FsEncodingType;
 
+typedef enum FsMapAdvice {
+  FsMapAdviceNormal,
+  FsMapAdviceSequential,
+  FsMapAdviceRandom,
+  FsMapAdviceWillNeed,
+} FsMapAdvice;
+
 typedef enum ReaddirResultType {
   ReaddirResultTypeString,
   ReaddirResultTypeBuffer,
@@ -35,6 +111,12 @@ typedef enum StringEncoding {
   StringEncodingHex,
 } StringEncoding;
 
+typedef struct AbortSignal AbortSignal;
+
+typedef struct AsyncBatchClosure AsyncBatchClosure;
+
+typedef struct AsyncBatchItemClosure AsyncBatchItemClosure;
+
 typedef struct AsyncBoolClosure AsyncBoolClosure;
 
 typedef struct AsyncClosure AsyncClosure;
@@ -59,8 +141,14 @@ typedef struct AsyncUsizeClosure AsyncUsizeClosure;
 
 typedef struct AsyncWatchClosure AsyncWatchClosure;
 
+typedef struct Blob Blob;
+
+typedef struct BlobStream BlobStream;
+
 typedef struct Buffer Buffer;
 
+typedef struct File File;
+
 typedef struct FileDir FileDir;
 
 typedef struct FileDirent FileDirent;
@@ -69,12 +157,16 @@ typedef struct FileHandle FileHandle;
 
 typedef struct FileWatchEvent FileWatchEvent;
 
+typedef struct FsBatchResult FsBatchResult;
+
 typedef struct FsEncoding FsEncoding;
 
 typedef struct NodeError NodeError;
 
 typedef struct ReaddirResult ReaddirResult;
 
+typedef struct StringDecoder StringDecoder;
+
 typedef struct WatchEvent WatchEvent;
 
 typedef struct FileStat {
@@ -105,6 +197,35 @@ typedef struct FileStat {
   bool isSymbolicLink;
 } FileStat;
 
+/**
+ * Receives the net change in bytes held by buffers since its last call, pass it on to
+ * e.g. `AdjustAmountOfExternalAllocatedMemory`. It may run on any thread.
+ */
+typedef void (*BufferExternalMemoryCallback)(int64_t change, void *context);
+
+typedef struct BufferMemoryUsage {
+  uint64_t allocated;
+  uint64_t freed;
+  uint64_t current;
+} BufferMemoryUsage;
+
+/**
+ * Invoked once Rust no longer references memory passed to `buffer_from_external`.
+ */
+typedef void (*BufferFinalizer)(uint8_t *data, uintptr_t length, void *context);
+
+/**
+ * A `buf.fill` value, `number` is read for numbers (only the low byte is used), `buffer`
+ * for buffers and `data`/`length` otherwise. UTF-16 strings are `length` code units.
+ */
+typedef struct BufferFill {
+  enum BufferFillType type_;
+  int64_t number;
+  const void *data;
+  uintptr_t length;
+  const struct Buffer *buffer;
+} BufferFill;
+
 typedef struct ReaddirResultArray {
   struct ReaddirResult *data;
   uintptr_t length;
@@ -140,6 +261,12 @@ typedef struct ReaddirOptions {
 typedef struct ReadFileOptions {
   int32_t flag;
   enum FsEncodingType encoding;
+  /**
+   * Map regular files copy-on-write instead of reading them. Only set it for files nothing
+   * truncates while the buffer is alive, touching pages past the new end of a truncated
+   * file raises SIGBUS, see `fs_map_file_sync`.
+   */
+  bool map;
 } ReadFileOptions;
 
 typedef struct ReadLinkOptions {
@@ -175,6 +302,266 @@ typedef struct WriteFileOptions {
   int32_t flag;
 } WriteFileOptions;
 
+typedef struct FsBatchResultArray {
+  struct FsBatchResult *data;
+  uintptr_t length;
+} FsBatchResultArray;
+
+/**
+ * A single entry of a batch, `mode` is only read for access, `read_file_options` for
+ * read_file and `readdir_options` for readdir.
+ */
+typedef struct FsBatchOperation {
+  enum FsBatchOperationType type_;
+  const char *path;
+  int32_t mode;
+  struct ReadFileOptions read_file_options;
+  struct ReaddirOptions readdir_options;
+} FsBatchOperation;
+
+/**
+ * Receives the Chrome trace JSON, the string is only valid for the duration of the call.
+ */
+typedef void (*TraceCallback)(const char *json, uintptr_t length, void *data);
+
+/**
+ * A source of `blob_create`, `data`/`length` are read for bytes and strings (UTF-8, may
+ * contain NULs), `buffer` for buffers and `blob` for blobs.
+ */
+typedef struct BlobPart {
+  enum BlobPartType type_;
+  const uint8_t *data;
+  uintptr_t length;
+  const struct Buffer *buffer;
+  const struct Blob *blob;
+} BlobPart;
+
+/**
+ * Exports of the `"fs.cp"` capability, `node_c_v1_fs_cp` returns null when this build
+ * lacks it.
+ */
+typedef struct NodeCV1FsCp {
+  void (*fs_cp_sync)(const char *src, const char *dest, uint32_t flags);
+  void (*fs_async_cp)(const char *src,
+                      const char *dest,
+                      uint32_t flags,
+                      const struct AsyncClosure *callback);
+} NodeCV1FsCp;
+
+/**
+ * Exports of the `"fs.watch"` capability, `node_c_v1_fs_watch` returns null when this
+ * build lacks it.
+ */
+typedef struct NodeCV1FsWatch {
+  void (*fs_async_watch)(const char *filename,
+                         bool persistent,
+                         bool recursive,
+                         enum FsEncodingType encoding,
+                         const struct AsyncWatchClosure *callback);
+  void (*fs_async_watcher_ref)(const char *filename, const struct AsyncWatchClosure *callback);
+  void (*fs_async_watcher_unref)(const char *filename, const struct AsyncWatchClosure *callback);
+  void (*fs_async_watcher_close)(const char *filename,
+                                 const struct AsyncWatchClosure *callback,
+                                 const struct AsyncClosure *on_close);
+  void (*fs_async_watch_file)(const char *filename,
+                              bool bigint,
+                              bool persistent,
+                              uint64_t interval,
+                              enum FsEncodingType encoding,
+                              const struct AsyncFileWatchClosure *callback);
+  void (*fs_async_unwatch_file)(const char *filename);
+  void (*fs_async_unwatch_file_with_callback)(const char *filename,
+                                              const struct AsyncFileWatchClosure *callback);
+  void (*fs_watch_event_destroy)(struct WatchEvent *event);
+  const char *(*fs_watch_event_event_type)(const struct WatchEvent *event);
+  const char *(*fs_watch_event_filename)(const struct WatchEvent *event);
+  struct AsyncWatchClosure *(*fs_async_create_async_fs_watch_closure)(void *on_success,
+                                                                      void *on_error,
+                                                                      void *data);
+  void (*fs_async_watch_closure_destroy)(struct AsyncWatchClosure *value);
+  struct AsyncFileWatchClosure *(*fs_async_create_async_file_watch_closure)(void *on_success,
+                                                                            void *on_error,
+                                                                            void *data);
+  void (*fs_async_file_watch_closure_destroy)(struct AsyncFileWatchClosure *value);
+} NodeCV1FsWatch;
+
+/**
+ * Exports of the `"fs.batch"` capability, `node_c_v1_fs_batch` returns null when this
+ * build lacks it.
+ */
+typedef struct NodeCV1FsBatch {
+  struct FsBatchResultArray *(*fs_batch_sync)(const struct FsBatchOperation *operations,
+                                              uintptr_t length);
+  const struct FsBatchResult *(*fs_batch_result_array_get_at)(const struct FsBatchResultArray *value,
+                                                              uintptr_t at);
+  void (*fs_batch_result_array_destroy)(struct FsBatchResultArray *value);
+  void (*fs_batch_result_destroy)(struct FsBatchResult *value);
+  enum FsBatchResultType (*fs_batch_result_get_type)(const struct FsBatchResult *value);
+  struct NodeError *(*fs_batch_result_get_error)(const struct FsBatchResult *value);
+  struct FileStat *(*fs_batch_result_get_stat)(const struct FsBatchResult *value);
+  bool (*fs_batch_result_get_exists)(const struct FsBatchResult *value);
+  struct FsEncoding *(*fs_batch_result_get_fs_encoding)(const struct FsBatchResult *value);
+  struct ReaddirResultArray *(*fs_batch_result_get_readdir)(const struct FsBatchResult *value);
+} NodeCV1FsBatch;
+
+/**
+ * Exports of the `"buffer.external"` capability, `node_c_v1_buffer_external` returns null
+ * when this build lacks it.
+ */
+typedef struct NodeCV1BufferExternal {
+  struct Buffer *(*buffer_from_external)(uint8_t *data,
+                                         uintptr_t size,
+                                         BufferFinalizer finalizer,
+                                         void *context);
+} NodeCV1BufferExternal;
+
+/**
+ * Exports of the `"trace"` capability, `node_c_v1_trace` returns null when this build
+ * lacks it.
+ */
+typedef struct NodeCV1Trace {
+  void (*node_trace_enable)(const char *categories);
+  void (*node_trace_disable)(void);
+  void (*node_trace_set_large_allocation_threshold)(uintptr_t bytes);
+  bool (*node_trace_flush_to_file)(const char *path);
+  void (*node_trace_flush)(TraceCallback callback, void *data);
+  void (*node_trace_set_max_events)(uintptr_t count);
+} NodeCV1Trace;
+
+/**
+ * Exports of the `"buffer.blob"` capability, `node_c_v1_buffer_blob` returns null when
+ * this build lacks it.
+ */
+typedef struct NodeCV1BufferBlob {
+  struct Blob *(*blob_create)(const struct BlobPart *parts, uintptr_t length, const char *type_);
+  void (*blob_destroy)(struct Blob *blob);
+  struct Blob *(*blob_clone)(const struct Blob *blob);
+  uintptr_t (*blob_get_size)(const struct Blob *blob);
+  const char *(*blob_get_type)(const struct Blob *blob);
+  struct Blob *(*blob_slice)(const struct Blob *blob,
+                             intptr_t start,
+                             intptr_t end,
+                             const char *type_);
+  intptr_t (*blob_read_at)(const struct Blob *blob,
+                           uintptr_t offset,
+                           uint8_t *data,
+                           uintptr_t length);
+  struct Buffer *(*blob_array_buffer)(const struct Blob *blob);
+  const char *(*blob_text)(const struct Blob *blob);
+  struct BlobStream *(*blob_stream)(const struct Blob *blob, uintptr_t chunk_size);
+  struct Buffer *(*blob_stream_next)(struct BlobStream *stream);
+  void (*blob_stream_destroy)(struct BlobStream *stream);
+} NodeCV1BufferBlob;
+
+/**
+ * Exports of the `"fs.open_as_blob"` capability, `node_c_v1_fs_open_as_blob` returns null
+ * when this build lacks it.
+ */
+typedef struct NodeCV1FsOpenAsBlob {
+  struct Blob *(*fs_open_as_blob)(const char *path, const char *type_);
+} NodeCV1FsOpenAsBlob;
+
+/**
+ * Exports of the `"buffer.memory_accounting"` capability,
+ * `node_c_v1_buffer_memory_accounting` returns null when this build lacks it.
+ */
+typedef struct NodeCV1BufferMemoryAccounting {
+  struct BufferMemoryUsage (*buffer_memory_usage)(void);
+} NodeCV1BufferMemoryAccounting;
+
+/**
+ * Exports of the `"buffer.fill"` capability, `node_c_v1_buffer_fill` returns null when
+ * this build lacks it.
+ */
+typedef struct NodeCV1BufferFill {
+  bool (*buffer_fill)(struct Buffer *buffer,
+                      const struct BufferFill *fill,
+                      intptr_t offset,
+                      intptr_t end,
+                      enum StringEncoding encoding);
+  void (*buffer_fill_string)(struct Buffer *buffer, const char *string, enum StringEncoding encoding);
+  void (*buffer_fill_utf8_string)(struct Buffer *buffer,
+                                  const uint8_t *string,
+                                  uintptr_t length,
+                                  enum StringEncoding encoding);
+  void (*buffer_fill_utf16_string)(struct Buffer *buffer,
+                                   const uint16_t *string,
+                                   uintptr_t length,
+                                   enum StringEncoding encoding);
+} NodeCV1BufferFill;
+
+/**
+ * Exports of the `"buffer.write"` capability, `node_c_v1_buffer_write` returns null when
+ * this build lacks it.
+ */
+typedef struct NodeCV1BufferWrite {
+  intptr_t (*buffer_write_string)(struct Buffer *buffer,
+                                  const char *string,
+                                  intptr_t offset,
+                                  intptr_t length,
+                                  enum StringEncoding encoding);
+  intptr_t (*buffer_write_utf8_string)(struct Buffer *buffer,
+                                       const uint8_t *string,
+                                       uintptr_t string_length,
+                                       intptr_t offset,
+                                       intptr_t length,
+                                       enum StringEncoding encoding);
+  intptr_t (*buffer_write_utf16_string)(struct Buffer *buffer,
+                                        const uint16_t *string,
+                                        uintptr_t string_length,
+                                        intptr_t offset,
+                                        intptr_t length,
+                                        enum StringEncoding encoding);
+  uintptr_t (*buffer_byte_length_utf8)(const uint8_t *string,
+                                       uintptr_t length,
+                                       enum StringEncoding encoding);
+  uintptr_t (*buffer_byte_length_utf16)(const uint16_t *string,
+                                        uintptr_t length,
+                                        enum StringEncoding encoding);
+} NodeCV1BufferWrite;
+
+/**
+ * Exports of the `"fs.map_file"` capability, `node_c_v1_fs_map_file` returns null when
+ * this build lacks it.
+ */
+typedef struct NodeCV1FsMapFile {
+  struct Buffer *(*fs_map_file_sync)(const char *path, enum FsMapAdvice advice);
+  struct Buffer *(*fs_map_file_with_fd_sync)(int32_t fd, enum FsMapAdvice advice);
+} NodeCV1FsMapFile;
+
+/**
+ * Exports of the `"fs.read_file_limits"` capability, `node_c_v1_fs_read_file_limits`
+ * returns null when this build lacks it.
+ */
+typedef struct NodeCV1FsReadFileLimits {
+  void (*fs_set_read_file_max_size)(uint64_t size);
+  uint64_t (*fs_read_file_max_size)(void);
+  struct AbortSignal *(*abort_signal_create)(void);
+  void (*abort_signal_abort)(const struct AbortSignal *signal);
+  bool (*abort_signal_aborted)(const struct AbortSignal *signal);
+  void (*abort_signal_destroy)(struct AbortSignal *signal);
+  struct FsEncoding *(*fs_read_file_with_signal_sync)(const char *path,
+                                                      struct ReadFileOptions options,
+                                                      const struct AbortSignal *signal);
+  void (*fs_async_read_file_with_signal)(const char *path,
+                                         struct ReadFileOptions options,
+                                         const struct AbortSignal *signal,
+                                         const struct AsyncFsEncodingClosure *callback);
+  void (*fs_async_read_file_with_fd_and_signal)(int32_t fd,
+                                                struct ReadFileOptions options,
+                                                const struct AbortSignal *signal,
+                                                const struct AsyncFsEncodingClosure *callback);
+} NodeCV1FsReadFileLimits;
+
+/**
+ * Exports of the `"fs.fd_tracking"` capability, `node_c_v1_fs_fd_tracking` returns null
+ * when this build lacks it.
+ */
+typedef struct NodeCV1FsFdTracking {
+  void (*fs_set_fd_tracking)(bool enabled);
+  uintptr_t (*fs_report_fd_leaks)(void);
+} NodeCV1FsFdTracking;
+
 void node_string_destroy(char *string);
 
 void filestat_destroy(struct FileStat *file_stat);
@@ -183,6 +570,11 @@ const char *node_error_get_clazz(const struct NodeError *error);
 
 const char *node_error_get_message(const struct NodeError *error);
 
+/**
+ * Node's `code` e.g. `ERR_OUT_OF_RANGE`, null when the error has none.
+ */
+const char *node_error_get_code(const struct NodeError *error);
+
 void node_error_destroy(struct NodeError *error);
 
 /**
@@ -196,6 +588,12 @@ void node_error_destroy(struct NodeError *error);
  */
 int node_error_message(char *buffer, int length);
 
+/**
+ * The `code` of the last error without clearing it, null when there is no error
+ * or it has no code. Free with `node_string_destroy`.
+ */
+const char *node_last_error_code(void);
+
 /**
  * Calculate the number of bytes in the last error's error message **not**
  * including any trailing `null` characters.
@@ -208,10 +606,49 @@ struct Buffer *buffer_clone(const struct Buffer *buffer);
 
 struct Buffer *buffer_alloc(uintptr_t size);
 
+/**
+ * `Buffer.allocUnsafe(size)`, small buffers share a pool slab.
+ */
+struct Buffer *buffer_alloc_unsafe(uintptr_t size);
+
+struct Buffer *buffer_alloc_unsafe_slow(uintptr_t size);
+
+uintptr_t buffer_get_pool_size(void);
+
+/**
+ * Sets `Buffer.poolSize`, 0 disables pooling.
+ */
+void buffer_set_pool_size(uintptr_t size);
+
+/**
+ * Installs or, when `callback` is null, removes the external memory callback. Changes are
+ * coalesced until they reach `threshold` bytes, 0 reports every allocation. Only changes
+ * after the first install are reported, seed the host's count from `buffer_memory_usage`.
+ */
+void buffer_set_external_memory_callback(BufferExternalMemoryCallback callback,
+                                         void *context,
+                                         uintptr_t threshold);
+
+/**
+ * Bytes allocated and freed by buffers since startup, host memory wrapped with
+ * `buffer_from_external` or `buffer_from_reference` is not counted.
+ */
+struct BufferMemoryUsage buffer_memory_usage(void);
+
 struct Buffer *buffer_alloc_with_size_string_encoding(uintptr_t size,
                                                       const char *string,
                                                       enum StringEncoding encoding);
 
+struct Buffer *buffer_alloc_with_size_utf8_string_encoding(uintptr_t size,
+                                                           const uint8_t *string,
+                                                           uintptr_t length,
+                                                           enum StringEncoding encoding);
+
+struct Buffer *buffer_alloc_with_size_utf16_string_encoding(uintptr_t size,
+                                                            const uint16_t *string,
+                                                            uintptr_t length,
+                                                            enum StringEncoding encoding);
+
 struct Buffer *buffer_concat(const uint8_t *const *buffers,
                              const uintptr_t *buffers_length,
                              uintptr_t count);
@@ -223,18 +660,123 @@ struct Buffer *buffer_concat_length(const uint8_t *const *buffers,
 
 struct Buffer *buffer_from_string(const char *string, enum StringEncoding encoding);
 
+/**
+ * `string` is `length` bytes of UTF-8 and may contain NULs.
+ */
+struct Buffer *buffer_from_utf8_string(const uint8_t *string,
+                                       uintptr_t length,
+                                       enum StringEncoding encoding);
+
+/**
+ * `string` is `length` UTF-16 code units, lone surrogates are allowed.
+ */
+struct Buffer *buffer_from_utf16_string(const uint16_t *string,
+                                        uintptr_t length,
+                                        enum StringEncoding encoding);
+
 struct Buffer *buffer_from_slice(const uint8_t *slice, uintptr_t length);
 
 struct Buffer *buffer_copy_bytes_from(const struct Buffer *buffer);
 
 struct Buffer *buffer_from_reference(uint8_t *data, uintptr_t size);
 
+/**
+ * Wraps host owned memory (e.g. an ArrayBuffer backing store) without copying.
+ * `finalizer` is called exactly once, possibly from an I/O thread, after the last
+ * clone is dropped, so the memory can safely outlive `buffer_destroy` while an
+ * async fs operation is still using it.
+ */
+struct Buffer *buffer_from_external(uint8_t *data,
+                                    uintptr_t size,
+                                    BufferFinalizer finalizer,
+                                    void *context);
+
+/**
+ * Shares `buffer`'s memory from `offset`, a negative `length` takes the rest of the buffer.
+ * Returns null and sets `ERR_BUFFER_OUT_OF_BOUNDS` as the last error when out of range.
+ */
+struct Buffer *buffer_from_buffer_view(const struct Buffer *buffer,
+                                       uintptr_t offset,
+                                       intptr_t length);
+
+/**
+ * `buf.subarray(start, end)`, negative values count from the end and both are clamped,
+ * pass the buffer length (or `PTRDIFF_MAX`) as `end` to view up to the end.
+ */
+struct Buffer *buffer_subarray(const struct Buffer *buffer, intptr_t start, intptr_t end);
+
 const char *buffer_atob(const char *string);
 
 const char *buffer_btoa(const char *string);
 
 void buffer_fill_string(struct Buffer *buffer, const char *string, enum StringEncoding encoding);
 
+void buffer_fill_utf8_string(struct Buffer *buffer,
+                             const uint8_t *string,
+                             uintptr_t length,
+                             enum StringEncoding encoding);
+
+void buffer_fill_utf16_string(struct Buffer *buffer,
+                              const uint16_t *string,
+                              uintptr_t length,
+                              enum StringEncoding encoding);
+
+/**
+ * `buf.fill(value, offset, end, encoding)`, negative `offset`/`end` use the defaults.
+ * Returns false and sets the last error when `end` is out of range or `fill` encodes to nothing.
+ */
+bool buffer_fill(struct Buffer *buffer,
+                 const struct BufferFill *fill,
+                 intptr_t offset,
+                 intptr_t end,
+                 enum StringEncoding encoding);
+
+/**
+ * `Buffer.alloc(size, fill, encoding)`, a null `fill` zero fills. Returns null and sets the
+ * last error when `fill` encodes to nothing.
+ */
+struct Buffer *buffer_alloc_fill(uintptr_t size,
+                                 const struct BufferFill *fill,
+                                 enum StringEncoding encoding);
+
+/**
+ * `buf.write(string, offset, length, encoding)`, a negative `offset` or `length` means it
+ * was omitted. Returns the bytes written or -1 with the last error set.
+ */
+intptr_t buffer_write_string(struct Buffer *buffer,
+                             const char *string,
+                             intptr_t offset,
+                             intptr_t length,
+                             enum StringEncoding encoding);
+
+intptr_t buffer_write_utf8_string(struct Buffer *buffer,
+                                  const uint8_t *string,
+                                  uintptr_t string_length,
+                                  intptr_t offset,
+                                  intptr_t length,
+                                  enum StringEncoding encoding);
+
+/**
+ * Same as `buffer_write_utf8_string` with `string_length` UTF-16 code units.
+ */
+intptr_t buffer_write_utf16_string(struct Buffer *buffer,
+                                   const uint16_t *string,
+                                   uintptr_t string_length,
+                                   intptr_t offset,
+                                   intptr_t length,
+                                   enum StringEncoding encoding);
+
+/**
+ * `Buffer.byteLength` for a string, without encoding it.
+ */
+uintptr_t buffer_byte_length_utf8(const uint8_t *string,
+                                  uintptr_t length,
+                                  enum StringEncoding encoding);
+
+uintptr_t buffer_byte_length_utf16(const uint16_t *string,
+                                   uintptr_t length,
+                                   enum StringEncoding encoding);
+
 const char *buffer_to_string(const struct Buffer *buffer,
                              enum StringEncoding encoding,
                              intptr_t start,
@@ -244,44 +786,52 @@ const char *buffer_to_print_string(const struct Buffer *buffer);
 
 uintptr_t buffer_length(const struct Buffer *buffer);
 
+/**
+ * Pointer to the buffer's bytes. Accesses through it aren't atomic like the buffer functions'
+ * are, so the host must not use it while another thread works on the same memory.
+ */
 uint8_t *buffer_buffer(struct Buffer *buffer);
 
-void buffer_write_int8(struct Buffer *buffer, int8_t value, intptr_t offset);
+uintptr_t buffer_write_int8(struct Buffer *buffer, int8_t value, intptr_t offset);
 
-void buffer_write_uint8(struct Buffer *buffer, uint8_t value, intptr_t offset);
+uintptr_t buffer_write_uint8(struct Buffer *buffer, uint8_t value, intptr_t offset);
 
-void buffer_write_uint16be(struct Buffer *buffer, uint16_t value, intptr_t offset);
+uintptr_t buffer_write_uint16be(struct Buffer *buffer, uint16_t value, intptr_t offset);
 
-void buffer_write_int16be(struct Buffer *buffer, int16_t value, intptr_t offset);
+uintptr_t buffer_write_int16be(struct Buffer *buffer, int16_t value, intptr_t offset);
 
-void buffer_write_uint16le(struct Buffer *buffer, uint16_t value, intptr_t offset);
+uintptr_t buffer_write_uint16le(struct Buffer *buffer, uint16_t value, intptr_t offset);
 
-void buffer_write_int16le(struct Buffer *buffer, int16_t value, intptr_t offset);
+uintptr_t buffer_write_int16le(struct Buffer *buffer, int16_t value, intptr_t offset);
 
-void buffer_write_uint32be(struct Buffer *buffer, uint32_t value, intptr_t offset);
+uintptr_t buffer_write_uint32be(struct Buffer *buffer, uint32_t value, intptr_t offset);
 
-void buffer_write_int32be(struct Buffer *buffer, int32_t value, intptr_t offset);
+uintptr_t buffer_write_int32be(struct Buffer *buffer, int32_t value, intptr_t offset);
 
-void buffer_write_uint32le(struct Buffer *buffer, uint32_t value, intptr_t offset);
+uintptr_t buffer_write_uint32le(struct Buffer *buffer, uint32_t value, intptr_t offset);
 
-void buffer_write_int32le(struct Buffer *buffer, int32_t value, intptr_t offset);
+uintptr_t buffer_write_int32le(struct Buffer *buffer, int32_t value, intptr_t offset);
 
-void buffer_write_big_uint64be(struct Buffer *buffer, uint64_t value, intptr_t offset);
+uintptr_t buffer_write_big_uint64be(struct Buffer *buffer, uint64_t value, intptr_t offset);
 
-void buffer_write_big_int64be(struct Buffer *buffer, int64_t value, intptr_t offset);
+uintptr_t buffer_write_big_int64be(struct Buffer *buffer, int64_t value, intptr_t offset);
 
-void buffer_write_big_uint64le(struct Buffer *buffer, uint64_t value, intptr_t offset);
+uintptr_t buffer_write_big_uint64le(struct Buffer *buffer, uint64_t value, intptr_t offset);
 
-void buffer_write_big_int64le(struct Buffer *buffer, int64_t value, intptr_t offset);
+uintptr_t buffer_write_big_int64le(struct Buffer *buffer, int64_t value, intptr_t offset);
 
-void buffer_write_float_be(struct Buffer *buffer, float value, intptr_t offset);
+uintptr_t buffer_write_float_be(struct Buffer *buffer, float value, intptr_t offset);
 
-void buffer_write_double_be(struct Buffer *buffer, double value, intptr_t offset);
+uintptr_t buffer_write_double_be(struct Buffer *buffer, double value, intptr_t offset);
 
-void buffer_write_float_le(struct Buffer *buffer, float value, intptr_t offset);
+uintptr_t buffer_write_float_le(struct Buffer *buffer, float value, intptr_t offset);
 
-void buffer_write_double_le(struct Buffer *buffer, double value, intptr_t offset);
+uintptr_t buffer_write_double_le(struct Buffer *buffer, double value, intptr_t offset);
 
+/**
+ * Fixed width readers return 0 and set the last error on `ERR_OUT_OF_RANGE`. They clear it
+ * first, so a non zero `node_last_error_length()` after a read tells an error from a real 0.
+ */
 int8_t buffer_read_int8(const struct Buffer *buffer, intptr_t offset);
 
 uint8_t buffer_read_uint8(const struct Buffer *buffer, intptr_t offset);
@@ -318,6 +868,196 @@ float buffer_read_float_le(const struct Buffer *buffer, intptr_t offset);
 
 double buffer_read_double_le(const struct Buffer *buffer, intptr_t offset);
 
+/**
+ * Variable width readers clear the last error first like the fixed width ones, check
+ * `node_last_error_length()` after a read that returned 0.
+ */
+int64_t buffer_read_int_be(const struct Buffer *buffer, uintptr_t offset, uintptr_t byte_length);
+
+int64_t buffer_read_int_le(const struct Buffer *buffer, uintptr_t offset, uintptr_t byte_length);
+
+uint64_t buffer_read_uint_be(const struct Buffer *buffer, uintptr_t offset, uintptr_t byte_length);
+
+uint64_t buffer_read_uint_le(const struct Buffer *buffer, uintptr_t offset, uintptr_t byte_length);
+
+uintptr_t buffer_write_int_be(struct Buffer *buffer,
+                              int64_t value,
+                              uintptr_t offset,
+                              uintptr_t byte_length);
+
+uintptr_t buffer_write_int_le(struct Buffer *buffer,
+                              int64_t value,
+                              uintptr_t offset,
+                              uintptr_t byte_length);
+
+uintptr_t buffer_write_uint_be(struct Buffer *buffer,
+                               int64_t value,
+                               uintptr_t offset,
+                               uintptr_t byte_length);
+
+uintptr_t buffer_write_uint_le(struct Buffer *buffer,
+                               int64_t value,
+                               uintptr_t offset,
+                               uintptr_t byte_length);
+
+void buffer_swap16(struct Buffer *buffer);
+
+void buffer_swap32(struct Buffer *buffer);
+
+void buffer_swap64(struct Buffer *buffer);
+
+/**
+ * `buf.copy(target, targetStart, sourceStart, sourceEnd)`, negative values use the defaults.
+ * Returns the number of bytes copied, 0 with the last error set on `ERR_OUT_OF_RANGE`.
+ */
+uintptr_t buffer_copy(const struct Buffer *buffer,
+                      struct Buffer *target,
+                      intptr_t target_start,
+                      intptr_t source_start,
+                      intptr_t source_end);
+
+/**
+ * `buf.copyWithin(target, start, end)`, negative values count from the end, pass the
+ * buffer length (or `PTRDIFF_MAX`) as `end` to copy up to the end.
+ */
+uintptr_t buffer_copy_within(struct Buffer *buffer, intptr_t target, intptr_t start, intptr_t end);
+
+void buffer_set(struct Buffer *buffer, const struct Buffer *source, uintptr_t offset);
+
+/**
+ * Returns a new buffer, or null with the last error set to `U_ILLEGAL_ARGUMENT_ERROR` when
+ * either encoding isn't one of utf8, ucs2, utf16le, latin1, binary or ascii, or to
+ * `U_INVALID_CHAR_FOUND` for invalid input between utf8 and ucs2.
+ */
+struct Buffer *buffer_transcode(const struct Buffer *buffer,
+                                enum StringEncoding from,
+                                enum StringEncoding to);
+
+bool buffer_is_utf8(const struct Buffer *buffer);
+
+bool buffer_is_ascii(const struct Buffer *buffer);
+
+/**
+ * Frees bytes returned with their `length`, e.g. by `string_decoder_write`.
+ */
+void node_bytes_destroy(uint8_t *bytes, uintptr_t length);
+
+struct StringDecoder *string_decoder_create(enum StringEncoding encoding);
+
+void string_decoder_destroy(struct StringDecoder *decoder);
+
+enum StringEncoding string_decoder_get_encoding(const struct StringDecoder *decoder);
+
+/**
+ * Returns the text decoded so far as UTF-8 with its byte length in `decoded_length`, free it
+ * with `node_bytes_destroy`. Bytes of an incomplete character are kept for the next write.
+ * Returns null only for a null decoder or null `data` with a non zero `length`.
+ */
+uint8_t *string_decoder_write(struct StringDecoder *decoder,
+                              const uint8_t *data,
+                              uintptr_t length,
+                              uintptr_t *decoded_length);
+
+uint8_t *string_decoder_write_buffer(struct StringDecoder *decoder,
+                                     const struct Buffer *buffer,
+                                     uintptr_t *decoded_length);
+
+/**
+ * Decodes `data` (which may be null) followed by whatever is left over, the decoder is
+ * reset afterwards. The result is returned like `string_decoder_write`'s.
+ */
+uint8_t *string_decoder_end(struct StringDecoder *decoder,
+                            const uint8_t *data,
+                            uintptr_t length,
+                            uintptr_t *decoded_length);
+
+int32_t buffer_compare(const struct Buffer *buffer, const struct Buffer *target);
+
+/**
+ * Negative starts and ends use their defaults. Returns -2 and sets `ERR_OUT_OF_RANGE`
+ * as the last error when a range is invalid.
+ */
+int32_t buffer_compare_range(const struct Buffer *buffer,
+                             const struct Buffer *target,
+                             intptr_t target_start,
+                             intptr_t target_end,
+                             intptr_t source_start,
+                             intptr_t source_end);
+
+bool buffer_equals(const struct Buffer *buffer, const struct Buffer *other);
+
+int64_t buffer_index_of_byte(const struct Buffer *buffer, uint8_t value, int64_t byte_offset);
+
+int64_t buffer_last_index_of_byte(const struct Buffer *buffer, uint8_t value, int64_t byte_offset);
+
+bool buffer_includes_byte(const struct Buffer *buffer, uint8_t value, int64_t byte_offset);
+
+int64_t buffer_index_of_buffer(const struct Buffer *buffer,
+                               const struct Buffer *value,
+                               int64_t byte_offset,
+                               enum StringEncoding encoding);
+
+int64_t buffer_last_index_of_buffer(const struct Buffer *buffer,
+                                    const struct Buffer *value,
+                                    int64_t byte_offset,
+                                    enum StringEncoding encoding);
+
+bool buffer_includes_buffer(const struct Buffer *buffer,
+                            const struct Buffer *value,
+                            int64_t byte_offset,
+                            enum StringEncoding encoding);
+
+int64_t buffer_index_of_string(const struct Buffer *buffer,
+                               const char *value,
+                               int64_t byte_offset,
+                               enum StringEncoding encoding);
+
+int64_t buffer_last_index_of_string(const struct Buffer *buffer,
+                                    const char *value,
+                                    int64_t byte_offset,
+                                    enum StringEncoding encoding);
+
+bool buffer_includes_string(const struct Buffer *buffer,
+                            const char *value,
+                            int64_t byte_offset,
+                            enum StringEncoding encoding);
+
+int64_t buffer_index_of_utf8_string(const struct Buffer *buffer,
+                                    const uint8_t *value,
+                                    uintptr_t length,
+                                    int64_t byte_offset,
+                                    enum StringEncoding encoding);
+
+int64_t buffer_last_index_of_utf8_string(const struct Buffer *buffer,
+                                         const uint8_t *value,
+                                         uintptr_t length,
+                                         int64_t byte_offset,
+                                         enum StringEncoding encoding);
+
+bool buffer_includes_utf8_string(const struct Buffer *buffer,
+                                 const uint8_t *value,
+                                 uintptr_t length,
+                                 int64_t byte_offset,
+                                 enum StringEncoding encoding);
+
+int64_t buffer_index_of_utf16_string(const struct Buffer *buffer,
+                                     const uint16_t *value,
+                                     uintptr_t length,
+                                     int64_t byte_offset,
+                                     enum StringEncoding encoding);
+
+int64_t buffer_last_index_of_utf16_string(const struct Buffer *buffer,
+                                          const uint16_t *value,
+                                          uintptr_t length,
+                                          int64_t byte_offset,
+                                          enum StringEncoding encoding);
+
+bool buffer_includes_utf16_string(const struct Buffer *buffer,
+                                  const uint16_t *value,
+                                  uintptr_t length,
+                                  int64_t byte_offset,
+                                  enum StringEncoding encoding);
+
 const char *fs_encoding_get_string_value(const struct FsEncoding *encoding);
 
 struct Buffer *fs_encoding_get_buffer_value(const struct FsEncoding *encoding);
@@ -421,6 +1161,18 @@ void fs_chown_sync(const char *path, uint32_t uid, uint32_t gid);
 
 void fs_close_sync(int32_t fd);
 
+/**
+ * Debug aid: records where node-fs opens and closes fds, logging double closes and uses of
+ * closed fds as they happen. Meant for development builds, it captures a backtrace per call.
+ */
+void fs_set_fd_tracking(bool enabled);
+
+/**
+ * Logs the fds node-fs opened since tracking was enabled that are still open, with where
+ * they were opened, and returns their count.
+ */
+uintptr_t fs_report_fd_leaks(void);
+
 void fs_copy_file_sync(const char *src, const char *dest, uint32_t flags);
 
 void fs_cp_sync(const char *src, const char *dest, uint32_t flags);
@@ -487,6 +1239,48 @@ struct FsEncoding *fs_read_file_sync(const char *path, struct ReadFileOptions op
 
 struct FsEncoding *fs_read_file_with_fd_sync(int32_t fd, struct ReadFileOptions options);
 
+struct AbortSignal *abort_signal_create(void);
+
+/**
+ * Operations holding the signal stop at their next check, it may be called from any thread.
+ */
+void abort_signal_abort(const struct AbortSignal *signal);
+
+bool abort_signal_aborted(const struct AbortSignal *signal);
+
+/**
+ * Operations already started keep their own reference, so the signal can be destroyed
+ * while they run.
+ */
+void abort_signal_destroy(struct AbortSignal *signal);
+
+/**
+ * Files larger than `size` make readFile fail with ERR_FS_FILE_TOO_LARGE, the default is
+ * Node's 2 GiB - 1.
+ */
+void fs_set_read_file_max_size(uint64_t size);
+
+uint64_t fs_read_file_max_size(void);
+
+/**
+ * Same as `fs_read_file_sync`, failing with an AbortError once `signal` is aborted.
+ */
+struct FsEncoding *fs_read_file_with_signal_sync(const char *path,
+                                                 struct ReadFileOptions options,
+                                                 const struct AbortSignal *signal);
+
+/**
+ * Maps the whole file copy-on-write into a buffer that is unmapped when its last view is
+ * destroyed, writes to the buffer never reach the file. Truncating the file while it is
+ * mapped makes reads past the new end raise SIGBUS.
+ */
+struct Buffer *fs_map_file_sync(const char *path, enum FsMapAdvice advice);
+
+/**
+ * Same as `fs_map_file_sync`, `fd` is left open.
+ */
+struct Buffer *fs_map_file_with_fd_sync(int32_t fd, enum FsMapAdvice advice);
+
 struct FsEncoding *fs_read_link_sync(const char *path, struct ReadLinkOptions options);
 
 uintptr_t fs_readv_sync(int32_t fd, struct Buffer **buffers, uintptr_t length, int64_t position);
@@ -525,6 +1319,18 @@ uintptr_t fs_write_string_sync(int32_t fd,
                                enum StringEncoding encoding,
                                intptr_t position);
 
+uintptr_t fs_write_utf8_string_sync(int32_t fd,
+                                    const uint8_t *string,
+                                    uintptr_t length,
+                                    enum StringEncoding encoding,
+                                    intptr_t position);
+
+uintptr_t fs_write_utf16_string_sync(int32_t fd,
+                                     const uint16_t *string,
+                                     uintptr_t length,
+                                     enum StringEncoding encoding,
+                                     intptr_t position);
+
 void fs_write_file_with_str_sync(int32_t fd, const char *data, struct WriteFileOptions options);
 
 void fs_write_file_with_bytes_sync(int32_t fd,
@@ -589,7 +1395,10 @@ void fs_async_copy_file(const char *src,
                         uint32_t flag,
                         const struct AsyncClosure *callback);
 
-void fs_async_cp(const char *_src, const char *_dest);
+void fs_async_cp(const char *src,
+                 const char *dest,
+                 uint32_t flags,
+                 const struct AsyncClosure *callback);
 
 void fs_async_exists(const char *path, const struct AsyncBoolClosure *callback);
 
@@ -677,6 +1486,20 @@ void fs_async_read_file_with_fd(int32_t fd,
                                 struct ReadFileOptions options,
                                 const struct AsyncFsEncodingClosure *callback);
 
+/**
+ * `fs.readFile(path, { signal })`, the callback receives an AbortError once `signal` is
+ * aborted.
+ */
+void fs_async_read_file_with_signal(const char *path,
+                                    struct ReadFileOptions options,
+                                    const struct AbortSignal *signal,
+                                    const struct AsyncFsEncodingClosure *callback);
+
+void fs_async_read_file_with_fd_and_signal(int32_t fd,
+                                           struct ReadFileOptions options,
+                                           const struct AbortSignal *signal,
+                                           const struct AsyncFsEncodingClosure *callback);
+
 void fs_async_read_link(const char *path,
                         struct ReadLinkOptions options,
                         const struct AsyncFsEncodingClosure *callback);
@@ -1004,4 +1827,213 @@ struct AsyncFileHandleClosure *fs_async_create_async_fs_file_handle_closure(void
                                                                             void *on_error,
                                                                             void *data);
 
+void fs_batch_result_array_destroy(struct FsBatchResultArray *value);
+
+void fs_batch_result_destroy(struct FsBatchResult *value);
+
+/**
+ * Borrows the result at `at`, it is owned by the array and must not be destroyed.
+ */
+const struct FsBatchResult *fs_batch_result_array_get_at(const struct FsBatchResultArray *value,
+                                                         uintptr_t at);
+
+enum FsBatchResultType fs_batch_result_get_type(const struct FsBatchResult *value);
+
+struct NodeError *fs_batch_result_get_error(const struct FsBatchResult *value);
+
+struct FileStat *fs_batch_result_get_stat(const struct FsBatchResult *value);
+
+bool fs_batch_result_get_exists(const struct FsBatchResult *value);
+
+struct FsEncoding *fs_batch_result_get_fs_encoding(const struct FsBatchResult *value);
+
+struct ReaddirResultArray *fs_batch_result_get_readdir(const struct FsBatchResult *value);
+
+/**
+ * Runs `length` operations on the calling thread and returns their results in order,
+ * a failing operation does not stop the rest of the batch.
+ */
+struct FsBatchResultArray *fs_batch_sync(const struct FsBatchOperation *operations,
+                                         uintptr_t length);
+
+/**
+ * Runs the operations on the I/O pool with at most `concurrency` in flight (`0` uses the
+ * cpu count), the callback receives every result at once in submission order.
+ */
+void fs_async_batch(const struct FsBatchOperation *operations,
+                    uintptr_t length,
+                    uintptr_t concurrency,
+                    const struct AsyncBatchClosure *callback);
+
+/**
+ * Like `fs_async_batch` but `on_item` is invoked with `(index, result)` as soon as each
+ * operation completes, in completion order, and `on_complete` once all of them have.
+ */
+void fs_async_batch_each(const struct FsBatchOperation *operations,
+                         uintptr_t length,
+                         uintptr_t concurrency,
+                         const struct AsyncBatchItemClosure *on_item,
+                         const struct AsyncClosure *on_complete);
+
+void fs_async_batch_closure_destroy(struct AsyncBatchClosure *value);
+
+void fs_async_batch_item_closure_destroy(struct AsyncBatchItemClosure *value);
+
+struct AsyncBatchClosure *fs_async_create_async_batch_closure(void *on_success,
+                                                              void *on_error,
+                                                              void *data);
+
+/**
+ * `on_success` receives `(index, result, data)`, the result is owned by the callee and must
+ * be released with `fs_batch_result_destroy`.
+ */
+struct AsyncBatchItemClosure *fs_async_create_async_batch_item_closure(void *on_success,
+                                                                       void *on_error,
+                                                                       void *data);
+
+/**
+ * Starts recording the comma separated categories: `node.fs.sync`, `node.fs.async`
+ * and `node.buffer` (allocations over 1 MiB).
+ */
+void node_trace_enable(const char *categories);
+
+void node_trace_disable(void);
+
+void node_trace_set_large_allocation_threshold(uintptr_t bytes);
+
+/**
+ * Older events are dropped once `count` are waiting to be flushed, the default keeps 100000.
+ */
+void node_trace_set_max_events(uintptr_t count);
+
+/**
+ * Writes and clears the recorded events, returns false and sets the last error on failure.
+ */
+bool node_trace_flush_to_file(const char *path);
+
+/**
+ * Hands the recorded events to `callback` and clears them.
+ */
+void node_trace_flush(TraceCallback callback, void *data);
+
+/**
+ * Returns null if a part is null or a string part isn't valid UTF-8.
+ */
+struct Blob *blob_create(const struct BlobPart *parts, uintptr_t length, const char *type_);
+
+void blob_destroy(struct Blob *blob);
+
+/**
+ * Another reference to the same immutable data.
+ */
+struct Blob *blob_clone(const struct Blob *blob);
+
+uintptr_t blob_get_size(const struct Blob *blob);
+
+const char *blob_get_type(const struct Blob *blob);
+
+/**
+ * `blob.slice(start, end, contentType)`, pass `end` as the size to slice to the end.
+ */
+struct Blob *blob_slice(const struct Blob *blob, intptr_t start, intptr_t end, const char *type_);
+
+/**
+ * Reads up to `length` bytes at `offset` into `data`, returns the count read or -1 with the
+ * last error set.
+ */
+intptr_t blob_read_at(const struct Blob *blob, uintptr_t offset, uint8_t *data, uintptr_t length);
+
+struct Buffer *blob_array_buffer(const struct Blob *blob);
+
+const char *blob_text(const struct Blob *blob);
+
+/**
+ * `chunk_size` of 0 uses Node's 64 KiB.
+ */
+struct BlobStream *blob_stream(const struct Blob *blob, uintptr_t chunk_size);
+
+/**
+ * Returns a copy of the next chunk, or null once the blob is exhausted or a read failed in
+ * which case the last error is set.
+ */
+struct Buffer *blob_stream_next(struct BlobStream *stream);
+
+void blob_stream_destroy(struct BlobStream *stream);
+
+/**
+ * `fs.openAsBlob(path, { type })`, the file is read lazily and reads set a `NotReadableError`
+ * once it was modified after opening.
+ */
+struct Blob *fs_open_as_blob(const char *path, const char *type_);
+
+/**
+ * `last_modified` is in ms since the epoch, a negative value uses the current time.
+ */
+struct File *file_create(const struct BlobPart *parts,
+                         uintptr_t length,
+                         const char *name,
+                         const char *type_,
+                         int64_t last_modified);
+
+void file_destroy(struct File *file);
+
+const char *file_get_name(const struct File *file);
+
+int64_t file_get_last_modified(const struct File *file);
+
+/**
+ * The file's data as a `Blob`, use it with the `blob_*` functions.
+ */
+struct Blob *file_get_blob(const struct File *file);
+
+/**
+ * Unversioned entry point, its signature never changes so hosts can always resolve it
+ * before picking the `node_c_v{N}_` group matching the returned value.
+ */
+uint32_t node_c_abi_version(void);
+
+uint32_t node_c_v1_abi_version(void);
+
+/**
+ * Semver of the library, the string is static and must not be freed.
+ */
+const char *node_c_v1_version(void);
+
+/**
+ * Bitset of the `NODE_C_CAPABILITY_*` values supported by this build.
+ */
+uint64_t node_c_v1_capabilities(void);
+
+/**
+ * Queries a capability by name e.g. `"fs.cp"`, `"fs.bigint_stats"` or `"crypto"`,
+ * unknown names return false.
+ */
+bool node_c_v1_has_capability(const char *name);
+
+const struct NodeCV1FsCp *node_c_v1_fs_cp(void);
+
+const struct NodeCV1FsWatch *node_c_v1_fs_watch(void);
+
+const struct NodeCV1FsBatch *node_c_v1_fs_batch(void);
+
+const struct NodeCV1BufferExternal *node_c_v1_buffer_external(void);
+
+const struct NodeCV1Trace *node_c_v1_trace(void);
+
+const struct NodeCV1BufferBlob *node_c_v1_buffer_blob(void);
+
+const struct NodeCV1FsOpenAsBlob *node_c_v1_fs_open_as_blob(void);
+
+const struct NodeCV1BufferMemoryAccounting *node_c_v1_buffer_memory_accounting(void);
+
+const struct NodeCV1BufferFill *node_c_v1_buffer_fill(void);
+
+const struct NodeCV1BufferWrite *node_c_v1_buffer_write(void);
+
+const struct NodeCV1FsMapFile *node_c_v1_fs_map_file(void);
+
+const struct NodeCV1FsReadFileLimits *node_c_v1_fs_read_file_limits(void);
+
+const struct NodeCV1FsFdTracking *node_c_v1_fs_fd_tracking(void);
+
 #endif /* NODE_C_H */
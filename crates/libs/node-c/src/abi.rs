@@ -0,0 +1,444 @@
+use std::ffi::{c_char, c_void, CStr};
+
+use crate::{
+    AbortSignal,
+    AsyncClosure,
+    AsyncFileWatchClosure,
+    AsyncFsEncodingClosure,
+    AsyncWatchClosure,
+    Blob,
+    BlobPart,
+    BlobStream,
+    Buffer,
+    BufferFill,
+    BufferMemoryUsage,
+    FileStat,
+    FsBatchOperation,
+    FsBatchResult,
+    FsBatchResultArray,
+    FsBatchResultType,
+    FsEncoding,
+    FsEncodingType,
+    FsMapAdvice,
+    NodeError,
+    ReadFileOptions,
+    ReaddirResultArray,
+    StringEncoding,
+    TraceCallback,
+    WatchEvent,
+};
+
+/// Bumped whenever an existing export changes signature or is removed, additions only
+/// add a capability bit.
+pub const NODE_C_ABI_VERSION: u32 = 1;
+
+pub const NODE_C_CAPABILITY_FS_CP: u64 = 1 << 0;
+pub const NODE_C_CAPABILITY_FS_BIGINT_STATS: u64 = 1 << 1;
+pub const NODE_C_CAPABILITY_FS_WATCH: u64 = 1 << 2;
+pub const NODE_C_CAPABILITY_FS_BATCH: u64 = 1 << 3;
+pub const NODE_C_CAPABILITY_BUFFER_EXTERNAL: u64 = 1 << 4;
+pub const NODE_C_CAPABILITY_CRYPTO: u64 = 1 << 5;
+pub const NODE_C_CAPABILITY_TRACE: u64 = 1 << 6;
+pub const NODE_C_CAPABILITY_BUFFER_BLOB: u64 = 1 << 7;
+pub const NODE_C_CAPABILITY_FS_OPEN_AS_BLOB: u64 = 1 << 8;
+pub const NODE_C_CAPABILITY_BUFFER_MEMORY_ACCOUNTING: u64 = 1 << 9;
+pub const NODE_C_CAPABILITY_BUFFER_FILL: u64 = 1 << 10;
+pub const NODE_C_CAPABILITY_BUFFER_WRITE: u64 = 1 << 11;
+pub const NODE_C_CAPABILITY_FS_MAP_FILE: u64 = 1 << 12;
+pub const NODE_C_CAPABILITY_FS_READ_FILE_LIMITS: u64 = 1 << 13;
+pub const NODE_C_CAPABILITY_FS_FD_TRACKING: u64 = 1 << 14;
+
+const CAPABILITIES: &[(&str, u64, bool)] = &[
+    ("fs.cp", NODE_C_CAPABILITY_FS_CP, false),
+    ("fs.bigint_stats", NODE_C_CAPABILITY_FS_BIGINT_STATS, false),
+    ("fs.watch", NODE_C_CAPABILITY_FS_WATCH, true),
+    ("fs.batch", NODE_C_CAPABILITY_FS_BATCH, true),
+    ("buffer.external", NODE_C_CAPABILITY_BUFFER_EXTERNAL, true),
+    ("crypto", NODE_C_CAPABILITY_CRYPTO, false),
+    ("trace", NODE_C_CAPABILITY_TRACE, true),
+    ("buffer.blob", NODE_C_CAPABILITY_BUFFER_BLOB, true),
+    ("fs.open_as_blob", NODE_C_CAPABILITY_FS_OPEN_AS_BLOB, true),
+    ("buffer.memory_accounting", NODE_C_CAPABILITY_BUFFER_MEMORY_ACCOUNTING, true),
+    ("buffer.fill", NODE_C_CAPABILITY_BUFFER_FILL, true),
+    ("buffer.write", NODE_C_CAPABILITY_BUFFER_WRITE, true),
+    ("fs.map_file", NODE_C_CAPABILITY_FS_MAP_FILE, true),
+    ("fs.read_file_limits", NODE_C_CAPABILITY_FS_READ_FILE_LIMITS, true),
+    ("fs.fd_tracking", NODE_C_CAPABILITY_FS_FD_TRACKING, true),
+];
+
+static VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+
+fn capabilities() -> u64 {
+    CAPABILITIES.iter()
+        .filter(|(_, _, enabled)| *enabled)
+        .fold(0, |bits, (_, bit, _)| bits | bit)
+}
+
+/// Unversioned entry point, its signature never changes so hosts can always resolve it
+/// before picking the `node_c_v{N}_` group matching the returned value.
+#[no_mangle]
+pub extern "C" fn node_c_abi_version() -> u32 {
+    NODE_C_ABI_VERSION
+}
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_abi_version() -> u32 {
+    NODE_C_ABI_VERSION
+}
+
+/// Semver of the library, the string is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn node_c_v1_version() -> *const c_char {
+    VERSION.as_ptr() as *const c_char
+}
+
+/// Bitset of the `NODE_C_CAPABILITY_*` values supported by this build.
+#[no_mangle]
+pub extern "C" fn node_c_v1_capabilities() -> u64 {
+    capabilities()
+}
+
+/// Queries a capability by name e.g. `"fs.cp"`, `"fs.bigint_stats"` or `"crypto"`,
+/// unknown names return false.
+#[no_mangle]
+pub extern "C" fn node_c_v1_has_capability(name: *const c_char) -> bool {
+    if name.is_null() {
+        return false;
+    }
+    let name = unsafe { CStr::from_ptr(name) };
+    let name = name.to_string_lossy();
+
+    CAPABILITIES.iter()
+        .any(|(capability, _, enabled)| *enabled && *capability == name.as_ref())
+}
+
+// Every optional capability also has its exports grouped in a table behind a versioned
+// getter, so a host can feature-detect a whole group with one lookup that never fails to
+// resolve. Fields are only ever appended, a changed signature gets a new `node_c_v{N}_`
+// getter and table.
+fn group<T>(capability: u64, table: &'static T) -> *const T {
+    if capabilities() & capability == 0 {
+        return std::ptr::null();
+    }
+    table
+}
+
+/// Exports of the `"fs.cp"` capability, `node_c_v1_fs_cp` returns null when this build
+/// lacks it.
+#[repr(C)]
+pub struct NodeCV1FsCp {
+    pub fs_cp_sync: extern "C" fn(src: *const c_char, dest: *const c_char, flags: u32),
+    pub fs_async_cp: extern "C" fn(src: *const c_char, dest: *const c_char, flags: u32, callback: *const AsyncClosure),
+}
+
+static FS_CP: NodeCV1FsCp = NodeCV1FsCp {
+    fs_cp_sync: crate::fs_cp_sync,
+    fs_async_cp: crate::fs_async_cp,
+};
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_fs_cp() -> *const NodeCV1FsCp {
+    group(NODE_C_CAPABILITY_FS_CP, &FS_CP)
+}
+
+/// Exports of the `"fs.watch"` capability, `node_c_v1_fs_watch` returns null when this
+/// build lacks it.
+#[repr(C)]
+pub struct NodeCV1FsWatch {
+    pub fs_async_watch: extern "C" fn(filename: *const c_char, persistent: bool, recursive: bool, encoding: FsEncodingType, callback: *const AsyncWatchClosure),
+    pub fs_async_watcher_ref: extern "C" fn(filename: *const c_char, callback: *const AsyncWatchClosure),
+    pub fs_async_watcher_unref: extern "C" fn(filename: *const c_char, callback: *const AsyncWatchClosure),
+    pub fs_async_watcher_close: extern "C" fn(filename: *const c_char, callback: *const AsyncWatchClosure, on_close: *const AsyncClosure),
+    pub fs_async_watch_file: extern "C" fn(filename: *const c_char, bigint: bool, persistent: bool, interval: u64, encoding: FsEncodingType, callback: *const AsyncFileWatchClosure),
+    pub fs_async_unwatch_file: extern "C" fn(filename: *const c_char),
+    pub fs_async_unwatch_file_with_callback: extern "C" fn(filename: *const c_char, callback: *const AsyncFileWatchClosure),
+    pub fs_watch_event_destroy: extern "C" fn(event: *mut WatchEvent),
+    pub fs_watch_event_event_type: extern "C" fn(event: *const WatchEvent) -> *const c_char,
+    pub fs_watch_event_filename: extern "C" fn(event: *const WatchEvent) -> *const c_char,
+    pub fs_async_create_async_fs_watch_closure: extern "C" fn(on_success: *mut c_void, on_error: *mut c_void, data: *mut c_void) -> *mut AsyncWatchClosure,
+    pub fs_async_watch_closure_destroy: unsafe extern "C" fn(value: *mut AsyncWatchClosure),
+    pub fs_async_create_async_file_watch_closure: extern "C" fn(on_success: *mut c_void, on_error: *mut c_void, data: *mut c_void) -> *mut AsyncFileWatchClosure,
+    pub fs_async_file_watch_closure_destroy: unsafe extern "C" fn(value: *mut AsyncFileWatchClosure),
+}
+
+static FS_WATCH: NodeCV1FsWatch = NodeCV1FsWatch {
+    fs_async_watch: crate::fs_async_watch,
+    fs_async_watcher_ref: crate::fs_async_watcher_ref,
+    fs_async_watcher_unref: crate::fs_async_watcher_unref,
+    fs_async_watcher_close: crate::fs_async_watcher_close,
+    fs_async_watch_file: crate::fs_async_watch_file,
+    fs_async_unwatch_file: crate::fs_async_unwatch_file,
+    fs_async_unwatch_file_with_callback: crate::fs_async_unwatch_file_with_callback,
+    fs_watch_event_destroy: crate::fs_watch_event_destroy,
+    fs_watch_event_event_type: crate::fs_watch_event_event_type,
+    fs_watch_event_filename: crate::fs_watch_event_filename,
+    fs_async_create_async_fs_watch_closure: crate::fs_async_create_async_fs_watch_closure,
+    fs_async_watch_closure_destroy: crate::fs_async_watch_closure_destroy,
+    fs_async_create_async_file_watch_closure: crate::fs_async_create_async_file_watch_closure,
+    fs_async_file_watch_closure_destroy: crate::fs_async_file_watch_closure_destroy,
+};
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_fs_watch() -> *const NodeCV1FsWatch {
+    group(NODE_C_CAPABILITY_FS_WATCH, &FS_WATCH)
+}
+
+/// Exports of the `"fs.batch"` capability, `node_c_v1_fs_batch` returns null when this
+/// build lacks it.
+#[repr(C)]
+pub struct NodeCV1FsBatch {
+    pub fs_batch_sync: extern "C" fn(operations: *const FsBatchOperation, length: usize) -> *mut FsBatchResultArray,
+    pub fs_batch_result_array_get_at: extern "C" fn(value: *const FsBatchResultArray, at: usize) -> *const FsBatchResult,
+    pub fs_batch_result_array_destroy: extern "C" fn(value: *mut FsBatchResultArray),
+    pub fs_batch_result_destroy: extern "C" fn(value: *mut FsBatchResult),
+    pub fs_batch_result_get_type: extern "C" fn(value: *const FsBatchResult) -> FsBatchResultType,
+    pub fs_batch_result_get_error: extern "C" fn(value: *const FsBatchResult) -> *mut NodeError,
+    pub fs_batch_result_get_stat: extern "C" fn(value: *const FsBatchResult) -> *mut FileStat,
+    pub fs_batch_result_get_exists: extern "C" fn(value: *const FsBatchResult) -> bool,
+    pub fs_batch_result_get_fs_encoding: extern "C" fn(value: *const FsBatchResult) -> *mut FsEncoding,
+    pub fs_batch_result_get_readdir: extern "C" fn(value: *const FsBatchResult) -> *mut ReaddirResultArray,
+}
+
+static FS_BATCH: NodeCV1FsBatch = NodeCV1FsBatch {
+    fs_batch_sync: crate::fs_batch_sync,
+    fs_batch_result_array_get_at: crate::fs_batch_result_array_get_at,
+    fs_batch_result_array_destroy: crate::fs_batch_result_array_destroy,
+    fs_batch_result_destroy: crate::fs_batch_result_destroy,
+    fs_batch_result_get_type: crate::fs_batch_result_get_type,
+    fs_batch_result_get_error: crate::fs_batch_result_get_error,
+    fs_batch_result_get_stat: crate::fs_batch_result_get_stat,
+    fs_batch_result_get_exists: crate::fs_batch_result_get_exists,
+    fs_batch_result_get_fs_encoding: crate::fs_batch_result_get_fs_encoding,
+    fs_batch_result_get_readdir: crate::fs_batch_result_get_readdir,
+};
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_fs_batch() -> *const NodeCV1FsBatch {
+    group(NODE_C_CAPABILITY_FS_BATCH, &FS_BATCH)
+}
+
+/// Exports of the `"buffer.external"` capability, `node_c_v1_buffer_external` returns null
+/// when this build lacks it.
+#[repr(C)]
+pub struct NodeCV1BufferExternal {
+    pub buffer_from_external: extern "C" fn(data: *mut u8, size: usize, finalizer: Option<node_buffer::BufferFinalizer>, context: *mut c_void) -> *mut Buffer,
+}
+
+static BUFFER_EXTERNAL: NodeCV1BufferExternal = NodeCV1BufferExternal {
+    buffer_from_external: crate::buffer_from_external,
+};
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_buffer_external() -> *const NodeCV1BufferExternal {
+    group(NODE_C_CAPABILITY_BUFFER_EXTERNAL, &BUFFER_EXTERNAL)
+}
+
+/// Exports of the `"trace"` capability, `node_c_v1_trace` returns null when this build
+/// lacks it.
+#[repr(C)]
+pub struct NodeCV1Trace {
+    pub node_trace_enable: extern "C" fn(categories: *const c_char),
+    pub node_trace_disable: extern "C" fn(),
+    pub node_trace_set_large_allocation_threshold: extern "C" fn(bytes: usize),
+    pub node_trace_flush_to_file: extern "C" fn(path: *const c_char) -> bool,
+    pub node_trace_flush: extern "C" fn(callback: TraceCallback, data: *mut c_void),
+    pub node_trace_set_max_events: extern "C" fn(count: usize),
+}
+
+static TRACE: NodeCV1Trace = NodeCV1Trace {
+    node_trace_enable: crate::node_trace_enable,
+    node_trace_disable: crate::node_trace_disable,
+    node_trace_set_large_allocation_threshold: crate::node_trace_set_large_allocation_threshold,
+    node_trace_flush_to_file: crate::node_trace_flush_to_file,
+    node_trace_flush: crate::node_trace_flush,
+    node_trace_set_max_events: crate::node_trace_set_max_events,
+};
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_trace() -> *const NodeCV1Trace {
+    group(NODE_C_CAPABILITY_TRACE, &TRACE)
+}
+
+/// Exports of the `"buffer.blob"` capability, `node_c_v1_buffer_blob` returns null when
+/// this build lacks it.
+#[repr(C)]
+pub struct NodeCV1BufferBlob {
+    pub blob_create: extern "C" fn(parts: *const BlobPart, length: usize, type_: *const c_char) -> *mut Blob,
+    pub blob_destroy: extern "C" fn(blob: *mut Blob),
+    pub blob_clone: extern "C" fn(blob: *const Blob) -> *mut Blob,
+    pub blob_get_size: extern "C" fn(blob: *const Blob) -> usize,
+    pub blob_get_type: extern "C" fn(blob: *const Blob) -> *const c_char,
+    pub blob_slice: extern "C" fn(blob: *const Blob, start: isize, end: isize, type_: *const c_char) -> *mut Blob,
+    pub blob_read_at: extern "C" fn(blob: *const Blob, offset: usize, data: *mut u8, length: usize) -> isize,
+    pub blob_array_buffer: extern "C" fn(blob: *const Blob) -> *mut Buffer,
+    pub blob_text: extern "C" fn(blob: *const Blob) -> *const c_char,
+    pub blob_stream: extern "C" fn(blob: *const Blob, chunk_size: usize) -> *mut BlobStream,
+    pub blob_stream_next: extern "C" fn(stream: *mut BlobStream) -> *mut Buffer,
+    pub blob_stream_destroy: extern "C" fn(stream: *mut BlobStream),
+}
+
+static BUFFER_BLOB: NodeCV1BufferBlob = NodeCV1BufferBlob {
+    blob_create: crate::blob_create,
+    blob_destroy: crate::blob_destroy,
+    blob_clone: crate::blob_clone,
+    blob_get_size: crate::blob_get_size,
+    blob_get_type: crate::blob_get_type,
+    blob_slice: crate::blob_slice,
+    blob_read_at: crate::blob_read_at,
+    blob_array_buffer: crate::blob_array_buffer,
+    blob_text: crate::blob_text,
+    blob_stream: crate::blob_stream,
+    blob_stream_next: crate::blob_stream_next,
+    blob_stream_destroy: crate::blob_stream_destroy,
+};
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_buffer_blob() -> *const NodeCV1BufferBlob {
+    group(NODE_C_CAPABILITY_BUFFER_BLOB, &BUFFER_BLOB)
+}
+
+/// Exports of the `"fs.open_as_blob"` capability, `node_c_v1_fs_open_as_blob` returns null
+/// when this build lacks it.
+#[repr(C)]
+pub struct NodeCV1FsOpenAsBlob {
+    pub fs_open_as_blob: extern "C" fn(path: *const c_char, type_: *const c_char) -> *mut Blob,
+}
+
+static FS_OPEN_AS_BLOB: NodeCV1FsOpenAsBlob = NodeCV1FsOpenAsBlob {
+    fs_open_as_blob: crate::fs_open_as_blob,
+};
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_fs_open_as_blob() -> *const NodeCV1FsOpenAsBlob {
+    group(NODE_C_CAPABILITY_FS_OPEN_AS_BLOB, &FS_OPEN_AS_BLOB)
+}
+
+/// Exports of the `"buffer.memory_accounting"` capability,
+/// `node_c_v1_buffer_memory_accounting` returns null when this build lacks it.
+#[repr(C)]
+pub struct NodeCV1BufferMemoryAccounting {
+    pub buffer_memory_usage: extern "C" fn() -> BufferMemoryUsage,
+}
+
+static BUFFER_MEMORY_ACCOUNTING: NodeCV1BufferMemoryAccounting = NodeCV1BufferMemoryAccounting {
+    buffer_memory_usage: crate::buffer_memory_usage,
+};
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_buffer_memory_accounting() -> *const NodeCV1BufferMemoryAccounting {
+    group(NODE_C_CAPABILITY_BUFFER_MEMORY_ACCOUNTING, &BUFFER_MEMORY_ACCOUNTING)
+}
+
+/// Exports of the `"buffer.fill"` capability, `node_c_v1_buffer_fill` returns null when
+/// this build lacks it.
+#[repr(C)]
+pub struct NodeCV1BufferFill {
+    pub buffer_fill: extern "C" fn(buffer: *mut Buffer, fill: *const BufferFill, offset: isize, end: isize, encoding: StringEncoding) -> bool,
+    pub buffer_fill_string: extern "C" fn(buffer: *mut Buffer, string: *const c_char, encoding: StringEncoding),
+    pub buffer_fill_utf8_string: extern "C" fn(buffer: *mut Buffer, string: *const u8, length: usize, encoding: StringEncoding),
+    pub buffer_fill_utf16_string: extern "C" fn(buffer: *mut Buffer, string: *const u16, length: usize, encoding: StringEncoding),
+}
+
+static BUFFER_FILL: NodeCV1BufferFill = NodeCV1BufferFill {
+    buffer_fill: crate::buffer_fill,
+    buffer_fill_string: crate::buffer_fill_string,
+    buffer_fill_utf8_string: crate::buffer_fill_utf8_string,
+    buffer_fill_utf16_string: crate::buffer_fill_utf16_string,
+};
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_buffer_fill() -> *const NodeCV1BufferFill {
+    group(NODE_C_CAPABILITY_BUFFER_FILL, &BUFFER_FILL)
+}
+
+/// Exports of the `"buffer.write"` capability, `node_c_v1_buffer_write` returns null when
+/// this build lacks it.
+#[repr(C)]
+pub struct NodeCV1BufferWrite {
+    pub buffer_write_string: extern "C" fn(buffer: *mut Buffer, string: *const c_char, offset: isize, length: isize, encoding: StringEncoding) -> isize,
+    pub buffer_write_utf8_string: extern "C" fn(buffer: *mut Buffer, string: *const u8, string_length: usize, offset: isize, length: isize, encoding: StringEncoding) -> isize,
+    pub buffer_write_utf16_string: extern "C" fn(buffer: *mut Buffer, string: *const u16, string_length: usize, offset: isize, length: isize, encoding: StringEncoding) -> isize,
+    pub buffer_byte_length_utf8: extern "C" fn(string: *const u8, length: usize, encoding: StringEncoding) -> usize,
+    pub buffer_byte_length_utf16: extern "C" fn(string: *const u16, length: usize, encoding: StringEncoding) -> usize,
+}
+
+static BUFFER_WRITE: NodeCV1BufferWrite = NodeCV1BufferWrite {
+    buffer_write_string: crate::buffer_write_string,
+    buffer_write_utf8_string: crate::buffer_write_utf8_string,
+    buffer_write_utf16_string: crate::buffer_write_utf16_string,
+    buffer_byte_length_utf8: crate::buffer_byte_length_utf8,
+    buffer_byte_length_utf16: crate::buffer_byte_length_utf16,
+};
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_buffer_write() -> *const NodeCV1BufferWrite {
+    group(NODE_C_CAPABILITY_BUFFER_WRITE, &BUFFER_WRITE)
+}
+
+/// Exports of the `"fs.map_file"` capability, `node_c_v1_fs_map_file` returns null when
+/// this build lacks it.
+#[repr(C)]
+pub struct NodeCV1FsMapFile {
+    pub fs_map_file_sync: extern "C" fn(path: *const c_char, advice: FsMapAdvice) -> *mut Buffer,
+    pub fs_map_file_with_fd_sync: extern "C" fn(fd: i32, advice: FsMapAdvice) -> *mut Buffer,
+}
+
+static FS_MAP_FILE: NodeCV1FsMapFile = NodeCV1FsMapFile {
+    fs_map_file_sync: crate::fs_map_file_sync,
+    fs_map_file_with_fd_sync: crate::fs_map_file_with_fd_sync,
+};
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_fs_map_file() -> *const NodeCV1FsMapFile {
+    group(NODE_C_CAPABILITY_FS_MAP_FILE, &FS_MAP_FILE)
+}
+
+/// Exports of the `"fs.read_file_limits"` capability, `node_c_v1_fs_read_file_limits`
+/// returns null when this build lacks it.
+#[repr(C)]
+pub struct NodeCV1FsReadFileLimits {
+    pub fs_set_read_file_max_size: extern "C" fn(size: u64),
+    pub fs_read_file_max_size: extern "C" fn() -> u64,
+    pub abort_signal_create: extern "C" fn() -> *mut AbortSignal,
+    pub abort_signal_abort: extern "C" fn(signal: *const AbortSignal),
+    pub abort_signal_aborted: extern "C" fn(signal: *const AbortSignal) -> bool,
+    pub abort_signal_destroy: extern "C" fn(signal: *mut AbortSignal),
+    pub fs_read_file_with_signal_sync: extern "C" fn(path: *const c_char, options: ReadFileOptions, signal: *const AbortSignal) -> *mut FsEncoding,
+    pub fs_async_read_file_with_signal: extern "C" fn(path: *const c_char, options: ReadFileOptions, signal: *const AbortSignal, callback: *const AsyncFsEncodingClosure),
+    pub fs_async_read_file_with_fd_and_signal: extern "C" fn(fd: i32, options: ReadFileOptions, signal: *const AbortSignal, callback: *const AsyncFsEncodingClosure),
+}
+
+static FS_READ_FILE_LIMITS: NodeCV1FsReadFileLimits = NodeCV1FsReadFileLimits {
+    fs_set_read_file_max_size: crate::fs_set_read_file_max_size,
+    fs_read_file_max_size: crate::fs_read_file_max_size,
+    abort_signal_create: crate::abort_signal_create,
+    abort_signal_abort: crate::abort_signal_abort,
+    abort_signal_aborted: crate::abort_signal_aborted,
+    abort_signal_destroy: crate::abort_signal_destroy,
+    fs_read_file_with_signal_sync: crate::fs_read_file_with_signal_sync,
+    fs_async_read_file_with_signal: crate::fs_async_read_file_with_signal,
+    fs_async_read_file_with_fd_and_signal: crate::fs_async_read_file_with_fd_and_signal,
+};
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_fs_read_file_limits() -> *const NodeCV1FsReadFileLimits {
+    group(NODE_C_CAPABILITY_FS_READ_FILE_LIMITS, &FS_READ_FILE_LIMITS)
+}
+
+/// Exports of the `"fs.fd_tracking"` capability, `node_c_v1_fs_fd_tracking` returns null
+/// when this build lacks it.
+#[repr(C)]
+pub struct NodeCV1FsFdTracking {
+    pub fs_set_fd_tracking: extern "C" fn(enabled: bool),
+    pub fs_report_fd_leaks: extern "C" fn() -> usize,
+}
+
+static FS_FD_TRACKING: NodeCV1FsFdTracking = NodeCV1FsFdTracking {
+    fs_set_fd_tracking: crate::fs_set_fd_tracking,
+    fs_report_fd_leaks: crate::fs_report_fd_leaks,
+};
+
+#[no_mangle]
+pub extern "C" fn node_c_v1_fs_fd_tracking() -> *const NodeCV1FsFdTracking {
+    group(NODE_C_CAPABILITY_FS_FD_TRACKING, &FS_FD_TRACKING)
+}
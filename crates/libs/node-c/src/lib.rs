@@ -7,6 +7,10 @@ use std::sync::Arc;
 use node_fs::prelude::{handle_meta};
 use node_core::error::{AnyError, get_custom_error_message, Result};
 
+mod abi;
+
+pub use abi::*;
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 pub struct WriteFileOptions {
@@ -57,6 +61,10 @@ pub struct ReadLinkOptions {
 pub struct ReadFileOptions {
     flag: i32,
     encoding: FsEncodingType,
+    /// Map regular files copy-on-write instead of reading them. Only set it for files nothing
+    /// truncates while the buffer is alive, touching pages past the new end of a truncated
+    /// file raises SIGBUS, see `fs_map_file_sync`.
+    map: bool,
 }
 
 #[repr(C)]
@@ -266,6 +274,20 @@ pub unsafe extern "C" fn node_error_get_message(error: *const NodeError) -> *con
 }
 
 
+/// Node's `code` e.g. `ERR_OUT_OF_RANGE`, null when the error has none.
+#[no_mangle]
+pub unsafe extern "C" fn node_error_get_code(error: *const NodeError) -> *const c_char {
+    if error.is_null() {
+        return std::ptr::null();
+    }
+    let error = unsafe { &*error };
+    match error.code() {
+        Some(code) => CString::new(code).unwrap().into_raw(),
+        None => std::ptr::null()
+    }
+}
+
+
 #[no_mangle]
 pub unsafe extern "C" fn node_error_destroy(error: *mut NodeError) {
     if error.is_null() {
@@ -308,6 +330,18 @@ impl NodeError {
     pub fn message(&self) -> Cow<str> {
         get_custom_error_message(&self.0).unwrap_or_default()
     }
+
+    pub fn code(&self) -> Option<&'static str> {
+        node_core::error::get_custom_error_code(&self.0)
+    }
+}
+
+/// Length delimited string input, `data` may only be null for an empty string.
+unsafe fn string_slice<'a, T>(data: *const T, length: usize) -> Option<&'a [T]> {
+    if data.is_null() {
+        return (length == 0).then_some(&[]);
+    }
+    Some(std::slice::from_raw_parts(data, length))
 }
 
 fn to_optional(value: isize) -> Option<usize> {
@@ -333,6 +367,13 @@ pub fn update_last_error<E: Into<Box<NodeError>> + 'static>(e: E) {
     });
 }
 
+/// Clear the thread-local `LAST_ERROR` variable.
+pub fn clear_last_error() {
+    LAST_ERROR.with(|last| {
+        *last.borrow_mut() = None;
+    });
+}
+
 /// Get the last error, clearing the variable in the process.
 pub fn get_last_error() -> Option<Box<NodeError>> {
     LAST_ERROR.with(|last| last.borrow_mut().take())
@@ -383,6 +424,22 @@ pub unsafe extern "C" fn node_error_message(buffer: *mut c_char, length: libc::c
 }
 
 
+/// The `code` of the last error without clearing it, null when there is no error
+/// or it has no code. Free with `node_string_destroy`.
+#[no_mangle]
+pub extern "C" fn node_last_error_code() -> *const c_char {
+    LAST_ERROR.with(|prev| match *prev.borrow() {
+        Some(ref err) => {
+            match err.code() {
+                Some(code) => CString::new(code).unwrap().into_raw(),
+                None => std::ptr::null()
+            }
+        }
+        None => std::ptr::null(),
+    })
+}
+
+
 /// Calculate the number of bytes in the last error's error message **not**
 /// including any trailing `null` characters.
 #[no_mangle]
@@ -434,6 +491,64 @@ pub extern "C" fn buffer_alloc(size: usize) -> *mut Buffer {
     )
 }
 
+/// `Buffer.allocUnsafe(size)`, small buffers share a pool slab.
+#[no_mangle]
+pub extern "C" fn buffer_alloc_unsafe(size: usize) -> *mut Buffer {
+    Box::into_raw(
+        Buffer(node_buffer::Buffer::alloc_unsafe(size)).into_box()
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_alloc_unsafe_slow(size: usize) -> *mut Buffer {
+    Box::into_raw(
+        Buffer(node_buffer::Buffer::alloc_unsafe_slow(size)).into_box()
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_get_pool_size() -> usize {
+    node_buffer::pool_size()
+}
+
+/// Sets `Buffer.poolSize`, 0 disables pooling.
+#[no_mangle]
+pub extern "C" fn buffer_set_pool_size(size: usize) {
+    node_buffer::set_pool_size(size)
+}
+
+/// Receives the net change in bytes held by buffers since its last call, pass it on to
+/// e.g. `AdjustAmountOfExternalAllocatedMemory`. It may run on any thread.
+pub type BufferExternalMemoryCallback = Option<extern "C" fn(change: i64, context: *mut c_void)>;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct BufferMemoryUsage {
+    allocated: u64,
+    freed: u64,
+    current: u64,
+}
+
+/// Installs or, when `callback` is null, removes the external memory callback. Changes are
+/// coalesced until they reach `threshold` bytes, 0 reports every allocation. Only changes
+/// after the first install are reported, seed the host's count from `buffer_memory_usage`.
+#[no_mangle]
+pub extern "C" fn buffer_set_external_memory_callback(callback: BufferExternalMemoryCallback, context: *mut c_void, threshold: usize) {
+    node_buffer::set_external_memory_callback(callback, context, threshold)
+}
+
+/// Bytes allocated and freed by buffers since startup, host memory wrapped with
+/// `buffer_from_external` or `buffer_from_reference` is not counted.
+#[no_mangle]
+pub extern "C" fn buffer_memory_usage() -> BufferMemoryUsage {
+    let usage = node_buffer::memory_usage();
+    BufferMemoryUsage {
+        allocated: usage.allocated,
+        freed: usage.freed,
+        current: usage.current(),
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn buffer_alloc_with_size_string_encoding(size: usize, string: *const c_char, encoding: StringEncoding) -> *mut Buffer {
     let string = unsafe { CStr::from_ptr(string) };
@@ -447,6 +562,32 @@ pub extern "C" fn buffer_alloc_with_size_string_encoding(size: usize, string: *c
     )
 }
 
+#[no_mangle]
+pub extern "C" fn buffer_alloc_with_size_utf8_string_encoding(size: usize, string: *const u8, length: usize, encoding: StringEncoding) -> *mut Buffer {
+    let Some(string) = (unsafe { string_slice(string, length) }) else {
+        return std::ptr::null_mut();
+    };
+    Box::into_raw(
+        Buffer(node_buffer::Buffer::builder()
+            .size(size)
+            .fill_text_utf8(string, encoding.into())
+            .build()).into_box()
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_alloc_with_size_utf16_string_encoding(size: usize, string: *const u16, length: usize, encoding: StringEncoding) -> *mut Buffer {
+    let Some(string) = (unsafe { string_slice(string, length) }) else {
+        return std::ptr::null_mut();
+    };
+    Box::into_raw(
+        Buffer(node_buffer::Buffer::builder()
+            .size(size)
+            .fill_text_utf16(string, encoding.into())
+            .build()).into_box()
+    )
+}
+
 #[no_mangle]
 pub extern "C" fn buffer_concat(buffers: *const *const u8, buffers_length: *const usize, count: usize) -> *mut Buffer {
     Box::into_raw(
@@ -475,6 +616,32 @@ pub extern "C" fn buffer_from_string(string: *const c_char, encoding: StringEnco
     )
 }
 
+/// `string` is `length` bytes of UTF-8 and may contain NULs.
+#[no_mangle]
+pub extern "C" fn buffer_from_utf8_string(string: *const u8, length: usize, encoding: StringEncoding) -> *mut Buffer {
+    let Some(string) = (unsafe { string_slice(string, length) }) else {
+        return std::ptr::null_mut();
+    };
+    Box::into_raw(
+        Buffer(
+            node_buffer::Buffer::from_utf8_string(string, encoding.into())
+        ).into_box()
+    )
+}
+
+/// `string` is `length` UTF-16 code units, lone surrogates are allowed.
+#[no_mangle]
+pub extern "C" fn buffer_from_utf16_string(string: *const u16, length: usize, encoding: StringEncoding) -> *mut Buffer {
+    let Some(string) = (unsafe { string_slice(string, length) }) else {
+        return std::ptr::null_mut();
+    };
+    Box::into_raw(
+        Buffer(
+            node_buffer::Buffer::from_utf16_string(string, encoding.into())
+        ).into_box()
+    )
+}
+
 #[no_mangle]
 pub extern "C" fn buffer_from_slice(slice: *const u8, length: usize) -> *mut Buffer {
     Box::into_raw(
@@ -508,6 +675,55 @@ pub extern "C" fn buffer_from_reference(data: *mut u8, size: usize) -> *mut Buff
     }
 }
 
+/// Wraps host owned memory (e.g. an ArrayBuffer backing store) without copying.
+/// `finalizer` is called exactly once, possibly from an I/O thread, after the last
+/// clone is dropped, so the memory can safely outlive `buffer_destroy` while an
+/// async fs operation is still using it.
+#[no_mangle]
+pub extern "C" fn buffer_from_external(data: *mut u8, size: usize, finalizer: Option<node_buffer::BufferFinalizer>, context: *mut c_void) -> *mut Buffer {
+    unsafe {
+        Box::into_raw(
+            Buffer(
+                node_buffer::Buffer::from_external(data, size, finalizer, context)
+            ).into_box()
+        )
+    }
+}
+
+/// Shares `buffer`'s memory from `offset`, a negative `length` takes the rest of the buffer.
+/// Returns null and sets `ERR_BUFFER_OUT_OF_BOUNDS` as the last error when out of range.
+#[no_mangle]
+pub extern "C" fn buffer_from_buffer_view(buffer: *const Buffer, offset: usize, length: isize) -> *mut Buffer {
+    if buffer.is_null() {
+        return std::ptr::null_mut();
+    }
+    let buffer = unsafe { &*buffer };
+
+    match node_buffer::Buffer::from_buffer_view(&buffer.0, offset, to_optional(length)) {
+        Ok(view) => Box::into_raw(Buffer(view).into_box()),
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// `buf.subarray(start, end)`, negative values count from the end and both are clamped,
+/// pass the buffer length (or `PTRDIFF_MAX`) as `end` to view up to the end.
+#[no_mangle]
+pub extern "C" fn buffer_subarray(buffer: *const Buffer, start: isize, end: isize) -> *mut Buffer {
+    if buffer.is_null() {
+        return std::ptr::null_mut();
+    }
+    let buffer = unsafe { &*buffer };
+
+    Box::into_raw(
+        Buffer(
+            buffer.0.subarray(Some(start), Some(end))
+        ).into_box()
+    )
+}
+
 #[no_mangle]
 pub extern "C" fn buffer_atob(string: *const c_char) -> *const c_char {
     unsafe { node_buffer::Buffer::atob_raw(string) }
@@ -524,7 +740,181 @@ pub extern "C" fn buffer_fill_string(buffer: *mut Buffer, string: *const c_char,
         return;
     }
     let buffer = unsafe { &mut *buffer };
-    unsafe { buffer.0.fill_str(CStr::from_ptr(string), Some(encoding.into())); }
+    if let Err(error) = buffer.0.fill_str(unsafe { CStr::from_ptr(string) }, Some(encoding.into())) {
+        update_last_error(error);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_fill_utf8_string(buffer: *mut Buffer, string: *const u8, length: usize, encoding: StringEncoding) {
+    if buffer.is_null() {
+        return;
+    }
+    let Some(string) = (unsafe { string_slice(string, length) }) else {
+        return;
+    };
+    let buffer = unsafe { &mut *buffer };
+    if let Err(error) = buffer.0.fill_utf8_string(string, Some(encoding.into())) {
+        update_last_error(error);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_fill_utf16_string(buffer: *mut Buffer, string: *const u16, length: usize, encoding: StringEncoding) {
+    if buffer.is_null() {
+        return;
+    }
+    let Some(string) = (unsafe { string_slice(string, length) }) else {
+        return;
+    };
+    let buffer = unsafe { &mut *buffer };
+    if let Err(error) = buffer.0.fill_utf16_string(string, Some(encoding.into())) {
+        update_last_error(error);
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum BufferFillType {
+    BufferFillTypeNumber,
+    BufferFillTypeBytes,
+    BufferFillTypeBuffer,
+    BufferFillTypeUtf8String,
+    BufferFillTypeUtf16String,
+}
+
+/// A `buf.fill` value, `number` is read for numbers (only the low byte is used), `buffer`
+/// for buffers and `data`/`length` otherwise. UTF-16 strings are `length` code units.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct BufferFill {
+    pub type_: BufferFillType,
+    pub number: i64,
+    pub data: *const c_void,
+    pub length: usize,
+    pub buffer: *const Buffer,
+}
+
+fn fill_value<'a>(fill: &BufferFill) -> Option<node_buffer::FillValue<'a>> {
+    match fill.type_ {
+        BufferFillType::BufferFillTypeNumber => Some(node_buffer::FillValue::Number(fill.number)),
+        BufferFillType::BufferFillTypeBytes => {
+            unsafe { string_slice(fill.data as *const u8, fill.length) }.map(node_buffer::FillValue::Bytes)
+        }
+        BufferFillType::BufferFillTypeBuffer => {
+            unsafe { fill.buffer.as_ref() }.map(|buffer| node_buffer::FillValue::Buffer(&buffer.0))
+        }
+        BufferFillType::BufferFillTypeUtf8String => {
+            unsafe { string_slice(fill.data as *const u8, fill.length) }.map(node_buffer::FillValue::Utf8String)
+        }
+        BufferFillType::BufferFillTypeUtf16String => {
+            unsafe { string_slice(fill.data as *const u16, fill.length) }.map(node_buffer::FillValue::Utf16String)
+        }
+    }
+}
+
+/// `buf.fill(value, offset, end, encoding)`, negative `offset`/`end` use the defaults.
+/// Returns false and sets the last error when `end` is out of range or `fill` encodes to nothing.
+#[no_mangle]
+pub extern "C" fn buffer_fill(buffer: *mut Buffer, fill: *const BufferFill, offset: isize, end: isize, encoding: StringEncoding) -> bool {
+    if buffer.is_null() || fill.is_null() {
+        return false;
+    }
+    let Some(fill) = fill_value(unsafe { &*fill }) else {
+        return false;
+    };
+    let buffer = unsafe { &mut *buffer };
+    match buffer.0.fill(fill, to_optional(offset), to_optional(end), Some(encoding.into())) {
+        Ok(_) => true,
+        Err(error) => {
+            update_last_error(error);
+            false
+        }
+    }
+}
+
+/// `Buffer.alloc(size, fill, encoding)`, a null `fill` zero fills. Returns null and sets the
+/// last error when `fill` encodes to nothing.
+#[no_mangle]
+pub extern "C" fn buffer_alloc_fill(size: usize, fill: *const BufferFill, encoding: StringEncoding) -> *mut Buffer {
+    let fill = match unsafe { fill.as_ref() } {
+        Some(fill) => match fill_value(fill) {
+            Some(fill) => Some(fill),
+            None => return std::ptr::null_mut()
+        },
+        None => None
+    };
+    match node_buffer::Buffer::alloc(size, fill, Some(encoding.into())) {
+        Ok(buffer) => Box::into_raw(Buffer(buffer).into_box()),
+        Err(error) => {
+            update_last_error(error);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+fn write_result(result: node_core::error::Result<usize>) -> isize {
+    match result {
+        Ok(written) => written as isize,
+        Err(error) => {
+            update_last_error(error);
+            -1
+        }
+    }
+}
+
+/// `buf.write(string, offset, length, encoding)`, a negative `offset` or `length` means it
+/// was omitted. Returns the bytes written or -1 with the last error set.
+#[no_mangle]
+pub extern "C" fn buffer_write_string(buffer: *mut Buffer, string: *const c_char, offset: isize, length: isize, encoding: StringEncoding) -> isize {
+    if buffer.is_null() || string.is_null() {
+        return -1;
+    }
+    let buffer = unsafe { &mut *buffer };
+    let string = unsafe { CStr::from_ptr(string) }.to_bytes();
+    write_result(buffer.0.write_utf8_string(string, to_optional(offset), to_optional(length), Some(encoding.into())))
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_write_utf8_string(buffer: *mut Buffer, string: *const u8, string_length: usize, offset: isize, length: isize, encoding: StringEncoding) -> isize {
+    if buffer.is_null() {
+        return -1;
+    }
+    let Some(string) = (unsafe { string_slice(string, string_length) }) else {
+        return -1;
+    };
+    let buffer = unsafe { &mut *buffer };
+    write_result(buffer.0.write_utf8_string(string, to_optional(offset), to_optional(length), Some(encoding.into())))
+}
+
+/// Same as `buffer_write_utf8_string` with `string_length` UTF-16 code units.
+#[no_mangle]
+pub extern "C" fn buffer_write_utf16_string(buffer: *mut Buffer, string: *const u16, string_length: usize, offset: isize, length: isize, encoding: StringEncoding) -> isize {
+    if buffer.is_null() {
+        return -1;
+    }
+    let Some(string) = (unsafe { string_slice(string, string_length) }) else {
+        return -1;
+    };
+    let buffer = unsafe { &mut *buffer };
+    write_result(buffer.0.write_utf16_string(string, to_optional(offset), to_optional(length), Some(encoding.into())))
+}
+
+/// `Buffer.byteLength` for a string, without encoding it.
+#[no_mangle]
+pub extern "C" fn buffer_byte_length_utf8(string: *const u8, length: usize, encoding: StringEncoding) -> usize {
+    match unsafe { string_slice(string, length) } {
+        Some(string) => node_buffer::byte_length_utf8(string, encoding.into()),
+        None => 0
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn buffer_byte_length_utf16(string: *const u16, length: usize, encoding: StringEncoding) -> usize {
+    match unsafe { string_slice(string, length) } {
+        Some(string) => node_buffer::byte_length_utf16(string, encoding.into()),
+        None => 0
+    }
 }
 
 #[no_mangle]
@@ -579,6 +969,8 @@ pub extern "C" fn buffer_length(buffer: *const Buffer) -> usize {
     buffer.0.length()
 }
 
+/// Pointer to the buffer's bytes. Accesses through it aren't atomic like the buffer functions'
+/// are, so the host must not use it while another thread works on the same memory.
 #[no_mangle]
 pub extern "C" fn buffer_buffer(buffer: *mut Buffer) -> *mut u8 {
     if buffer.is_null() {
@@ -586,597 +978,862 @@ pub extern "C" fn buffer_buffer(buffer: *mut Buffer) -> *mut u8 {
     }
     let buffer = unsafe { &mut *buffer };
 
-    buffer.0.buffer_mut().as_mut_ptr()
+    buffer.0.as_mut_ptr()
 }
 
 
 #[no_mangle]
-pub extern "C" fn buffer_write_int8(buffer: *mut Buffer, value: i8, offset: isize) {
+pub extern "C" fn buffer_write_int8(buffer: *mut Buffer, value: i8, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
 
-    buffer.0.write_int8(value, to_optional(offset));
+    match buffer.0.write_int8(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_uint8(buffer: *mut Buffer, value: u8, offset: isize) {
+pub extern "C" fn buffer_write_uint8(buffer: *mut Buffer, value: u8, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
 
-    buffer.0.write_uint8(value, to_optional(offset));
+    match buffer.0.write_uint8(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_uint16be(buffer: *mut Buffer, value: u16, offset: isize) {
+pub extern "C" fn buffer_write_uint16be(buffer: *mut Buffer, value: u16, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
-    buffer.0.write_uint16be(value, to_optional(offset));
+    match buffer.0.write_uint16be(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_int16be(buffer: *mut Buffer, value: i16, offset: isize) {
+pub extern "C" fn buffer_write_int16be(buffer: *mut Buffer, value: i16, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
 
-    buffer.0.write_int16be(value, to_optional(offset));
+    match buffer.0.write_int16be(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_uint16le(buffer: *mut Buffer, value: u16, offset: isize) {
+pub extern "C" fn buffer_write_uint16le(buffer: *mut Buffer, value: u16, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
 
-    buffer.0.write_uint16le(value, to_optional(offset));
+    match buffer.0.write_uint16le(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_int16le(buffer: *mut Buffer, value: i16, offset: isize) {
+pub extern "C" fn buffer_write_int16le(buffer: *mut Buffer, value: i16, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
 
-    buffer.0.write_int16le(value, to_optional(offset));
+    match buffer.0.write_int16le(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_uint32be(buffer: *mut Buffer, value: u32, offset: isize) {
+pub extern "C" fn buffer_write_uint32be(buffer: *mut Buffer, value: u32, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
-    buffer.0.write_uint32be(value, to_optional(offset));
+    match buffer.0.write_uint32be(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_int32be(buffer: *mut Buffer, value: i32, offset: isize) {
+pub extern "C" fn buffer_write_int32be(buffer: *mut Buffer, value: i32, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
-    buffer.0.write_int32be(value, to_optional(offset));
+    match buffer.0.write_int32be(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_uint32le(buffer: *mut Buffer, value: u32, offset: isize) {
+pub extern "C" fn buffer_write_uint32le(buffer: *mut Buffer, value: u32, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
-    buffer.0.write_uint32le(value, to_optional(offset));
+    match buffer.0.write_uint32le(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_int32le(buffer: *mut Buffer, value: i32, offset: isize) {
+pub extern "C" fn buffer_write_int32le(buffer: *mut Buffer, value: i32, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
-    buffer.0.write_int32le(value, to_optional(offset));
+    match buffer.0.write_int32le(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_big_uint64be(buffer: *mut Buffer, value: u64, offset: isize) {
+pub extern "C" fn buffer_write_big_uint64be(buffer: *mut Buffer, value: u64, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
-    buffer.0.write_big_uint64be(value, to_optional(offset));
+    match buffer.0.write_big_uint64be(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_big_int64be(buffer: *mut Buffer, value: i64, offset: isize) {
+pub extern "C" fn buffer_write_big_int64be(buffer: *mut Buffer, value: i64, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
-    buffer.0.write_big_int64be(value, to_optional(offset));
+    match buffer.0.write_big_int64be(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_big_uint64le(buffer: *mut Buffer, value: u64, offset: isize) {
+pub extern "C" fn buffer_write_big_uint64le(buffer: *mut Buffer, value: u64, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
-    buffer.0.write_big_uint64le(value, to_optional(offset));
+    match buffer.0.write_big_uint64le(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_big_int64le(buffer: *mut Buffer, value: i64, offset: isize) {
+pub extern "C" fn buffer_write_big_int64le(buffer: *mut Buffer, value: i64, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
-    buffer.0.write_big_int64le(value, to_optional(offset));
+    match buffer.0.write_big_int64le(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_float_be(buffer: *mut Buffer, value: f32, offset: isize) {
+pub extern "C" fn buffer_write_float_be(buffer: *mut Buffer, value: f32, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
-    buffer.0.write_float_be(value, to_optional(offset));
+    match buffer.0.write_float_be(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_double_be(buffer: *mut Buffer, value: f64, offset: isize) {
+pub extern "C" fn buffer_write_double_be(buffer: *mut Buffer, value: f64, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
 
-    buffer.0.write_double_be(value, to_optional(offset));
+    match buffer.0.write_double_be(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_float_le(buffer: *mut Buffer, value: f32, offset: isize) {
+pub extern "C" fn buffer_write_float_le(buffer: *mut Buffer, value: f32, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
-    buffer.0.write_float_le(value, to_optional(offset));
+    match buffer.0.write_float_le(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn buffer_write_double_le(buffer: *mut Buffer, value: f64, offset: isize) {
+pub extern "C" fn buffer_write_double_le(buffer: *mut Buffer, value: f64, offset: isize) -> usize {
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let buffer = unsafe { &mut *buffer };
-    buffer.0.write_double_le(value, to_optional(offset));
+    match buffer.0.write_double_le(value, to_optional(offset)) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 
+/// Fixed width readers return 0 and set the last error on `ERR_OUT_OF_RANGE`. They clear it
+/// first, so a non zero `node_last_error_length()` after a read tells an error from a real 0.
 #[no_mangle]
 pub extern "C" fn buffer_read_int8(buffer: *const Buffer, offset: isize) -> i8 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_int8(to_optional(offset))
+    match buffer.0.read_int8(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_uint8(buffer: *const Buffer, offset: isize) -> u8 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
 
-    buffer.0.read_uint8(to_optional(offset))
+    match buffer.0.read_uint8(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_uint16be(buffer: *const Buffer, offset: isize) -> u16 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_uint16be(to_optional(offset))
+    match buffer.0.read_uint16be(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_int16be(buffer: *const Buffer, offset: isize) -> i16 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_int16be(to_optional(offset))
+    match buffer.0.read_int16be(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_uint16le(buffer: *const Buffer, offset: isize) -> u16 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_uint16le(to_optional(offset))
+    match buffer.0.read_uint16le(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_int16le(buffer: *const Buffer, offset: isize) -> i16 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_int16le(to_optional(offset))
+    match buffer.0.read_int16le(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_uint32be(buffer: *const Buffer, offset: isize) -> u32 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_uint32be(to_optional(offset))
+    match buffer.0.read_uint32be(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_int32be(buffer: *const Buffer, offset: isize) -> i32 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_int32be(to_optional(offset))
+    match buffer.0.read_int32be(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_uint32le(buffer: *const Buffer, offset: isize) -> u32 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_uint32le(to_optional(offset))
+    match buffer.0.read_uint32le(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_int32le(buffer: *const Buffer, offset: isize) -> i32 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_int32le(to_optional(offset))
+    match buffer.0.read_int32le(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_big_uint64be(buffer: *const Buffer, offset: isize) -> u64 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_big_uint64be(to_optional(offset))
+    match buffer.0.read_big_uint64be(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_big_int64be(buffer: *const Buffer, offset: isize) -> i64 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_big_int64be(to_optional(offset))
+    match buffer.0.read_big_int64be(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_big_uint64le(buffer: *const Buffer, offset: isize) -> u64 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_big_uint64le(to_optional(offset))
+    match buffer.0.read_big_uint64le(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_big_int64le(buffer: *const Buffer, offset: isize) -> i64 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_big_int64le(to_optional(offset))
+    match buffer.0.read_big_int64le(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_float_be(buffer: *const Buffer, offset: isize) -> f32 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0.;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_float_be(to_optional(offset))
+    match buffer.0.read_float_be(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0.
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_double_be(buffer: *const Buffer, offset: isize) -> f64 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0.;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_double_be(to_optional(offset))
+    match buffer.0.read_double_be(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0.
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_float_le(buffer: *const Buffer, offset: isize) -> f32 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0.;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_float_le(to_optional(offset))
+    match buffer.0.read_float_le(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0.
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "C" fn buffer_read_double_le(buffer: *const Buffer, offset: isize) -> f64 {
+    clear_last_error();
+
     if buffer.is_null() {
         return 0.;
     }
 
     let buffer = unsafe { &*buffer };
-    buffer.0.read_double_le(to_optional(offset))
+    match buffer.0.read_double_le(to_optional(offset)) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0.
+        }
+    }
 }
 
+// Variable width accessors take a `byte_length` of 1 to 6, on `ERR_OUT_OF_RANGE` or
+// `ERR_BUFFER_OUT_OF_BOUNDS` they set the last error and return 0. Writers return the
+// offset after the written bytes.
 
+/// Variable width readers clear the last error first like the fixed width ones, check
+/// `node_last_error_length()` after a read that returned 0.
 #[no_mangle]
-pub extern "C" fn fs_encoding_get_string_value(encoding: *const FsEncoding) -> *const c_char {
-    if encoding.is_null() {
-        return std::ptr::null();
-    }
+pub extern "C" fn buffer_read_int_be(buffer: *const Buffer, offset: usize, byte_length: usize) -> i64 {
+    clear_last_error();
 
-    let encoding = unsafe { &*encoding };
+    if buffer.is_null() {
+        return 0;
+    }
 
-    match encoding.get_string_value() {
-        Ok(encoding) => {
-            CString::new(encoding).unwrap().into_raw()
-        }
+    let buffer = unsafe { &*buffer };
+    match buffer.0.read_int_be(offset, byte_length) {
+        Ok(value) => value,
         Err(err) => {
             update_last_error(err);
-            std::ptr::null()
+            0
         }
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_encoding_get_buffer_value(encoding: *const FsEncoding) -> *mut Buffer {
-    if encoding.is_null() {
-        return std::ptr::null_mut();
+pub extern "C" fn buffer_read_int_le(buffer: *const Buffer, offset: usize, byte_length: usize) -> i64 {
+    clear_last_error();
+
+    if buffer.is_null() {
+        return 0;
     }
 
-    let encoding = unsafe { &*encoding };
-    match encoding.get_buffer_value() {
-        Ok(buffer) => {
-            Box::into_raw(buffer)
-        }
+    let buffer = unsafe { &*buffer };
+    match buffer.0.read_int_le(offset, byte_length) {
+        Ok(value) => value,
         Err(err) => {
             update_last_error(err);
-            std::ptr::null_mut()
+            0
         }
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_encoding_is_buffer(encoding: *const FsEncoding) -> bool {
-    if encoding.is_null() {
-        return false;
+pub extern "C" fn buffer_read_uint_be(buffer: *const Buffer, offset: usize, byte_length: usize) -> u64 {
+    clear_last_error();
+
+    if buffer.is_null() {
+        return 0;
     }
 
-    let encoding = unsafe { &*encoding };
-    encoding.is_buffer()
+    let buffer = unsafe { &*buffer };
+    match buffer.0.read_uint_be(offset, byte_length) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
-#[derive(Debug)]
-pub struct FileDirent(node_fs::file_dirent::FileDirent);
-
 #[no_mangle]
-pub extern "C" fn fs_dirent_is_block_device(dirent: *const FileDirent) -> bool {
-    if dirent.is_null() {
-        return false;
+pub extern "C" fn buffer_read_uint_le(buffer: *const Buffer, offset: usize, byte_length: usize) -> u64 {
+    clear_last_error();
+
+    if buffer.is_null() {
+        return 0;
     }
 
-    let dirent = unsafe { &*dirent };
-    dirent.0.is_block_device()
+    let buffer = unsafe { &*buffer };
+    match buffer.0.read_uint_le(offset, byte_length) {
+        Ok(value) => value,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_dirent_path(dirent: *const FileDirent) -> *const c_char {
-    if dirent.is_null() {
-        return std::ptr::null();
+pub extern "C" fn buffer_write_int_be(buffer: *mut Buffer, value: i64, offset: usize, byte_length: usize) -> usize {
+    if buffer.is_null() {
+        return 0;
     }
 
-    let dirent = unsafe { &*dirent };
-    CString::new(dirent.0.path().to_string()).unwrap().into_raw()
-}
-
-#[no_mangle]
-pub extern "C" fn fs_dirent_name(dirent: *const FileDirent) -> *const c_char {
-    if dirent.is_null() {
-        return std::ptr::null();
+    let buffer = unsafe { &mut *buffer };
+    match buffer.0.write_int_be(value, offset, byte_length) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
     }
-
-    let dirent = unsafe { &*dirent };
-
-    CString::new(dirent.0.name().to_string()).unwrap().into_raw()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_dirent_is_character_device(dirent: *const FileDirent) -> bool {
-    if dirent.is_null() {
-        return false;
+pub extern "C" fn buffer_write_int_le(buffer: *mut Buffer, value: i64, offset: usize, byte_length: usize) -> usize {
+    if buffer.is_null() {
+        return 0;
     }
-    let dirent = unsafe { &*dirent };
-    dirent.0.is_character_device()
-}
 
-#[no_mangle]
-pub extern "C" fn fs_dirent_is_directory(dirent: *const FileDirent) -> bool {
-    if dirent.is_null() {
-        return false;
+    let buffer = unsafe { &mut *buffer };
+    match buffer.0.write_int_le(value, offset, byte_length) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
     }
-    let dirent = unsafe { &*dirent };
-    dirent.0.is_directory()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_dirent_is_fifo(dirent: *const FileDirent) -> bool {
-    if dirent.is_null() {
-        return false;
+pub extern "C" fn buffer_write_uint_be(buffer: *mut Buffer, value: i64, offset: usize, byte_length: usize) -> usize {
+    if buffer.is_null() {
+        return 0;
     }
-    let dirent = unsafe { &*dirent };
-    dirent.0.is_fifo()
-}
 
-#[no_mangle]
-pub extern "C" fn fs_dirent_is_file(dirent: *const FileDirent) -> bool {
-    if dirent.is_null() {
-        return false;
+    let buffer = unsafe { &mut *buffer };
+    match buffer.0.write_uint_be(value, offset, byte_length) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
     }
-    let dirent = unsafe { &*dirent };
-    dirent.0.is_file()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_dirent_is_socket(dirent: *const FileDirent) -> bool {
-    if dirent.is_null() {
-        return false;
+pub extern "C" fn buffer_write_uint_le(buffer: *mut Buffer, value: i64, offset: usize, byte_length: usize) -> usize {
+    if buffer.is_null() {
+        return 0;
+    }
+
+    let buffer = unsafe { &mut *buffer };
+    match buffer.0.write_uint_le(value, offset, byte_length) {
+        Ok(offset) => offset,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
     }
-    let dirent = unsafe { &*dirent };
-    dirent.0.is_socket()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_dirent_is_symbolic_link(dirent: *const FileDirent) -> bool {
-    if dirent.is_null() {
-        return false;
+pub extern "C" fn buffer_swap16(buffer: *mut Buffer) {
+    if buffer.is_null() {
+        return;
+    }
+
+    let buffer = unsafe { &mut *buffer };
+    if let Err(err) = buffer.0.swap16() {
+        update_last_error(err);
     }
-    let dirent = unsafe { &*dirent };
-    dirent.0.is_symbolic_link()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_dir_close_sync(dir: *const FileDir) {
-    if dir.is_null() {
+pub extern "C" fn buffer_swap32(buffer: *mut Buffer) {
+    if buffer.is_null() {
         return;
     }
-    let dir = unsafe { &*dir };
-
 
-    if let Err(err) = dir.0.close()
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
+    let buffer = unsafe { &mut *buffer };
+    if let Err(err) = buffer.0.swap32() {
         update_last_error(err);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_dir_path(dir: *const FileDir) -> *const c_char {
-    if dir.is_null() {
-        return std::ptr::null();
+pub extern "C" fn buffer_swap64(buffer: *mut Buffer) {
+    if buffer.is_null() {
+        return;
+    }
+
+    let buffer = unsafe { &mut *buffer };
+    if let Err(err) = buffer.0.swap64() {
+        update_last_error(err);
     }
-    let dir = unsafe { &*dir };
-    CString::new(
-        dir.0.path().to_string()
-    ).unwrap().into_raw()
 }
 
+/// `buf.copy(target, targetStart, sourceStart, sourceEnd)`, negative values use the defaults.
+/// Returns the number of bytes copied, 0 with the last error set on `ERR_OUT_OF_RANGE`.
 #[no_mangle]
-pub extern "C" fn fs_dir_read_sync(dir: *const FileDir) -> *mut FileDirent {
-    if dir.is_null() {
-        return std::ptr::null_mut();
+pub extern "C" fn buffer_copy(buffer: *const Buffer, target: *mut Buffer, target_start: isize, source_start: isize, source_end: isize) -> usize {
+    if buffer.is_null() || target.is_null() {
+        return 0;
     }
-    let dir = unsafe { &*dir };
-
-    match dir.0.read()
-        .map(|dirent| Box::new(FileDirent(dirent)))
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        Ok(dirent) => {
-            Box::into_raw(dirent)
-        }
+    let buffer = unsafe { &*buffer };
+    let target = unsafe { &mut *target };
+    match buffer.0.copy(&mut target.0, to_optional(target_start), to_optional(source_start), to_optional(source_end)) {
+        Ok(copied) => copied,
         Err(err) => {
             update_last_error(err);
-            std::ptr::null_mut()
+            0
         }
     }
 }
 
-
+/// `buf.copyWithin(target, start, end)`, negative values count from the end, pass the
+/// buffer length (or `PTRDIFF_MAX`) as `end` to copy up to the end.
 #[no_mangle]
-pub extern "C" fn fs_readdir_get_type_at(value: *const ReaddirResultArray, at: usize) -> ReaddirResultType {
-    let value = unsafe { &*value };
-    let slice = unsafe { std::slice::from_raw_parts(value.data, value.length) };
-    (&slice[at]).get_type()
+pub extern "C" fn buffer_copy_within(buffer: *mut Buffer, target: isize, start: isize, end: isize) -> usize {
+    if buffer.is_null() {
+        return 0;
+    }
+    let buffer = unsafe { &mut *buffer };
+    buffer.0.copy_within(target, start, Some(end))
 }
 
 #[no_mangle]
-pub extern "C" fn fs_readdir_get_string_value_at(value: *const ReaddirResultArray, at: usize) -> *const c_char {
-    if value.is_null() {
-        return std::ptr::null();
+pub extern "C" fn buffer_set(buffer: *mut Buffer, source: *const Buffer, offset: usize) {
+    if buffer.is_null() || source.is_null() {
+        return;
     }
-    let value = unsafe { &*value };
-    let slice = unsafe { std::slice::from_raw_parts(value.data, value.length) };
-
-    match (&slice[at]).get_string_value() {
-        Ok(value) => {
-            CString::new(value).unwrap().into_raw()
-        }
-        Err(err) => {
-            update_last_error(err);
-            std::ptr::null()
-        }
+    let buffer = unsafe { &mut *buffer };
+    let source = unsafe { &*source };
+    if let Err(err) = buffer.0.set(&source.0, offset) {
+        update_last_error(err);
     }
 }
 
+/// Returns a new buffer, or null with the last error set to `U_ILLEGAL_ARGUMENT_ERROR` when
+/// either encoding isn't one of utf8, ucs2, utf16le, latin1, binary or ascii, or to
+/// `U_INVALID_CHAR_FOUND` for invalid input between utf8 and ucs2.
 #[no_mangle]
-pub extern "C" fn fs_readdir_get_buffer_value_at(value: *const ReaddirResultArray, at: usize) -> *mut Buffer {
-    if value.is_null() {
+pub extern "C" fn buffer_transcode(buffer: *const Buffer, from: StringEncoding, to: StringEncoding) -> *mut Buffer {
+    if buffer.is_null() {
         return std::ptr::null_mut();
     }
-
-    let value = unsafe { &*value };
-    let slice = unsafe { std::slice::from_raw_parts(value.data, value.length) };
-
-    match (&slice[at]).get_buffer_value() {
-        Ok(value) => { Box::into_raw(value) }
+    let buffer = unsafe { &*buffer };
+    match buffer.0.with_slice(|bytes| node_buffer::transcode(bytes, from.into(), to.into())) {
+        Ok(buffer) => Box::into_raw(Buffer(buffer).into_box()),
         Err(err) => {
             update_last_error(err);
             std::ptr::null_mut()
@@ -1185,457 +1842,459 @@ pub extern "C" fn fs_readdir_get_buffer_value_at(value: *const ReaddirResultArra
 }
 
 #[no_mangle]
-pub extern "C" fn fs_readdir_get_type_value_at(value: *const ReaddirResultArray, at: usize) -> *mut FileDirent {
-    if value.is_null() {
-        return std::ptr::null_mut();
+pub extern "C" fn buffer_is_utf8(buffer: *const Buffer) -> bool {
+    if buffer.is_null() {
+        return false;
     }
+    let buffer = unsafe { &*buffer };
+    buffer.0.with_slice(node_buffer::is_utf8)
+}
 
-    let value = unsafe { &*value };
-    let slice = unsafe { std::slice::from_raw_parts(value.data, value.length) };
+#[no_mangle]
+pub extern "C" fn buffer_is_ascii(buffer: *const Buffer) -> bool {
+    if buffer.is_null() {
+        return false;
+    }
+    let buffer = unsafe { &*buffer };
+    buffer.0.with_slice(node_buffer::is_ascii)
+}
 
-    match (&slice[at]).get_type_value() {
-        Ok(value) => {
-            Box::into_raw(value)
-        }
-        Err(err) => {
-            update_last_error(err);
-            std::ptr::null_mut()
-        }
+pub struct StringDecoder(node_buffer::string_decoder::StringDecoder);
+
+fn decoded_to_c_string(decoded: String) -> *const c_char {
+    match CString::new(decoded) {
+        Ok(cstring) => cstring.into_raw(),
+        Err(_) => std::ptr::null()
+    }
+}
+
+/// Hands decoded text out as UTF-8 bytes, it can contain U+0000 so it isn't NUL terminated.
+fn decoded_to_bytes(decoded: String, length: *mut usize) -> *mut u8 {
+    let bytes = decoded.into_bytes().into_boxed_slice();
+    if !length.is_null() {
+        unsafe { *length = bytes.len() };
     }
+    Box::into_raw(bytes) as *mut u8
 }
 
+/// Frees bytes returned with their `length`, e.g. by `string_decoder_write`.
 #[no_mangle]
-pub extern "C" fn fs_readdir_get_type(value: *const ReaddirResult) -> ReaddirResultType {
-    let value = unsafe { &*value };
-    return value.get_type();
+pub unsafe extern "C" fn node_bytes_destroy(bytes: *mut u8, length: usize) {
+    if bytes.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(bytes, length)) };
 }
 
 #[no_mangle]
-pub extern "C" fn fs_readdir_get_string_value(value: *const ReaddirResult) -> *const c_char {
-    let value = unsafe { &*value };
-    match value.get_string_value() {
-        Ok(value) => {
-            CString::new(value).unwrap().into_raw()
-        }
-        Err(err) => {
-            update_last_error(err);
-            std::ptr::null()
-        }
+pub extern "C" fn string_decoder_create(encoding: StringEncoding) -> *mut StringDecoder {
+    Box::into_raw(Box::new(StringDecoder(node_buffer::string_decoder::StringDecoder::new(encoding.into()))))
+}
+
+#[no_mangle]
+pub extern "C" fn string_decoder_destroy(decoder: *mut StringDecoder) {
+    if decoder.is_null() {
+        return;
     }
+
+    let _ = unsafe { Box::from_raw(decoder) };
 }
 
 #[no_mangle]
-pub extern "C" fn fs_readdir_get_buffer_value(value: *const ReaddirResult) -> *mut Buffer {
-    if value.is_null() {
+pub extern "C" fn string_decoder_get_encoding(decoder: *const StringDecoder) -> StringEncoding {
+    if decoder.is_null() {
+        return StringEncoding::StringEncodingUtf8;
+    }
+    let decoder = unsafe { &*decoder };
+    decoder.0.encoding().into()
+}
+
+/// Returns the text decoded so far as UTF-8 with its byte length in `decoded_length`, free it
+/// with `node_bytes_destroy`. Bytes of an incomplete character are kept for the next write.
+/// Returns null only for a null decoder or null `data` with a non zero `length`.
+#[no_mangle]
+pub extern "C" fn string_decoder_write(decoder: *mut StringDecoder, data: *const u8, length: usize, decoded_length: *mut usize) -> *mut u8 {
+    if decoder.is_null() {
         return std::ptr::null_mut();
     }
-    let value = unsafe { &*value };
+    let Some(data) = (unsafe { string_slice(data, length) }) else {
+        return std::ptr::null_mut();
+    };
+    let decoder = unsafe { &mut *decoder };
+    decoded_to_bytes(decoder.0.write(data), decoded_length)
+}
 
-    match value.get_buffer_value() {
-        Ok(value) => { Box::into_raw(value) }
-        Err(err) => {
-            update_last_error(err);
-            std::ptr::null_mut()
-        }
+#[no_mangle]
+pub extern "C" fn string_decoder_write_buffer(decoder: *mut StringDecoder, buffer: *const Buffer, decoded_length: *mut usize) -> *mut u8 {
+    if decoder.is_null() || buffer.is_null() {
+        return std::ptr::null_mut();
     }
+    let decoder = unsafe { &mut *decoder };
+    let buffer = unsafe { &*buffer };
+    decoded_to_bytes(buffer.0.with_slice(|bytes| decoder.0.write(bytes)), decoded_length)
 }
 
+/// Decodes `data` (which may be null) followed by whatever is left over, the decoder is
+/// reset afterwards. The result is returned like `string_decoder_write`'s.
 #[no_mangle]
-pub extern "C" fn fs_readdir_get_type_value(value: *const ReaddirResult) -> *mut FileDirent {
-    if value.is_null() {
+pub extern "C" fn string_decoder_end(decoder: *mut StringDecoder, data: *const u8, length: usize, decoded_length: *mut usize) -> *mut u8 {
+    if decoder.is_null() {
         return std::ptr::null_mut();
     }
-    let value = unsafe { &*value };
+    let Some(data) = (unsafe { string_slice(data, length) }) else {
+        return std::ptr::null_mut();
+    };
+    let decoder = unsafe { &mut *decoder };
+    decoded_to_bytes(decoder.0.end(Some(data)), decoded_length)
+}
 
-    match value.get_type_value() {
-        Ok(value) => {
-            Box::into_raw(value)
-        }
+#[no_mangle]
+pub extern "C" fn buffer_compare(buffer: *const Buffer, target: *const Buffer) -> i32 {
+    if buffer.is_null() || target.is_null() {
+        return 0;
+    }
+    let buffer = unsafe { &*buffer };
+    let target = unsafe { &*target };
+    buffer.0.compare(&target.0)
+}
+
+/// Negative starts and ends use their defaults. Returns -2 and sets `ERR_OUT_OF_RANGE`
+/// as the last error when a range is invalid.
+#[no_mangle]
+pub extern "C" fn buffer_compare_range(
+    buffer: *const Buffer,
+    target: *const Buffer,
+    target_start: isize,
+    target_end: isize,
+    source_start: isize,
+    source_end: isize,
+) -> i32 {
+    if buffer.is_null() || target.is_null() {
+        return 0;
+    }
+    let buffer = unsafe { &*buffer };
+    let target = unsafe { &*target };
+    match buffer.0.compare_range(
+        &target.0,
+        to_optional(target_start),
+        to_optional(target_end),
+        to_optional(source_start),
+        to_optional(source_end),
+    ) {
+        Ok(value) => value,
         Err(err) => {
             update_last_error(err);
-            std::ptr::null_mut()
+            -2
         }
     }
 }
 
+#[no_mangle]
+pub extern "C" fn buffer_equals(buffer: *const Buffer, other: *const Buffer) -> bool {
+    if buffer.is_null() || other.is_null() {
+        return false;
+    }
+    let buffer = unsafe { &*buffer };
+    let other = unsafe { &*other };
+    buffer.0.equals(&other.0)
+}
 
-#[derive(Clone)]
-pub struct Metadata(std::fs::Metadata);
+// `byte_offset` follows node once `undefined` has been resolved by the caller,
+// i.e. 0 for index_of/includes and the buffer length for last_index_of.
+fn buffer_search(
+    buffer: *const Buffer,
+    value: node_buffer::IndexOfValue,
+    byte_offset: i64,
+    encoding: StringEncoding,
+    forward: bool,
+) -> i64 {
+    if buffer.is_null() {
+        return -1;
+    }
+    let buffer = unsafe { &*buffer };
+    if forward {
+        buffer.0.index_of(value, Some(byte_offset), Some(encoding.into()))
+    } else {
+        buffer.0.last_index_of(value, Some(byte_offset), Some(encoding.into()))
+    }
+}
 
+#[no_mangle]
+pub extern "C" fn buffer_index_of_byte(buffer: *const Buffer, value: u8, byte_offset: i64) -> i64 {
+    buffer_search(buffer, node_buffer::IndexOfValue::Byte(value), byte_offset, StringEncoding::StringEncodingUtf8, true)
+}
 
-#[derive(Clone, Debug)]
-pub struct ReaddirResult(node_fs::sync::ReaddirResult);
+#[no_mangle]
+pub extern "C" fn buffer_last_index_of_byte(buffer: *const Buffer, value: u8, byte_offset: i64) -> i64 {
+    buffer_search(buffer, node_buffer::IndexOfValue::Byte(value), byte_offset, StringEncoding::StringEncodingUtf8, false)
+}
 
-impl ReaddirResult {
-    pub fn get_type(&self) -> ReaddirResultType {
-        match &self.0 {
-            node_fs::sync::ReaddirResult::String(_) => ReaddirResultType::ReaddirResultTypeString,
-            node_fs::sync::ReaddirResult::Buffer(_) => ReaddirResultType::ReaddirResultTypeBuffer,
-            node_fs::sync::ReaddirResult::Type(_) => ReaddirResultType::ReaddirResultTypeType
-        }
-    }
+#[no_mangle]
+pub extern "C" fn buffer_includes_byte(buffer: *const Buffer, value: u8, byte_offset: i64) -> bool {
+    buffer_index_of_byte(buffer, value, byte_offset) != -1
+}
 
-    pub fn get_string_value(&self) -> Result<String> {
-        match self.0.get_string_value() {
-            None => {
-                Err(node_core::error::generic_error("Invalid Type".to_string()))
-            }
-            Some(value) => {
-                Ok(value.to_string_lossy().to_string())
-            }
-        }
+#[no_mangle]
+pub extern "C" fn buffer_index_of_buffer(buffer: *const Buffer, value: *const Buffer, byte_offset: i64, encoding: StringEncoding) -> i64 {
+    if value.is_null() {
+        return -1;
     }
+    let value = unsafe { &*value };
+    buffer_search(buffer, node_buffer::IndexOfValue::Buffer(&value.0), byte_offset, encoding, true)
+}
 
-    pub fn get_buffer_value(&self) -> Result<Box<Buffer>> {
-        match self.0.get_buffer_value() {
-            None => {
-                Err(node_core::error::generic_error("Invalid Type".to_string()))
-            }
-            Some(buffer) => {
-                Ok(Box::new(Buffer(buffer)))
-            }
-        }
+#[no_mangle]
+pub extern "C" fn buffer_last_index_of_buffer(buffer: *const Buffer, value: *const Buffer, byte_offset: i64, encoding: StringEncoding) -> i64 {
+    if value.is_null() {
+        return -1;
     }
+    let value = unsafe { &*value };
+    buffer_search(buffer, node_buffer::IndexOfValue::Buffer(&value.0), byte_offset, encoding, false)
+}
 
-    pub fn get_type_value(&self) -> Result<Box<FileDirent>> {
-        match self.0.get_type_value() {
-            None => {
-                Err(node_core::error::generic_error("Invalid Type".to_string()))
-            }
-            Some(dirent) => {
-                Ok(Box::new(FileDirent(dirent)))
-            }
-        }
-    }
+#[no_mangle]
+pub extern "C" fn buffer_includes_buffer(buffer: *const Buffer, value: *const Buffer, byte_offset: i64, encoding: StringEncoding) -> bool {
+    buffer_index_of_buffer(buffer, value, byte_offset, encoding) != -1
 }
 
 #[no_mangle]
-pub extern "C" fn fs_parse_flag_sync(value: *const c_char) -> c_int {
+pub extern "C" fn buffer_index_of_string(buffer: *const Buffer, value: *const c_char, byte_offset: i64, encoding: StringEncoding) -> i64 {
     if value.is_null() {
-        return 0;
+        return -1;
     }
     let value = unsafe { CStr::from_ptr(value) };
-    let value = value.to_string_lossy();
-    node_fs::prelude::parse_flag(value.as_ref())
+    buffer_search(buffer, node_buffer::IndexOfValue::String(value), byte_offset, encoding, true)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_access_sync(path: *const c_char, mode: i32) {
-    if path.is_null() {
-        return;
-    }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
-    if let Err(err) = node_fs::sync::access(path.as_ref(), mode)
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+pub extern "C" fn buffer_last_index_of_string(buffer: *const Buffer, value: *const c_char, byte_offset: i64, encoding: StringEncoding) -> i64 {
+    if value.is_null() {
+        return -1;
     }
+    let value = unsafe { CStr::from_ptr(value) };
+    buffer_search(buffer, node_buffer::IndexOfValue::String(value), byte_offset, encoding, false)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_append_file_sync(fd: i32, buffer: *const Buffer, options: AppendFileOptions) {
-    if buffer.is_null() {
-        return;
-    }
-    let buffer = unsafe { &*buffer };
-    if let Err(err) = node_fs::sync::append_file_with_buffer(fd, &buffer.0, options.into())
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
-    }
+pub extern "C" fn buffer_includes_string(buffer: *const Buffer, value: *const c_char, byte_offset: i64, encoding: StringEncoding) -> bool {
+    buffer_index_of_string(buffer, value, byte_offset, encoding) != -1
 }
 
 #[no_mangle]
-pub extern "C" fn fs_append_file_with_bytes_sync(fd: i32, bytes: *const u8, length: usize, options: AppendFileOptions) {
-    if bytes.is_null() || length == 0 {
-        return;
-    }
-    let bytes = unsafe { std::slice::from_raw_parts(bytes, length) };
-    if let Err(err) = node_fs::sync::append_file_with_bytes(fd, bytes, options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
-    }
+pub extern "C" fn buffer_index_of_utf8_string(buffer: *const Buffer, value: *const u8, length: usize, byte_offset: i64, encoding: StringEncoding) -> i64 {
+    let Some(value) = (unsafe { string_slice(value, length) }) else {
+        return -1;
+    };
+    buffer_search(buffer, node_buffer::IndexOfValue::Utf8String(value), byte_offset, encoding, true)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_append_file_with_string_sync(fd: i32, string: *const c_char, options: AppendFileOptions) {
-    if string.is_null() {
-        return;
-    }
-
-    let string = unsafe { CStr::from_ptr(string) };
-    let string = string.to_string_lossy();
-    if let Err(err) = node_fs::sync::append_file_with_str(fd, string.as_ref(), options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
-    }
+pub extern "C" fn buffer_last_index_of_utf8_string(buffer: *const Buffer, value: *const u8, length: usize, byte_offset: i64, encoding: StringEncoding) -> i64 {
+    let Some(value) = (unsafe { string_slice(value, length) }) else {
+        return -1;
+    };
+    buffer_search(buffer, node_buffer::IndexOfValue::Utf8String(value), byte_offset, encoding, false)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_append_file_with_path_sync(path: *const c_char, buffer: *const Buffer, options: AppendFileOptions) {
-    if buffer.is_null() || path.is_null() {
-        return;
-    }
-    let buffer = unsafe { &*buffer };
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
-
-    if let Err(err) = node_fs::sync::append_file_with_path_buffer(path.as_ref(), &buffer.0, options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
-    }
+pub extern "C" fn buffer_includes_utf8_string(buffer: *const Buffer, value: *const u8, length: usize, byte_offset: i64, encoding: StringEncoding) -> bool {
+    buffer_index_of_utf8_string(buffer, value, length, byte_offset, encoding) != -1
 }
 
 #[no_mangle]
-pub extern "C" fn fs_append_file_with_path_bytes_sync(path: *const c_char, bytes: *const u8, length: usize, options: AppendFileOptions) {
-    if bytes.is_null() || length == 0 || path.is_null() {
-        return;
-    }
-    let bytes = unsafe { std::slice::from_raw_parts(bytes, length) };
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
+pub extern "C" fn buffer_index_of_utf16_string(buffer: *const Buffer, value: *const u16, length: usize, byte_offset: i64, encoding: StringEncoding) -> i64 {
+    let Some(value) = (unsafe { string_slice(value, length) }) else {
+        return -1;
+    };
+    buffer_search(buffer, node_buffer::IndexOfValue::Utf16String(value), byte_offset, encoding, true)
+}
 
-    if let Err(err) = node_fs::sync::append_file_with_path_bytes(path.as_ref(), bytes, options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
-    }
+#[no_mangle]
+pub extern "C" fn buffer_last_index_of_utf16_string(buffer: *const Buffer, value: *const u16, length: usize, byte_offset: i64, encoding: StringEncoding) -> i64 {
+    let Some(value) = (unsafe { string_slice(value, length) }) else {
+        return -1;
+    };
+    buffer_search(buffer, node_buffer::IndexOfValue::Utf16String(value), byte_offset, encoding, false)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_append_file_with_path_buffer_sync(path: *const c_char, buffer: *const Buffer, options: AppendFileOptions) {
-    if buffer.is_null() || path.is_null() {
-        return;
-    }
-    let buffer = unsafe { &*buffer };
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
-    if let Err(err) = node_fs::sync::append_file_with_path_buffer(path.as_ref(), &buffer.0, options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
-    }
+pub extern "C" fn buffer_includes_utf16_string(buffer: *const Buffer, value: *const u16, length: usize, byte_offset: i64, encoding: StringEncoding) -> bool {
+    buffer_index_of_utf16_string(buffer, value, length, byte_offset, encoding) != -1
 }
 
 
 #[no_mangle]
-pub extern "C" fn fs_append_file_with_path_string_sync(path: *const c_char, string: *const c_char, options: AppendFileOptions) {
-    if path.is_null() || string.is_null() {
-        return;
+pub extern "C" fn fs_encoding_get_string_value(encoding: *const FsEncoding) -> *const c_char {
+    if encoding.is_null() {
+        return std::ptr::null();
     }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
 
-    let string = unsafe { CStr::from_ptr(string) };
-    let string = string.to_string_lossy();
-    if let Err(err) = node_fs::sync::append_file_with_path_str(path.as_ref(), string.as_ref(), options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+    let encoding = unsafe { &*encoding };
+
+    match encoding.get_string_value() {
+        Ok(encoding) => {
+            CString::new(encoding).unwrap().into_raw()
+        }
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null()
+        }
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_append_file_with_buffer_buffer_sync(
-    dest: *mut Buffer,
-    data: *const Buffer,
-    options: AppendFileOptions,
-) {
-    if dest.is_null() || data.is_null() {
-        return;
+pub extern "C" fn fs_encoding_get_buffer_value(encoding: *const FsEncoding) -> *mut Buffer {
+    if encoding.is_null() {
+        return std::ptr::null_mut();
     }
-    let dest = unsafe { &mut *dest };
-    let data = unsafe { &*data };
 
-    if let Err(err) = node_fs::sync::append_file_with_buffer_buffer(&mut dest.0, &data.0, options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+    let encoding = unsafe { &*encoding };
+    match encoding.get_buffer_value() {
+        Ok(buffer) => {
+            Box::into_raw(buffer)
+        }
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
     }
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_append_file_with_buffer_string_sync(
-    dest: *mut Buffer,
-    data: *const c_char,
-    options: AppendFileOptions,
-) {
-    if dest.is_null() || data.is_null() {
-        return;
+pub extern "C" fn fs_encoding_is_buffer(encoding: *const FsEncoding) -> bool {
+    if encoding.is_null() {
+        return false;
     }
-    let dest = unsafe { &mut *dest };
-    let data = unsafe { CStr::from_ptr(data) };
-    let data = data.to_string_lossy();
 
+    let encoding = unsafe { &*encoding };
+    encoding.is_buffer()
+}
+
+#[derive(Debug)]
+pub struct FileDirent(node_fs::file_dirent::FileDirent);
 
-    if let Err(err) = node_fs::sync::append_file_with_buffer_string(&mut dest.0, data.as_ref(), options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+#[no_mangle]
+pub extern "C" fn fs_dirent_is_block_device(dirent: *const FileDirent) -> bool {
+    if dirent.is_null() {
+        return false;
     }
+
+    let dirent = unsafe { &*dirent };
+    dirent.0.is_block_device()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_append_file_with_buffer_string(
-    dest: *mut Buffer,
-    data: *const c_char,
-    options: AppendFileOptions,
-    callback: *const AsyncClosure,
-) {
-    if dest.is_null() || data.is_null() {
-        return;
+pub extern "C" fn fs_dirent_path(dirent: *const FileDirent) -> *const c_char {
+    if dirent.is_null() {
+        return std::ptr::null();
     }
-    let dest = unsafe { &mut *dest };
-    let data = unsafe { CStr::from_ptr(data) };
-    let data = data.to_string_lossy();
 
+    let dirent = unsafe { &*dirent };
+    CString::new(dirent.0.path().to_string()).unwrap().into_raw()
+}
 
-    let callback = unsafe { &*callback };
+#[no_mangle]
+pub extern "C" fn fs_dirent_name(dirent: *const FileDirent) -> *const c_char {
+    if dirent.is_null() {
+        return std::ptr::null();
+    }
 
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
+    let dirent = unsafe { &*dirent };
 
-    node_fs::a_sync::append_file_with_buffer_string(&mut dest.0, data.as_ref(), options.into(), cb);
+    CString::new(dirent.0.name().to_string()).unwrap().into_raw()
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_async_append_file_with_buffer_buffer(
-    dest: *mut Buffer,
-    data: *const Buffer,
-    options: AppendFileOptions,
-    callback: *const AsyncClosure,
-) {
-    if dest.is_null() || data.is_null() {
-        return;
+pub extern "C" fn fs_dirent_is_character_device(dirent: *const FileDirent) -> bool {
+    if dirent.is_null() {
+        return false;
     }
-    let dest = unsafe { &mut *dest };
-    let data = unsafe { &*data };
-
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-
-
-    node_fs::a_sync::append_file_with_buffer_buffer(&mut dest.0, &data.0, options.into(), cb);
+    let dirent = unsafe { &*dirent };
+    dirent.0.is_character_device()
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_chmod_sync(path: *const c_char, mode: u32) {
-    if path.is_null() {
-        return;
-    }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
-    if let Err(err) = node_fs::sync::chmod(path.as_ref(), mode).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+pub extern "C" fn fs_dirent_is_directory(dirent: *const FileDirent) -> bool {
+    if dirent.is_null() {
+        return false;
     }
+    let dirent = unsafe { &*dirent };
+    dirent.0.is_directory()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_chown_sync(path: *const c_char, uid: u32, gid: u32) {
-    if path.is_null() {
-        return;
-    }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
-
-    if let Err(err) = node_fs::sync::chown(path.as_ref(), uid, gid).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+pub extern "C" fn fs_dirent_is_fifo(dirent: *const FileDirent) -> bool {
+    if dirent.is_null() {
+        return false;
     }
+    let dirent = unsafe { &*dirent };
+    dirent.0.is_fifo()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_close_sync(fd: i32) {
-    node_fs::sync::close_fd(fd);
+pub extern "C" fn fs_dirent_is_file(dirent: *const FileDirent) -> bool {
+    if dirent.is_null() {
+        return false;
+    }
+    let dirent = unsafe { &*dirent };
+    dirent.0.is_file()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_copy_file_sync(src: *const c_char, dest: *const c_char, flags: u32) {
-    if src.is_null() || dest.is_null() {
-        return;
-    }
-    let src = unsafe { CStr::from_ptr(src) };
-    let src = src.to_string_lossy();
-
-
-    let dest = unsafe { CStr::from_ptr(dest) };
-    let dest = dest.to_string_lossy();
-
-
-    if let Err(err) = node_fs::sync::copy_file(src.as_ref(), dest.as_ref(), flags).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+pub extern "C" fn fs_dirent_is_socket(dirent: *const FileDirent) -> bool {
+    if dirent.is_null() {
+        return false;
     }
+    let dirent = unsafe { &*dirent };
+    dirent.0.is_socket()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_cp_sync(src: *const c_char, dest: *const c_char, flags: u32) {
-    if src.is_null() || dest.is_null() {
-        return;
+pub extern "C" fn fs_dirent_is_symbolic_link(dirent: *const FileDirent) -> bool {
+    if dirent.is_null() {
+        return false;
     }
-    let src = unsafe { CStr::from_ptr(src) };
-    let src = src.to_string_lossy();
-
-
-    let dest = unsafe { CStr::from_ptr(dest) };
-    let dest = dest.to_string_lossy();
-
-    node_fs::sync::cp(src.as_ref(), dest.as_ref(), flags);
+    let dirent = unsafe { &*dirent };
+    dirent.0.is_symbolic_link()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_exists_sync(src: *const c_char) -> bool {
-    if src.is_null() {
-        return false;
+pub extern "C" fn fs_dir_close_sync(dir: *const FileDir) {
+    if dir.is_null() {
+        return;
     }
-    let src = unsafe { CStr::from_ptr(src) };
-    let src = src.to_string_lossy();
+    let dir = unsafe { &*dir };
 
-    node_fs::sync::exists(src.as_ref())
-}
 
-#[no_mangle]
-pub extern "C" fn fs_fchmod_sync(fd: i32, mode: u32) {
-    if let Err(err) = node_fs::sync::fchmod(fd, mode as u16).map_err(|e| node_core::error::error_from_io_error(e)) {
+    if let Err(err) = dir.0.close()
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_fchown_sync(fd: i32, uid: u32, gid: u32) {
-    if let Err(err) = node_fs::sync::fchown(fd, uid, gid).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+pub extern "C" fn fs_dir_path(dir: *const FileDir) -> *const c_char {
+    if dir.is_null() {
+        return std::ptr::null();
     }
+    let dir = unsafe { &*dir };
+    CString::new(
+        dir.0.path().to_string()
+    ).unwrap().into_raw()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_fdatasync_sync(fd: i32) {
-    if let Err(err) = node_fs::sync::fdatasync(fd).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+pub extern "C" fn fs_dir_read_sync(dir: *const FileDir) -> *mut FileDirent {
+    if dir.is_null() {
+        return std::ptr::null_mut();
     }
-}
+    let dir = unsafe { &*dir };
 
-#[no_mangle]
-pub extern "C" fn fs_fstat_sync(fd: i32) -> *mut FileStat {
-    match node_fs::sync::fstat(fd).map(|metadata| {
-        handle_meta(&metadata)
-    })
+    match dir.0.read()
+        .map(|dirent| Box::new(FileDirent(dirent)))
         .map_err(|e| node_core::error::error_from_io_error(e)) {
-        Ok(stat) => {
-            Box::into_raw(
-                Box::new(FileStat::from(stat))
-            )
+        Ok(dirent) => {
+            Box::into_raw(dirent)
         }
         Err(err) => {
             update_last_error(err);
@@ -1644,100 +2303,100 @@ pub extern "C" fn fs_fstat_sync(fd: i32) -> *mut FileStat {
     }
 }
 
+
 #[no_mangle]
-pub extern "C" fn fs_fsync_sync(fd: i32) {
-    if let Err(err) = node_fs::sync::fsync(fd).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
-    }
+pub extern "C" fn fs_readdir_get_type_at(value: *const ReaddirResultArray, at: usize) -> ReaddirResultType {
+    let value = unsafe { &*value };
+    let slice = unsafe { std::slice::from_raw_parts(value.data, value.length) };
+    (&slice[at]).get_type()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_ftruncate_sync(fd: i32, len: usize) {
-    if let Err(err) = node_fs::sync::ftruncate(fd, len.try_into().unwrap()).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+pub extern "C" fn fs_readdir_get_string_value_at(value: *const ReaddirResultArray, at: usize) -> *const c_char {
+    if value.is_null() {
+        return std::ptr::null();
     }
-}
+    let value = unsafe { &*value };
+    let slice = unsafe { std::slice::from_raw_parts(value.data, value.length) };
 
-#[no_mangle]
-pub extern "C" fn fs_futimes_sync(fd: i32, atime: usize, mtime: usize) {
-    if let Err(err) = node_fs::sync::futimes(fd, atime.try_into().unwrap(), mtime.try_into().unwrap()).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+    match (&slice[at]).get_string_value() {
+        Ok(value) => {
+            CString::new(value).unwrap().into_raw()
+        }
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null()
+        }
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_lchmod_sync(path: *const c_char, mode: u32) {
-    if path.is_null() {
-        return;
+pub extern "C" fn fs_readdir_get_buffer_value_at(value: *const ReaddirResultArray, at: usize) -> *mut Buffer {
+    if value.is_null() {
+        return std::ptr::null_mut();
     }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
 
-    if let Err(err) = node_fs::sync::chmod(path.as_ref(), mode).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+    let value = unsafe { &*value };
+    let slice = unsafe { std::slice::from_raw_parts(value.data, value.length) };
+
+    match (&slice[at]).get_buffer_value() {
+        Ok(value) => { Box::into_raw(value) }
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_lchown_sync(path: *const c_char, uid: u32, gid: u32) {
-    if path.is_null() {
-        return;
+pub extern "C" fn fs_readdir_get_type_value_at(value: *const ReaddirResultArray, at: usize) -> *mut FileDirent {
+    if value.is_null() {
+        return std::ptr::null_mut();
     }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
 
-    if let Err(err) = node_fs::sync::chown(path.as_ref(), uid, gid).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+    let value = unsafe { &*value };
+    let slice = unsafe { std::slice::from_raw_parts(value.data, value.length) };
+
+    match (&slice[at]).get_type_value() {
+        Ok(value) => {
+            Box::into_raw(value)
+        }
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_lutimes_sync(path: *const c_char, atime: i64, mtime: i64) {
-    if path.is_null() {
-        return;
-    }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
-
-    if let Err(err) = node_fs::sync::lutimes(path.as_ref(), atime.try_into().unwrap(), mtime.try_into().unwrap()).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
-    }
+pub extern "C" fn fs_readdir_get_type(value: *const ReaddirResult) -> ReaddirResultType {
+    let value = unsafe { &*value };
+    return value.get_type();
 }
 
 #[no_mangle]
-pub extern "C" fn fs_link_sync(existing_path: *const c_char, new_path: *const c_char) {
-    if existing_path.is_null() || new_path.is_null() {
-        return;
-    }
-    let existing_path = unsafe { CStr::from_ptr(existing_path) };
-    let existing_path = existing_path.to_string_lossy();
-
-    let new_path = unsafe { CStr::from_ptr(new_path) };
-    let new_path = new_path.to_string_lossy();
-
-    if let Err(err) = node_fs::sync::link(existing_path.as_ref(), new_path.as_ref()).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+pub extern "C" fn fs_readdir_get_string_value(value: *const ReaddirResult) -> *const c_char {
+    let value = unsafe { &*value };
+    match value.get_string_value() {
+        Ok(value) => {
+            CString::new(value).unwrap().into_raw()
+        }
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null()
+        }
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_lstat_sync(path: *const c_char) -> *mut FileStat {
-    if path.is_null() {
+pub extern "C" fn fs_readdir_get_buffer_value(value: *const ReaddirResult) -> *mut Buffer {
+    if value.is_null() {
         return std::ptr::null_mut();
     }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
+    let value = unsafe { &*value };
 
-    match node_fs::sync::lstat(path.as_ref())
-        .map(|metadata| {
-            handle_meta(&metadata)
-        })
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        Ok(stat) => {
-            Box::into_raw(
-                Box::new(FileStat::from(stat))
-            )
-        }
+    match value.get_buffer_value() {
+        Ok(value) => { Box::into_raw(value) }
         Err(err) => {
             update_last_error(err);
             std::ptr::null_mut()
@@ -1746,1610 +2405,1367 @@ pub extern "C" fn fs_lstat_sync(path: *const c_char) -> *mut FileStat {
 }
 
 #[no_mangle]
-pub extern "C" fn fs_mkdir_sync(path: *const c_char, options: MkDirOptions) {
-    if path.is_null() {
-        return;
+pub extern "C" fn fs_readdir_get_type_value(value: *const ReaddirResult) -> *mut FileDirent {
+    if value.is_null() {
+        return std::ptr::null_mut();
     }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
+    let value = unsafe { &*value };
 
-    if let Err(err) = node_fs::sync::mkdir(path.as_ref(), options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
+    match value.get_type_value() {
+        Ok(value) => {
+            Box::into_raw(value)
+        }
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
     }
 }
 
-#[no_mangle]
-pub extern "C" fn fs_mkdtemp_sync(prefix: *const c_char, options: MkdTempOptions) -> *const c_char {
-    if prefix.is_null() {
-        return std::ptr::null();
+
+#[derive(Clone)]
+pub struct Metadata(std::fs::Metadata);
+
+
+#[derive(Clone, Debug)]
+pub struct ReaddirResult(node_fs::sync::ReaddirResult);
+
+impl ReaddirResult {
+    pub fn get_type(&self) -> ReaddirResultType {
+        match &self.0 {
+            node_fs::sync::ReaddirResult::String(_) => ReaddirResultType::ReaddirResultTypeString,
+            node_fs::sync::ReaddirResult::Buffer(_) => ReaddirResultType::ReaddirResultTypeBuffer,
+            node_fs::sync::ReaddirResult::Type(_) => ReaddirResultType::ReaddirResultTypeType
+        }
     }
-    let prefix = unsafe { CStr::from_ptr(prefix) };
-    let prefix = prefix.to_string_lossy();
 
-    match node_fs::sync::mkdtemp(prefix.as_ref(), options.into())
-        .map(|path| path.to_string_lossy().to_string())
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        Ok(path) => {
-            CString::new(path).unwrap().into_raw()
+    pub fn get_string_value(&self) -> Result<String> {
+        match self.0.get_string_value() {
+            None => {
+                Err(node_core::error::generic_error("Invalid Type".to_string()))
+            }
+            Some(value) => {
+                Ok(value.to_string_lossy().to_string())
+            }
         }
-        Err(err) => {
-            update_last_error(err);
-            std::ptr::null()
+    }
+
+    pub fn get_buffer_value(&self) -> Result<Box<Buffer>> {
+        match self.0.get_buffer_value() {
+            None => {
+                Err(node_core::error::generic_error("Invalid Type".to_string()))
+            }
+            Some(buffer) => {
+                Ok(Box::new(Buffer(buffer)))
+            }
+        }
+    }
+
+    pub fn get_type_value(&self) -> Result<Box<FileDirent>> {
+        match self.0.get_type_value() {
+            None => {
+                Err(node_core::error::generic_error("Invalid Type".to_string()))
+            }
+            Some(dirent) => {
+                Ok(Box::new(FileDirent(dirent)))
+            }
         }
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_open_sync(path: *const c_char, flag: i32, mode: i32) -> c_int {
-    if path.is_null() {
+pub extern "C" fn fs_parse_flag_sync(value: *const c_char) -> c_int {
+    if value.is_null() {
         return 0;
     }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
-
-    match node_fs::sync::open(path.as_ref(), flag, mode)
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        Ok(fd) => fd,
-        Err(err) => {
-            update_last_error(err);
-            0
-        }
-    }
+    let value = unsafe { CStr::from_ptr(value) };
+    let value = value.to_string_lossy();
+    node_fs::prelude::parse_flag(value.as_ref())
 }
 
 #[no_mangle]
-pub extern "C" fn fs_opendir_sync(path: *const c_char, options: OpenDirOptions) -> *mut FileDir {
+pub extern "C" fn fs_access_sync(path: *const c_char, mode: i32) {
     if path.is_null() {
-        return std::ptr::null_mut();
+        return;
     }
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
-    match node_fs::sync::opendir(path.as_ref(), options.into())
-        .map(|dir| {
-            Box::new(FileDir(dir))
-        })
+    if let Err(err) = node_fs::sync::access(path.as_ref(), mode)
         .map_err(|e| node_core::error::error_from_io_error(e)) {
-        Ok(dir) => { Box::into_raw(dir) }
-        Err(err) => {
-            update_last_error(err);
-            std::ptr::null_mut()
-        }
+        update_last_error(err);
     }
 }
 
-
-#[cfg(not(windows))]
 #[no_mangle]
-pub extern "C" fn fs_read_sync(
-    fd: i32,
-    buffer: *mut u8,
-    buffer_length: usize,
-    offset: usize,
-    length: usize,
-    position: isize,
-) -> usize {
-    let buffer = unsafe { std::slice::from_raw_parts_mut(buffer, buffer_length) };
-    node_fs::sync::read(fd, buffer, offset, length, position).map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+pub extern "C" fn fs_append_file_sync(fd: i32, buffer: *const Buffer, options: AppendFileOptions) {
+    if buffer.is_null() {
+        return;
+    }
+    let buffer = unsafe { &*buffer };
+    if let Err(err) = node_fs::sync::append_file_with_buffer(fd, &buffer.0, options.into())
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
-        0
-    })
+    }
 }
 
-#[cfg(windows)]
 #[no_mangle]
-pub extern "C" fn fs_read_sync(
-    fd: i64,
-    buffer: *mut u8,
-    buffer_length: usize,
-    offset: usize,
-    length: usize,
-    position: isize,
-) -> usize {
-    unsafe {
-        let buffer = std::slice::from_raw_parts_mut(buffer, buffer_length);
-        let fd = fd as *mut std::ffi::c_void;
-        node_fs::sync::read(fd, buffer, offset, length, position).map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
-            update_last_error(err);
-            0
-        })
+pub extern "C" fn fs_append_file_with_bytes_sync(fd: i32, bytes: *const u8, length: usize, options: AppendFileOptions) {
+    if bytes.is_null() || length == 0 {
+        return;
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(bytes, length) };
+    if let Err(err) = node_fs::sync::append_file_with_bytes(fd, bytes, options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
     }
 }
 
+#[no_mangle]
+pub extern "C" fn fs_append_file_with_string_sync(fd: i32, string: *const c_char, options: AppendFileOptions) {
+    if string.is_null() {
+        return;
+    }
 
-#[repr(C)]
-pub struct ReaddirResultArray {
-    data: *mut ReaddirResult,
-    length: usize,
-}
-
-impl Drop for ReaddirResultArray {
-    fn drop(&mut self) {
-        let _ = unsafe { Vec::from_raw_parts(self.data, self.length, self.length) };
+    let string = unsafe { CStr::from_ptr(string) };
+    let string = string.to_string_lossy();
+    if let Err(err) = node_fs::sync::append_file_with_str(fd, string.as_ref(), options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
     }
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_readdir_result_array_destroy(value: *mut ReaddirResultArray) {
-    if value.is_null() {
+pub extern "C" fn fs_append_file_with_path_sync(path: *const c_char, buffer: *const Buffer, options: AppendFileOptions) {
+    if buffer.is_null() || path.is_null() {
         return;
     }
-    let _ = unsafe { Box::from_raw(value) };
+    let buffer = unsafe { &*buffer };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
+
+    if let Err(err) = node_fs::sync::append_file_with_path_buffer(path.as_ref(), &buffer.0, options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_readdir_sync(path: *const c_char, options: ReaddirOptions) -> *mut ReaddirResultArray {
-    if path.is_null() {
-        return std::ptr::null_mut();
+pub extern "C" fn fs_append_file_with_path_bytes_sync(path: *const c_char, bytes: *const u8, length: usize, options: AppendFileOptions) {
+    if bytes.is_null() || length == 0 || path.is_null() {
+        return;
     }
+    let bytes = unsafe { std::slice::from_raw_parts(bytes, length) };
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    match node_fs::sync::readdir(path.as_ref(), options.into())
-        .map(|mut value| {
-            value.into_iter()
-                .map(|value| ReaddirResult(value))
-                .collect::<Vec<ReaddirResult>>()
-        })
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        Ok(mut result) => {
-            let ptr = result.as_mut_ptr();
-            let len = result.len();
-            result.shrink_to_fit();
-            std::mem::forget(result);
-            let ret = ReaddirResultArray {
-                data: ptr,
-                length: len,
-            };
-            Box::into_raw(
-                Box::new(ret)
-            )
-        }
-        Err(err) => {
-            update_last_error(err);
-            std::ptr::null_mut()
-        }
+    if let Err(err) = node_fs::sync::append_file_with_path_bytes(path.as_ref(), bytes, options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
     }
 }
 
-
-#[derive(Clone, Eq, PartialEq, Debug)]
-pub struct FsEncoding(node_fs::FsEncoding);
+#[no_mangle]
+pub extern "C" fn fs_append_file_with_path_buffer_sync(path: *const c_char, buffer: *const Buffer, options: AppendFileOptions) {
+    if buffer.is_null() || path.is_null() {
+        return;
+    }
+    let buffer = unsafe { &*buffer };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
+    if let Err(err) = node_fs::sync::append_file_with_path_buffer(path.as_ref(), &buffer.0, options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
+    }
+}
 
 
 #[no_mangle]
-pub extern "C" fn fs_encoding_destroy(value: *mut FsEncoding) {
-    if value.is_null() {
+pub extern "C" fn fs_append_file_with_path_string_sync(path: *const c_char, string: *const c_char, options: AppendFileOptions) {
+    if path.is_null() || string.is_null() {
         return;
     }
-    let _ = unsafe { Box::from_raw(value) };
-}
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-impl FsEncoding {
-    pub fn is_buffer(&self) -> bool {
-        match &self.0 {
-            node_fs::FsEncoding::String(_) => false,
-            node_fs::FsEncoding::Buffer(_) => true
-        }
-    }
-    pub fn get_string_value(&self) -> Result<String> {
-        match self.0.get_string_value() {
-            Some(value) => { Ok(value.to_string_lossy().to_string()) }
-            None => {
-                Err(node_core::error::generic_error("Invalid Type".to_string()))
-            }
-        }
-    }
-
-    pub fn get_buffer_value(&self) -> Result<Box<Buffer>> {
-        match self.0.get_buffer_value() {
-            None => {
-                Err(node_core::error::generic_error("Invalid Type".to_string()))
-            }
-            Some(buffer) => Ok(Box::new(Buffer(buffer)))
-        }
+    let string = unsafe { CStr::from_ptr(string) };
+    let string = string.to_string_lossy();
+    if let Err(err) = node_fs::sync::append_file_with_path_str(path.as_ref(), string.as_ref(), options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_read_file_sync(path: *const c_char, options: ReadFileOptions) -> *mut FsEncoding {
-    if path.is_null() {
-        return std::ptr::null_mut();
+pub extern "C" fn fs_append_file_with_buffer_buffer_sync(
+    dest: *mut Buffer,
+    data: *const Buffer,
+    options: AppendFileOptions,
+) {
+    if dest.is_null() || data.is_null() {
+        return;
     }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
+    let dest = unsafe { &mut *dest };
+    let data = unsafe { &*data };
 
-    match node_fs::sync::read_file(path.as_ref(), options.into())
-        .map(|f| Box::new(FsEncoding(f)))
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        Ok(encoding) => Box::into_raw(encoding),
-        Err(err) => {
-            update_last_error(err);
-            std::ptr::null_mut()
-        }
+    if let Err(err) = node_fs::sync::append_file_with_buffer_buffer(&mut dest.0, &data.0, options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
     }
 }
 
-#[no_mangle]
-pub extern "C" fn fs_read_file_with_fd_sync(fd: i32, options: ReadFileOptions) -> *mut FsEncoding {
-    match node_fs::sync::read_file_with_fd(fd, options.into())
-        .map(|f| Box::new(FsEncoding(f)))
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        Ok(encoding) => Box::into_raw(encoding),
-        Err(err) => {
-            update_last_error(err);
-            std::ptr::null_mut()
-        }
-    }
-}
 
 #[no_mangle]
-pub extern "C" fn fs_read_link_sync(path: *const c_char, options: ReadLinkOptions) -> *mut FsEncoding {
-    if path.is_null() {
-        return std::ptr::null_mut();
-    }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
-
-    match node_fs::sync::read_link(path.as_ref(), options.into())
-        .map(|f| Box::new(FsEncoding(f)))
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        Ok(encoding) => Box::into_raw(encoding),
-        Err(err) => {
-            update_last_error(err);
-            std::ptr::null_mut()
-        }
+pub extern "C" fn fs_append_file_with_buffer_string_sync(
+    dest: *mut Buffer,
+    data: *const c_char,
+    options: AppendFileOptions,
+) {
+    if dest.is_null() || data.is_null() {
+        return;
     }
-}
-
-#[no_mangle]
-pub extern "C" fn fs_readv_sync(fd: i32, buffers: *mut *mut Buffer, length: usize, position: i64) -> usize {
-    let buffers = unsafe { std::slice::from_raw_parts_mut(buffers, length) };
+    let dest = unsafe { &mut *dest };
+    let data = unsafe { CStr::from_ptr(data) };
+    let data = data.to_string_lossy();
 
-    let mut buffers = buffers.iter().map(|buffer| {
-        let buffer = unsafe { &mut **buffer };
-        buffer.0.clone()
-    })
-        .collect::<Vec<node_buffer::Buffer>>();
 
-    node_fs::sync::readv(fd, buffers.as_mut_slice(), position.try_into().unwrap())
-        .map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+    if let Err(err) = node_fs::sync::append_file_with_buffer_string(&mut dest.0, data.as_ref(), options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
-        0
-    })
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_readv_sync_slice(fd: i32, buffers: *const *mut u8, buffers_buffers: *const usize, length: usize, position: i64) -> usize {
-    let buffer_length = unsafe { std::slice::from_raw_parts(buffers_buffers, length) };
-    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
+pub extern "C" fn fs_async_append_file_with_buffer_string(
+    dest: *mut Buffer,
+    data: *const c_char,
+    options: AppendFileOptions,
+    callback: *const AsyncClosure,
+) {
+    if dest.is_null() || data.is_null() {
+        return;
+    }
+    let dest = unsafe { &mut *dest };
+    let data = unsafe { CStr::from_ptr(data) };
+    let data = data.to_string_lossy();
 
-    let mut buffers = buffers.iter().zip(buffer_length.iter())
-        .map(|(buffer, len)| {
-            unsafe { node_buffer::Buffer::from_reference(*buffer, *len) }
-        }).collect::<Vec<node_buffer::Buffer>>();
 
-    node_fs::sync::readv(fd, buffers.as_mut_slice(), position.try_into().unwrap())
-        .map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
-        update_last_error(err);
-        0
-    })
-}
+    let callback = unsafe { &*callback };
 
-#[no_mangle]
-pub extern "C" fn fs_real_path_sync(path: *const c_char, options: RealPathOptions) -> *const c_char {
-    if path.is_null() {
-        return std::ptr::null_mut();
-    }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
 
-    match node_fs::sync::real_path(path.as_ref(), options.into())
-        .map(|v| v.to_string_lossy().to_string())
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        Ok(path) => CString::new(path).unwrap().into_raw(),
-        Err(err) => {
-            update_last_error(err);
-            std::ptr::null()
-        }
-    }
+    node_fs::a_sync::append_file_with_buffer_string(&mut dest.0, data.as_ref(), options.into(), cb);
 }
 
+
 #[no_mangle]
-pub extern "C" fn fs_rename_sync(old_path: *const c_char, new_path: *const c_char) {
-    if old_path.is_null() || new_path.is_null() {
+pub extern "C" fn fs_async_append_file_with_buffer_buffer(
+    dest: *mut Buffer,
+    data: *const Buffer,
+    options: AppendFileOptions,
+    callback: *const AsyncClosure,
+) {
+    if dest.is_null() || data.is_null() {
         return;
     }
-    let old_path = unsafe { CStr::from_ptr(old_path) };
-    let old_path = old_path.to_string_lossy();
+    let dest = unsafe { &mut *dest };
+    let data = unsafe { &*data };
 
-    let new_path = unsafe { CStr::from_ptr(new_path) };
-    let new_path = new_path.to_string_lossy();
+    let callback = unsafe { &*callback };
 
-    if let Err(err) = node_fs::sync::rename(old_path.as_ref(), new_path.as_ref())
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
-    }
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+
+
+    node_fs::a_sync::append_file_with_buffer_buffer(&mut dest.0, &data.0, options.into(), cb);
 }
 
+
 #[no_mangle]
-pub extern "C" fn fs_rmdir_sync(
-    path: *const c_char,
-    options: RmDirOptions,
-) {
+pub extern "C" fn fs_chmod_sync(path: *const c_char, mode: u32) {
     if path.is_null() {
         return;
     }
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
-
-    if let Err(err) = node_fs::sync::rmdir(path.as_ref(), options.into()) {
+    if let Err(err) = node_fs::sync::chmod(path.as_ref(), mode).map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_rm_sync(
-    path: *const c_char,
-    options: RmOptions,
-) {
+pub extern "C" fn fs_chown_sync(path: *const c_char, uid: u32, gid: u32) {
     if path.is_null() {
         return;
     }
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    if let Err(err) = node_fs::sync::rm(path.as_ref(), options.into()) {
+    if let Err(err) = node_fs::sync::chown(path.as_ref(), uid, gid).map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_stat_sync(path: *const c_char) -> *mut FileStat {
-    if path.is_null() {
-        return std::ptr::null_mut();
+pub extern "C" fn fs_close_sync(fd: i32) {
+    if let Err(err) = node_fs::sync::close_fd(fd).map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
     }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
+}
 
-    match node_fs::sync::stat(path.as_ref())
-        .map(|metadata| {
-            handle_meta(&metadata)
-        })
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        Ok(stat) => Box::into_raw(Box::new(FileStat::from(stat))),
-        Err(err) => {
-            update_last_error(err);
-            std::ptr::null_mut()
-        }
-    }
+/// Debug aid: records where node-fs opens and closes fds, logging double closes and uses of
+/// closed fds as they happen. Meant for development builds, it captures a backtrace per call.
+#[no_mangle]
+pub extern "C" fn fs_set_fd_tracking(enabled: bool) {
+    node_fs::fd::set_tracking(enabled)
 }
 
+/// Logs the fds node-fs opened since tracking was enabled that are still open, with where
+/// they were opened, and returns their count.
 #[no_mangle]
-pub extern "C" fn fs_symlink_sync(target: *const c_char, path: *const c_char, _type_: *const c_char) {
-    if target.is_null() || path.is_null() || _type_.is_null() {
+pub extern "C" fn fs_report_fd_leaks() -> usize {
+    node_fs::fd::report_leaks()
+}
+
+#[no_mangle]
+pub extern "C" fn fs_copy_file_sync(src: *const c_char, dest: *const c_char, flags: u32) {
+    if src.is_null() || dest.is_null() {
         return;
     }
+    let src = unsafe { CStr::from_ptr(src) };
+    let src = src.to_string_lossy();
 
-    let target = unsafe { CStr::from_ptr(target) };
-    let target = target.to_string_lossy();
 
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
+    let dest = unsafe { CStr::from_ptr(dest) };
+    let dest = dest.to_string_lossy();
 
-    let _type_ = unsafe { CStr::from_ptr(_type_) };
-    let _type_ = _type_.to_string_lossy();
 
-    if let Err(err) = node_fs::sync::symlink(target.as_ref(), path.as_ref(), _type_.as_ref())
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
+    if let Err(err) = node_fs::sync::copy_file(src.as_ref(), dest.as_ref(), flags).map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_truncate_sync(path: *const c_char, len: u64) {
-    if path.is_null() {
-        return;
+pub extern "C" fn fs_cp_sync(src: *const c_char, dest: *const c_char, flags: u32) {
+    if src.is_null() || dest.is_null() {
+        return;
     }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
+    let src = unsafe { CStr::from_ptr(src) };
+    let src = src.to_string_lossy();
 
-    if let Err(err) = node_fs::sync::truncate(path.as_ref(), len.try_into().unwrap())
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
+
+    let dest = unsafe { CStr::from_ptr(dest) };
+    let dest = dest.to_string_lossy();
+
+    if let Err(err) = node_fs::sync::cp(src.as_ref(), dest.as_ref(), flags).map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_unlink_sync(path: *const c_char) {
-    if path.is_null() {
-        return;
+pub extern "C" fn fs_exists_sync(src: *const c_char) -> bool {
+    if src.is_null() {
+        return false;
     }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
+    let src = unsafe { CStr::from_ptr(src) };
+    let src = src.to_string_lossy();
 
-    if let Err(err) = node_fs::sync::unlink(path.as_ref())
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
-        update_last_error(err);
-    }
+    node_fs::sync::exists(src.as_ref())
 }
 
 #[no_mangle]
-pub extern "C" fn fs_utimes_sync(path: *const c_char, atime: i64, mtime: i64) {
-    if path.is_null() {
-        return;
-    }
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
-
-    if let Err(err) = node_fs::sync::utimes(path.as_ref(), atime.try_into().unwrap(), mtime.try_into().unwrap())
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
+pub extern "C" fn fs_fchmod_sync(fd: i32, mode: u32) {
+    if let Err(err) = node_fs::sync::fchmod(fd, mode as u16).map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_write_sync(
-    fd: i32,
-    buffer: *const u8,
-    length: usize,
-    options: WriteOptions,
-) -> usize {
-    let buffer = unsafe { std::slice::from_raw_parts(buffer, length) };
-    node_fs::sync::write(fd, buffer, options.into())
-        .map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+pub extern "C" fn fs_fchown_sync(fd: i32, uid: u32, gid: u32) {
+    if let Err(err) = node_fs::sync::fchown(fd, uid, gid).map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
-        0
-    })
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_write_string_sync(
-    fd: i32,
-    string: *const c_char,
-    encoding: StringEncoding,
-    position: isize,
-) -> usize {
-    if string.is_null() {
-        return 0;
+pub extern "C" fn fs_fdatasync_sync(fd: i32) {
+    if let Err(err) = node_fs::sync::fdatasync(fd).map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
     }
-    let string = unsafe { CStr::from_ptr(string) };
-    let string = string.to_string_lossy();
+}
 
-    node_fs::sync::write_string(
-        fd, string.as_ref(), encoding.into(), position,
-    ).map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
-        update_last_error(err);
-        0
+#[no_mangle]
+pub extern "C" fn fs_fstat_sync(fd: i32) -> *mut FileStat {
+    match node_fs::sync::fstat(fd).map(|metadata| {
+        handle_meta(&metadata)
     })
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(stat) => {
+            Box::into_raw(
+                Box::new(FileStat::from(stat))
+            )
+        }
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_write_file_with_str_sync(fd: i32, data: *const c_char, options: WriteFileOptions) {
-    if data.is_null() {
-        return;
+pub extern "C" fn fs_fsync_sync(fd: i32) {
+    if let Err(err) = node_fs::sync::fsync(fd).map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
     }
-    let data = unsafe { CStr::from_ptr(data) };
-    let data = data.to_string_lossy();
+}
 
-    if let Err(err) = node_fs::sync::write_file_with_str(fd, data.as_ref(), options.into())
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
+#[no_mangle]
+pub extern "C" fn fs_ftruncate_sync(fd: i32, len: usize) {
+    if let Err(err) = node_fs::sync::ftruncate(fd, len.try_into().unwrap()).map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_write_file_with_bytes_sync(fd: i32, data: *const u8, length: usize, options: WriteFileOptions) {
-    if data.is_null() || length == 0 {
-        return;
-    }
-    let data = unsafe { std::slice::from_raw_parts(data, length) };
-    if let Err(err) = node_fs::sync::write_file_with_bytes(fd, data, options.into())
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
+pub extern "C" fn fs_futimes_sync(fd: i32, atime: usize, mtime: usize) {
+    if let Err(err) = node_fs::sync::futimes(fd, atime.try_into().unwrap(), mtime.try_into().unwrap()).map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_write_file_with_str_from_path_sync(
-    path: *const c_char,
-    data: *const c_char,
-    options: WriteFileOptions,
-) {
-    if path.is_null() || data.is_null() {
+pub extern "C" fn fs_lchmod_sync(path: *const c_char, mode: u32) {
+    if path.is_null() {
         return;
     }
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let data = unsafe { CStr::from_ptr(data) };
-    let data = data.to_string_lossy();
-
-    if let Err(err) = node_fs::sync::write_file_with_str_from_path(
-        path.as_ref(), data.as_ref(), options.into(),
-    )
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
+    if let Err(err) = node_fs::sync::chmod(path.as_ref(), mode).map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_write_file_with_bytes_from_path_sync(
-    path: *const c_char,
-    data: *const u8,
-    length: usize,
-    options: WriteFileOptions,
-) {
-    if path.is_null() || data.is_null() || length == 0 {
+pub extern "C" fn fs_lchown_sync(path: *const c_char, uid: u32, gid: u32) {
+    if path.is_null() {
         return;
     }
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let data = unsafe { std::slice::from_raw_parts(data, length) };
-
-    if let Err(err) = node_fs::sync::write_file_with_bytes_from_path(
-        path.as_ref(), data, options.into(),
-    )
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
+    if let Err(err) = node_fs::sync::chown(path.as_ref(), uid, gid).map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_write_file_with_buffer_from_path_sync(
-    path: *const c_char,
-    data: *const Buffer,
-    options: WriteFileOptions,
-) {
-    if path.is_null() || data.is_null() {
+pub extern "C" fn fs_lutimes_sync(path: *const c_char, atime: i64, mtime: i64) {
+    if path.is_null() {
         return;
     }
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let data = unsafe { &*data };
-
-    if let Err(err) = node_fs::sync::write_file_with_buffer_from_path(
-        path.as_ref(), &data.0, options.into(),
-    )
-        .map_err(|e| node_core::error::error_from_io_error(e)) {
+    if let Err(err) = node_fs::sync::lutimes(path.as_ref(), atime.try_into().unwrap(), mtime.try_into().unwrap()).map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
     }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_writev_sync(fd: i32, buffers: *mut *mut Buffer, length: usize, position: i64) -> usize {
-    let buffers = unsafe { std::slice::from_raw_parts_mut(buffers, length) };
-    let buffers = buffers.iter().map(|buffer| {
-        let buffer = unsafe { &mut **buffer };
-        buffer.0.clone()
-    }).collect::<Vec<node_buffer::Buffer>>();
-    node_fs::sync::writev(
-        fd, buffers, position.try_into().unwrap(),
-    )
-        .map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+pub extern "C" fn fs_link_sync(existing_path: *const c_char, new_path: *const c_char) {
+    if existing_path.is_null() || new_path.is_null() {
+        return;
+    }
+    let existing_path = unsafe { CStr::from_ptr(existing_path) };
+    let existing_path = existing_path.to_string_lossy();
+
+    let new_path = unsafe { CStr::from_ptr(new_path) };
+    let new_path = new_path.to_string_lossy();
+
+    if let Err(err) = node_fs::sync::link(existing_path.as_ref(), new_path.as_ref()).map_err(|e| node_core::error::error_from_io_error(e)) {
         update_last_error(err);
-        0
-    })
+    }
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_writev_sync_slice(fd: i32, buffers: *const *const u8, buffers_buffers: *const usize, length: usize, position: i64) -> usize {
-    let buffer_length = unsafe { std::slice::from_raw_parts(buffers_buffers, length) };
-    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
-
-    let buffers = buffers.iter().zip(buffer_length.iter())
-        .map(|(buffer, len)| {
-            unsafe { std::slice::from_raw_parts(*buffer, *len) }
-        }).collect::<Vec<_>>();
-
+pub extern "C" fn fs_lstat_sync(path: *const c_char) -> *mut FileStat {
+    if path.is_null() {
+        return std::ptr::null_mut();
+    }
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-    node_fs::sync::writev_slice(
-        fd, buffers.as_slice(), position.try_into().unwrap(),
-    )
-        .map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
-        update_last_error(err);
-        0
-    })
+    match node_fs::sync::lstat(path.as_ref())
+        .map(|metadata| {
+            handle_meta(&metadata)
+        })
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(stat) => {
+            Box::into_raw(
+                Box::new(FileStat::from(stat))
+            )
+        }
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
+    }
 }
 
-// async
-
 #[no_mangle]
-pub extern "C" fn fs_async_access(path: *const c_char, access: i32, callback: *const AsyncClosure) {
-    if path.is_null() || callback.is_null() {
+pub extern "C" fn fs_mkdir_sync(path: *const c_char, options: MkDirOptions) {
+    if path.is_null() {
         return;
     }
-
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::access(path.as_ref(), access, cb)
+    if let Err(err) = node_fs::sync::mkdir(path.as_ref(), options.into()).map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_append_file_with_str(fd: i32, data: *const c_char, options: AppendFileOptions, callback: *const AsyncClosure) {
-    if data.is_null() || callback.is_null() {
-        return;
+pub extern "C" fn fs_mkdtemp_sync(prefix: *const c_char, options: MkdTempOptions) -> *const c_char {
+    if prefix.is_null() {
+        return std::ptr::null();
     }
+    let prefix = unsafe { CStr::from_ptr(prefix) };
+    let prefix = prefix.to_string_lossy();
 
-    let data = unsafe { CStr::from_ptr(data) };
-    let data = data.to_string_lossy();
-
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::append_file_with_str(fd, data.as_ref(), options.into(), cb)
-}
-
-#[no_mangle]
-pub extern "C" fn fs_async_append_file_with_bytes(fd: i32, data: *const Buffer, options: AppendFileOptions, callback: *const AsyncClosure) {
-    if data.is_null() || callback.is_null() {
-        return;
+    match node_fs::sync::mkdtemp(prefix.as_ref(), options.into())
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(path) => {
+            CString::new(path).unwrap().into_raw()
+        }
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null()
+        }
     }
-
-    let data = unsafe { &*data };
-
-    let callback = unsafe { &*callback };
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::append_file_with_bytes(fd, &data.0, options.into(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_append_file_with_path_str(
-    path: *const c_char,
-    data: *const c_char,
-    options: AppendFileOptions,
-    callback: *const AsyncClosure,
-) {
-    if path.is_null() || data.is_null() || callback.is_null() {
-        return;
+pub extern "C" fn fs_open_sync(path: *const c_char, flag: i32, mode: i32) -> c_int {
+    if path.is_null() {
+        return 0;
     }
-
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let data = unsafe { CStr::from_ptr(data) };
-    let data = data.to_string_lossy();
-
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::append_file_with_path_str(path.as_ref(), data.as_ref(), options.into(), cb)
+    match node_fs::sync::open(path.as_ref(), flag, mode)
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(fd) => fd,
+        Err(err) => {
+            update_last_error(err);
+            0
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_append_file_with_path_bytes(
-    path: *const c_char,
-    data: *const Buffer,
-    options: AppendFileOptions,
-    callback: *const AsyncClosure,
-) {
-    if path.is_null() || data.is_null() || callback.is_null() {
-        return;
+pub extern "C" fn fs_opendir_sync(path: *const c_char, options: OpenDirOptions) -> *mut FileDir {
+    if path.is_null() {
+        return std::ptr::null_mut();
     }
-
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
+    match node_fs::sync::opendir(path.as_ref(), options.into())
+        .map(|dir| {
+            Box::new(FileDir(dir))
+        })
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(dir) => { Box::into_raw(dir) }
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
+    }
+}
 
-    let data = unsafe { &*data };
-
-    let callback = unsafe { &*callback };
 
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::append_file_with_path_bytes(path.as_ref(), &data.0, options.into(), cb)
+#[cfg(not(windows))]
+#[no_mangle]
+pub extern "C" fn fs_read_sync(
+    fd: i32,
+    buffer: *mut u8,
+    buffer_length: usize,
+    offset: usize,
+    length: usize,
+    position: isize,
+) -> usize {
+    let buffer = unsafe { std::slice::from_raw_parts_mut(buffer, buffer_length) };
+    node_fs::sync::read(fd, buffer, offset, length, position).map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+        update_last_error(err);
+        0
+    })
 }
 
+#[cfg(windows)]
 #[no_mangle]
-pub extern "C" fn fs_async_chmod(path: *const c_char, mode: u32, callback: *const AsyncClosure) {
-    if path.is_null() || callback.is_null() {
-        return;
+pub extern "C" fn fs_read_sync(
+    fd: i64,
+    buffer: *mut u8,
+    buffer_length: usize,
+    offset: usize,
+    length: usize,
+    position: isize,
+) -> usize {
+    unsafe {
+        let buffer = std::slice::from_raw_parts_mut(buffer, buffer_length);
+        let fd = fd as *mut std::ffi::c_void;
+        node_fs::sync::read(fd, buffer, offset, length, position).map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+            update_last_error(err);
+            0
+        })
     }
+}
 
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
+#[repr(C)]
+pub struct ReaddirResultArray {
+    data: *mut ReaddirResult,
+    length: usize,
+}
 
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::chmod(path.as_ref(), mode, cb)
+impl Drop for ReaddirResultArray {
+    fn drop(&mut self) {
+        let _ = unsafe { Vec::from_raw_parts(self.data, self.length, self.length) };
+    }
 }
 
+
 #[no_mangle]
-pub extern "C" fn fs_async_chown(path: *const c_char, uid: u32, gid: u32, callback: *const AsyncClosure) {
-    if path.is_null() || callback.is_null() {
+pub extern "C" fn fs_readdir_result_array_destroy(value: *mut ReaddirResultArray) {
+    if value.is_null() {
         return;
     }
+    let _ = unsafe { Box::from_raw(value) };
+}
 
+#[no_mangle]
+pub extern "C" fn fs_readdir_sync(path: *const c_char, options: ReaddirOptions) -> *mut ReaddirResultArray {
+    if path.is_null() {
+        return std::ptr::null_mut();
+    }
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
+    match node_fs::sync::readdir(path.as_ref(), options.into())
+        .map(|mut value| {
+            value.into_iter()
+                .map(|value| ReaddirResult(value))
+                .collect::<Vec<ReaddirResult>>()
+        })
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(mut result) => {
+            let ptr = result.as_mut_ptr();
+            let len = result.len();
+            result.shrink_to_fit();
+            std::mem::forget(result);
+            let ret = ReaddirResultArray {
+                data: ptr,
+                length: len,
+            };
+            Box::into_raw(
+                Box::new(ret)
+            )
+        }
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
+    }
+}
 
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
 
-    node_fs::a_sync::chown(path.as_ref(), uid, gid, cb)
-}
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct FsEncoding(node_fs::FsEncoding);
+
 
 #[no_mangle]
-pub extern "C" fn fs_async_close(fd: i32, callback: *const AsyncClosure) {
-    if callback.is_null() {
+pub extern "C" fn fs_encoding_destroy(value: *mut FsEncoding) {
+    if value.is_null() {
         return;
     }
+    let _ = unsafe { Box::from_raw(value) };
+}
 
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
+impl FsEncoding {
+    pub fn is_buffer(&self) -> bool {
+        match &self.0 {
+            node_fs::FsEncoding::String(_) => false,
+            node_fs::FsEncoding::Buffer(_) => true
+        }
+    }
+    pub fn get_string_value(&self) -> Result<String> {
+        match self.0.get_string_value() {
+            Some(value) => { Ok(value.to_string_lossy().to_string()) }
+            None => {
+                Err(node_core::error::generic_error("Invalid Type".to_string()))
             }
-        }))
-    );
-    node_fs::a_sync::close(fd, cb)
-}
-
-#[no_mangle]
-pub extern "C" fn fs_async_copy_file(src: *const c_char, dest: *const c_char, flag: u32, callback: *const AsyncClosure) {
-    if src.is_null() || dest.is_null() || callback.is_null() {
-        return;
+        }
     }
 
-    let src = unsafe { CStr::from_ptr(src) };
-    let src = src.to_string_lossy();
-
-    let dest = unsafe { CStr::from_ptr(dest) };
-    let dest = dest.to_string_lossy();
-
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
+    pub fn get_buffer_value(&self) -> Result<Box<Buffer>> {
+        match self.0.get_buffer_value() {
+            None => {
+                Err(node_core::error::generic_error("Invalid Type".to_string()))
             }
-        }))
-    );
-    node_fs::a_sync::copy_file(src.as_ref(), dest.as_ref(), flag, cb)
+            Some(buffer) => Ok(Box::new(Buffer(buffer)))
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_cp(_src: *const c_char, _dest: *const c_char) {}
-
-#[no_mangle]
-pub extern "C" fn fs_async_exists(path: *const c_char, callback: *const AsyncBoolClosure) {
-    if path.is_null() || callback.is_null() {
-        return;
+pub extern "C" fn fs_read_file_sync(path: *const c_char, options: ReadFileOptions) -> *mut FsEncoding {
+    if path.is_null() {
+        return std::ptr::null_mut();
     }
-
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(result)
-            }
-        }))
-    );
-    node_fs::a_sync::exists(path.as_ref(), cb)
+    match node_fs::sync::read_file(path.as_ref(), options.into())
+        .map(|f| Box::new(FsEncoding(f)))
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(encoding) => Box::into_raw(encoding),
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_fchmod(fd: i32, mode: u16, callback: *const AsyncClosure) {
-    if callback.is_null() {
-        return;
+pub extern "C" fn fs_read_file_with_fd_sync(fd: i32, options: ReadFileOptions) -> *mut FsEncoding {
+    match node_fs::sync::read_file_with_fd(fd, options.into())
+        .map(|f| Box::new(FsEncoding(f)))
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(encoding) => Box::into_raw(encoding),
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
     }
+}
 
-    let callback = unsafe { &*callback };
+pub struct AbortSignal(node_core::abort::AbortSignal);
 
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::fchmod(fd, mode, cb)
+#[no_mangle]
+pub extern "C" fn abort_signal_create() -> *mut AbortSignal {
+    Box::into_raw(Box::new(AbortSignal(node_core::abort::AbortSignal::new())))
 }
 
+/// Operations holding the signal stop at their next check, it may be called from any thread.
 #[no_mangle]
-pub extern "C" fn fs_async_fchown(fd: i32, uid: u32, gid: u32, callback: *const AsyncClosure) {
-    if callback.is_null() {
+pub extern "C" fn abort_signal_abort(signal: *const AbortSignal) {
+    if signal.is_null() {
         return;
     }
-
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::fchown(fd, uid, gid, cb)
+    let signal = unsafe { &*signal };
+    signal.0.abort()
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_fdatasync(fd: i32, callback: *const AsyncClosure) {
-    if callback.is_null() {
-        return;
+pub extern "C" fn abort_signal_aborted(signal: *const AbortSignal) -> bool {
+    if signal.is_null() {
+        return false;
     }
-
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::fdatasync(fd, cb)
+    let signal = unsafe { &*signal };
+    signal.0.aborted()
 }
 
+/// Operations already started keep their own reference, so the signal can be destroyed
+/// while they run.
 #[no_mangle]
-pub extern "C" fn fs_async_fstat(fd: i32, callback: *const AsyncFileStatClosure) {
-    if callback.is_null() {
+pub extern "C" fn abort_signal_destroy(signal: *mut AbortSignal) {
+    if signal.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let _ = unsafe { Box::from_raw(signal) };
+}
 
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(result.map(|stat: node_fs::file_stat::FileStat| stat.into()))
-            }
-        }))
-    );
-    node_fs::a_sync::fstat(fd, cb);
+/// Files larger than `size` make readFile fail with ERR_FS_FILE_TOO_LARGE, the default is
+/// Node's 2 GiB - 1.
+#[no_mangle]
+pub extern "C" fn fs_set_read_file_max_size(size: u64) {
+    node_fs::sync::set_read_file_max_size(size)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_fsync(fd: i32, callback: *const AsyncClosure) {
-    if callback.is_null() {
-        return;
+pub extern "C" fn fs_read_file_max_size() -> u64 {
+    node_fs::sync::read_file_max_size()
+}
+
+/// Same as `fs_read_file_sync`, failing with an AbortError once `signal` is aborted.
+#[no_mangle]
+pub extern "C" fn fs_read_file_with_signal_sync(path: *const c_char, options: ReadFileOptions, signal: *const AbortSignal) -> *mut FsEncoding {
+    if path.is_null() || signal.is_null() {
+        return std::ptr::null_mut();
     }
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
+    let signal = unsafe { &*signal };
 
-    let callback = unsafe { &*callback };
+    match node_fs::sync::read_file_with_signal(path.as_ref(), options.into(), &signal.0)
+        .map(|f| Box::new(FsEncoding(f)))
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(encoding) => Box::into_raw(encoding),
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
+    }
+}
 
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::fdatasync(fd, cb)
+#[repr(C)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FsMapAdvice {
+    FsMapAdviceNormal,
+    FsMapAdviceSequential,
+    FsMapAdviceRandom,
+    FsMapAdviceWillNeed,
+}
+
+fn map_options(advice: FsMapAdvice) -> node_fs::mmap::MapOptions {
+    let mut options = node_fs::mmap::MapOptions::default();
+    options.set_advice(match advice {
+        FsMapAdvice::FsMapAdviceNormal => node_fs::mmap::MapAdvice::Normal,
+        FsMapAdvice::FsMapAdviceSequential => node_fs::mmap::MapAdvice::Sequential,
+        FsMapAdvice::FsMapAdviceRandom => node_fs::mmap::MapAdvice::Random,
+        FsMapAdvice::FsMapAdviceWillNeed => node_fs::mmap::MapAdvice::WillNeed,
+    });
+    options
 }
 
+/// Maps the whole file copy-on-write into a buffer that is unmapped when its last view is
+/// destroyed, writes to the buffer never reach the file. Truncating the file while it is
+/// mapped makes reads past the new end raise SIGBUS.
 #[no_mangle]
-pub extern "C" fn fs_async_ftruncate(fd: i32, len: i64, callback: *const AsyncClosure) {
-    if callback.is_null() {
-        return;
+pub extern "C" fn fs_map_file_sync(path: *const c_char, advice: FsMapAdvice) -> *mut Buffer {
+    if path.is_null() {
+        return std::ptr::null_mut();
     }
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::ftruncate(fd, len.try_into().unwrap(), cb)
+    match unsafe { node_fs::mmap::map_file(path.as_ref(), map_options(advice)) }
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(buffer) => Box::into_raw(Buffer(buffer).into_box()),
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
+    }
 }
 
+/// Same as `fs_map_file_sync`, `fd` is left open.
 #[no_mangle]
-pub extern "C" fn fs_async_futimes(fd: i32, atime: i64, mtime: i64, callback: *const AsyncClosure) {
-    if callback.is_null() {
-        return;
+pub extern "C" fn fs_map_file_with_fd_sync(fd: i32, advice: FsMapAdvice) -> *mut Buffer {
+    match unsafe { node_fs::mmap::map_file_with_fd(fd, map_options(advice)) }
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(buffer) => Box::into_raw(Buffer(buffer).into_box()),
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
     }
-
-    let callback = unsafe { &*callback };
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::futimes(fd, atime.try_into().unwrap(), mtime.try_into().unwrap(), cb)
-}
+}
 
 #[no_mangle]
-pub extern "C" fn fs_async_lchmod(path: *const c_char, mode: u16, callback: *const AsyncClosure) {
-    if path.is_null() || callback.is_null() {
-        return;
+pub extern "C" fn fs_read_link_sync(path: *const c_char, options: ReadLinkOptions) -> *mut FsEncoding {
+    if path.is_null() {
+        return std::ptr::null_mut();
     }
-
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::lchmod(path.as_ref(), mode, cb)
+    match node_fs::sync::read_link(path.as_ref(), options.into())
+        .map(|f| Box::new(FsEncoding(f)))
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(encoding) => Box::into_raw(encoding),
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_lchown(path: *const c_char, uid: u32, gid: u32, callback: *const AsyncClosure) {
-    if path.is_null() || callback.is_null() {
-        return;
-    }
+pub extern "C" fn fs_readv_sync(fd: i32, buffers: *mut *mut Buffer, length: usize, position: i64) -> usize {
+    let buffers = unsafe { std::slice::from_raw_parts_mut(buffers, length) };
 
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
+    let mut buffers = buffers.iter().map(|buffer| {
+        let buffer = unsafe { &mut **buffer };
+        buffer.0.clone()
+    })
+        .collect::<Vec<node_buffer::Buffer>>();
 
-    let callback = unsafe { &*callback };
+    node_fs::sync::readv(fd, buffers.as_mut_slice(), position.try_into().unwrap())
+        .map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+        update_last_error(err);
+        0
+    })
+}
 
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::lchown(path.as_ref(), uid, gid, cb)
+#[no_mangle]
+pub extern "C" fn fs_readv_sync_slice(fd: i32, buffers: *const *mut u8, buffers_buffers: *const usize, length: usize, position: i64) -> usize {
+    let buffer_length = unsafe { std::slice::from_raw_parts(buffers_buffers, length) };
+    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
+
+    let mut buffers = buffers.iter().zip(buffer_length.iter())
+        .map(|(buffer, len)| {
+            unsafe { node_buffer::Buffer::from_reference(*buffer, *len) }
+        }).collect::<Vec<node_buffer::Buffer>>();
+
+    node_fs::sync::readv(fd, buffers.as_mut_slice(), position.try_into().unwrap())
+        .map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+        update_last_error(err);
+        0
+    })
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_lutimes(path: *const c_char, atime: i64, mtime: i64, callback: *const AsyncClosure) {
-    if path.is_null() || callback.is_null() {
-        return;
+pub extern "C" fn fs_real_path_sync(path: *const c_char, options: RealPathOptions) -> *const c_char {
+    if path.is_null() {
+        return std::ptr::null_mut();
     }
-
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::lutimes(path.as_ref(), atime.try_into().unwrap(), mtime.try_into().unwrap(), cb)
+    match node_fs::sync::real_path(path.as_ref(), options.into())
+        .map(|v| v.to_string_lossy().to_string())
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(path) => CString::new(path).unwrap().into_raw(),
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null()
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_link(existing_path: *const c_char, new_path: *const c_char, callback: *const AsyncClosure) {
-    if existing_path.is_null() || new_path.is_null() || callback.is_null() {
+pub extern "C" fn fs_rename_sync(old_path: *const c_char, new_path: *const c_char) {
+    if old_path.is_null() || new_path.is_null() {
         return;
     }
-
-    let existing_path = unsafe { CStr::from_ptr(existing_path) };
-    let existing_path = existing_path.to_string_lossy();
+    let old_path = unsafe { CStr::from_ptr(old_path) };
+    let old_path = old_path.to_string_lossy();
 
     let new_path = unsafe { CStr::from_ptr(new_path) };
     let new_path = new_path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::link(existing_path.as_ref(), new_path.as_ref(), cb)
+    if let Err(err) = node_fs::sync::rename(old_path.as_ref(), new_path.as_ref())
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_lstat(path: *const c_char, callback: *const AsyncFileStatClosure) {
-    if path.is_null() || callback.is_null() {
+pub extern "C" fn fs_rmdir_sync(
+    path: *const c_char,
+    options: RmDirOptions,
+) {
+    if path.is_null() {
         return;
     }
-
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(result.map(|stat: node_fs::file_stat::FileStat| stat.into()))
-            }
-        }))
-    );
-
-    node_fs::a_sync::lstat(path.as_ref(), cb)
+    if let Err(err) = node_fs::sync::rmdir(path.as_ref(), options.into()) {
+        update_last_error(err);
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_mkdir(path: *const c_char, options: MkDirOptions, callback: *const AsyncClosure) {
-    if path.is_null() || callback.is_null() {
+pub extern "C" fn fs_rm_sync(
+    path: *const c_char,
+    options: RmOptions,
+) {
+    if path.is_null() {
         return;
     }
-
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(None)
-            }
-        }))
-    );
-    node_fs::a_sync::mkdir(path.as_ref(), options.into(), cb)
+    if let Err(err) = node_fs::sync::rm(path.as_ref(), options.into()) {
+        update_last_error(err);
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_mkdtemp(prefix: *const c_char, options: MkdTempOptions, callback: *const AsyncStringClosure) {
-    if prefix.is_null() || callback.is_null() {
-        return;
+pub extern "C" fn fs_stat_sync(path: *const c_char) -> *mut FileStat {
+    if path.is_null() {
+        return std::ptr::null_mut();
     }
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-    let prefix = unsafe { CStr::from_ptr(prefix) };
-    let prefix = prefix.to_string_lossy();
-
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |path, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(path.map(|path: std::path::PathBuf| path.to_string_lossy().to_string()))
-            }
-        }))
-    );
-    node_fs::a_sync::mkdtemp(prefix.as_ref(), options.into(), cb);
+    match node_fs::sync::stat(path.as_ref())
+        .map(|metadata| {
+            handle_meta(&metadata)
+        })
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(stat) => Box::into_raw(Box::new(FileStat::from(stat))),
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_open(path: *const c_char, flag: i32, mode: i32, callback: *const AsyncI32Closure) {
-    if path.is_null() || callback.is_null() {
+pub extern "C" fn fs_symlink_sync(target: *const c_char, path: *const c_char, _type_: *const c_char) {
+    if target.is_null() || path.is_null() || _type_.is_null() {
         return;
     }
 
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
+    let target = unsafe { CStr::from_ptr(target) };
+    let target = target.to_string_lossy();
 
-    let callback = unsafe { &*callback };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error: Option<std::io::Error>| {
-            if error.is_some() {
-                let error = error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError);
+    let _type_ = unsafe { CStr::from_ptr(_type_) };
+    let _type_ = _type_.to_string_lossy();
 
-                callback.on_error(error)
-            } else {
-                callback.on_success(result)
-            }
-        }))
-    );
-    node_fs::a_sync::open(path.as_ref(), flag, mode, cb);
+    if let Err(err) = node_fs::sync::symlink(target.as_ref(), path.as_ref(), _type_.as_ref())
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
+    }
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_async_open_handle(path: *const c_char, flag: i32, mode: i32, callback: *const AsyncFileHandleClosure) {
-    if path.is_null() || callback.is_null() {
+pub extern "C" fn fs_truncate_sync(path: *const c_char, len: u64) {
+    if path.is_null() {
         return;
     }
-
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error: Option<std::io::Error>| {
-            if error.is_some() {
-                let error = error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError);
+    if let Err(err) = node_fs::sync::truncate(path.as_ref(), len.try_into().unwrap())
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
+    }
+}
 
-                callback.on_error(error)
-            } else {
-                callback.on_success(result.map(FileHandle))
-            }
-        }))
-    );
+#[no_mangle]
+pub extern "C" fn fs_unlink_sync(path: *const c_char) {
+    if path.is_null() {
+        return;
+    }
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-    node_fs::file_handle::FileHandle::new_async(path.as_ref(), flag, mode, cb);
+    if let Err(err) = node_fs::sync::unlink(path.as_ref())
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_opendir(path: *const c_char, options: OpenDirOptions, callback: *const AsyncFileDirClosure) {
-    if path.is_null() || callback.is_null() {
+pub extern "C" fn fs_utimes_sync(path: *const c_char, atime: i64, mtime: i64) {
+    if path.is_null() {
         return;
     }
-
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(result.map(FileDir))
-            }
-        }))
-    );
-    node_fs::a_sync::opendir(path.as_ref(), options.into(), cb);
+    if let Err(err) = node_fs::sync::utimes(path.as_ref(), atime.try_into().unwrap(), mtime.try_into().unwrap())
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_read(
+pub extern "C" fn fs_write_sync(
     fd: i32,
-    buffer: *mut Buffer,
-    offset: usize,
+    buffer: *const u8,
     length: usize,
+    options: WriteOptions,
+) -> usize {
+    let buffer = unsafe { std::slice::from_raw_parts(buffer, length) };
+    node_fs::sync::write(fd, buffer, options.into())
+        .map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+        update_last_error(err);
+        0
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn fs_write_string_sync(
+    fd: i32,
+    string: *const c_char,
+    encoding: StringEncoding,
     position: isize,
-    callback: *const AsyncUsizeClosure,
-) {
-    if buffer.is_null() || callback.is_null() {
-        return;
+) -> usize {
+    if string.is_null() {
+        return 0;
     }
+    let string = unsafe { CStr::from_ptr(string) };
+    let string = string.to_string_lossy();
 
-    let buffer = unsafe { &mut *buffer };
-
-    let callback = unsafe { &*callback };
+    node_fs::sync::write_string(
+        fd, string.as_ref(), encoding.into(), position,
+    ).map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+        update_last_error(err);
+        0
+    })
+}
 
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(result)
-            }
-        }))
-    );
+#[no_mangle]
+pub extern "C" fn fs_write_utf8_string_sync(
+    fd: i32,
+    string: *const u8,
+    length: usize,
+    encoding: StringEncoding,
+    position: isize,
+) -> usize {
+    let Some(string) = (unsafe { string_slice(string, length) }) else {
+        return 0;
+    };
 
-    node_fs::a_sync::read(fd, &mut buffer.0, offset, length, position, cb)
+    node_fs::sync::write_utf8_string(
+        fd, string, encoding.into(), position,
+    ).map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+        update_last_error(err);
+        0
+    })
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_async_read_bytes(
+pub extern "C" fn fs_write_utf16_string_sync(
     fd: i32,
-    buffer: *mut u8,
-    buffer_length: usize,
-    offset: usize,
+    string: *const u16,
     length: usize,
+    encoding: StringEncoding,
     position: isize,
-    callback: *const AsyncUsizeClosure,
-) {
-    if buffer.is_null() || callback.is_null() {
-        return;
-    }
+) -> usize {
+    let Some(string) = (unsafe { string_slice(string, length) }) else {
+        return 0;
+    };
 
-    let bytes = unsafe { std::slice::from_raw_parts_mut(buffer, buffer_length) };
+    node_fs::sync::write_utf16_string(
+        fd, string, encoding.into(), position,
+    ).map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+        update_last_error(err);
+        0
+    })
+}
 
-    let callback = unsafe { &*callback };
+#[no_mangle]
+pub extern "C" fn fs_write_file_with_str_sync(fd: i32, data: *const c_char, options: WriteFileOptions) {
+    if data.is_null() {
+        return;
+    }
+    let data = unsafe { CStr::from_ptr(data) };
+    let data = data.to_string_lossy();
 
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(result)
-            }
-        }))
-    );
+    if let Err(err) = node_fs::sync::write_file_with_str(fd, data.as_ref(), options.into())
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
+    }
+}
 
-    node_fs::a_sync::read_bytes(fd, bytes, offset, length, position, cb)
+#[no_mangle]
+pub extern "C" fn fs_write_file_with_bytes_sync(fd: i32, data: *const u8, length: usize, options: WriteFileOptions) {
+    if data.is_null() || length == 0 {
+        return;
+    }
+    let data = unsafe { std::slice::from_raw_parts(data, length) };
+    if let Err(err) = node_fs::sync::write_file_with_bytes(fd, data, options.into())
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_readdir(
+pub extern "C" fn fs_write_file_with_str_from_path_sync(
     path: *const c_char,
-    options: ReaddirOptions,
-    callback: *const AsyncReaddirClosure,
+    data: *const c_char,
+    options: WriteFileOptions,
 ) {
-    if path.is_null() || callback.is_null() {
+    if path.is_null() || data.is_null() {
         return;
     }
-
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result: Option<Vec<node_fs::sync::ReaddirResult>>, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(result.map(|result| result.into_iter()
-                    .map(|value| ReaddirResult(value))
-                    .collect::<Vec<ReaddirResult>>()))
-            }
-        }))
-    );
+    let data = unsafe { CStr::from_ptr(data) };
+    let data = data.to_string_lossy();
 
-    node_fs::a_sync::readdir(path.as_ref(), options.into(), cb)
+    if let Err(err) = node_fs::sync::write_file_with_str_from_path(
+        path.as_ref(), data.as_ref(), options.into(),
+    )
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_read_file(path: *const c_char, options: ReadFileOptions, callback: *const AsyncFsEncodingClosure) {
-    if path.is_null() || callback.is_null() {
+pub extern "C" fn fs_write_file_with_bytes_from_path_sync(
+    path: *const c_char,
+    data: *const u8,
+    length: usize,
+    options: WriteFileOptions,
+) {
+    if path.is_null() || data.is_null() || length == 0 {
         return;
     }
-
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(result.map(FsEncoding))
-            }
-        }))
-    );
+    let data = unsafe { std::slice::from_raw_parts(data, length) };
 
-    node_fs::a_sync::read_file(path.as_ref(), options.into(), cb)
+    if let Err(err) = node_fs::sync::write_file_with_bytes_from_path(
+        path.as_ref(), data, options.into(),
+    )
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_read_file_with_fd(fd: i32, options: ReadFileOptions, callback: *const AsyncFsEncodingClosure) {
-    if callback.is_null() {
+pub extern "C" fn fs_write_file_with_buffer_from_path_sync(
+    path: *const c_char,
+    data: *const Buffer,
+    options: WriteFileOptions,
+) {
+    if path.is_null() || data.is_null() {
         return;
     }
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
-
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(result.map(FsEncoding))
-            }
-        }))
-    );
+    let data = unsafe { &*data };
 
-    node_fs::a_sync::read_file_with_fd(fd, options.into(), cb)
+    if let Err(err) = node_fs::sync::write_file_with_buffer_from_path(
+        path.as_ref(), &data.0, options.into(),
+    )
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        update_last_error(err);
+    }
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_read_link(path: *const c_char, options: ReadLinkOptions, callback: *const AsyncFsEncodingClosure) {
-    if path.is_null() || callback.is_null() {
-        return;
-    }
+pub extern "C" fn fs_writev_sync(fd: i32, buffers: *mut *mut Buffer, length: usize, position: i64) -> usize {
+    let buffers = unsafe { std::slice::from_raw_parts_mut(buffers, length) };
+    let buffers = buffers.iter().map(|buffer| {
+        let buffer = unsafe { &mut **buffer };
+        buffer.0.clone()
+    }).collect::<Vec<node_buffer::Buffer>>();
+    node_fs::sync::writev(
+        fd, buffers, position.try_into().unwrap(),
+    )
+        .map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+        update_last_error(err);
+        0
+    })
+}
 
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
 
-    let callback = unsafe { &*callback };
+#[no_mangle]
+pub extern "C" fn fs_writev_sync_slice(fd: i32, buffers: *const *const u8, buffers_buffers: *const usize, length: usize, position: i64) -> usize {
+    let buffer_length = unsafe { std::slice::from_raw_parts(buffers_buffers, length) };
+    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
 
-    let callback = Arc::clone(&callback.0);
-    let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
-            if error.is_some() {
-                callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
-            } else {
-                callback.on_success(result.map(FsEncoding))
-            }
-        }))
-    );
+    let buffers = buffers.iter().zip(buffer_length.iter())
+        .map(|(buffer, len)| {
+            unsafe { std::slice::from_raw_parts(*buffer, *len) }
+        }).collect::<Vec<_>>();
 
-    node_fs::a_sync::read_link(path.as_ref(), options.into(), cb)
+
+    node_fs::sync::writev_slice(
+        fd, buffers.as_slice(), position.try_into().unwrap(),
+    )
+        .map_err(|e| node_core::error::error_from_io_error(e)).unwrap_or_else(|err| {
+        update_last_error(err);
+        0
+    })
 }
 
+// async
+
 #[no_mangle]
-pub extern "C" fn fs_async_readv(
-    fd: i32,
-    buffers: *const Buffer,
-    length: usize,
-    position: usize,
-    callback: *const AsyncUsizeClosure,
-) {
-    if buffers.is_null() || length == 0 || callback.is_null() {
+pub extern "C" fn fs_async_access(path: *const c_char, access: i32, callback: *const AsyncClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(result)
+                callback.on_success(None)
             }
         }))
     );
-
-    let buffers = buffers.into_iter().map(|buffer| buffer.0.clone()).collect::<Vec<node_buffer::Buffer>>();
-
-    node_fs::a_sync::readv(fd, buffers, position.try_into().unwrap(), cb)
+    node_fs::a_sync::access(path.as_ref(), access, cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_real_path(path: *const c_char, options: RealPathOptions, callback: *const AsyncStringClosure) {
-    if path.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_append_file_with_str(fd: i32, data: *const c_char, options: AppendFileOptions, callback: *const AsyncClosure) {
+    if data.is_null() || callback.is_null() {
         return;
     }
 
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
+    let data = unsafe { CStr::from_ptr(data) };
+    let data = data.to_string_lossy();
 
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result: Option<std::path::PathBuf>, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(result.map(|result| result.to_string_lossy().to_string()))
+                callback.on_success(None)
             }
         }))
     );
-
-    node_fs::a_sync::real_path(path.as_ref(), options.into(), cb)
+    node_fs::a_sync::append_file_with_str(fd, data.as_ref(), options.into(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_rename(old_path: *const c_char, new_path: *const c_char, callback: *const AsyncClosure) {
-    if old_path.is_null() || new_path.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_append_file_with_bytes(fd: i32, data: *const Buffer, options: AppendFileOptions, callback: *const AsyncClosure) {
+    if data.is_null() || callback.is_null() {
         return;
     }
 
-    let old_path = unsafe { CStr::from_ptr(old_path) };
-    let old_path = old_path.to_string_lossy();
-
-    let new_path = unsafe { CStr::from_ptr(new_path) };
-    let new_path = new_path.to_string_lossy();
+    let data = unsafe { &*data };
 
     let callback = unsafe { &*callback };
-
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
         node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
@@ -3363,22 +3779,26 @@ pub extern "C" fn fs_async_rename(old_path: *const c_char, new_path: *const c_ch
             }
         }))
     );
-    node_fs::a_sync::rename(old_path.as_ref(), new_path.as_ref(), cb)
+    node_fs::a_sync::append_file_with_bytes(fd, &data.0, options.into(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_rmdir(
+pub extern "C" fn fs_async_append_file_with_path_str(
     path: *const c_char,
-    options: RmDirOptions,
+    data: *const c_char,
+    options: AppendFileOptions,
     callback: *const AsyncClosure,
 ) {
-    if path.is_null() || callback.is_null() {
+    if path.is_null() || data.is_null() || callback.is_null() {
         return;
     }
 
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
+    let data = unsafe { CStr::from_ptr(data) };
+    let data = data.to_string_lossy();
+
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
@@ -3394,22 +3814,25 @@ pub extern "C" fn fs_async_rmdir(
             }
         }))
     );
-    node_fs::a_sync::rmdir(path.as_ref(), options.into(), cb)
+    node_fs::a_sync::append_file_with_path_str(path.as_ref(), data.as_ref(), options.into(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_rm(
+pub extern "C" fn fs_async_append_file_with_path_bytes(
     path: *const c_char,
-    options: RmOptions,
+    data: *const Buffer,
+    options: AppendFileOptions,
     callback: *const AsyncClosure,
 ) {
-    if path.is_null() || callback.is_null() {
+    if path.is_null() || data.is_null() || callback.is_null() {
         return;
     }
 
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
+    let data = unsafe { &*data };
+
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
@@ -3417,6 +3840,7 @@ pub extern "C" fn fs_async_rm(
         node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
@@ -3424,11 +3848,11 @@ pub extern "C" fn fs_async_rm(
             }
         }))
     );
-    node_fs::a_sync::rm(path.as_ref(), options.into(), cb)
+    node_fs::a_sync::append_file_with_path_bytes(path.as_ref(), &data.0, options.into(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_stat(path: *const c_char, throw_if_no_entry: bool, callback: *const AsyncFileStatClosure) {
+pub extern "C" fn fs_async_chmod(path: *const c_char, mode: u32, callback: *const AsyncClosure) {
     if path.is_null() || callback.is_null() {
         return;
     }
@@ -3440,36 +3864,29 @@ pub extern "C" fn fs_async_stat(path: *const c_char, throw_if_no_entry: bool, ca
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result: Option<node_fs::file_stat::FileStat>, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(result.map(|result| result.into()))
+                callback.on_success(None)
             }
         }))
     );
-    node_fs::a_sync::stat(path.as_ref(), throw_if_no_entry, cb)
+    node_fs::a_sync::chmod(path.as_ref(), mode, cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_symlink(target: *const c_char, path: *const c_char, type_: *const c_char, callback: *const AsyncClosure) {
-    if target.is_null() || path.is_null() || type_.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_chown(path: *const c_char, uid: u32, gid: u32, callback: *const AsyncClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let target = unsafe { CStr::from_ptr(target) };
-    let target = target.to_string_lossy();
-
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-
-    let type_ = unsafe { CStr::from_ptr(type_) };
-    let type_ = type_.to_string_lossy();
-
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
@@ -3485,18 +3902,16 @@ pub extern "C" fn fs_async_symlink(target: *const c_char, path: *const c_char, t
             }
         }))
     );
-    node_fs::a_sync::symlink(target.as_ref(), path.as_ref(), type_.as_ref(), cb)
+
+    node_fs::a_sync::chown(path.as_ref(), uid, gid, cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_truncate(path: *const c_char, len: u64, callback: *const AsyncClosure) {
-    if path.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_close(fd: i32, callback: *const AsyncClosure) {
+    if callback.is_null() {
         return;
     }
 
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
-
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
@@ -3512,17 +3927,20 @@ pub extern "C" fn fs_async_truncate(path: *const c_char, len: u64, callback: *co
             }
         }))
     );
-    node_fs::a_sync::truncate(path.as_ref(), len, cb)
+    node_fs::a_sync::close(fd, cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_unlink(path: *const c_char, callback: *const AsyncClosure) {
-    if path.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_copy_file(src: *const c_char, dest: *const c_char, flag: u32, callback: *const AsyncClosure) {
+    if src.is_null() || dest.is_null() || callback.is_null() {
         return;
     }
 
-    let path = unsafe { CStr::from_ptr(path) };
-    let path = path.to_string_lossy();
+    let src = unsafe { CStr::from_ptr(src) };
+    let src = src.to_string_lossy();
+
+    let dest = unsafe { CStr::from_ptr(dest) };
+    let dest = dest.to_string_lossy();
 
     let callback = unsafe { &*callback };
 
@@ -3539,50 +3957,41 @@ pub extern "C" fn fs_async_unlink(path: *const c_char, callback: *const AsyncClo
             }
         }))
     );
-    node_fs::a_sync::unlink(path.as_ref(), cb)
+    node_fs::a_sync::copy_file(src.as_ref(), dest.as_ref(), flag, cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_unwatch_file(filename: *const c_char) {
-    if filename.is_null() {
+pub extern "C" fn fs_async_cp(src: *const c_char, dest: *const c_char, flags: u32, callback: *const AsyncClosure) {
+    if src.is_null() || dest.is_null() || callback.is_null() {
         return;
     }
 
-    let filename = unsafe { CStr::from_ptr(filename) };
-    let filename = filename.to_string_lossy();
-
-    node_fs::a_sync::unwatch_file(filename.as_ref(), None)
-}
-
-#[no_mangle]
-pub extern "C" fn fs_async_unwatch_file_with_callback(filename: *const c_char, callback: *const AsyncFileWatchClosure) {
-    if filename.is_null() || callback.is_null() {
-        return;
-    }
+    let src = unsafe { CStr::from_ptr(src) };
+    let src = src.to_string_lossy();
 
-    let filename = unsafe { CStr::from_ptr(filename) };
-    let filename = filename.to_string_lossy();
+    let dest = unsafe { CStr::from_ptr(dest) };
+    let dest = dest.to_string_lossy();
 
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(event.map(FileWatchEvent))
+                callback.on_success(None)
             }
         }))
     );
-    node_fs::a_sync::unwatch_file(filename.as_ref(), Some(cb))
+    node_fs::a_sync::cp(src.as_ref(), dest.as_ref(), flags, cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_utimes(path: *const c_char, atime: i64, mtime: i64, callback: *const AsyncClosure) {
+pub extern "C" fn fs_async_exists(path: *const c_char, callback: *const AsyncBoolClosure) {
     if path.is_null() || callback.is_null() {
         return;
     }
@@ -3594,388 +4003,333 @@ pub extern "C" fn fs_async_utimes(path: *const c_char, atime: i64, mtime: i64, c
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(result)
             }
         }))
     );
-    node_fs::a_sync::utimes(path.as_ref(), atime.try_into().unwrap(), mtime.try_into().unwrap(), cb)
+    node_fs::a_sync::exists(path.as_ref(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_file_watcher_unref(filename: *const c_char, callback: *const AsyncFileWatchClosure) {
-    if filename.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_fchmod(fd: i32, mode: u16, callback: *const AsyncClosure) {
+    if callback.is_null() {
         return;
     }
 
-    let filename = unsafe { CStr::from_ptr(filename) };
-    let filename = filename.to_string_lossy();
-
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(event.map(FileWatchEvent))
+                callback.on_success(None)
             }
         }))
     );
-    node_fs::a_sync::file_watcher_unref(filename.as_ref(), cb);
+    node_fs::a_sync::fchmod(fd, mode, cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_file_watcher_ref(filename: *const c_char, callback: *const AsyncFileWatchClosure) {
-    if filename.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_fchown(fd: i32, uid: u32, gid: u32, callback: *const AsyncClosure) {
+    if callback.is_null() {
         return;
     }
 
-    let filename = unsafe { CStr::from_ptr(filename) };
-    let filename = filename.to_string_lossy();
-
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(event.map(FileWatchEvent))
+                callback.on_success(None)
             }
         }))
     );
-    node_fs::a_sync::file_watcher_ref(filename.as_ref(), cb)
+    node_fs::a_sync::fchown(fd, uid, gid, cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_watch(
-    filename: *const c_char,
-    persistent: bool,
-    recursive: bool,
-    encoding: FsEncodingType,
-    callback: *const AsyncWatchClosure,
-) {
-    if filename.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_fdatasync(fd: i32, callback: *const AsyncClosure) {
+    if callback.is_null() {
         return;
     }
 
-    let filename = unsafe { CStr::from_ptr(filename) };
-    let filename = filename.to_string_lossy();
-
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(event.map(WatchEvent))
+                callback.on_success(None)
             }
         }))
     );
-
-    node_fs::a_sync::watch(
-        filename.as_ref(), persistent, recursive, encoding.into(), cb,
-    )
+    node_fs::a_sync::fdatasync(fd, cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_watcher_unref(filename: *const c_char, callback: *const AsyncWatchClosure) {
-    if filename.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_fstat(fd: i32, callback: *const AsyncFileStatClosure) {
+    if callback.is_null() {
         return;
     }
 
-    let filename = unsafe { CStr::from_ptr(filename) };
-    let filename = filename.to_string_lossy();
-
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(event.map(WatchEvent))
+                callback.on_success(result.map(|stat: node_fs::file_stat::FileStat| stat.into()))
             }
         }))
     );
-
-    node_fs::a_sync::watcher_unref(filename.as_ref(), cb)
+    node_fs::a_sync::fstat(fd, cb);
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_watcher_ref(filename: *const c_char, callback: *const AsyncWatchClosure) {
-    if filename.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_fsync(fd: i32, callback: *const AsyncClosure) {
+    if callback.is_null() {
         return;
     }
 
-    let filename = unsafe { CStr::from_ptr(filename) };
-    let filename = filename.to_string_lossy();
-
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(event.map(WatchEvent))
+                callback.on_success(None)
             }
         }))
     );
-    node_fs::a_sync::watcher_ref(filename.as_ref(), cb)
+    node_fs::a_sync::fdatasync(fd, cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_watcher_close(
-    filename: *const c_char,
-    callback: *const AsyncWatchClosure,
-    on_close: *const AsyncClosure,
-) {
-    if filename.is_null() || callback.is_null() || on_close.is_null() {
+pub extern "C" fn fs_async_ftruncate(fd: i32, len: i64, callback: *const AsyncClosure) {
+    if callback.is_null() {
         return;
     }
 
-    let filename = unsafe { CStr::from_ptr(filename) };
-    let filename = filename.to_string_lossy();
-
     let callback = unsafe { &*callback };
 
-    let on_close = unsafe { &*on_close };
-
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(event.map(WatchEvent))
+                callback.on_success(None)
             }
         }))
     );
+    node_fs::a_sync::ftruncate(fd, len.try_into().unwrap(), cb)
+}
 
+#[no_mangle]
+pub extern "C" fn fs_async_futimes(fd: i32, atime: i64, mtime: i64, callback: *const AsyncClosure) {
+    if callback.is_null() {
+        return;
+    }
 
-    let on_close = Arc::clone(&on_close.0);
-    let on_close_cb = Arc::new(
+    let callback = unsafe { &*callback };
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
         node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
-                on_close.on_error(error
+                callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                on_close.on_success(None)
+                callback.on_success(None)
             }
         }))
     );
-
-
-    node_fs::a_sync::watcher_close(filename.as_ref(), cb, on_close_cb)
+    node_fs::a_sync::futimes(fd, atime.try_into().unwrap(), mtime.try_into().unwrap(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_watch_file(
-    filename: *const c_char,
-    bigint: bool,
-    persistent: bool,
-    interval: u64,
-    encoding: FsEncodingType,
-    callback: *const AsyncFileWatchClosure,
-) {
-    if filename.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_lchmod(path: *const c_char, mode: u16, callback: *const AsyncClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let filename = unsafe { CStr::from_ptr(filename) };
-    let filename = filename.to_string_lossy();
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
     let callback = unsafe { &*callback };
-
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(event.map(FileWatchEvent))
+                callback.on_success(None)
             }
         }))
     );
-
-    node_fs::a_sync::watch_file(filename.as_ref(), bigint, persistent, interval.try_into().unwrap(), encoding.into(), cb)
+    node_fs::a_sync::lchmod(path.as_ref(), mode, cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_write(
-    fd: i32,
-    buffer: *const Buffer,
-    options: WriteOptions,
-    callback: *const AsyncUsizeClosure,
-) {
-    if buffer.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_lchown(path: *const c_char, uid: u32, gid: u32, callback: *const AsyncClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let buffer = unsafe { &*buffer };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(wrote)
+                callback.on_success(None)
             }
         }))
     );
-
-    node_fs::a_sync::write(fd, &buffer.0, options.into(), cb)
+    node_fs::a_sync::lchown(path.as_ref(), uid, gid, cb)
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_async_write_slice(
-    fd: i32,
-    buffer: *const u8,
-    length: usize,
-    options: WriteOptions,
-    callback: *const AsyncUsizeClosure,
-) {
-    if buffer.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_lutimes(path: *const c_char, atime: i64, mtime: i64, callback: *const AsyncClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let buffer = unsafe { node_buffer::Buffer::from_reference(buffer as _, length) };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
+
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
-
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(wrote)
+                callback.on_success(None)
             }
         }))
     );
-
-    node_fs::a_sync::write(fd, &buffer, options.into(), cb);
+    node_fs::a_sync::lutimes(path.as_ref(), atime.try_into().unwrap(), mtime.try_into().unwrap(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_write_string(
-    fd: i32,
-    string: *const c_char,
-    encoding: StringEncoding,
-    position: isize,
-    callback: *const AsyncUsizeClosure,
-) {
-    if string.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_link(existing_path: *const c_char, new_path: *const c_char, callback: *const AsyncClosure) {
+    if existing_path.is_null() || new_path.is_null() || callback.is_null() {
         return;
     }
 
-    let string = unsafe { CStr::from_ptr(string) };
-    let string = string.to_string_lossy();
+    let existing_path = unsafe { CStr::from_ptr(existing_path) };
+    let existing_path = existing_path.to_string_lossy();
+
+    let new_path = unsafe { CStr::from_ptr(new_path) };
+    let new_path = new_path.to_string_lossy();
 
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(wrote)
+                callback.on_success(None)
             }
         }))
     );
-    node_fs::a_sync::write_string(fd, string.as_ref(), encoding.into(), position, cb)
+    node_fs::a_sync::link(existing_path.as_ref(), new_path.as_ref(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_write_file_with_str(
-    fd: i32,
-    data: *const c_char,
-    options: WriteFileOptions,
-    callback: *const AsyncClosure,
-) {
-    if data.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_lstat(path: *const c_char, callback: *const AsyncFileStatClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let data = unsafe { CStr::from_ptr(data) };
-    let data = data.to_string_lossy();
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(result.map(|stat: node_fs::file_stat::FileStat| stat.into()))
             }
         }))
     );
-    node_fs::a_sync::write_file_with_str(fd, data.as_ref(), options.into(), cb)
+
+    node_fs::a_sync::lstat(path.as_ref(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_write_file_with_bytes(fd: i32, data: *const Buffer, options: WriteFileOptions, callback: *const AsyncClosure) {
-    if data.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_mkdir(path: *const c_char, options: MkDirOptions, callback: *const AsyncClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let data = unsafe { &*data };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
     let callback = unsafe { &*callback };
 
@@ -3992,224 +4346,196 @@ pub extern "C" fn fs_async_write_file_with_bytes(fd: i32, data: *const Buffer, o
             }
         }))
     );
-    node_fs::a_sync::write_file_with_bytes(fd, &data.0, options.into(), cb)
+    node_fs::a_sync::mkdir(path.as_ref(), options.into(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_write_file_with_bytes_raw(fd: i32, data: *const u8, length: usize, options: WriteFileOptions, callback: *const AsyncClosure) {
-    if data.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_mkdtemp(prefix: *const c_char, options: MkdTempOptions, callback: *const AsyncStringClosure) {
+    if prefix.is_null() || callback.is_null() {
         return;
     }
 
-    let data = unsafe { node_buffer::Buffer::from_reference(data as _, length) };
+    let prefix = unsafe { CStr::from_ptr(prefix) };
+    let prefix = prefix.to_string_lossy();
 
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |path, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(path.map(|path: std::path::PathBuf| path.to_string_lossy().to_string()))
             }
         }))
     );
-    node_fs::a_sync::write_file_with_bytes(fd, &data, options.into(), cb)
+    node_fs::a_sync::mkdtemp(prefix.as_ref(), options.into(), cb);
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_write_file_with_str_from_path(
-    path: *const c_char,
-    data: *const c_char,
-    options: WriteFileOptions,
-    callback: *const AsyncClosure,
-) {
-    if path.is_null() || data.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_open(path: *const c_char, flag: i32, mode: i32, callback: *const AsyncI32Closure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let data = unsafe { CStr::from_ptr(data) };
-    let data = data.to_string_lossy();
-
     let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error: Option<std::io::Error>| {
             if error.is_some() {
-                callback.on_error(error
+                let error = error
                     .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
+                    .map(NodeError);
+
+                callback.on_error(error)
             } else {
-                callback.on_success(None)
+                callback.on_success(result)
             }
         }))
     );
-    node_fs::a_sync::write_file_with_str_from_path(
-        path.as_ref(), data.as_ref(), options.into(), cb,
-    )
+    node_fs::a_sync::open(path.as_ref(), flag, mode, cb);
 }
 
+
 #[no_mangle]
-pub extern "C" fn fs_async_write_file_with_bytes_from_path(
-    path: *const c_char,
-    data: *const Buffer,
-    options: WriteFileOptions,
-    callback: *const AsyncClosure,
-) {
-    if path.is_null() || data.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_open_handle(path: *const c_char, flag: i32, mode: i32, callback: *const AsyncFileHandleClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let data = unsafe { &*data };
-
     let callback = unsafe { &*callback };
 
-
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error: Option<std::io::Error>| {
             if error.is_some() {
-                callback.on_error(error
+                let error = error
                     .map(node_core::error::error_from_io_error)
-                    .map(NodeError)
-                )
+                    .map(NodeError);
+
+                callback.on_error(error)
             } else {
-                callback.on_success(None)
+                callback.on_success(result.map(FileHandle))
             }
         }))
     );
-    node_fs::a_sync::write_file_with_bytes_from_path(path.as_ref(), &data.0, options.into(), cb)
+
+    node_fs::file_handle::FileHandle::new_async(path.as_ref(), flag, mode, cb);
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_write_file_with_bytes_from_path_raw(
-    path: *const c_char,
-    data: *const u8,
-    length: usize,
-    options: WriteFileOptions,
-    callback: *const AsyncClosure,
-) {
-    if path.is_null() || data.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_opendir(path: *const c_char, options: OpenDirOptions, callback: *const AsyncFileDirClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
     let path = unsafe { CStr::from_ptr(path) };
     let path = path.to_string_lossy();
 
-    let data = unsafe { node_buffer::Buffer::from_reference(data as _, length) };
-
     let callback = unsafe { &*callback };
 
-
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(result.map(FileDir))
             }
         }))
     );
-    node_fs::a_sync::write_file_with_bytes_from_path(path.as_ref(), &data, options.into(), cb)
+    node_fs::a_sync::opendir(path.as_ref(), options.into(), cb);
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_writev(
+pub extern "C" fn fs_async_read(
     fd: i32,
-    buffers: *const Buffer,
+    buffer: *mut Buffer,
+    offset: usize,
     length: usize,
-    position: usize,
+    position: isize,
     callback: *const AsyncUsizeClosure,
 ) {
-    if buffers.is_null() || length == 0 || callback.is_null() {
+    if buffer.is_null() || callback.is_null() {
         return;
     }
 
-    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
+    let buffer = unsafe { &mut *buffer };
 
     let callback = unsafe { &*callback };
 
-
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(wrote)
+                callback.on_success(result)
             }
         }))
     );
 
-    let buffers = buffers.iter().map(|buffer| buffer.0.clone())
-        .collect::<Vec<node_buffer::Buffer>>();
-
-    node_fs::a_sync::writev(fd, buffers, position.try_into().unwrap(), cb)
+    node_fs::a_sync::read(fd, &mut buffer.0, offset, length, position, cb)
 }
 
 
 #[no_mangle]
-pub extern "C" fn fs_async_writev_slice(fd: c_int, buffers: *const *mut u8, buffers_buffers: *const usize, length: usize, position: i64, callback: *const AsyncUsizeClosure) {
-    if buffers.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_read_bytes(
+    fd: i32,
+    buffer: *mut u8,
+    buffer_length: usize,
+    offset: usize,
+    length: usize,
+    position: isize,
+    callback: *const AsyncUsizeClosure,
+) {
+    if buffer.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let bytes = unsafe { std::slice::from_raw_parts_mut(buffer, buffer_length) };
 
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |read, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(read)
+                callback.on_success(result)
             }
         }))
     );
 
-    let buffer_length = unsafe { std::slice::from_raw_parts(buffers_buffers, length) };
-    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
-
-    let buffers = buffers.iter().zip(buffer_length.iter())
-        .map(|(buffer, len)| {
-            unsafe { node_buffer::Buffer::from_reference(*buffer, *len) }
-        }).collect::<Vec<node_buffer::Buffer>>();
-
-    node_fs::a_sync::writev(fd, buffers, position.try_into().unwrap(), cb);
+    node_fs::a_sync::read_bytes(fd, bytes, offset, length, position, cb)
 }
 
-
-// fs filehandle
-
 #[no_mangle]
-pub extern "C" fn fs_handle_new_async(
+pub extern "C" fn fs_async_readdir(
     path: *const c_char,
-    flags: i32,
-    mode: i32,
-    callback: *const AsyncFileHandleClosure,
+    options: ReaddirOptions,
+    callback: *const AsyncReaddirClosure,
 ) {
     if path.is_null() || callback.is_null() {
         return;
@@ -4222,498 +4548,414 @@ pub extern "C" fn fs_handle_new_async(
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |handle, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result: Option<Vec<node_fs::sync::ReaddirResult>>, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(handle
-                    .map(FileHandle))
+                callback.on_success(result.map(|result| result.into_iter()
+                    .map(|value| ReaddirResult(value))
+                    .collect::<Vec<ReaddirResult>>()))
             }
         }))
     );
 
-    node_fs::file_handle::FileHandle::new_async(
-        path.as_ref(), flags, mode, cb,
-    )
+    node_fs::a_sync::readdir(path.as_ref(), options.into(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_handle_append_file_with_str(
-    handle: *mut FileHandle,
-    data: *const c_char,
-    options: AppendFileOptions,
-    callback: *const AsyncClosure,
-) {
-    if handle.is_null() || data.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_read_file(path: *const c_char, options: ReadFileOptions, callback: *const AsyncFsEncodingClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let data = unsafe { CStr::from_ptr(data) };
-    let data = data.to_string_lossy();
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
     let callback = unsafe { &*callback };
 
-    let handle = unsafe { &mut *handle };
-
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(result.map(FsEncoding))
             }
         }))
     );
 
-    handle.0.append_file_with_str(
-        data.as_ref(), options.into(), cb,
-    )
+    node_fs::a_sync::read_file(path.as_ref(), options.into(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_handle_append_file_with_bytes(
-    handle: *mut FileHandle,
-    data: *const Buffer,
-    options: AppendFileOptions,
-    callback: *const AsyncClosure,
-) {
-    if handle.is_null() || data.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_read_file_with_fd(fd: i32, options: ReadFileOptions, callback: *const AsyncFsEncodingClosure) {
+    if callback.is_null() {
         return;
     }
 
-    let data = unsafe { &*data };
-
     let callback = unsafe { &*callback };
 
-    let handle = unsafe { &mut *handle };
-
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(result.map(FsEncoding))
             }
         }))
     );
 
-    handle.0.append_file_with_bytes(
-        &data.0, options.into(), cb,
-    )
+    node_fs::a_sync::read_file_with_fd(fd, options.into(), cb)
 }
 
+/// `fs.readFile(path, { signal })`, the callback receives an AbortError once `signal` is
+/// aborted.
 #[no_mangle]
-pub extern "C" fn fs_handle_chmod(handle: *mut FileHandle, mode: u16, callback: *const AsyncClosure) {
-    if handle.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_read_file_with_signal(path: *const c_char, options: ReadFileOptions, signal: *const AbortSignal, callback: *const AsyncFsEncodingClosure) {
+    if path.is_null() || signal.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
+    let signal = unsafe { &*signal };
 
-    let handle = unsafe { &mut *handle };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(result.map(FsEncoding))
             }
         }))
     );
 
-    handle.0.chmod(
-        mode, cb,
-    )
+    node_fs::a_sync::read_file_with_signal(path.as_ref(), options.into(), signal.0.clone(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_handle_chown(handle: *mut FileHandle, uid: u32, gid: u32, callback: *const AsyncClosure) {
-    if handle.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_read_file_with_fd_and_signal(fd: i32, options: ReadFileOptions, signal: *const AbortSignal, callback: *const AsyncFsEncodingClosure) {
+    if signal.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let signal = unsafe { &*signal };
 
-    let handle = unsafe { &mut *handle };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(result.map(FsEncoding))
             }
         }))
     );
 
-    handle.0.chown(
-        uid, gid, cb,
-    )
+    node_fs::a_sync::read_file_with_fd_and_signal(fd, options.into(), signal.0.clone(), cb)
 }
 
-// consumes the handle, do not use after closing
 #[no_mangle]
-pub extern "C" fn fs_handle_close(handle: *mut FileHandle, callback: *const AsyncClosure) {
-    if handle.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_read_link(path: *const c_char, options: ReadLinkOptions, callback: *const AsyncFsEncodingClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-    let handle = unsafe { Box::from_raw(handle) };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(result.map(FsEncoding))
             }
         }))
     );
 
-
-    handle.0.close(
-        cb
-    )
+    node_fs::a_sync::read_link(path.as_ref(), options.into(), cb)
 }
 
-// TODO
-// #[no_mangle]
-// pub extern "C" fn fs_handle_create_read_stream() {}
-
-// TODO
-// #[no_mangle]
-// pub extern "C" fn fs_handle_create_write_stream() {}
-
 #[no_mangle]
-pub extern "C" fn fs_handle_datasync(handle: *mut FileHandle, callback: *const AsyncClosure) {
-    if handle.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_readv(
+    fd: i32,
+    buffers: *const Buffer,
+    length: usize,
+    position: usize,
+    callback: *const AsyncUsizeClosure,
+) {
+    if buffers.is_null() || length == 0 || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
 
-    let handle = unsafe { &mut *handle };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(result)
             }
         }))
     );
 
-    handle.0.datasync(
-        cb
-    )
+    let buffers = buffers.into_iter().map(|buffer| buffer.0.clone()).collect::<Vec<node_buffer::Buffer>>();
+
+    node_fs::a_sync::readv(fd, buffers, position.try_into().unwrap(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_handle_fd(handle: *mut FileHandle) -> i32 {
-    if handle.is_null() {
-        return 0;
-    }
-
-    let handle = unsafe { &mut *handle };
-
-    handle.0.fd()
-}
-
-#[no_mangle]
-pub extern "C" fn fs_handle_read(
-    handle: *mut FileHandle,
-    buffer: *mut Buffer,
-    offset: usize,
-    length: usize,
-    position: isize,
-    callback: *const AsyncUsizeClosure,
-) {
-    if handle.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_real_path(path: *const c_char, options: RealPathOptions, callback: *const AsyncStringClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
-
-    let handle = unsafe { &mut *handle };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-    let buffer = unsafe { &mut *buffer };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |read, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result: Option<std::path::PathBuf>, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(read)
+                callback.on_success(result.map(|result| result.to_string_lossy().to_string()))
             }
         }))
     );
 
-    handle.0.read(
-        &mut buffer.0, offset, length, position, cb,
-    )
+    node_fs::a_sync::real_path(path.as_ref(), options.into(), cb)
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_handle_read_bytes(
-    handle: *mut FileHandle,
-    buffer: *mut u8,
-    buffer_length: usize,
-    offset: usize,
-    length: usize,
-    position: isize,
-    callback: *const AsyncUsizeClosure,
-) {
-    if handle.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_rename(old_path: *const c_char, new_path: *const c_char, callback: *const AsyncClosure) {
+    if old_path.is_null() || new_path.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let old_path = unsafe { CStr::from_ptr(old_path) };
+    let old_path = old_path.to_string_lossy();
 
-    let handle = unsafe { &mut *handle };
+    let new_path = unsafe { CStr::from_ptr(new_path) };
+    let new_path = new_path.to_string_lossy();
 
-    let buffer = unsafe { std::slice::from_raw_parts_mut(buffer, buffer_length) };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |read, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(read)
+                callback.on_success(None)
             }
         }))
     );
-
-    handle.0.read_bytes(
-        buffer, offset, length, position, cb,
-    )
+    node_fs::a_sync::rename(old_path.as_ref(), new_path.as_ref(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_handle_read_file(
-    handle: *mut FileHandle,
-    options: ReadFileOptions,
-    callback: *const AsyncFsEncodingClosure,
+pub extern "C" fn fs_async_rmdir(
+    path: *const c_char,
+    options: RmDirOptions,
+    callback: *const AsyncClosure,
 ) {
-    if handle.is_null() || callback.is_null() {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-    let handle = unsafe { &mut *handle };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(result.map(FsEncoding))
+                callback.on_success(None)
             }
         }))
     );
-
-    handle.0.read_file(
-        options.into(), cb,
-    )
+    node_fs::a_sync::rmdir(path.as_ref(), options.into(), cb)
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_async_readv_slice(fd: c_int, buffers: *const *mut u8, buffers_buffers: *const usize, length: usize, position: i64, callback: *const AsyncUsizeClosure) {
-    if buffers.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_rm(
+    path: *const c_char,
+    options: RmOptions,
+    callback: *const AsyncClosure,
+) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |read, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
-                    .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(read)
+                callback.on_success(None)
             }
         }))
     );
-
-    let buffer_length = unsafe { std::slice::from_raw_parts(buffers_buffers, length) };
-    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
-
-    let buffers = buffers.iter().zip(buffer_length.iter())
-        .map(|(buffer, len)| {
-            unsafe { node_buffer::Buffer::from_reference(*buffer, *len) }
-        }).collect::<Vec<node_buffer::Buffer>>();
-
-    node_fs::a_sync::readv(fd, buffers, position.try_into().unwrap(), cb);
+    node_fs::a_sync::rm(path.as_ref(), options.into(), cb)
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_handle_readv_slice(handle: *mut FileHandle, buffers: *const *mut u8, buffers_buffers: *const usize, length: usize, position: i64, callback: *const AsyncUsizeClosure) {
-    if handle.is_null() || buffers.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_stat(path: *const c_char, throw_if_no_entry: bool, callback: *const AsyncFileStatClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
-
-    let handle = unsafe { &mut *handle };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |read, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result: Option<node_fs::file_stat::FileStat>, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(read)
+                callback.on_success(result.map(|result| result.into()))
             }
         }))
     );
-
-    let buffer_length = unsafe { std::slice::from_raw_parts(buffers_buffers, length) };
-    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
-
-    let buffers = buffers.iter().zip(buffer_length.iter())
-        .map(|(buffer, len)| {
-            unsafe { node_buffer::Buffer::from_reference(*buffer, *len) }
-        }).collect::<Vec<node_buffer::Buffer>>();
-
-    handle.0.readv(
-        buffers, position.try_into().unwrap(), cb,
-    )
+    node_fs::a_sync::stat(path.as_ref(), throw_if_no_entry, cb)
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_handle_readv(
-    handle: *mut FileHandle,
-    buffers: *const Buffer,
-    length: usize,
-    position: usize,
-    callback: *const AsyncUsizeClosure,
-) {
-    if handle.is_null() || buffers.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_symlink(target: *const c_char, path: *const c_char, type_: *const c_char, callback: *const AsyncClosure) {
+    if target.is_null() || path.is_null() || type_.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let target = unsafe { CStr::from_ptr(target) };
+    let target = target.to_string_lossy();
 
-    let handle = unsafe { &mut *handle };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
+
+    let type_ = unsafe { CStr::from_ptr(type_) };
+    let type_ = type_.to_string_lossy();
+
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |read, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(read)
+                callback.on_success(None)
             }
         }))
     );
-
-    let buffers = buffers.iter().map(|buffer| buffer.0.clone())
-        .collect::<Vec<node_buffer::Buffer>>();
-
-    handle.0.readv(
-        buffers, position.try_into().unwrap(), cb,
-    )
+    node_fs::a_sync::symlink(target.as_ref(), path.as_ref(), type_.as_ref(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_handle_stat(handle: *mut FileHandle, callback: *const AsyncFileStatClosure) {
-    if handle.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_truncate(path: *const c_char, len: u64, callback: *const AsyncClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-    let handle = unsafe { &mut *handle };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |stat: Option<node_fs::file_stat::FileStat>, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(stat.map(|stat| stat.into()))
+                callback.on_success(None)
             }
         }))
     );
-
-    handle.0.stat(
-        cb
-    )
+    node_fs::a_sync::truncate(path.as_ref(), len, cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_handle_sync(handle: *mut FileHandle, callback: *const AsyncClosure) {
-    if handle.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_unlink(path: *const c_char, callback: *const AsyncClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-    let handle = unsafe { &mut *handle };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
@@ -4728,55 +4970,58 @@ pub extern "C" fn fs_handle_sync(handle: *mut FileHandle, callback: *const Async
             }
         }))
     );
+    node_fs::a_sync::unlink(path.as_ref(), cb)
+}
 
-    handle.0.sync(
-        cb
-    )
+#[no_mangle]
+pub extern "C" fn fs_async_unwatch_file(filename: *const c_char) {
+    if filename.is_null() {
+        return;
+    }
+
+    let filename = unsafe { CStr::from_ptr(filename) };
+    let filename = filename.to_string_lossy();
+
+    node_fs::a_sync::unwatch_file(filename.as_ref(), None)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_handle_truncate(handle: *mut FileHandle, len: usize, callback: *const AsyncClosure) {
-    if handle.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_unwatch_file_with_callback(filename: *const c_char, callback: *const AsyncFileWatchClosure) {
+    if filename.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let filename = unsafe { CStr::from_ptr(filename) };
+    let filename = filename.to_string_lossy();
 
-    let handle = unsafe { &mut *handle };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(event.map(FileWatchEvent))
             }
         }))
     );
-
-    handle.0.truncate(
-        len.try_into().unwrap(), cb,
-    )
+    node_fs::a_sync::unwatch_file(filename.as_ref(), Some(cb))
 }
 
 #[no_mangle]
-pub extern "C" fn fs_handle_utimes(
-    handle: *mut FileHandle,
-    atime: usize,
-    mtime: usize,
-    callback: *const AsyncClosure,
-) {
-    if handle.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_utimes(path: *const c_char, atime: i64, mtime: i64, callback: *const AsyncClosure) {
+    if path.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-    let handle = unsafe { &mut *handle };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
@@ -4791,305 +5036,253 @@ pub extern "C" fn fs_handle_utimes(
             }
         }))
     );
-
-    handle.0.utimes(
-        atime.try_into().unwrap(), mtime.try_into().unwrap(), cb,
-    )
+    node_fs::a_sync::utimes(path.as_ref(), atime.try_into().unwrap(), mtime.try_into().unwrap(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_handle_write(
-    handle: *mut FileHandle,
-    buffer: *const Buffer,
-    options: WriteOptions,
-    callback: *const AsyncUsizeClosure,
-) {
-    if handle.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_file_watcher_unref(filename: *const c_char, callback: *const AsyncFileWatchClosure) {
+    if filename.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
-
-    let handle = unsafe { &mut *handle };
+    let filename = unsafe { CStr::from_ptr(filename) };
+    let filename = filename.to_string_lossy();
 
-    let buffer = unsafe { &*buffer };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(wrote)
+                callback.on_success(event.map(FileWatchEvent))
             }
         }))
     );
-
-    handle.0.write(
-        &buffer.0, options.into(), cb,
-    )
+    node_fs::a_sync::file_watcher_unref(filename.as_ref(), cb);
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_handle_write_bytes(
-    handle: *mut FileHandle,
-    buffer: *const u8,
-    length: usize,
-    options: WriteOptions,
-    callback: *const AsyncUsizeClosure,
-) {
-    if handle.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_file_watcher_ref(filename: *const c_char, callback: *const AsyncFileWatchClosure) {
+    if filename.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
-
-    let handle = unsafe { &mut *handle };
+    let filename = unsafe { CStr::from_ptr(filename) };
+    let filename = filename.to_string_lossy();
 
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(wrote)
+                callback.on_success(event.map(FileWatchEvent))
             }
         }))
     );
-
-    let buffer = unsafe { node_buffer::Buffer::from_reference(buffer as _, length) };
-
-    handle.0.write(
-        &buffer, options.into(), cb,
-    )
+    node_fs::a_sync::file_watcher_ref(filename.as_ref(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_handle_write_string(
-    handle: *mut FileHandle,
-    data: *const c_char,
-    encoding: StringEncoding,
-    position: isize,
-    callback: *const AsyncUsizeClosure,
+pub extern "C" fn fs_async_watch(
+    filename: *const c_char,
+    persistent: bool,
+    recursive: bool,
+    encoding: FsEncodingType,
+    callback: *const AsyncWatchClosure,
 ) {
-    if handle.is_null() || data.is_null() || callback.is_null() {
+    if filename.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
-
-    let handle = unsafe { &mut *handle };
+    let filename = unsafe { CStr::from_ptr(filename) };
+    let filename = filename.to_string_lossy();
 
-    let data = unsafe { CStr::from_ptr(data) };
-    let data = data.to_string_lossy();
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(wrote)
+                callback.on_success(event.map(WatchEvent))
             }
         }))
     );
 
-    handle.0.write_string(
-        data.as_ref(), encoding.into(), position, cb,
+    node_fs::a_sync::watch(
+        filename.as_ref(), persistent, recursive, encoding.into(), cb,
     )
 }
 
 #[no_mangle]
-pub extern "C" fn fs_handle_write_file_with_str(
-    handle: *mut FileHandle,
-    data: *const c_char,
-    options: WriteFileOptions,
-    callback: *const AsyncClosure,
-) {
-    if handle.is_null() || data.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_watcher_unref(filename: *const c_char, callback: *const AsyncWatchClosure) {
+    if filename.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
-
-    let handle = unsafe { &mut *handle };
+    let filename = unsafe { CStr::from_ptr(filename) };
+    let filename = filename.to_string_lossy();
 
-    let data = unsafe { CStr::from_ptr(data) };
-    let data = data.to_string_lossy();
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(event.map(WatchEvent))
             }
         }))
     );
 
-    handle.0.write_file_with_str(
-        data.as_ref(), options.into(), cb,
-    )
+    node_fs::a_sync::watcher_unref(filename.as_ref(), cb)
 }
 
 #[no_mangle]
-pub extern "C" fn fs_handle_write_file_with_bytes(
-    handle: *mut FileHandle,
-    data: *const Buffer,
-    options: WriteFileOptions,
-    callback: *const AsyncClosure,
-) {
-    if handle.is_null() || data.is_null() || callback.is_null() {
+pub extern "C" fn fs_async_watcher_ref(filename: *const c_char, callback: *const AsyncWatchClosure) {
+    if filename.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
-
-    let handle = unsafe { &mut *handle };
+    let filename = unsafe { CStr::from_ptr(filename) };
+    let filename = filename.to_string_lossy();
 
-    let data = unsafe { &*data };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(event.map(WatchEvent))
             }
         }))
     );
-
-    handle.0.write_file_with_bytes(
-        &data.0, options.into(), cb,
-    )
+    node_fs::a_sync::watcher_ref(filename.as_ref(), cb)
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_handle_write_file_with_bytes_slice(
-    handle: *mut FileHandle,
-    data: *const u8,
-    length: usize,
-    options: WriteFileOptions,
-    callback: *const AsyncClosure,
+pub extern "C" fn fs_async_watcher_close(
+    filename: *const c_char,
+    callback: *const AsyncWatchClosure,
+    on_close: *const AsyncClosure,
 ) {
-    if handle.is_null() || data.is_null() || callback.is_null() {
+    if filename.is_null() || callback.is_null() || on_close.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
+    let filename = unsafe { CStr::from_ptr(filename) };
+    let filename = filename.to_string_lossy();
 
-    let handle = unsafe { &mut *handle };
+    let callback = unsafe { &*callback };
 
+    let on_close = unsafe { &*on_close };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(None)
+                callback.on_success(event.map(WatchEvent))
             }
         }))
     );
 
-    let buffer = unsafe { node_buffer::Buffer::from_reference(data as _, length) };
-    handle.0.write_file_with_bytes(
-        &buffer, options.into(), cb,
-    )
-}
+
+    let on_close = Arc::clone(&on_close.0);
+    let on_close_cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                on_close.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                on_close.on_success(None)
+            }
+        }))
+    );
 
 
+    node_fs::a_sync::watcher_close(filename.as_ref(), cb, on_close_cb)
+}
+
 #[no_mangle]
-pub extern "C" fn fs_handle_writev(
-    handle: *mut FileHandle,
-    buffers: *const Buffer,
-    length: usize,
-    position: usize,
-    callback: *const AsyncUsizeClosure,
+pub extern "C" fn fs_async_watch_file(
+    filename: *const c_char,
+    bigint: bool,
+    persistent: bool,
+    interval: u64,
+    encoding: FsEncodingType,
+    callback: *const AsyncFileWatchClosure,
 ) {
-    if handle.is_null() || buffers.is_null() || length == 0 || callback.is_null() {
+    if filename.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
-
-    let handle = unsafe { &mut *handle };
+    let filename = unsafe { CStr::from_ptr(filename) };
+    let filename = filename.to_string_lossy();
 
-    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
-        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |event, error| {
             if error.is_some() {
                 callback.on_error(error
                     .map(node_core::error::error_from_io_error)
                     .map(NodeError)
                 )
             } else {
-                callback.on_success(wrote)
+                callback.on_success(event.map(FileWatchEvent))
             }
         }))
     );
 
-    let buffers = buffers.iter().map(|buffer| buffer.0.clone())
-        .collect::<Vec<node_buffer::Buffer>>();
-
-    handle.0.writev(
-        buffers, position.try_into().unwrap(), cb,
-    )
+    node_fs::a_sync::watch_file(filename.as_ref(), bigint, persistent, interval.try_into().unwrap(), encoding.into(), cb)
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_handle_writev_slice(
-    handle: *mut FileHandle,
-    buffers: *const *const u8,
-    buffers_buffers: *const usize,
-    length: usize,
-    position: usize,
+pub extern "C" fn fs_async_write(
+    fd: i32,
+    buffer: *const Buffer,
+    options: WriteOptions,
     callback: *const AsyncUsizeClosure,
 ) {
-    if handle.is_null() || buffers.is_null() || length == 0 || callback.is_null() {
+    if buffer.is_null() || callback.is_null() {
         return;
     }
 
-    let callback = unsafe { &*callback };
-
-    let handle = unsafe { &mut *handle };
-
-
-    let buffer_length = unsafe { std::slice::from_raw_parts(buffers_buffers, length) };
-    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
-
-
-    let buffers = buffers.iter().zip(buffer_length.iter())
-        .map(|(buffer, len)| {
-            unsafe { node_buffer::Buffer::from_reference((*buffer) as *mut _, length) }
-        }).collect::<Vec<_>>();
+    let buffer = unsafe { &*buffer };
 
+    let callback = unsafe { &*callback };
 
     let callback = Arc::clone(&callback.0);
     let cb = Arc::new(
@@ -5105,198 +5298,1436 @@ pub extern "C" fn fs_handle_writev_slice(
         }))
     );
 
-    handle.0.writev(
-        buffers, position.try_into().unwrap(), cb,
-    )
+    node_fs::a_sync::write(fd, &buffer.0, options.into(), cb)
 }
 
-#[derive(Copy, Clone, Debug)]
-pub struct FileWatchEvent(node_fs::a_sync::FileWatchEvent);
-
 
 #[no_mangle]
-pub extern "C" fn fs_filewatch_event_destroy(
-    event: *mut FileWatchEvent,
-) {
-    if event.is_null() {
-        return;
+pub extern "C" fn fs_async_write_slice(
+    fd: i32,
+    buffer: *const u8,
+    length: usize,
+    options: WriteOptions,
+    callback: *const AsyncUsizeClosure,
+) {
+    if buffer.is_null() || callback.is_null() {
+        return;
     }
 
-    let _ = unsafe { Box::from_raw(event) };
-}
+    let buffer = unsafe { node_buffer::Buffer::from_reference(buffer as _, length) };
+    let callback = unsafe { &*callback };
 
-#[no_mangle]
-pub extern "C" fn fs_filewatch_event_current(
-    event: *const FileWatchEvent,
-) -> *mut FileStat {
-    if event.is_null() {
-        return std::ptr::null_mut();
-    }
+    let callback = Arc::clone(&callback.0);
 
-    let event = unsafe { &*event };
-    let current = event.0.current();
-    current
-        .map(|stat| {
-            Box::into_raw(
-                Box::new(
-                    FileStat::from(stat)
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
                 )
-            )
-        })
-        .unwrap_or_else(|| {
-            std::ptr::null_mut()
-        })
-}
-
-
-#[no_mangle]
-pub extern "C" fn fs_filewatch_event_previous(
-    event: *const FileWatchEvent,
-) -> *mut FileStat {
-    if event.is_null() {
-        return std::ptr::null_mut();
-    }
+            } else {
+                callback.on_success(wrote)
+            }
+        }))
+    );
 
-    let event = unsafe { &*event };
-    let previous = event.0.previous();
-    previous
-        .map(|stat| {
-            Box::into_raw(
-                Box::new(
-                    FileStat::from(stat)
-                )
-            )
-        })
-        .unwrap_or_else(|| {
-            std::ptr::null_mut()
-        })
+    node_fs::a_sync::write(fd, &buffer, options.into(), cb);
 }
 
-
-#[derive(Clone, Debug)]
-pub struct WatchEvent(node_fs::a_sync::WatchEvent);
-
-
 #[no_mangle]
-pub extern "C" fn fs_watch_event_destroy(
-    event: *mut WatchEvent,
+pub extern "C" fn fs_async_write_string(
+    fd: i32,
+    string: *const c_char,
+    encoding: StringEncoding,
+    position: isize,
+    callback: *const AsyncUsizeClosure,
 ) {
-    if event.is_null() {
+    if string.is_null() || callback.is_null() {
         return;
     }
 
-    let _ = unsafe { Box::from_raw(event) };
-}
+    let string = unsafe { CStr::from_ptr(string) };
+    let string = string.to_string_lossy();
 
-#[no_mangle]
-pub extern "C" fn fs_watch_event_event_type(
-    event: *const WatchEvent,
-) -> *const c_char {
-    if event.is_null() {
-        return std::ptr::null_mut();
-    }
+    let callback = unsafe { &*callback };
 
-    let event = unsafe { &*event };
-    let event_type = event.0.event_type();
-    event_type
-        .map(|value| {
-            CString::new(value.to_string()).unwrap().into_raw()
-        })
-        .unwrap_or_else(|| {
-            std::ptr::null_mut()
-        })
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(wrote)
+            }
+        }))
+    );
+    node_fs::a_sync::write_string(fd, string.as_ref(), encoding.into(), position, cb)
 }
 
-
 #[no_mangle]
-pub extern "C" fn fs_watch_event_filename(
-    event: *const WatchEvent,
-) -> *const c_char {
-    if event.is_null() {
-        return std::ptr::null_mut();
+pub extern "C" fn fs_async_write_file_with_str(
+    fd: i32,
+    data: *const c_char,
+    options: WriteFileOptions,
+    callback: *const AsyncClosure,
+) {
+    if data.is_null() || callback.is_null() {
+        return;
     }
 
-    let event = unsafe { &*event };
-    let filename = event.0.filename();
-    filename
-        .map(|value| {
-            CString::new(value.to_string()).unwrap().into_raw()
-        })
-        .unwrap_or_else(|| {
-            std::ptr::null_mut()
-        })
-}
-
-#[derive(Clone)]
-pub struct FileDir(node_fs::file_dir::FileDir);
+    let data = unsafe { CStr::from_ptr(data) };
+    let data = data.to_string_lossy();
 
+    let callback = unsafe { &*callback };
 
-#[no_mangle]
-pub extern "C" fn fs_file_dir_destroy(value: *mut FileDir) {
-    if value.is_null() {
-        return;
-    }
-    let _ = unsafe { Box::from_raw(value) };
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+    node_fs::a_sync::write_file_with_str(fd, data.as_ref(), options.into(), cb)
 }
 
-pub struct FileHandle(node_fs::file_handle::FileHandle);
-
 #[no_mangle]
-pub extern "C" fn fs_filehandle_destroy(value: *mut FileHandle) {
-    if value.is_null() {
+pub extern "C" fn fs_async_write_file_with_bytes(fd: i32, data: *const Buffer, options: WriteFileOptions, callback: *const AsyncClosure) {
+    if data.is_null() || callback.is_null() {
         return;
     }
-    let _ = unsafe { Box::from_raw(value) };
-}
 
-#[derive(Clone)]
-pub struct AsyncClosure(Arc<node_fs::a_sync::AsyncClosure<(), NodeError>>);
-
-#[derive(Clone)]
-pub struct AsyncBoolClosure(Arc<node_fs::a_sync::AsyncClosure<bool, NodeError>>);
-
-#[derive(Clone)]
-pub struct AsyncFileStatClosure(Arc<node_fs::a_sync::AsyncClosure<FileStat, NodeError>>);
-
-#[derive(Clone)]
-pub struct AsyncStringClosure(Arc<node_fs::a_sync::AsyncClosure<String, NodeError>>);
-
-#[derive(Clone)]
-pub struct AsyncUsizeClosure(Arc<node_fs::a_sync::AsyncClosure<usize, NodeError>>);
-
-#[derive(Clone)]
-pub struct AsyncI32Closure(Arc<node_fs::a_sync::AsyncClosure<i32, NodeError>>);
+    let data = unsafe { &*data };
 
-#[derive(Clone)]
-pub struct AsyncFileWatchClosure(Arc<node_fs::a_sync::AsyncClosure<FileWatchEvent, NodeError>>);
+    let callback = unsafe { &*callback };
 
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+    node_fs::a_sync::write_file_with_bytes(fd, &data.0, options.into(), cb)
+}
 
 #[no_mangle]
-pub unsafe extern "C" fn fs_async_file_watch_closure_destroy(value: *mut AsyncFileWatchClosure) {
-    if value.is_null() {
+pub extern "C" fn fs_async_write_file_with_bytes_raw(fd: i32, data: *const u8, length: usize, options: WriteFileOptions, callback: *const AsyncClosure) {
+    if data.is_null() || callback.is_null() {
         return;
     }
-    let _ = unsafe { Box::from_raw(value) };
-}
 
-#[derive(Clone)]
-pub struct AsyncFsEncodingClosure(Arc<node_fs::a_sync::AsyncClosure<FsEncoding, NodeError>>);
+    let data = unsafe { node_buffer::Buffer::from_reference(data as _, length) };
 
-#[derive(Clone)]
-pub struct AsyncWatchClosure(Arc<node_fs::a_sync::AsyncClosure<WatchEvent, NodeError>>);
+    let callback = unsafe { &*callback };
 
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+    node_fs::a_sync::write_file_with_bytes(fd, &data, options.into(), cb)
+}
 
 #[no_mangle]
-pub unsafe extern "C" fn fs_async_watch_closure_destroy(value: *mut AsyncWatchClosure) {
-    if value.is_null() {
+pub extern "C" fn fs_async_write_file_with_str_from_path(
+    path: *const c_char,
+    data: *const c_char,
+    options: WriteFileOptions,
+    callback: *const AsyncClosure,
+) {
+    if path.is_null() || data.is_null() || callback.is_null() {
         return;
     }
-    let _ = unsafe { Box::from_raw(value) };
-}
 
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
 
-#[derive(Clone)]
-pub struct AsyncReaddirClosure(Arc<node_fs::a_sync::AsyncClosure<Vec<ReaddirResult>, NodeError>>);
+    let data = unsafe { CStr::from_ptr(data) };
+    let data = data.to_string_lossy();
+
+    let callback = unsafe { &*callback };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+    node_fs::a_sync::write_file_with_str_from_path(
+        path.as_ref(), data.as_ref(), options.into(), cb,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_async_write_file_with_bytes_from_path(
+    path: *const c_char,
+    data: *const Buffer,
+    options: WriteFileOptions,
+    callback: *const AsyncClosure,
+) {
+    if path.is_null() || data.is_null() || callback.is_null() {
+        return;
+    }
+
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
+
+    let data = unsafe { &*data };
+
+    let callback = unsafe { &*callback };
+
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+    node_fs::a_sync::write_file_with_bytes_from_path(path.as_ref(), &data.0, options.into(), cb)
+}
+
+#[no_mangle]
+pub extern "C" fn fs_async_write_file_with_bytes_from_path_raw(
+    path: *const c_char,
+    data: *const u8,
+    length: usize,
+    options: WriteFileOptions,
+    callback: *const AsyncClosure,
+) {
+    if path.is_null() || data.is_null() || callback.is_null() {
+        return;
+    }
+
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
+
+    let data = unsafe { node_buffer::Buffer::from_reference(data as _, length) };
+
+    let callback = unsafe { &*callback };
+
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+    node_fs::a_sync::write_file_with_bytes_from_path(path.as_ref(), &data, options.into(), cb)
+}
+
+#[no_mangle]
+pub extern "C" fn fs_async_writev(
+    fd: i32,
+    buffers: *const Buffer,
+    length: usize,
+    position: usize,
+    callback: *const AsyncUsizeClosure,
+) {
+    if buffers.is_null() || length == 0 || callback.is_null() {
+        return;
+    }
+
+    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
+
+    let callback = unsafe { &*callback };
+
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(wrote)
+            }
+        }))
+    );
+
+    let buffers = buffers.iter().map(|buffer| buffer.0.clone())
+        .collect::<Vec<node_buffer::Buffer>>();
+
+    node_fs::a_sync::writev(fd, buffers, position.try_into().unwrap(), cb)
+}
+
+
+#[no_mangle]
+pub extern "C" fn fs_async_writev_slice(fd: c_int, buffers: *const *mut u8, buffers_buffers: *const usize, length: usize, position: i64, callback: *const AsyncUsizeClosure) {
+    if buffers.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |read, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(read)
+            }
+        }))
+    );
+
+    let buffer_length = unsafe { std::slice::from_raw_parts(buffers_buffers, length) };
+    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
+
+    let buffers = buffers.iter().zip(buffer_length.iter())
+        .map(|(buffer, len)| {
+            unsafe { node_buffer::Buffer::from_reference(*buffer, *len) }
+        }).collect::<Vec<node_buffer::Buffer>>();
+
+    node_fs::a_sync::writev(fd, buffers, position.try_into().unwrap(), cb);
+}
+
+
+// fs filehandle
+
+#[no_mangle]
+pub extern "C" fn fs_handle_new_async(
+    path: *const c_char,
+    flags: i32,
+    mode: i32,
+    callback: *const AsyncFileHandleClosure,
+) {
+    if path.is_null() || callback.is_null() {
+        return;
+    }
+
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
+
+    let callback = unsafe { &*callback };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |handle, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(handle
+                    .map(FileHandle))
+            }
+        }))
+    );
+
+    node_fs::file_handle::FileHandle::new_async(
+        path.as_ref(), flags, mode, cb,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_append_file_with_str(
+    handle: *mut FileHandle,
+    data: *const c_char,
+    options: AppendFileOptions,
+    callback: *const AsyncClosure,
+) {
+    if handle.is_null() || data.is_null() || callback.is_null() {
+        return;
+    }
+
+    let data = unsafe { CStr::from_ptr(data) };
+    let data = data.to_string_lossy();
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+
+    handle.0.append_file_with_str(
+        data.as_ref(), options.into(), cb,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_append_file_with_bytes(
+    handle: *mut FileHandle,
+    data: *const Buffer,
+    options: AppendFileOptions,
+    callback: *const AsyncClosure,
+) {
+    if handle.is_null() || data.is_null() || callback.is_null() {
+        return;
+    }
+
+    let data = unsafe { &*data };
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+
+    handle.0.append_file_with_bytes(
+        &data.0, options.into(), cb,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_chmod(handle: *mut FileHandle, mode: u16, callback: *const AsyncClosure) {
+    if handle.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+
+    handle.0.chmod(
+        mode, cb,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_chown(handle: *mut FileHandle, uid: u32, gid: u32, callback: *const AsyncClosure) {
+    if handle.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+
+    handle.0.chown(
+        uid, gid, cb,
+    )
+}
+
+// consumes the handle, do not use after closing
+#[no_mangle]
+pub extern "C" fn fs_handle_close(handle: *mut FileHandle, callback: *const AsyncClosure) {
+    if handle.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { Box::from_raw(handle) };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+
+
+    handle.0.close(
+        cb
+    )
+}
+
+// TODO
+// #[no_mangle]
+// pub extern "C" fn fs_handle_create_read_stream() {}
+
+// TODO
+// #[no_mangle]
+// pub extern "C" fn fs_handle_create_write_stream() {}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_datasync(handle: *mut FileHandle, callback: *const AsyncClosure) {
+    if handle.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+
+    handle.0.datasync(
+        cb
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_fd(handle: *mut FileHandle) -> i32 {
+    if handle.is_null() {
+        return 0;
+    }
+
+    let handle = unsafe { &mut *handle };
+
+    handle.0.fd()
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_read(
+    handle: *mut FileHandle,
+    buffer: *mut Buffer,
+    offset: usize,
+    length: usize,
+    position: isize,
+    callback: *const AsyncUsizeClosure,
+) {
+    if handle.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let buffer = unsafe { &mut *buffer };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |read, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(read)
+            }
+        }))
+    );
+
+    handle.0.read(
+        &mut buffer.0, offset, length, position, cb,
+    )
+}
+
+
+#[no_mangle]
+pub extern "C" fn fs_handle_read_bytes(
+    handle: *mut FileHandle,
+    buffer: *mut u8,
+    buffer_length: usize,
+    offset: usize,
+    length: usize,
+    position: isize,
+    callback: *const AsyncUsizeClosure,
+) {
+    if handle.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let buffer = unsafe { std::slice::from_raw_parts_mut(buffer, buffer_length) };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |read, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(read)
+            }
+        }))
+    );
+
+    handle.0.read_bytes(
+        buffer, offset, length, position, cb,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_read_file(
+    handle: *mut FileHandle,
+    options: ReadFileOptions,
+    callback: *const AsyncFsEncodingClosure,
+) {
+    if handle.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(result.map(FsEncoding))
+            }
+        }))
+    );
+
+    handle.0.read_file(
+        options.into(), cb,
+    )
+}
+
+
+#[no_mangle]
+pub extern "C" fn fs_async_readv_slice(fd: c_int, buffers: *const *mut u8, buffers_buffers: *const usize, length: usize, position: i64, callback: *const AsyncUsizeClosure) {
+    if buffers.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |read, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(read)
+            }
+        }))
+    );
+
+    let buffer_length = unsafe { std::slice::from_raw_parts(buffers_buffers, length) };
+    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
+
+    let buffers = buffers.iter().zip(buffer_length.iter())
+        .map(|(buffer, len)| {
+            unsafe { node_buffer::Buffer::from_reference(*buffer, *len) }
+        }).collect::<Vec<node_buffer::Buffer>>();
+
+    node_fs::a_sync::readv(fd, buffers, position.try_into().unwrap(), cb);
+}
+
+
+#[no_mangle]
+pub extern "C" fn fs_handle_readv_slice(handle: *mut FileHandle, buffers: *const *mut u8, buffers_buffers: *const usize, length: usize, position: i64, callback: *const AsyncUsizeClosure) {
+    if handle.is_null() || buffers.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |read, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(read)
+            }
+        }))
+    );
+
+    let buffer_length = unsafe { std::slice::from_raw_parts(buffers_buffers, length) };
+    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
+
+    let buffers = buffers.iter().zip(buffer_length.iter())
+        .map(|(buffer, len)| {
+            unsafe { node_buffer::Buffer::from_reference(*buffer, *len) }
+        }).collect::<Vec<node_buffer::Buffer>>();
+
+    handle.0.readv(
+        buffers, position.try_into().unwrap(), cb,
+    )
+}
+
+
+#[no_mangle]
+pub extern "C" fn fs_handle_readv(
+    handle: *mut FileHandle,
+    buffers: *const Buffer,
+    length: usize,
+    position: usize,
+    callback: *const AsyncUsizeClosure,
+) {
+    if handle.is_null() || buffers.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |read, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(read)
+            }
+        }))
+    );
+
+    let buffers = buffers.iter().map(|buffer| buffer.0.clone())
+        .collect::<Vec<node_buffer::Buffer>>();
+
+    handle.0.readv(
+        buffers, position.try_into().unwrap(), cb,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_stat(handle: *mut FileHandle, callback: *const AsyncFileStatClosure) {
+    if handle.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |stat: Option<node_fs::file_stat::FileStat>, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(stat.map(|stat| stat.into()))
+            }
+        }))
+    );
+
+    handle.0.stat(
+        cb
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_sync(handle: *mut FileHandle, callback: *const AsyncClosure) {
+    if handle.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+
+    handle.0.sync(
+        cb
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_truncate(handle: *mut FileHandle, len: usize, callback: *const AsyncClosure) {
+    if handle.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+
+    handle.0.truncate(
+        len.try_into().unwrap(), cb,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_utimes(
+    handle: *mut FileHandle,
+    atime: usize,
+    mtime: usize,
+    callback: *const AsyncClosure,
+) {
+    if handle.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+
+    handle.0.utimes(
+        atime.try_into().unwrap(), mtime.try_into().unwrap(), cb,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_write(
+    handle: *mut FileHandle,
+    buffer: *const Buffer,
+    options: WriteOptions,
+    callback: *const AsyncUsizeClosure,
+) {
+    if handle.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let buffer = unsafe { &*buffer };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(wrote)
+            }
+        }))
+    );
+
+    handle.0.write(
+        &buffer.0, options.into(), cb,
+    )
+}
+
+
+#[no_mangle]
+pub extern "C" fn fs_handle_write_bytes(
+    handle: *mut FileHandle,
+    buffer: *const u8,
+    length: usize,
+    options: WriteOptions,
+    callback: *const AsyncUsizeClosure,
+) {
+    if handle.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(wrote)
+            }
+        }))
+    );
+
+    let buffer = unsafe { node_buffer::Buffer::from_reference(buffer as _, length) };
+
+    handle.0.write(
+        &buffer, options.into(), cb,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_write_string(
+    handle: *mut FileHandle,
+    data: *const c_char,
+    encoding: StringEncoding,
+    position: isize,
+    callback: *const AsyncUsizeClosure,
+) {
+    if handle.is_null() || data.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let data = unsafe { CStr::from_ptr(data) };
+    let data = data.to_string_lossy();
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(wrote)
+            }
+        }))
+    );
+
+    handle.0.write_string(
+        data.as_ref(), encoding.into(), position, cb,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_write_file_with_str(
+    handle: *mut FileHandle,
+    data: *const c_char,
+    options: WriteFileOptions,
+    callback: *const AsyncClosure,
+) {
+    if handle.is_null() || data.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let data = unsafe { CStr::from_ptr(data) };
+    let data = data.to_string_lossy();
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+
+    handle.0.write_file_with_str(
+        data.as_ref(), options.into(), cb,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn fs_handle_write_file_with_bytes(
+    handle: *mut FileHandle,
+    data: *const Buffer,
+    options: WriteFileOptions,
+    callback: *const AsyncClosure,
+) {
+    if handle.is_null() || data.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let data = unsafe { &*data };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+
+    handle.0.write_file_with_bytes(
+        &data.0, options.into(), cb,
+    )
+}
+
+
+#[no_mangle]
+pub extern "C" fn fs_handle_write_file_with_bytes_slice(
+    handle: *mut FileHandle,
+    data: *const u8,
+    length: usize,
+    options: WriteFileOptions,
+    callback: *const AsyncClosure,
+) {
+    if handle.is_null() || data.is_null() || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(None)
+            }
+        }))
+    );
+
+    let buffer = unsafe { node_buffer::Buffer::from_reference(data as _, length) };
+    handle.0.write_file_with_bytes(
+        &buffer, options.into(), cb,
+    )
+}
+
+
+#[no_mangle]
+pub extern "C" fn fs_handle_writev(
+    handle: *mut FileHandle,
+    buffers: *const Buffer,
+    length: usize,
+    position: usize,
+    callback: *const AsyncUsizeClosure,
+) {
+    if handle.is_null() || buffers.is_null() || length == 0 || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(wrote)
+            }
+        }))
+    );
+
+    let buffers = buffers.iter().map(|buffer| buffer.0.clone())
+        .collect::<Vec<node_buffer::Buffer>>();
+
+    handle.0.writev(
+        buffers, position.try_into().unwrap(), cb,
+    )
+}
+
+
+#[no_mangle]
+pub extern "C" fn fs_handle_writev_slice(
+    handle: *mut FileHandle,
+    buffers: *const *const u8,
+    buffers_buffers: *const usize,
+    length: usize,
+    position: usize,
+    callback: *const AsyncUsizeClosure,
+) {
+    if handle.is_null() || buffers.is_null() || length == 0 || callback.is_null() {
+        return;
+    }
+
+    let callback = unsafe { &*callback };
+
+    let handle = unsafe { &mut *handle };
+
+
+    let buffer_length = unsafe { std::slice::from_raw_parts(buffers_buffers, length) };
+    let buffers = unsafe { std::slice::from_raw_parts(buffers, length) };
+
+
+    let buffers = buffers.iter().zip(buffer_length.iter())
+        .map(|(buffer, len)| {
+            unsafe { node_buffer::Buffer::from_reference((*buffer) as *mut _, length) }
+        }).collect::<Vec<_>>();
+
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |wrote, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(wrote)
+            }
+        }))
+    );
+
+    handle.0.writev(
+        buffers, position.try_into().unwrap(), cb,
+    )
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct FileWatchEvent(node_fs::a_sync::FileWatchEvent);
+
+
+#[no_mangle]
+pub extern "C" fn fs_filewatch_event_destroy(
+    event: *mut FileWatchEvent,
+) {
+    if event.is_null() {
+        return;
+    }
+
+    let _ = unsafe { Box::from_raw(event) };
+}
+
+#[no_mangle]
+pub extern "C" fn fs_filewatch_event_current(
+    event: *const FileWatchEvent,
+) -> *mut FileStat {
+    if event.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let event = unsafe { &*event };
+    let current = event.0.current();
+    current
+        .map(|stat| {
+            Box::into_raw(
+                Box::new(
+                    FileStat::from(stat)
+                )
+            )
+        })
+        .unwrap_or_else(|| {
+            std::ptr::null_mut()
+        })
+}
+
+
+#[no_mangle]
+pub extern "C" fn fs_filewatch_event_previous(
+    event: *const FileWatchEvent,
+) -> *mut FileStat {
+    if event.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let event = unsafe { &*event };
+    let previous = event.0.previous();
+    previous
+        .map(|stat| {
+            Box::into_raw(
+                Box::new(
+                    FileStat::from(stat)
+                )
+            )
+        })
+        .unwrap_or_else(|| {
+            std::ptr::null_mut()
+        })
+}
+
+
+#[derive(Clone, Debug)]
+pub struct WatchEvent(node_fs::a_sync::WatchEvent);
+
+
+#[no_mangle]
+pub extern "C" fn fs_watch_event_destroy(
+    event: *mut WatchEvent,
+) {
+    if event.is_null() {
+        return;
+    }
+
+    let _ = unsafe { Box::from_raw(event) };
+}
+
+#[no_mangle]
+pub extern "C" fn fs_watch_event_event_type(
+    event: *const WatchEvent,
+) -> *const c_char {
+    if event.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let event = unsafe { &*event };
+    let event_type = event.0.event_type();
+    event_type
+        .map(|value| {
+            CString::new(value.to_string()).unwrap().into_raw()
+        })
+        .unwrap_or_else(|| {
+            std::ptr::null_mut()
+        })
+}
+
+
+#[no_mangle]
+pub extern "C" fn fs_watch_event_filename(
+    event: *const WatchEvent,
+) -> *const c_char {
+    if event.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let event = unsafe { &*event };
+    let filename = event.0.filename();
+    filename
+        .map(|value| {
+            CString::new(value.to_string()).unwrap().into_raw()
+        })
+        .unwrap_or_else(|| {
+            std::ptr::null_mut()
+        })
+}
+
+#[derive(Clone)]
+pub struct FileDir(node_fs::file_dir::FileDir);
+
+
+#[no_mangle]
+pub extern "C" fn fs_file_dir_destroy(value: *mut FileDir) {
+    if value.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(value) };
+}
+
+pub struct FileHandle(node_fs::file_handle::FileHandle);
+
+#[no_mangle]
+pub extern "C" fn fs_filehandle_destroy(value: *mut FileHandle) {
+    if value.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(value) };
+}
+
+#[derive(Clone)]
+pub struct AsyncClosure(Arc<node_fs::a_sync::AsyncClosure<(), NodeError>>);
+
+#[derive(Clone)]
+pub struct AsyncBoolClosure(Arc<node_fs::a_sync::AsyncClosure<bool, NodeError>>);
+
+#[derive(Clone)]
+pub struct AsyncFileStatClosure(Arc<node_fs::a_sync::AsyncClosure<FileStat, NodeError>>);
+
+#[derive(Clone)]
+pub struct AsyncStringClosure(Arc<node_fs::a_sync::AsyncClosure<String, NodeError>>);
+
+#[derive(Clone)]
+pub struct AsyncUsizeClosure(Arc<node_fs::a_sync::AsyncClosure<usize, NodeError>>);
+
+#[derive(Clone)]
+pub struct AsyncI32Closure(Arc<node_fs::a_sync::AsyncClosure<i32, NodeError>>);
+
+#[derive(Clone)]
+pub struct AsyncFileWatchClosure(Arc<node_fs::a_sync::AsyncClosure<FileWatchEvent, NodeError>>);
+
+
+#[no_mangle]
+pub unsafe extern "C" fn fs_async_file_watch_closure_destroy(value: *mut AsyncFileWatchClosure) {
+    if value.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(value) };
+}
+
+#[derive(Clone)]
+pub struct AsyncFsEncodingClosure(Arc<node_fs::a_sync::AsyncClosure<FsEncoding, NodeError>>);
+
+#[derive(Clone)]
+pub struct AsyncWatchClosure(Arc<node_fs::a_sync::AsyncClosure<WatchEvent, NodeError>>);
+
+
+#[no_mangle]
+pub unsafe extern "C" fn fs_async_watch_closure_destroy(value: *mut AsyncWatchClosure) {
+    if value.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(value) };
+}
+
+
+#[derive(Clone)]
+pub struct AsyncReaddirClosure(Arc<node_fs::a_sync::AsyncClosure<Vec<ReaddirResult>, NodeError>>);
 
 #[derive(Clone)]
 pub struct AsyncFileDirClosure(Arc<node_fs::a_sync::AsyncClosure<FileDir, NodeError>>);
@@ -5698,43 +7129,438 @@ pub extern "C" fn fs_async_create_async_fs_readdir_closure(on_success: *mut c_vo
                     } else {
                         let on_success = on_success as *const ();
 
-                        let mut result = value.unwrap();
+                        let mut result = value.unwrap();
+
+                        let ptr = result.as_mut_ptr();
+                        let len = result.len();
+                        result.shrink_to_fit();
+                        std::mem::forget(result);
+                        let ret = ReaddirResultArray {
+                            data: ptr,
+                            length: len,
+                        };
+
+
+                        let on_success: fn(*mut ReaddirResultArray, *mut c_void) = unsafe { std::mem::transmute(on_success) };
+                        on_success(Box::into_raw(
+                            Box::new(ret)
+                        ), data);
+                    }
+                }))
+            )
+        )
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn fs_async_create_async_fs_file_dir_closure(on_success: *mut c_void, on_error: *mut c_void, data: *mut c_void) -> *mut AsyncFileDirClosure {
+    Box::into_raw(Box::new(
+        AsyncFileDirClosure(
+            Arc::new(
+                node_fs::a_sync::AsyncClosure::new(Box::new(move |value, error| {
+                    if error.is_some() {
+                        let on_error = on_error as *const ();
+                        let on_error = unsafe { std::mem::transmute::<*const (), fn(*mut NodeError, *mut c_void)>(on_error) };
+                        on_error(Box::into_raw(Box::new(error.unwrap())), data);
+                    } else {
+                        let on_success = on_success as *const ();
+                        let on_success = unsafe { std::mem::transmute::<*const (), fn(*mut FileDir, *mut c_void)>(on_success) };
+                        on_success(Box::into_raw(Box::new(value.unwrap())), data);
+                    }
+                }))
+            )
+        )
+    ))
+}
+
+#[no_mangle]
+pub extern "C" fn fs_async_create_async_fs_file_handle_closure(on_success: *mut c_void, on_error: *mut c_void, data: *mut c_void) -> *mut AsyncFileHandleClosure {
+    Box::into_raw(Box::new(
+        AsyncFileHandleClosure(
+            Arc::new(
+                node_fs::a_sync::AsyncClosure::new(Box::new(move |value, error| {
+                    if error.is_some() {
+                        let on_error = on_error as *const ();
+                        let on_error = unsafe { std::mem::transmute::<*const (), fn(*mut NodeError, *mut c_void)>(on_error) };
+                        on_error(Box::into_raw(Box::new(error.unwrap())), data);
+                    } else {
+                        let on_success = on_success as *const ();
+                        let on_success = unsafe { std::mem::transmute::<*const (), fn(*mut FileHandle, *mut c_void)>(on_success) };
+                        on_success(Box::into_raw(Box::new(value.unwrap())), data);
+                    }
+                }))
+            )
+        )
+    ))
+}
+
+
+impl From<node_fs::file_stat::FileStat> for FileStat {
+    fn from(value: node_fs::file_stat::FileStat) -> Self {
+        FileStat {
+            dev: value.dev,
+            ino: value.ino,
+            mode: value.mode,
+            nlink: value.nlink,
+            uid: value.uid,
+            gid: value.gid,
+            rdev: value.rdev,
+            size: value.size,
+            blksize: value.blksize,
+            blocks: value.blocks,
+            atimeMs: value.atimeMs,
+            mtimeMs: value.mtimeMs,
+            ctimeMs: value.ctimeMs,
+            birthtimeMs: value.birthtimeMs,
+            birthtime: value.birthtime,
+            atime: value.atime,
+            mtime: value.mtime,
+            ctime: value.ctime,
+            isBlockDevice: value.isBlockDevice,
+            isCharacterDevice: value.isCharacterDevice,
+            isDirectory: value.isDirectory,
+            isFIFO: value.isFIFO,
+            isFile: value.isFile,
+            isSocket: value.isSocket,
+            isSymbolicLink: value.isSymbolicLink,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FsBatchOperationType {
+    FsBatchOperationTypeStat,
+    FsBatchOperationTypeLstat,
+    FsBatchOperationTypeReadFile,
+    FsBatchOperationTypeAccess,
+    FsBatchOperationTypeExists,
+    FsBatchOperationTypeReaddir,
+}
+
+/// A single entry of a batch, `mode` is only read for access, `read_file_options` for
+/// read_file and `readdir_options` for readdir.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct FsBatchOperation {
+    type_: FsBatchOperationType,
+    path: *const c_char,
+    mode: i32,
+    read_file_options: ReadFileOptions,
+    readdir_options: ReaddirOptions,
+}
+
+impl Into<node_fs::batch::BatchOperation> for FsBatchOperation {
+    fn into(self) -> node_fs::batch::BatchOperation {
+        let path = if self.path.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(self.path) }.to_string_lossy().to_string()
+        };
+
+        match self.type_ {
+            FsBatchOperationType::FsBatchOperationTypeStat => node_fs::batch::BatchOperation::Stat(path),
+            FsBatchOperationType::FsBatchOperationTypeLstat => node_fs::batch::BatchOperation::Lstat(path),
+            FsBatchOperationType::FsBatchOperationTypeReadFile => node_fs::batch::BatchOperation::ReadFile(path, self.read_file_options.into()),
+            FsBatchOperationType::FsBatchOperationTypeAccess => node_fs::batch::BatchOperation::Access(path, self.mode),
+            FsBatchOperationType::FsBatchOperationTypeExists => node_fs::batch::BatchOperation::Exists(path),
+            FsBatchOperationType::FsBatchOperationTypeReaddir => node_fs::batch::BatchOperation::Readdir(path, self.readdir_options.into()),
+        }
+    }
+}
+
+fn batch_operations(operations: *const FsBatchOperation, length: usize) -> Vec<node_fs::batch::BatchOperation> {
+    if operations.is_null() || length == 0 {
+        return Vec::new();
+    }
+    let operations = unsafe { std::slice::from_raw_parts(operations, length) };
+    operations.iter().map(|operation| (*operation).into()).collect()
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FsBatchResultType {
+    FsBatchResultTypeStat,
+    FsBatchResultTypeReadFile,
+    FsBatchResultTypeAccess,
+    FsBatchResultTypeExists,
+    FsBatchResultTypeReaddir,
+    FsBatchResultTypeError,
+}
+
+#[derive(Debug)]
+pub struct FsBatchResult(node_fs::batch::BatchResult);
+
+#[repr(C)]
+pub struct FsBatchResultArray {
+    data: *mut FsBatchResult,
+    length: usize,
+}
+
+impl Drop for FsBatchResultArray {
+    fn drop(&mut self) {
+        let _ = unsafe { Vec::from_raw_parts(self.data, self.length, self.length) };
+    }
+}
+
+impl From<Vec<FsBatchResult>> for FsBatchResultArray {
+    fn from(mut value: Vec<FsBatchResult>) -> Self {
+        value.shrink_to_fit();
+        let data = value.as_mut_ptr();
+        let length = value.len();
+        std::mem::forget(value);
+        FsBatchResultArray { data, length }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn fs_batch_result_array_destroy(value: *mut FsBatchResultArray) {
+    if value.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(value) };
+}
+
+#[no_mangle]
+pub extern "C" fn fs_batch_result_destroy(value: *mut FsBatchResult) {
+    if value.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(value) };
+}
+
+/// Borrows the result at `at`, it is owned by the array and must not be destroyed.
+#[no_mangle]
+pub extern "C" fn fs_batch_result_array_get_at(value: *const FsBatchResultArray, at: usize) -> *const FsBatchResult {
+    if value.is_null() {
+        return std::ptr::null();
+    }
+    let value = unsafe { &*value };
+    if at >= value.length {
+        return std::ptr::null();
+    }
+    unsafe { value.data.add(at) }
+}
+
+#[no_mangle]
+pub extern "C" fn fs_batch_result_get_type(value: *const FsBatchResult) -> FsBatchResultType {
+    let value = unsafe { &*value };
+    match value.0 {
+        node_fs::batch::BatchResult::Stat(_) => FsBatchResultType::FsBatchResultTypeStat,
+        node_fs::batch::BatchResult::ReadFile(_) => FsBatchResultType::FsBatchResultTypeReadFile,
+        node_fs::batch::BatchResult::Access => FsBatchResultType::FsBatchResultTypeAccess,
+        node_fs::batch::BatchResult::Exists(_) => FsBatchResultType::FsBatchResultTypeExists,
+        node_fs::batch::BatchResult::Readdir(_) => FsBatchResultType::FsBatchResultTypeReaddir,
+        node_fs::batch::BatchResult::Error(_) => FsBatchResultType::FsBatchResultTypeError,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn fs_batch_result_get_error(value: *const FsBatchResult) -> *mut NodeError {
+    if value.is_null() {
+        return std::ptr::null_mut();
+    }
+    let value = unsafe { &*value };
+    match &value.0 {
+        node_fs::batch::BatchResult::Error(error) => {
+            let error = node_core::error::clone_io_error(error);
+            Box::into_raw(Box::new(NodeError(node_core::error::error_from_io_error(error))))
+        }
+        _ => std::ptr::null_mut()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn fs_batch_result_get_stat(value: *const FsBatchResult) -> *mut FileStat {
+    if value.is_null() {
+        return std::ptr::null_mut();
+    }
+    let value = unsafe { &*value };
+    match &value.0 {
+        node_fs::batch::BatchResult::Stat(stat) => Box::into_raw(Box::new(FileStat::from(*stat))),
+        _ => std::ptr::null_mut()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn fs_batch_result_get_exists(value: *const FsBatchResult) -> bool {
+    if value.is_null() {
+        return false;
+    }
+    let value = unsafe { &*value };
+    match &value.0 {
+        node_fs::batch::BatchResult::Exists(exists) => *exists,
+        _ => false
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn fs_batch_result_get_fs_encoding(value: *const FsBatchResult) -> *mut FsEncoding {
+    if value.is_null() {
+        return std::ptr::null_mut();
+    }
+    let value = unsafe { &*value };
+    match &value.0 {
+        node_fs::batch::BatchResult::ReadFile(encoding) => Box::into_raw(Box::new(FsEncoding(encoding.clone()))),
+        _ => std::ptr::null_mut()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn fs_batch_result_get_readdir(value: *const FsBatchResult) -> *mut ReaddirResultArray {
+    if value.is_null() {
+        return std::ptr::null_mut();
+    }
+    let value = unsafe { &*value };
+    match &value.0 {
+        node_fs::batch::BatchResult::Readdir(entries) => {
+            let mut result = entries.iter()
+                .map(|value| ReaddirResult(value.clone()))
+                .collect::<Vec<ReaddirResult>>();
+            result.shrink_to_fit();
+            let ptr = result.as_mut_ptr();
+            let len = result.len();
+            std::mem::forget(result);
+            Box::into_raw(Box::new(ReaddirResultArray {
+                data: ptr,
+                length: len,
+            }))
+        }
+        _ => std::ptr::null_mut()
+    }
+}
+
+/// Runs `length` operations on the calling thread and returns their results in order,
+/// a failing operation does not stop the rest of the batch.
+#[no_mangle]
+pub extern "C" fn fs_batch_sync(operations: *const FsBatchOperation, length: usize) -> *mut FsBatchResultArray {
+    let operations = batch_operations(operations, length);
+    let result = node_fs::batch::batch_sync(&operations)
+        .into_iter()
+        .map(FsBatchResult)
+        .collect::<Vec<FsBatchResult>>();
+    Box::into_raw(Box::new(result.into()))
+}
+
+/// Runs the operations on the I/O pool with at most `concurrency` in flight (`0` uses the
+/// cpu count), the callback receives every result at once in submission order.
+#[no_mangle]
+pub extern "C" fn fs_async_batch(
+    operations: *const FsBatchOperation,
+    length: usize,
+    concurrency: usize,
+    callback: *const AsyncBatchClosure,
+) {
+    if callback.is_null() {
+        return;
+    }
+
+    let operations = batch_operations(operations, length);
+
+    let callback = unsafe { &*callback };
+
+    let callback = Arc::clone(&callback.0);
+    let cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result: Option<Vec<node_fs::batch::BatchResult>>, error| {
+            if error.is_some() {
+                callback.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                callback.on_success(result.map(|result| result.into_iter()
+                    .map(FsBatchResult)
+                    .collect::<Vec<FsBatchResult>>()))
+            }
+        }))
+    );
+    node_fs::batch::batch(operations, concurrency, cb)
+}
+
+/// Like `fs_async_batch` but `on_item` is invoked with `(index, result)` as soon as each
+/// operation completes, in completion order, and `on_complete` once all of them have.
+#[no_mangle]
+pub extern "C" fn fs_async_batch_each(
+    operations: *const FsBatchOperation,
+    length: usize,
+    concurrency: usize,
+    on_item: *const AsyncBatchItemClosure,
+    on_complete: *const AsyncClosure,
+) {
+    if on_item.is_null() || on_complete.is_null() {
+        return;
+    }
+
+    let operations = batch_operations(operations, length);
+
+    let on_item = unsafe { &*on_item };
+    let on_complete = unsafe { &*on_complete };
+
+    let on_item = Arc::clone(&on_item.0);
+    let item_cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |result: Option<(usize, node_fs::batch::BatchResult)>, error| {
+            if error.is_some() {
+                on_item.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                on_item.on_success(result.map(|(index, result)| (index, FsBatchResult(result))))
+            }
+        }))
+    );
+
+    let on_complete = Arc::clone(&on_complete.0);
+    let complete_cb = Arc::new(
+        node_fs::a_sync::AsyncClosure::new(Box::new(move |_, error| {
+            if error.is_some() {
+                on_complete.on_error(error
+                    .map(node_core::error::error_from_io_error)
+                    .map(NodeError)
+                )
+            } else {
+                on_complete.on_success(None)
+            }
+        }))
+    );
+    node_fs::batch::batch_each(operations, concurrency, item_cb, complete_cb)
+}
+
+#[derive(Clone)]
+pub struct AsyncBatchClosure(Arc<node_fs::a_sync::AsyncClosure<Vec<FsBatchResult>, NodeError>>);
 
-                        let ptr = result.as_mut_ptr();
-                        let len = result.len();
-                        result.shrink_to_fit();
-                        std::mem::forget(result);
-                        let ret = ReaddirResultArray {
-                            data: ptr,
-                            length: len,
-                        };
+#[no_mangle]
+pub unsafe extern "C" fn fs_async_batch_closure_destroy(value: *mut AsyncBatchClosure) {
+    if value.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(value) };
+}
 
+#[derive(Clone)]
+pub struct AsyncBatchItemClosure(Arc<node_fs::a_sync::AsyncClosure<(usize, FsBatchResult), NodeError>>);
 
-                        let on_success: fn(*mut ReaddirResultArray, *mut c_void) = unsafe { std::mem::transmute(on_success) };
-                        on_success(Box::into_raw(
-                            Box::new(ret)
-                        ), data);
-                    }
-                }))
-            )
-        )
-    ))
+#[no_mangle]
+pub unsafe extern "C" fn fs_async_batch_item_closure_destroy(value: *mut AsyncBatchItemClosure) {
+    if value.is_null() {
+        return;
+    }
+    let _ = unsafe { Box::from_raw(value) };
 }
 
 #[no_mangle]
-pub extern "C" fn fs_async_create_async_fs_file_dir_closure(on_success: *mut c_void, on_error: *mut c_void, data: *mut c_void) -> *mut AsyncFileDirClosure {
+pub extern "C" fn fs_async_create_async_batch_closure(on_success: *mut c_void, on_error: *mut c_void, data: *mut c_void) -> *mut AsyncBatchClosure {
     Box::into_raw(Box::new(
-        AsyncFileDirClosure(
+        AsyncBatchClosure(
             Arc::new(
                 node_fs::a_sync::AsyncClosure::new(Box::new(move |value, error| {
                     if error.is_some() {
-                        let on_error = on_error as *const ();
-                        let on_error = unsafe { std::mem::transmute::<*const (), fn(*mut NodeError, *mut c_void)>(on_error) };
+                        let on_error: fn(*mut NodeError, *mut c_void) = unsafe { std::mem::transmute(on_error) };
                         on_error(Box::into_raw(Box::new(error.unwrap())), data);
                     } else {
-                        let on_success = on_success as *const ();
-                        let on_success = unsafe { std::mem::transmute::<*const (), fn(*mut FileDir, *mut c_void)>(on_success) };
-                        on_success(Box::into_raw(Box::new(value.unwrap())), data);
+                        let result: FsBatchResultArray = value.unwrap_or_default().into();
+                        let on_success: fn(*mut FsBatchResultArray, *mut c_void) = unsafe { std::mem::transmute(on_success) };
+                        on_success(Box::into_raw(Box::new(result)), data);
                     }
                 }))
             )
@@ -5742,20 +7568,20 @@ pub extern "C" fn fs_async_create_async_fs_file_dir_closure(on_success: *mut c_v
     ))
 }
 
+/// `on_success` receives `(index, result, data)`, the result is owned by the callee and must
+/// be released with `fs_batch_result_destroy`.
 #[no_mangle]
-pub extern "C" fn fs_async_create_async_fs_file_handle_closure(on_success: *mut c_void, on_error: *mut c_void, data: *mut c_void) -> *mut AsyncFileHandleClosure {
+pub extern "C" fn fs_async_create_async_batch_item_closure(on_success: *mut c_void, on_error: *mut c_void, data: *mut c_void) -> *mut AsyncBatchItemClosure {
     Box::into_raw(Box::new(
-        AsyncFileHandleClosure(
+        AsyncBatchItemClosure(
             Arc::new(
                 node_fs::a_sync::AsyncClosure::new(Box::new(move |value, error| {
                     if error.is_some() {
-                        let on_error = on_error as *const ();
-                        let on_error = unsafe { std::mem::transmute::<*const (), fn(*mut NodeError, *mut c_void)>(on_error) };
+                        let on_error: fn(*mut NodeError, *mut c_void) = unsafe { std::mem::transmute(on_error) };
                         on_error(Box::into_raw(Box::new(error.unwrap())), data);
-                    } else {
-                        let on_success = on_success as *const ();
-                        let on_success = unsafe { std::mem::transmute::<*const (), fn(*mut FileHandle, *mut c_void)>(on_success) };
-                        on_success(Box::into_raw(Box::new(value.unwrap())), data);
+                    } else if let Some((index, result)) = value {
+                        let on_success: fn(usize, *mut FsBatchResult, *mut c_void) = unsafe { std::mem::transmute(on_success) };
+                        on_success(index, Box::into_raw(Box::new(result)), data);
                     }
                 }))
             )
@@ -5764,34 +7590,340 @@ pub extern "C" fn fs_async_create_async_fs_file_handle_closure(on_success: *mut
 }
 
 
-impl From<node_fs::file_stat::FileStat> for FileStat {
-    fn from(value: node_fs::file_stat::FileStat) -> Self {
-        FileStat {
-            dev: value.dev,
-            ino: value.ino,
-            mode: value.mode,
-            nlink: value.nlink,
-            uid: value.uid,
-            gid: value.gid,
-            rdev: value.rdev,
-            size: value.size,
-            blksize: value.blksize,
-            blocks: value.blocks,
-            atimeMs: value.atimeMs,
-            mtimeMs: value.mtimeMs,
-            ctimeMs: value.ctimeMs,
-            birthtimeMs: value.birthtimeMs,
-            birthtime: value.birthtime,
-            atime: value.atime,
-            mtime: value.mtime,
-            ctime: value.ctime,
-            isBlockDevice: value.isBlockDevice,
-            isCharacterDevice: value.isCharacterDevice,
-            isDirectory: value.isDirectory,
-            isFIFO: value.isFIFO,
-            isFile: value.isFile,
-            isSocket: value.isSocket,
-            isSymbolicLink: value.isSymbolicLink,
+/// Receives the Chrome trace JSON, the string is only valid for the duration of the call.
+pub type TraceCallback = Option<extern "C" fn(json: *const c_char, length: usize, data: *mut c_void)>;
+
+/// Starts recording the comma separated categories: `node.fs.sync`, `node.fs.async`
+/// and `node.buffer` (allocations over 1 MiB).
+#[no_mangle]
+pub extern "C" fn node_trace_enable(categories: *const c_char) {
+    if categories.is_null() {
+        return;
+    }
+    let categories = unsafe { CStr::from_ptr(categories) };
+    let categories = categories.to_string_lossy();
+    node_core::trace::enable(categories.as_ref());
+}
+
+#[no_mangle]
+pub extern "C" fn node_trace_disable() {
+    node_core::trace::disable();
+}
+
+#[no_mangle]
+pub extern "C" fn node_trace_set_large_allocation_threshold(bytes: usize) {
+    node_core::trace::set_large_allocation_threshold(bytes);
+}
+
+/// Older events are dropped once `count` are waiting to be flushed, the default keeps 100000.
+#[no_mangle]
+pub extern "C" fn node_trace_set_max_events(count: usize) {
+    node_core::trace::set_max_events(count);
+}
+
+/// Writes and clears the recorded events, returns false and sets the last error on failure.
+#[no_mangle]
+pub extern "C" fn node_trace_flush_to_file(path: *const c_char) -> bool {
+    if path.is_null() {
+        return false;
+    }
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
+
+    match node_core::trace::flush_to_file(path.as_ref()) {
+        Ok(_) => true,
+        Err(error) => {
+            update_last_error(node_core::error::error_from_io_error(error));
+            false
+        }
+    }
+}
+
+/// Hands the recorded events to `callback` and clears them.
+#[no_mangle]
+pub extern "C" fn node_trace_flush(callback: TraceCallback, data: *mut c_void) {
+    let json = node_core::trace::flush();
+    if let Some(callback) = callback {
+        let length = json.len();
+        let json = CString::new(json).unwrap();
+        callback(json.as_ptr(), length, data);
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum BlobPartType {
+    BlobPartTypeBytes,
+    BlobPartTypeBuffer,
+    BlobPartTypeString,
+    BlobPartTypeBlob,
+}
+
+/// A source of `blob_create`, `data`/`length` are read for bytes and strings (UTF-8, may
+/// contain NULs), `buffer` for buffers and `blob` for blobs.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct BlobPart {
+    pub type_: BlobPartType,
+    pub data: *const u8,
+    pub length: usize,
+    pub buffer: *const Buffer,
+    pub blob: *const Blob,
+}
+
+pub struct Blob(node_buffer::blob::Blob);
+
+pub struct File(node_buffer::blob::File);
+
+pub struct BlobStream(node_buffer::blob::BlobStream);
+
+fn blob_parts<'a>(parts: *const BlobPart, length: usize) -> Option<Vec<node_buffer::blob::BlobPart<'a>>> {
+    let parts = unsafe { string_slice(parts, length) }?;
+    parts.iter().map(|part| {
+        match part.type_ {
+            BlobPartType::BlobPartTypeBytes => {
+                unsafe { string_slice(part.data, part.length) }.map(node_buffer::blob::BlobPart::Bytes)
+            }
+            BlobPartType::BlobPartTypeString => {
+                unsafe { string_slice(part.data, part.length) }
+                    .and_then(|data| std::str::from_utf8(data).ok())
+                    .map(node_buffer::blob::BlobPart::String)
+            }
+            BlobPartType::BlobPartTypeBuffer => {
+                unsafe { part.buffer.as_ref() }.map(|buffer| node_buffer::blob::BlobPart::Buffer(&buffer.0))
+            }
+            BlobPartType::BlobPartTypeBlob => {
+                unsafe { part.blob.as_ref() }.map(|blob| node_buffer::blob::BlobPart::Blob(&blob.0))
+            }
         }
+    }).collect()
+}
+
+fn optional_str<'a>(value: *const c_char) -> Cow<'a, str> {
+    if value.is_null() {
+        return Cow::Borrowed("");
+    }
+    unsafe { CStr::from_ptr(value) }.to_string_lossy()
+}
+
+/// Returns null if a part is null or a string part isn't valid UTF-8.
+#[no_mangle]
+pub extern "C" fn blob_create(parts: *const BlobPart, length: usize, type_: *const c_char) -> *mut Blob {
+    let Some(parts) = blob_parts(parts, length) else {
+        return std::ptr::null_mut();
+    };
+    let type_ = optional_str(type_);
+    Box::into_raw(Box::new(Blob(node_buffer::blob::Blob::new(&parts, type_.as_ref()))))
+}
+
+#[no_mangle]
+pub extern "C" fn blob_destroy(blob: *mut Blob) {
+    if blob.is_null() {
+        return;
+    }
+
+    let _ = unsafe { Box::from_raw(blob) };
+}
+
+/// Another reference to the same immutable data.
+#[no_mangle]
+pub extern "C" fn blob_clone(blob: *const Blob) -> *mut Blob {
+    if blob.is_null() {
+        return std::ptr::null_mut();
+    }
+    let blob = unsafe { &*blob };
+    Box::into_raw(Box::new(Blob(blob.0.clone())))
+}
+
+#[no_mangle]
+pub extern "C" fn blob_get_size(blob: *const Blob) -> usize {
+    if blob.is_null() {
+        return 0;
+    }
+    let blob = unsafe { &*blob };
+    blob.0.size()
+}
+
+#[no_mangle]
+pub extern "C" fn blob_get_type(blob: *const Blob) -> *const c_char {
+    if blob.is_null() {
+        return std::ptr::null();
+    }
+    let blob = unsafe { &*blob };
+    CString::new(blob.0.content_type()).unwrap().into_raw()
+}
+
+/// `blob.slice(start, end, contentType)`, pass `end` as the size to slice to the end.
+#[no_mangle]
+pub extern "C" fn blob_slice(blob: *const Blob, start: isize, end: isize, type_: *const c_char) -> *mut Blob {
+    if blob.is_null() {
+        return std::ptr::null_mut();
+    }
+    let blob = unsafe { &*blob };
+    let type_ = optional_str(type_);
+    Box::into_raw(Box::new(Blob(blob.0.slice(Some(start), Some(end), Some(type_.as_ref())))))
+}
+
+/// Reads up to `length` bytes at `offset` into `data`, returns the count read or -1 with the
+/// last error set.
+#[no_mangle]
+pub extern "C" fn blob_read_at(blob: *const Blob, offset: usize, data: *mut u8, length: usize) -> isize {
+    if blob.is_null() || (data.is_null() && length > 0) {
+        return -1;
+    }
+    let blob = unsafe { &*blob };
+    let data = if length == 0 { &mut [] } else { unsafe { std::slice::from_raw_parts_mut(data, length) } };
+    match blob.0.read_at(offset, data) {
+        Ok(read) => read as isize,
+        Err(err) => {
+            update_last_error(err);
+            -1
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn blob_array_buffer(blob: *const Blob) -> *mut Buffer {
+    if blob.is_null() {
+        return std::ptr::null_mut();
+    }
+    let blob = unsafe { &*blob };
+    match blob.0.array_buffer() {
+        Ok(buffer) => Box::into_raw(Buffer(buffer).into_box()),
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn blob_text(blob: *const Blob) -> *const c_char {
+    if blob.is_null() {
+        return std::ptr::null();
+    }
+    let blob = unsafe { &*blob };
+    match blob.0.text() {
+        Ok(text) => decoded_to_c_string(text),
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null()
+        }
+    }
+}
+
+/// `chunk_size` of 0 uses Node's 64 KiB.
+#[no_mangle]
+pub extern "C" fn blob_stream(blob: *const Blob, chunk_size: usize) -> *mut BlobStream {
+    if blob.is_null() {
+        return std::ptr::null_mut();
+    }
+    let blob = unsafe { &*blob };
+    Box::into_raw(Box::new(BlobStream(blob.0.stream(Some(chunk_size)))))
+}
+
+/// Returns a copy of the next chunk, or null once the blob is exhausted or a read failed in
+/// which case the last error is set.
+#[no_mangle]
+pub extern "C" fn blob_stream_next(stream: *mut BlobStream) -> *mut Buffer {
+    if stream.is_null() {
+        return std::ptr::null_mut();
+    }
+    let stream = unsafe { &mut *stream };
+    match stream.0.next() {
+        Some(Ok(buffer)) => Box::into_raw(Buffer(buffer).into_box()),
+        Some(Err(err)) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
+        None => std::ptr::null_mut()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn blob_stream_destroy(stream: *mut BlobStream) {
+    if stream.is_null() {
+        return;
+    }
+
+    let _ = unsafe { Box::from_raw(stream) };
+}
+
+/// `fs.openAsBlob(path, { type })`, the file is read lazily and reads set a `NotReadableError`
+/// once it was modified after opening.
+#[no_mangle]
+pub extern "C" fn fs_open_as_blob(path: *const c_char, type_: *const c_char) -> *mut Blob {
+    if path.is_null() {
+        return std::ptr::null_mut();
+    }
+    let path = unsafe { CStr::from_ptr(path) };
+    let path = path.to_string_lossy();
+    let type_ = optional_str(type_);
+
+    match node_fs::blob::open_as_blob(path.as_ref(), type_.as_ref())
+        .map_err(|e| node_core::error::error_from_io_error(e)) {
+        Ok(blob) => Box::into_raw(Box::new(Blob(blob))),
+        Err(err) => {
+            update_last_error(err);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// `last_modified` is in ms since the epoch, a negative value uses the current time.
+#[no_mangle]
+pub extern "C" fn file_create(
+    parts: *const BlobPart,
+    length: usize,
+    name: *const c_char,
+    type_: *const c_char,
+    last_modified: i64,
+) -> *mut File {
+    let Some(parts) = blob_parts(parts, length) else {
+        return std::ptr::null_mut();
+    };
+    let name = optional_str(name);
+    let type_ = optional_str(type_);
+    let last_modified = (last_modified >= 0).then_some(last_modified);
+    Box::into_raw(Box::new(File(node_buffer::blob::File::new(&parts, name.as_ref(), type_.as_ref(), last_modified))))
+}
+
+#[no_mangle]
+pub extern "C" fn file_destroy(file: *mut File) {
+    if file.is_null() {
+        return;
+    }
+
+    let _ = unsafe { Box::from_raw(file) };
+}
+
+#[no_mangle]
+pub extern "C" fn file_get_name(file: *const File) -> *const c_char {
+    if file.is_null() {
+        return std::ptr::null();
+    }
+    let file = unsafe { &*file };
+    match CString::new(file.0.name()) {
+        Ok(name) => name.into_raw(),
+        Err(_) => std::ptr::null()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn file_get_last_modified(file: *const File) -> i64 {
+    if file.is_null() {
+        return 0;
+    }
+    let file = unsafe { &*file };
+    file.0.last_modified()
+}
+
+/// The file's data as a `Blob`, use it with the `blob_*` functions.
+#[no_mangle]
+pub extern "C" fn file_get_blob(file: *const File) -> *mut Blob {
+    if file.is_null() {
+        return std::ptr::null_mut();
     }
-}
\ No newline at end of file
+    let file = unsafe { &*file };
+    Box::into_raw(Box::new(Blob(file.0.blob().clone())))
+}
@@ -0,0 +1,29 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Node's `AbortSignal` for native work, clones share the same state so the host keeps one
+/// to call `abort` while the operation polls another between steps.
+#[derive(Clone, Debug, Default)]
+pub struct AbortSignal(Arc<AtomicBool>);
+
+impl AbortSignal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn abort(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    pub fn aborted(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// `signal.throwIfAborted()`, fails with an `AbortError` once aborted.
+    pub fn throw_if_aborted(&self) -> crate::error::Result<()> {
+        if self.aborted() {
+            return Err(crate::error::abort_error());
+        }
+        Ok(())
+    }
+}
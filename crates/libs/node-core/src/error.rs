@@ -7,9 +7,10 @@ use std::fmt::{Display, Formatter};
 /// wrapped in an `anyhow::Error`. To retrieve the error class name from a wrapped
 /// `CustomError`, use the function `get_custom_error_class()`.
 ///
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct CustomError {
     class: &'static str,
+    code: Option<&'static str>,
     message: Cow<'static, str>,
 }
 
@@ -20,9 +21,108 @@ impl Display for CustomError {
 }
 
 
+/// OS errors keep their errno as Node's `code`, e.g. `ENOENT`, with a message such as
+/// `ENOENT: no such file or directory`.
 pub fn error_from_io_error(error: std::io::Error) -> anyhow::Error {
+    if let Some(errno) = error.raw_os_error() {
+        return os_error(errno);
+    }
     let kind = error.kind();
-    generic_error(kind.to_string())
+    match error.into_inner().map(|inner| inner.downcast::<CustomError>()) {
+        Some(Ok(error)) => (*error).into(),
+        _ => generic_error(kind.to_string()),
+    }
+}
+
+fn os_error(errno: i32) -> anyhow::Error {
+    let description = std::io::Error::from_raw_os_error(errno).to_string();
+    // strip std's " (os error N)" suffix, uv uses lowercase descriptions
+    let description = description.rsplit_once(" (os error").map_or(description.as_str(), |(description, _)| description);
+    let mut chars = description.chars();
+    let description = chars
+        .next()
+        .map(|first| first.to_lowercase().chain(chars).collect::<String>())
+        .unwrap_or_default();
+    let code = errno_name(errno);
+    CustomError {
+        class: "Error",
+        code,
+        message: match code {
+            Some(code) => format!("{code}: {description}").into(),
+            None => description.into(),
+        },
+    }
+        .into()
+}
+
+fn errno_name(errno: i32) -> Option<&'static str> {
+    let name = match errno {
+        libc::E2BIG => "E2BIG",
+        libc::EACCES => "EACCES",
+        libc::EADDRINUSE => "EADDRINUSE",
+        libc::EADDRNOTAVAIL => "EADDRNOTAVAIL",
+        libc::EAGAIN => "EAGAIN",
+        libc::EBADF => "EBADF",
+        libc::EBUSY => "EBUSY",
+        libc::ECANCELED => "ECANCELED",
+        libc::ECONNABORTED => "ECONNABORTED",
+        libc::ECONNREFUSED => "ECONNREFUSED",
+        libc::ECONNRESET => "ECONNRESET",
+        libc::EEXIST => "EEXIST",
+        libc::EFAULT => "EFAULT",
+        libc::EFBIG => "EFBIG",
+        libc::EINTR => "EINTR",
+        libc::EINVAL => "EINVAL",
+        libc::EIO => "EIO",
+        libc::EISDIR => "EISDIR",
+        libc::ELOOP => "ELOOP",
+        libc::EMFILE => "EMFILE",
+        libc::EMLINK => "EMLINK",
+        libc::ENAMETOOLONG => "ENAMETOOLONG",
+        libc::ENFILE => "ENFILE",
+        libc::ENODEV => "ENODEV",
+        libc::ENOENT => "ENOENT",
+        libc::ENOMEM => "ENOMEM",
+        libc::ENOSPC => "ENOSPC",
+        libc::ENOSYS => "ENOSYS",
+        libc::ENOTCONN => "ENOTCONN",
+        libc::ENOTDIR => "ENOTDIR",
+        libc::ENOTEMPTY => "ENOTEMPTY",
+        libc::ENOTSUP => "ENOTSUP",
+        libc::ENXIO => "ENXIO",
+        libc::EPERM => "EPERM",
+        libc::EPIPE => "EPIPE",
+        libc::ERANGE => "ERANGE",
+        libc::EROFS => "EROFS",
+        libc::ESPIPE => "ESPIPE",
+        libc::ESRCH => "ESRCH",
+        libc::ETIMEDOUT => "ETIMEDOUT",
+        libc::ETXTBSY => "ETXTBSY",
+        libc::EXDEV => "EXDEV",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Copies `error` for callers that only hold a reference to it, keeping its OS error code
+/// or the class, code and message an `into_io_error` error carries.
+pub fn clone_io_error(error: &std::io::Error) -> std::io::Error {
+    if let Some(code) = error.raw_os_error() {
+        return std::io::Error::from_raw_os_error(code);
+    }
+    match error.get_ref().and_then(|inner| inner.downcast_ref::<CustomError>()) {
+        Some(inner) => std::io::Error::new(error.kind(), inner.clone()),
+        None => std::io::Error::new(error.kind(), error.to_string()),
+    }
+}
+
+/// Carries an error from this module through an `io::Result`, `error_from_io_error` gives
+/// back the original class, code and message.
+pub fn into_io_error(error: anyhow::Error) -> std::io::Error {
+    match error.downcast::<CustomError>() {
+        Ok(error) => std::io::Error::other(error),
+        Err(error) => std::io::Error::other(error.to_string()),
+    }
 }
 
 
@@ -38,6 +138,12 @@ pub fn get_custom_error_message(error: &anyhow::Error) -> Option<Cow<str>> {
     error.downcast_ref::<CustomError>().map(|e| e.message.clone())
 }
 
+/// If this error was created from an `ErrorCode`, return Node's `code` e.g.
+/// `ERR_OUT_OF_RANGE`. In all other cases this function returns `None`.
+pub fn get_custom_error_code(error: &anyhow::Error) -> Option<&'static str> {
+    error.downcast_ref::<CustomError>().and_then(|e| e.code)
+}
+
 pub type AnyError = anyhow::Error;
 
 pub type Result<T> = anyhow::Result<T>;
@@ -49,6 +155,7 @@ pub fn custom_error(
 ) -> anyhow::Error {
     CustomError {
         class,
+        code: None,
         message: message.into(),
     }
         .into()
@@ -60,4 +167,154 @@ pub fn generic_error(message: impl Into<Cow<'static, str>>) -> anyhow::Error {
 
 pub fn type_error(message: impl Into<Cow<'static, str>>) -> anyhow::Error {
     custom_error("TypeError", message)
-}
\ No newline at end of file
+}
+
+pub fn range_error(message: impl Into<Cow<'static, str>>) -> anyhow::Error {
+    custom_error("RangeError", message)
+}
+
+/// Node's internal error codes, `class` and `code` match what Node sets on the thrown error.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ErrorCode {
+    InvalidArgType,
+    InvalidArgValue,
+    OutOfRange,
+    BufferOutOfBounds,
+    InvalidBufferSize,
+    UnknownEncoding,
+    FsFileTooLarge,
+    DirClosed,
+    IcuIllegalArgument,
+    IcuInvalidChar,
+    Abort,
+}
+
+impl ErrorCode {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorCode::InvalidArgType => "ERR_INVALID_ARG_TYPE",
+            ErrorCode::InvalidArgValue => "ERR_INVALID_ARG_VALUE",
+            ErrorCode::OutOfRange => "ERR_OUT_OF_RANGE",
+            ErrorCode::BufferOutOfBounds => "ERR_BUFFER_OUT_OF_BOUNDS",
+            ErrorCode::InvalidBufferSize => "ERR_INVALID_BUFFER_SIZE",
+            ErrorCode::UnknownEncoding => "ERR_UNKNOWN_ENCODING",
+            ErrorCode::FsFileTooLarge => "ERR_FS_FILE_TOO_LARGE",
+            ErrorCode::DirClosed => "ERR_DIR_CLOSED",
+            ErrorCode::IcuIllegalArgument => "U_ILLEGAL_ARGUMENT_ERROR",
+            ErrorCode::IcuInvalidChar => "U_INVALID_CHAR_FOUND",
+            ErrorCode::Abort => "ABORT_ERR",
+        }
+    }
+
+    pub fn class(&self) -> &'static str {
+        match self {
+            ErrorCode::InvalidArgType | ErrorCode::InvalidArgValue | ErrorCode::UnknownEncoding => "TypeError",
+            ErrorCode::OutOfRange | ErrorCode::BufferOutOfBounds | ErrorCode::InvalidBufferSize | ErrorCode::FsFileTooLarge => "RangeError",
+            ErrorCode::DirClosed | ErrorCode::IcuIllegalArgument | ErrorCode::IcuInvalidChar => "Error",
+            ErrorCode::Abort => "AbortError",
+        }
+    }
+
+    pub fn error(&self, message: impl Into<Cow<'static, str>>) -> anyhow::Error {
+        CustomError {
+            class: self.class(),
+            code: Some(self.code()),
+            message: message.into(),
+        }
+            .into()
+    }
+}
+
+/// `The argument 'name' is invalid. Received <value>`
+pub fn err_invalid_arg_value(name: &str, value: impl Display) -> anyhow::Error {
+    err_invalid_arg_value_with_reason(name, value, "is invalid")
+}
+
+pub fn err_invalid_arg_value_with_reason(name: &str, value: impl Display, reason: &str) -> anyhow::Error {
+    let kind = if name.contains('.') { "property" } else { "argument" };
+    ErrorCode::InvalidArgValue.error(format!(
+        "The {kind} '{name}' {reason}. Received {value}"
+    ))
+}
+
+/// `The value of "name" is out of range. It must be <range>. Received <received>`
+pub fn err_out_of_range(name: &str, range: &str, received: impl Display) -> anyhow::Error {
+    let received = add_numerical_separator(received.to_string());
+    ErrorCode::OutOfRange.error(format!(
+        "The value of \"{name}\" is out of range. It must be {range}. Received {received}"
+    ))
+}
+
+// node groups the digits of integers larger than 2 ** 32, e.g. `4_294_967_296`
+fn add_numerical_separator(value: String) -> String {
+    match value.parse::<i128>() {
+        Ok(number) if number.unsigned_abs() > 1 << 32 => {
+            let digits = number.unsigned_abs().to_string();
+            let mut output = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+            if number < 0 {
+                output.push('-');
+            }
+            for (i, digit) in digits.chars().enumerate() {
+                if i > 0 && (digits.len() - i) % 3 == 0 {
+                    output.push('_');
+                }
+                output.push(digit);
+            }
+            output
+        }
+        _ => value
+    }
+}
+
+/// `"name" is outside of buffer bounds`, or the generic message when `name` is `None`.
+pub fn err_buffer_out_of_bounds(name: Option<&str>) -> anyhow::Error {
+    match name {
+        Some(name) => ErrorCode::BufferOutOfBounds.error(format!("\"{name}\" is outside of buffer bounds")),
+        None => ErrorCode::BufferOutOfBounds.error("Attempt to access memory outside buffer bounds"),
+    }
+}
+
+/// `Buffer size must be a multiple of <bits>`
+pub fn err_invalid_buffer_size(bits: &str) -> anyhow::Error {
+    ErrorCode::InvalidBufferSize.error(format!("Buffer size must be a multiple of {bits}"))
+}
+
+pub fn err_unknown_encoding(encoding: impl Display) -> anyhow::Error {
+    ErrorCode::UnknownEncoding.error(format!("Unknown encoding: {encoding}"))
+}
+
+/// Thrown by `buffer.transcode` for an unsupported encoding pair.
+pub fn err_unable_to_transcode() -> anyhow::Error {
+    ErrorCode::IcuIllegalArgument.error("Unable to transcode Buffer [U_ILLEGAL_ARGUMENT_ERROR]")
+}
+
+/// Thrown by `buffer.transcode` between utf8 and ucs2 when the source isn't valid.
+pub fn err_transcode_invalid_char() -> anyhow::Error {
+    ErrorCode::IcuInvalidChar.error("Unable to transcode Buffer [U_INVALID_CHAR_FOUND]")
+}
+
+/// The `DOMException` a `Blob` read rejects with when its backing file changed or went away.
+pub fn not_readable_error() -> anyhow::Error {
+    custom_error("NotReadableError", "The blob could not be read")
+}
+
+/// `max` is the limit that was exceeded, Node's own `kIoMaxLength` (2 GiB - 1) reads as
+/// "2 GiB" like Node words it, any other limit is given in bytes.
+pub fn err_fs_file_too_large(size: u64, max: u64) -> anyhow::Error {
+    let limit = if max == (1 << 31) - 1 {
+        "2 GiB".to_string()
+    } else {
+        format!("{max} bytes")
+    };
+    ErrorCode::FsFileTooLarge.error(format!("File size ({size}) is greater than {limit}"))
+}
+
+/// What an operation cancelled through its `AbortSignal` fails with.
+pub fn abort_error() -> anyhow::Error {
+    ErrorCode::Abort.error("The operation was aborted")
+}
+
+/// What reading or closing a `Dir` fails with once it's been closed.
+pub fn err_dir_closed() -> anyhow::Error {
+    ErrorCode::DirClosed.error("Directory handle was closed")
+}
@@ -1,2 +1,4 @@
+pub mod abort;
 pub mod error;
-pub mod thread;
\ No newline at end of file
+pub mod thread;
+pub mod trace;
\ No newline at end of file
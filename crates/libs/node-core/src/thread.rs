@@ -2,7 +2,8 @@ pub fn spawn<F>(f: F)
     where F: FnOnce(),
           F: Send + 'static
 {
+    let queued = crate::trace::queued_at();
     rayon::spawn(move || {
-        f();
+        crate::trace::run_on_pool(queued, f);
     });
-}
\ No newline at end of file
+}
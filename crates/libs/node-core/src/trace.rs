@@ -0,0 +1,229 @@
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+pub const CATEGORY_FS_SYNC: &str = "node.fs.sync";
+pub const CATEGORY_FS_ASYNC: &str = "node.fs.async";
+pub const CATEGORY_BUFFER: &str = "node.buffer";
+
+const FLAG_FS_SYNC: u32 = 1 << 0;
+const FLAG_FS_ASYNC: u32 = 1 << 1;
+const FLAG_BUFFER: u32 = 1 << 2;
+
+/// Allocations of at least this many bytes are recorded under `node.buffer`.
+pub const DEFAULT_LARGE_ALLOCATION: usize = 1024 * 1024;
+
+/// How many events are kept between flushes before the oldest are dropped.
+pub const DEFAULT_MAX_EVENTS: usize = 100_000;
+
+static CATEGORIES: AtomicU32 = AtomicU32::new(0);
+static LARGE_ALLOCATION: AtomicUsize = AtomicUsize::new(DEFAULT_LARGE_ALLOCATION);
+static MAX_EVENTS: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_EVENTS);
+static EVENTS: Mutex<VecDeque<TraceEvent>> = Mutex::new(VecDeque::new());
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+static NEXT_TID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static TID: u64 = NEXT_TID.fetch_add(1, Ordering::Relaxed);
+    static ON_POOL: Cell<bool> = const { Cell::new(false) };
+}
+
+#[derive(Debug, Clone)]
+struct TraceEvent {
+    name: Cow<'static, str>,
+    category: &'static str,
+    ts: u64,
+    dur: u64,
+    tid: u64,
+    args: Option<(&'static str, u64)>,
+}
+
+fn flag(category: &str) -> u32 {
+    match category.trim() {
+        CATEGORY_FS_SYNC => FLAG_FS_SYNC,
+        CATEGORY_FS_ASYNC => FLAG_FS_ASYNC,
+        CATEGORY_BUFFER => FLAG_BUFFER,
+        "node.fs" | "node.fs.*" => FLAG_FS_SYNC | FLAG_FS_ASYNC,
+        "*" => FLAG_FS_SYNC | FLAG_FS_ASYNC | FLAG_BUFFER,
+        _ => 0,
+    }
+}
+
+fn now() -> u64 {
+    EPOCH.get_or_init(Instant::now).elapsed().as_micros() as u64
+}
+
+/// Starts recording the comma separated `categories`, e.g. `"node.fs.sync,node.fs.async"`,
+/// replacing any previously enabled set. Unknown categories are ignored.
+pub fn enable(categories: &str) {
+    let flags = categories.split(',').fold(0, |flags, category| flags | flag(category));
+    let _ = EPOCH.get_or_init(Instant::now);
+    CATEGORIES.store(flags, Ordering::SeqCst);
+}
+
+/// Stops recording, already recorded events are kept until flushed.
+pub fn disable() {
+    CATEGORIES.store(0, Ordering::SeqCst);
+}
+
+pub fn is_enabled(category: &str) -> bool {
+    let flags = CATEGORIES.load(Ordering::Relaxed);
+    flags != 0 && flags & flag(category) != 0
+}
+
+pub fn set_large_allocation_threshold(bytes: usize) {
+    LARGE_ALLOCATION.store(bytes, Ordering::Relaxed);
+}
+
+/// Caps the events kept between flushes, once reached each new event drops the oldest one.
+pub fn set_max_events(count: usize) {
+    MAX_EVENTS.store(count, Ordering::Relaxed);
+    if let Ok(mut events) = EVENTS.lock() {
+        let excess = events.len().saturating_sub(count);
+        events.drain(..excess);
+    }
+}
+
+fn record(event: TraceEvent) {
+    let max = MAX_EVENTS.load(Ordering::Relaxed);
+    if max == 0 {
+        return;
+    }
+    if let Ok(mut events) = EVENTS.lock() {
+        if events.len() >= max {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+}
+
+/// A begin/end pair recorded as a single complete event when dropped.
+#[must_use]
+pub struct TraceSpan {
+    name: Cow<'static, str>,
+    category: &'static str,
+    start: u64,
+    args: Option<(&'static str, u64)>,
+}
+
+impl TraceSpan {
+    pub fn new(category: &'static str, name: impl Into<Cow<'static, str>>) -> Option<Self> {
+        if !is_enabled(category) {
+            return None;
+        }
+        Some(Self {
+            name: name.into(),
+            category,
+            start: now(),
+            args: None,
+        })
+    }
+}
+
+impl Drop for TraceSpan {
+    fn drop(&mut self) {
+        let end = now();
+        record(TraceEvent {
+            name: std::mem::take(&mut self.name),
+            category: self.category,
+            ts: self.start,
+            dur: end.saturating_sub(self.start),
+            tid: TID.with(|tid| *tid),
+            args: self.args,
+        });
+    }
+}
+
+/// Span for a node-fs operation, categorised as `node.fs.async` when running on the I/O pool.
+pub fn fs_span(name: &'static str) -> Option<TraceSpan> {
+    let category = if ON_POOL.with(|on_pool| on_pool.get()) {
+        CATEGORY_FS_ASYNC
+    } else {
+        CATEGORY_FS_SYNC
+    };
+    TraceSpan::new(category, name)
+}
+
+/// Span for an allocation, only recorded when `size` reaches the large allocation threshold.
+pub fn allocation_span(size: usize) -> Option<TraceSpan> {
+    if size < LARGE_ALLOCATION.load(Ordering::Relaxed) {
+        return None;
+    }
+    TraceSpan::new(CATEGORY_BUFFER, "alloc").map(|mut span| {
+        span.args = Some(("size", size as u64));
+        span
+    })
+}
+
+pub(crate) fn queued_at() -> Option<u64> {
+    is_enabled(CATEGORY_FS_ASYNC).then(now)
+}
+
+/// Runs `f` as a pool task, recording how long it waited since `queued` when tracing.
+pub(crate) fn run_on_pool<F: FnOnce()>(queued: Option<u64>, f: F) {
+    if let Some(queued) = queued {
+        let start = now();
+        record(TraceEvent {
+            name: Cow::Borrowed("queued"),
+            category: CATEGORY_FS_ASYNC,
+            ts: queued,
+            dur: start.saturating_sub(queued),
+            tid: TID.with(|tid| *tid),
+            args: None,
+        });
+    }
+    let previous = ON_POOL.with(|on_pool| on_pool.replace(true));
+    f();
+    ON_POOL.with(|on_pool| on_pool.set(previous));
+}
+
+fn escape(value: &str, output: &mut String) {
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(output, "\\u{:04x}", c as u32);
+            }
+            c => output.push(c),
+        }
+    }
+}
+
+/// Takes every recorded event and returns it as a Chrome trace JSON document,
+/// loadable in `chrome://tracing` or Perfetto.
+pub fn flush() -> String {
+    let events = EVENTS.lock()
+        .map(|mut events| std::mem::take(&mut *events))
+        .unwrap_or_default();
+
+    let pid = std::process::id();
+    let mut output = String::from("{\"traceEvents\":[");
+    for (i, event) in events.iter().enumerate() {
+        if i > 0 {
+            output.push(',');
+        }
+        output.push_str("{\"name\":\"");
+        escape(&event.name, &mut output);
+        let _ = write!(
+            output,
+            "\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":{},\"tid\":{}",
+            event.category, event.ts, event.dur, pid, event.tid
+        );
+        if let Some((key, value)) = event.args {
+            let _ = write!(output, ",\"args\":{{\"{}\":{}}}", key, value);
+        }
+        output.push('}');
+    }
+    output.push_str("]}");
+    output
+}
+
+pub fn flush_to_file(path: impl AsRef<Path>) -> std::io::Result<()> {
+    std::fs::write(path, flush())
+}
@@ -2,12 +2,8 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::ffi::{c_void, CString, OsString};
 use std::fmt::Debug;
-use std::fs::File;
 use std::io::{Error, ErrorKind, Write};
 use std::os::raw::c_longlong;
-#[cfg(unix)]
-use std::os::unix::prelude::*;
-use std::panic::catch_unwind;
 use std::path::{Path, PathBuf};
 use std::ptr::NonNull;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -20,6 +16,7 @@ use notify::event::{AccessKind, CreateKind, DataChange, MetadataKind, ModifyKind
 use once_cell::sync::OnceCell;
 use parking_lot::Mutex;
 use node_buffer::{Buffer, StringEncoding};
+use node_core::abort::AbortSignal;
 
 use crate::file_dir::FileDir;
 use crate::file_dirent::FileDirent;
@@ -183,7 +180,7 @@ pub fn append_file_with_str(fd: c_int, data: &str, options: AppendFileOptions, c
 pub fn append_file_with_bytes(fd: c_int, data: &Buffer, options: AppendFileOptions, callback: Arc<AsyncClosure<(), Error>>) {
     let data = data.clone();
     let _ = node_core::thread::spawn(move || {
-        match super::sync::append_file_with_bytes(fd, data.buffer(), options) {
+        match super::sync::append_file_with_buffer(fd, &data, options) {
             Ok(_) => {
                 callback.on_success(None);
             }
@@ -223,7 +220,7 @@ pub fn append_file_with_path_bytes(
     let data = data.clone();
     let path = path.to_string();
     let _ = node_core::thread::spawn(move || {
-        match super::sync::append_file_with_path_bytes(&path, data.buffer(), options) {
+        match super::sync::append_file_with_path_buffer(&path, &data, options) {
             Ok(_) => {
                 callback.on_success(None);
             }
@@ -243,9 +240,7 @@ pub fn append_file_with_buffer_string(
     let mut dest = dest.clone();
     let data = data.to_string();
     let _ = node_core::thread::spawn(move || {
-        let mut dest = dest.buffer_mut();
-        let buffer = Buffer::from_string(CString::new(data).unwrap(), options.encoding);
-        match dest.write(buffer.buffer()).map(|_| ()) {
+        match super::sync::append_file_with_buffer_string(&mut dest, &data, options) {
             Ok(_) => {
                 callback.on_success(None);
             }
@@ -266,9 +261,7 @@ pub fn append_file_with_buffer_buffer(
     let mut dest = dest.clone();
     let data = data.clone();
     let _ = node_core::thread::spawn(move || {
-        let mut dest = dest.buffer_mut();
-        let data = data.buffer();
-        match dest.write(data).map(|_| ()) {
+        match super::sync::append_file_with_buffer_buffer(&mut dest, &data, options) {
             Ok(_) => {
                 callback.on_success(None);
             }
@@ -310,17 +303,12 @@ pub fn chown(path: &str, uid: c_uint, gid: c_uint, callback: Arc<AsyncClosure<()
 
 pub fn close(fd: c_int, callback: Arc<AsyncClosure<(), Error>>) {
     let _ = node_core::thread::spawn(move || {
-        match catch_unwind(|| {
-            let _ = unsafe { File::from_raw_fd(fd) };
-        }) {
+        match super::sync::close_fd(fd) {
             Ok(_) => {
                 callback.on_success(None);
             }
-            Err(_) => {
-                callback.on_error(Some(Error::new(
-                    ErrorKind::Other,
-                    "Failed to close descriptor",
-                )));
+            Err(error) => {
+                callback.on_error(Some(error));
             }
         }
     });
@@ -330,6 +318,7 @@ pub fn copy_file(src: &str, dest: &str, flags: c_uint, callback: Arc<AsyncClosur
     let src = src.to_string();
     let dest = dest.to_string();
     let _ = node_core::thread::spawn(move || {
+        let _span = node_core::trace::fs_span("copyFile");
         let src = Path::new(&src);
         let dest = Path::new(&dest);
         match super::copy_file::copy_file(src, dest, flags) {
@@ -343,14 +332,19 @@ pub fn copy_file(src: &str, dest: &str, flags: c_uint, callback: Arc<AsyncClosur
     });
 }
 
-pub fn cp(_src: &str, _dest: &str) {
-    // 
-    todo!()
-    // let src = Path::new(src);
-    // let dest = Path::new(dest);
-    // for entry in fs::read_dir(src)? {
-    //     let entry = entry?;
-    // }
+pub fn cp(src: &str, dest: &str, flags: c_uint, callback: Arc<AsyncClosure<(), Error>>) {
+    let src = src.to_string();
+    let dest = dest.to_string();
+    let _ = node_core::thread::spawn(move || {
+        match super::sync::cp(&src, &dest, flags) {
+            Ok(_) => {
+                callback.on_success(None);
+            }
+            Err(error) => {
+                callback.on_error(Some(error));
+            }
+        }
+    });
 }
 
 pub fn exists(path: &str, callback: Arc<AsyncClosure<bool, Error>>) {
@@ -593,7 +587,7 @@ pub fn read(
     let mut buffer = buffer.clone();
 
     let _ = node_core::thread::spawn(move || {
-        match super::sync::read(fd, buffer.buffer_mut(), offset, length, position) {
+        match buffer.with_slice_mut(|buffer| super::sync::read(fd, buffer, offset, length, position)) {
             Ok(read) => {
                 callback.on_success(Some(read));
             }
@@ -616,7 +610,7 @@ pub fn read_bytes(
     // only wrapping to make em happy
     let mut buf = unsafe { Buffer::from_reference(buffer.as_mut_ptr(), buffer.len()) };
     let _ = node_core::thread::spawn(move || {
-        match super::sync::read(fd, buf.buffer_mut(), offset, length, position) {
+        match buf.with_slice_mut(|buf| super::sync::read(fd, buf, offset, length, position)) {
             Ok(read) => {
                 callback.on_success(Some(read));
             }
@@ -673,6 +667,33 @@ pub fn read_file_with_fd(fd: c_int, options: ReadFileOptions, callback: Arc<Asyn
     });
 }
 
+pub fn read_file_with_signal(path: &str, options: ReadFileOptions, signal: AbortSignal, callback: Arc<AsyncClosure<FsEncoding, Error>>) {
+    let path = path.to_string();
+    let _ = node_core::thread::spawn(move || {
+        match super::sync::read_file_with_signal(&path, options, &signal) {
+            Ok(read) => {
+                callback.on_success(Some(read));
+            }
+            Err(error) => {
+                callback.on_error(Some(error));
+            }
+        }
+    });
+}
+
+pub fn read_file_with_fd_and_signal(fd: c_int, options: ReadFileOptions, signal: AbortSignal, callback: Arc<AsyncClosure<FsEncoding, Error>>) {
+    let _ = node_core::thread::spawn(move || {
+        match super::sync::read_file_with_fd_and_signal(fd, options, &signal) {
+            Ok(read) => {
+                callback.on_success(Some(read));
+            }
+            Err(error) => {
+                callback.on_error(Some(error));
+            }
+        }
+    });
+}
+
 pub fn read_link(path: &str, options: ReadLinkOptions, callback: Arc<AsyncClosure<FsEncoding, Error>>) {
     let path = path.to_string();
     let _ = node_core::thread::spawn(move || {
@@ -1277,7 +1298,7 @@ pub fn write(
 ) {
     let buffer = buffer.clone();
     let _ = node_core::thread::spawn(move || {
-        match super::sync::write(fd, buffer.buffer(), options) {
+        match buffer.with_slice(|buffer| super::sync::write(fd, buffer, options)) {
             Ok(wrote) => {
                 callback.on_success(Some(wrote));
             }
@@ -1332,7 +1353,7 @@ pub fn write_file_with_str(
 pub fn write_file_with_bytes(fd: c_int, data: &Buffer, options: WriteFileOptions, callback: Arc<AsyncClosure<(), Error>>) {
     let data = data.clone();
     let _ = node_core::thread::spawn(move || {
-        match super::sync::write_file_with_bytes(fd, data.buffer(), options) {
+        match data.with_slice(|data| super::sync::write_file_with_bytes(fd, data, options)) {
             Ok(_) => {
                 callback.on_success(None);
             }
@@ -1374,11 +1395,7 @@ pub fn write_file_with_bytes_from_path(
     let data = data.clone();
 
     let _ = node_core::thread::spawn(move || {
-        match super::sync::write_file_with_bytes_from_path(
-            &path,
-            data.buffer(),
-            options,
-        ) {
+        match super::sync::write_file_with_buffer_from_path(&path, &data, options) {
             Ok(_) => {
                 callback.on_success(None);
             }
@@ -0,0 +1,162 @@
+use std::io::Error;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use libc::c_int;
+use parking_lot::Mutex;
+
+use crate::a_sync::AsyncClosure;
+use crate::file_stat::FileStat;
+use crate::prelude::{FsEncoding, handle_meta};
+use crate::sync::{ReaddirOptions, ReaddirResult, ReadFileOptions};
+
+#[derive(Clone, Debug)]
+pub enum BatchOperation {
+    Stat(String),
+    Lstat(String),
+    ReadFile(String, ReadFileOptions),
+    Access(String, c_int),
+    Exists(String),
+    Readdir(String, ReaddirOptions),
+}
+
+#[derive(Debug)]
+pub enum BatchResult {
+    Stat(FileStat),
+    ReadFile(FsEncoding),
+    Access,
+    Exists(bool),
+    Readdir(Vec<ReaddirResult>),
+    Error(Error),
+}
+
+// readdir only produces `FileDirent::new_regular` entries which own a `fs::DirEntry`,
+// the raw `libc::dirent` variant is never part of a batch result.
+unsafe impl Send for BatchResult {}
+
+impl BatchOperation {
+    pub fn run(&self) -> BatchResult {
+        let result = match self {
+            BatchOperation::Stat(path) => {
+                super::sync::stat(path).map(|metadata| BatchResult::Stat(handle_meta(&metadata)))
+            }
+            BatchOperation::Lstat(path) => {
+                super::sync::lstat(path).map(|metadata| BatchResult::Stat(handle_meta(&metadata)))
+            }
+            BatchOperation::ReadFile(path, options) => {
+                super::sync::read_file(path, *options).map(BatchResult::ReadFile)
+            }
+            BatchOperation::Access(path, mode) => {
+                super::sync::access(path, *mode).map(|_| BatchResult::Access)
+            }
+            BatchOperation::Exists(path) => {
+                Ok(BatchResult::Exists(super::sync::exists(path)))
+            }
+            BatchOperation::Readdir(path, options) => {
+                super::sync::readdir(path, *options).map(BatchResult::Readdir)
+            }
+        };
+
+        result.unwrap_or_else(BatchResult::Error)
+    }
+}
+
+fn worker_count(concurrency: usize, length: usize) -> usize {
+    let concurrency = if concurrency == 0 {
+        num_cpus::get()
+    } else {
+        concurrency
+    };
+    concurrency.min(length).max(1)
+}
+
+/// Runs at most `concurrency` operations at a time on the I/O pool (`0` uses the cpu count).
+/// `on_item` receives each result with its index as soon as it completes, `on_complete` runs
+/// once after every operation has finished.
+fn dispatch<F, C>(operations: Vec<BatchOperation>, concurrency: usize, on_item: F, on_complete: C)
+    where F: Fn(usize, BatchResult) + Send + Sync + 'static,
+          C: FnOnce() + Send + 'static
+{
+    let length = operations.len();
+    if length == 0 {
+        on_complete();
+        return;
+    }
+
+    let operations = Arc::new(operations);
+    let next = Arc::new(AtomicUsize::new(0));
+    let remaining = Arc::new(AtomicUsize::new(length));
+    let on_item = Arc::new(on_item);
+    let on_complete = Arc::new(Mutex::new(Some(on_complete)));
+
+    for _ in 0..worker_count(concurrency, length) {
+        let operations = Arc::clone(&operations);
+        let next = Arc::clone(&next);
+        let remaining = Arc::clone(&remaining);
+        let on_item = Arc::clone(&on_item);
+        let on_complete = Arc::clone(&on_complete);
+        let _ = node_core::thread::spawn(move || {
+            loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                if index >= operations.len() {
+                    break;
+                }
+
+                on_item(index, operations[index].run());
+
+                if remaining.fetch_sub(1, Ordering::SeqCst) == 1 {
+                    if let Some(on_complete) = on_complete.lock().take() {
+                        on_complete();
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Runs every operation in order on the calling thread.
+pub fn batch_sync(operations: &[BatchOperation]) -> Vec<BatchResult> {
+    operations.iter().map(BatchOperation::run).collect()
+}
+
+/// Runs the operations on the I/O pool and delivers all results, in submission order, at once.
+pub fn batch(
+    operations: Vec<BatchOperation>,
+    concurrency: usize,
+    callback: Arc<AsyncClosure<Vec<BatchResult>, Error>>,
+) {
+    let length = operations.len();
+    let results: Arc<Mutex<Vec<Option<BatchResult>>>> = Arc::new(Mutex::new((0..length).map(|_| None).collect()));
+    let slots = Arc::clone(&results);
+    dispatch(
+        operations,
+        concurrency,
+        move |index, result| {
+            slots.lock()[index] = Some(result);
+        },
+        move || {
+            let results = std::mem::take(&mut *results.lock());
+            callback.on_success(Some(results.into_iter().flatten().collect()));
+        },
+    );
+}
+
+/// Runs the operations on the I/O pool, `on_item` is called with `(index, result)` as each one
+/// finishes and `on_complete` once they all have.
+pub fn batch_each(
+    operations: Vec<BatchOperation>,
+    concurrency: usize,
+    on_item: Arc<AsyncClosure<(usize, BatchResult), Error>>,
+    on_complete: Arc<AsyncClosure<(), Error>>,
+) {
+    dispatch(
+        operations,
+        concurrency,
+        move |index, result| {
+            on_item.on_success(Some((index, result)));
+        },
+        move || {
+            on_complete.on_success(None);
+        },
+    );
+}
@@ -0,0 +1,71 @@
+use std::fs::{File, Metadata};
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use node_buffer::blob::{Blob, BlobSource};
+
+/// The file behind `fs.openAsBlob`, size and mtime are taken when opened and every read
+/// fails with `NotReadableError` if the file no longer matches them.
+#[derive(Debug)]
+pub struct FileBlobSource {
+    path: PathBuf,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+impl FileBlobSource {
+    pub fn open(path: &str) -> io::Result<Self> {
+        let metadata = std::fs::metadata(path)?;
+        if metadata.is_dir() {
+            return Err(io::Error::from_raw_os_error(libc::EISDIR));
+        }
+        Ok(Self {
+            path: PathBuf::from(path),
+            size: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+
+    fn unchanged(&self, metadata: &Metadata) -> bool {
+        metadata.len() == self.size && metadata.modified().ok() == self.modified
+    }
+
+    fn open_unchanged(&self) -> node_core::error::Result<File> {
+        let file = File::open(&self.path).map_err(|_| node_core::error::not_readable_error())?;
+        match file.metadata() {
+            Ok(metadata) if self.unchanged(&metadata) => Ok(file),
+            _ => Err(node_core::error::not_readable_error())
+        }
+    }
+}
+
+#[cfg(unix)]
+fn read_at(file: &File, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+    std::os::unix::fs::FileExt::read_at(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn read_at(file: &File, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+    std::os::windows::fs::FileExt::seek_read(file, buf, offset)
+}
+
+impl BlobSource for FileBlobSource {
+    fn size(&self) -> usize {
+        self.size as usize
+    }
+
+    fn read_at(&self, offset: usize, buf: &mut [u8]) -> node_core::error::Result<usize> {
+        let _span = node_core::trace::fs_span("read");
+        let file = self.open_unchanged()?;
+        read_at(&file, offset as u64, buf).map_err(node_core::error::error_from_io_error)
+    }
+}
+
+/// `fs.openAsBlob(path, { type })`, nothing is read until the blob is.
+pub fn open_as_blob(path: &str, content_type: &str) -> io::Result<Blob> {
+    let _span = node_core::trace::fs_span("openAsBlob");
+    let source = FileBlobSource::open(path)?;
+    Ok(Blob::from_source(Arc::new(source), content_type))
+}
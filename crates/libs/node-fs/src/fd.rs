@@ -0,0 +1,139 @@
+use std::backtrace::Backtrace;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io;
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::os::unix::io::{FromRawFd, IntoRawFd, RawFd};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+
+/// A descriptor that belongs to the caller, usable as a `File` for the length of a call and
+/// never closed by dropping it. Every function taking a raw fd goes through this, only
+/// `close` closes one.
+pub struct BorrowedFile(ManuallyDrop<File>);
+
+impl BorrowedFile {
+    /// # Safety
+    ///
+    /// `fd` must stay open while the value is alive.
+    pub unsafe fn new(fd: RawFd) -> Self {
+        if tracking() {
+            check_use(fd);
+        }
+        Self(ManuallyDrop::new(File::from_raw_fd(fd)))
+    }
+}
+
+impl Deref for BorrowedFile {
+    type Target = File;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for BorrowedFile {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Hands an open file over to the caller as a raw fd, which it now owns and closes with
+/// `close`.
+pub fn into_raw(file: File) -> RawFd {
+    let fd = file.into_raw_fd();
+    if tracking() {
+        record_open(fd);
+    }
+    fd
+}
+
+/// Takes ownership of a caller's fd, it is closed when the returned `File` drops.
+///
+/// # Safety
+///
+/// `fd` must be open and not used by the caller afterwards.
+pub unsafe fn from_raw(fd: RawFd) -> File {
+    if tracking() {
+        check_use(fd);
+        TRACKER.lock().open.remove(&fd);
+    }
+    File::from_raw_fd(fd)
+}
+
+/// `fs.close`, reports `EBADF` and the like instead of ignoring them as dropping a `File`
+/// does.
+pub fn close(fd: RawFd) -> io::Result<()> {
+    if tracking() {
+        record_close(fd);
+    }
+    if unsafe { libc::close(fd) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+static TRACKING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Default)]
+struct Tracker {
+    // Where each fd node-fs handed out was opened.
+    open: HashMap<RawFd, Backtrace>,
+    // Where each fd was closed, until node-fs hands the number out again.
+    closed: HashMap<RawFd, Backtrace>,
+}
+
+static TRACKER: Lazy<Mutex<Tracker>> = Lazy::new(Default::default);
+
+pub fn tracking() -> bool {
+    TRACKING.load(Ordering::Relaxed)
+}
+
+/// Debug aid that records a backtrace for every fd node-fs opens or closes. Double closes
+/// and uses of a closed fd are logged as errors when they happen, leaks once
+/// `report_leaks` is called. Only fds opened after enabling are tracked.
+///
+/// The OS reuses fd numbers, so an fd the host opened and passed in after node-fs closed the
+/// same number is reported as a use after close.
+pub fn set_tracking(enabled: bool) {
+    let mut tracker = TRACKER.lock();
+    *tracker = Tracker::default();
+    TRACKING.store(enabled, Ordering::Relaxed);
+}
+
+/// Logs every tracked fd that is still open with the backtrace of where it was opened, and
+/// returns how many there are.
+pub fn report_leaks() -> usize {
+    let tracker = TRACKER.lock();
+    for (fd, opened) in tracker.open.iter() {
+        log::warn!("fd {fd} is still open, opened at:\n{opened}");
+    }
+    tracker.open.len()
+}
+
+fn record_open(fd: RawFd) {
+    let mut tracker = TRACKER.lock();
+    tracker.closed.remove(&fd);
+    tracker.open.insert(fd, Backtrace::force_capture());
+}
+
+fn record_close(fd: RawFd) {
+    let backtrace = Backtrace::force_capture();
+    let mut tracker = TRACKER.lock();
+    if let Some(closed) = tracker.closed.get(&fd) {
+        log::error!("fd {fd} closed twice at:\n{backtrace}\npreviously closed at:\n{closed}");
+    }
+    tracker.open.remove(&fd);
+    tracker.closed.insert(fd, backtrace);
+}
+
+fn check_use(fd: RawFd) {
+    let tracker = TRACKER.lock();
+    if let Some(closed) = tracker.closed.get(&fd) {
+        let backtrace = Backtrace::force_capture();
+        log::error!("fd {fd} used after close at:\n{backtrace}\nclosed at:\n{closed}");
+    }
+}
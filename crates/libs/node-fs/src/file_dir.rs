@@ -14,7 +14,8 @@ use crate::file_dirent::FileDirent;
 
 #[derive(Clone)]
 #[repr(transparent)]
-struct FileDirInner(Arc<RwLock<(String,NonNull<DIR>)>>);
+// The `DIR` is taken out once closed.
+struct FileDirInner(Arc<RwLock<(String, Option<NonNull<DIR>>)>>);
 
 unsafe impl Send for FileDirInner {}
 
@@ -36,12 +37,14 @@ impl Drop for FileDir {
 
 impl FileDir {
     pub(crate) fn new(path: String, dir: *mut DIR) -> FileDir {
-        Self(FileDirInner(Arc::new(RwLock::new((path, NonNull::new(dir).unwrap())))))
+        Self(FileDirInner(Arc::new(RwLock::new((path, Some(NonNull::new(dir).unwrap()))))))
     }
 
+    /// Fails with `ERR_DIR_CLOSED` if this or any clone of it was closed already.
     pub fn close(&self) -> std::io::Result<()> {
-        let dir = self.0.0.read();
-        let ret = unsafe { libc::closedir(dir.1.as_ptr() as _) };
+        let _span = node_core::trace::fs_span("closedir");
+        let dir = self.0.0.write().1.take().ok_or_else(dir_closed)?;
+        let ret = unsafe { libc::closedir(dir.as_ptr()) };
 
         if ret == -1 {
             let last_error = std::io::Error::last_os_error();
@@ -67,15 +70,15 @@ impl FileDir {
         RwLockReadGuard::map(self.0.0.read(), |f| f.0.as_str())
     }
 
-    pub(crate) fn dir(&self) -> MappedRwLockReadGuard<'_, RawRwLock, *mut DIR> {
-        RwLockReadGuard::map(self.0.0.read(), |f| {
-            unsafe {std::mem::transmute(f.1.as_ptr())}
-        })
+    pub(crate) fn dir(&self) -> MappedRwLockReadGuard<'_, RawRwLock, Option<NonNull<DIR>>> {
+        RwLockReadGuard::map(self.0.0.read(), |f| &f.1)
     }
 
+    /// Fails with `ERR_DIR_CLOSED` once this or any clone of it was closed.
     pub fn read(&self) -> std::io::Result<FileDirent> {
+        let _span = node_core::trace::fs_span("readdir");
         let lock = self.0.0.read();
-        let dir = unsafe { lock.1.as_ptr() };
+        let dir = lock.1.ok_or_else(dir_closed)?.as_ptr();
         let ret = unsafe { libc::readdir(dir) };
 
         if ret.is_null() {
@@ -106,3 +109,7 @@ impl FileDir {
         });
     }
 }
+
+fn dir_closed() -> std::io::Error {
+    node_core::error::into_io_error(node_core::error::err_dir_closed())
+}
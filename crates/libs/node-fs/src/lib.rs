@@ -5,11 +5,15 @@ mod constants;
 pub use constants::*;
 
 pub mod a_sync;
+pub mod batch;
+pub mod blob;
 pub mod copy_file;
+pub mod fd;
 pub mod file_dir;
 pub mod file_dirent;
 pub mod file_handle;
 pub mod file_stat;
+pub mod mmap;
 pub mod prelude;
 pub mod sync;
 
@@ -0,0 +1,103 @@
+use std::ffi::{c_int, c_void};
+use std::fs::File;
+use std::io;
+use std::os::unix::io::AsRawFd;
+
+use node_buffer::Buffer;
+
+/// The `madvise` hint applied to the whole mapping.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum MapAdvice {
+    #[default]
+    Normal,
+    Sequential,
+    Random,
+    WillNeed,
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct MapOptions {
+    advice: MapAdvice,
+}
+
+impl MapOptions {
+    pub fn advice(&self) -> MapAdvice {
+        self.advice
+    }
+
+    pub fn set_advice(&mut self, advice: MapAdvice) {
+        self.advice = advice;
+    }
+}
+
+/// Maps the whole file at `path` without copying it, empty files give an empty buffer. The
+/// mapping is private, `PROT_READ | PROT_WRITE` since a `Buffer` is always writable: writes
+/// through the buffer copy the page and never reach the file, pages not yet written still
+/// reflect later writes to it. It is unmapped when the buffer's last view is dropped and is
+/// not counted by `node_buffer::memory_usage`.
+///
+/// # Safety
+///
+/// The mapping stays tied to the file. If the file is truncated while mapped, touching a page
+/// past the new end raises `SIGBUS`, which kills the process unless the host handles it.
+/// This holds for copy-on-write mappings too, even for pages already written through them.
+/// Only map files nothing truncates, such as bundled assets or files replaced by renaming
+/// over them.
+pub unsafe fn map_file(path: &str, options: MapOptions) -> io::Result<Buffer> {
+    let _span = node_core::trace::fs_span("mapFile");
+    let file = File::open(path)?;
+    map(&file, options)
+}
+
+/// Same as `map_file` for an open descriptor, which stays open and owned by the caller. The
+/// mapping keeps working after it is closed.
+///
+/// # Safety
+///
+/// Same as `map_file`, `fd` must be open for reading.
+pub unsafe fn map_file_with_fd(fd: c_int, options: MapOptions) -> io::Result<Buffer> {
+    let _span = node_core::trace::fs_span("mapFile");
+    let file = crate::fd::BorrowedFile::new(fd);
+    map(&file, options)
+}
+
+extern "C" fn unmap(data: *mut u8, length: usize, _context: *mut c_void) {
+    unsafe {
+        libc::munmap(data as *mut c_void, length);
+    }
+}
+
+pub(crate) unsafe fn map(file: &File, options: MapOptions) -> io::Result<Buffer> {
+    let metadata = file.metadata()?;
+    if metadata.is_dir() {
+        return Err(io::Error::from_raw_os_error(libc::EISDIR));
+    }
+    let length = usize::try_from(metadata.len()).map_err(|_| io::Error::from_raw_os_error(libc::EFBIG))?;
+    if length == 0 {
+        return Ok(Buffer::default());
+    }
+
+    let data = libc::mmap(
+        std::ptr::null_mut(),
+        length,
+        libc::PROT_READ | libc::PROT_WRITE,
+        libc::MAP_PRIVATE,
+        file.as_raw_fd(),
+        0,
+    );
+    if data == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+
+    let advice = match options.advice {
+        MapAdvice::Normal => libc::MADV_NORMAL,
+        MapAdvice::Sequential => libc::MADV_SEQUENTIAL,
+        MapAdvice::Random => libc::MADV_RANDOM,
+        MapAdvice::WillNeed => libc::MADV_WILLNEED,
+    };
+    // Only a hint, the mapping is usable either way.
+    libc::madvise(data, length, advice);
+
+    Ok(Buffer::from_external(data as *mut u8, length, Some(unmap), std::ptr::null_mut()))
+}
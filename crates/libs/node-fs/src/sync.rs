@@ -2,14 +2,12 @@ use std::borrow::Cow;
 use std::cmp::min;
 use std::ffi::{CStr, CString, OsString};
 use std::fs::{File, OpenOptions, Permissions};
-use std::io::{IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
+use std::io::{IoSlice, Read, Seek, SeekFrom, Write};
 use std::os::raw::c_ulonglong;
 
 
 #[cfg(unix)]
-use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
-#[cfg(unix)]
-use std::os::unix::prelude::IntoRawFd;
+use std::os::unix::io::{AsRawFd, RawFd};
 #[cfg(unix)]
 use std::os::unix::prelude::*;
 #[cfg(unix)]
@@ -20,7 +18,7 @@ use std::os::windows::prelude::*;
 
 
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::atomic::{AtomicI32, AtomicU64, Ordering};
 use std::time::Duration;
 use std::{fs, io};
 
@@ -34,8 +32,11 @@ use backoff::ExponentialBackoff;
 use faccess::PathExt;
 use libc::{c_char, c_int, c_long, c_uint, c_ushort, option};
 use rand::{thread_rng, Rng};
-use node_buffer::{Buffer, get_bytes, StringEncoding};
+use node_buffer::{Buffer, get_bytes, get_bytes_utf16, get_bytes_utf8, StringEncoding};
+use node_core::abort::AbortSignal;
 
+use crate::fd;
+use crate::fd::BorrowedFile;
 use crate::file_dir::FileDir;
 use crate::file_dirent::FileDirent;
 use crate::file_handle::FileHandle;
@@ -81,18 +82,20 @@ fn file_from_path(path: &str, flag: c_int, mode: c_int) -> std::io::Result<File>
 }
 
 pub fn open_path(path: &str, flag: c_int, mode: c_int) -> std::io::Result<RawFd> {
+    let _span = node_core::trace::fs_span("open");
     let file = file_from_path(path, flag, mode)?;
-    Ok(file.into_raw_fd())
+    Ok(fd::into_raw(file))
 }
 
 pub fn open_handle_with_fd(fd: i32) -> std::io::Result<FileHandle> {
+    let _span = node_core::trace::fs_span("open");
     if fd == -1 {
         return Err(std::io::Error::new(
             std::io::ErrorKind::Other,
             "Bad file descriptor",
         ));
     }
-    let file = unsafe { File::from_raw_fd(fd) };
+    let file = unsafe { fd::from_raw(fd) };
     Ok(FileHandle::new(file))
 }
 
@@ -101,10 +104,12 @@ pub fn open_handle_with_path(
     flag: c_int,
     mode: c_int,
 ) -> std::io::Result<FileHandle> {
+    let _span = node_core::trace::fs_span("open");
     file_from_path(path, flag, mode).map(|v| FileHandle::new(v))
 }
 
 pub fn access(path: &str, access: c_int) -> io::Result<()> {
+    let _span = node_core::trace::fs_span("access");
     let path = Path::new(path);
     let mut mode = faccess::AccessMode::empty();
     if (access & FILE_ACCESS_OPTIONS_F_OK) == FILE_ACCESS_OPTIONS_F_OK {
@@ -145,23 +150,22 @@ impl Default for AppendFileOptions {
 }
 
 pub fn append_file_with_str(fd: c_int, data: &str, options: AppendFileOptions) -> std::io::Result<()> {
-    let mut file = unsafe { File::from_raw_fd(fd) };
+    let _span = node_core::trace::fs_span("appendFile");
+    let mut file = unsafe { BorrowedFile::new(fd) };
     let bytes = Buffer::from_string(CString::new(data).unwrap(), options.encoding);
-    let _ = file.write(bytes.buffer())?;
-    let _ = file.into_raw_fd();
+    let _ = bytes.with_slice(|bytes| file.write(bytes))?;
     Ok(())
 }
 
 pub fn append_file_with_bytes(fd: c_int, data: &[u8], options: AppendFileOptions) -> std::io::Result<()> {
-    let mut file = unsafe { File::from_raw_fd(fd) };
+    let _span = node_core::trace::fs_span("appendFile");
+    let mut file = unsafe { BorrowedFile::new(fd) };
     let _ = file.write(data)?;
-    let _ = file.into_raw_fd();
     Ok(())
 }
 
 pub fn append_file_with_buffer(fd: c_int, data: &Buffer, options: AppendFileOptions) -> std::io::Result<()> {
-    let data = data.buffer();
-    append_file_with_bytes(fd, data, options)
+    data.with_slice(|data| append_file_with_bytes(fd, data, options))
 }
 
 pub fn append_file_with_path_str(
@@ -169,12 +173,10 @@ pub fn append_file_with_path_str(
     data: &str,
     options: AppendFileOptions,
 ) -> std::io::Result<()> {
-    let fd = open(path, options.flag, options.mode)?;
-    let mut file = unsafe { File::from_raw_fd(fd) };
+    let _span = node_core::trace::fs_span("appendFile");
+    let mut file = file_from_path(path, options.flag, options.mode)?;
     let buffer = Buffer::from_string(CString::new(data).unwrap(), options.encoding);
-    let ret = file.write(buffer.buffer()).map(|_| ());
-    let _ = file.into_raw_fd();
-    ret
+    buffer.with_slice(|buffer| file.write(buffer)).map(|_| ())
 }
 
 pub fn append_file_with_path_bytes(
@@ -182,11 +184,9 @@ pub fn append_file_with_path_bytes(
     data: &[u8],
     options: AppendFileOptions,
 ) -> std::io::Result<()> {
-    let fd = open(path, options.flag, options.mode)?;
-    let mut file = unsafe { File::from_raw_fd(fd) };
-    let ret = file.write(data).map(|_| ());
-    let _ = file.into_raw_fd();
-    ret
+    let _span = node_core::trace::fs_span("appendFile");
+    let mut file = file_from_path(path, options.flag, options.mode)?;
+    file.write(data).map(|_| ())
 }
 
 pub fn append_file_with_path_buffer(
@@ -194,8 +194,7 @@ pub fn append_file_with_path_buffer(
     data: &Buffer,
     options: AppendFileOptions,
 ) -> std::io::Result<()> {
-    let data = data.buffer();
-    append_file_with_path_bytes(path, data, options)
+    data.with_slice(|data| append_file_with_path_bytes(path, data, options))
 }
 
 
@@ -204,9 +203,9 @@ pub fn append_file_with_buffer_buffer(
     data: &Buffer,
     options: AppendFileOptions,
 ) -> std::io::Result<()> {
-    let mut dest = dest.buffer_mut();
-    let data = data.buffer();
-    dest.write(data).map(|_| ())
+    let _span = node_core::trace::fs_span("appendFile");
+    let _ = data.copy(dest, None, None, None);
+    Ok(())
 }
 
 pub fn append_file_with_buffer_string(
@@ -214,36 +213,37 @@ pub fn append_file_with_buffer_string(
     data: &str,
     options: AppendFileOptions,
 ) -> std::io::Result<()> {
-    let mut dest = dest.buffer_mut();
+    let _span = node_core::trace::fs_span("appendFile");
     let buffer = Buffer::from_string(CString::new(data).unwrap(), options.encoding);
-    dest.write(buffer.buffer()).map(|_| ())
+    let _ = buffer.copy(dest, None, None, None);
+    Ok(())
 }
 
 pub fn chmod(path: &str, mode: c_uint) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("chmod");
     fs::set_permissions(path, Permissions::from_mode(mode))
 }
 
 pub fn chown(path: &str, uid: c_uint, gid: c_uint) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("chown");
     std::os::unix::fs::chown(path, Some(uid), Some(gid))
 }
 
-pub fn close_fd(fd: c_int) {
-    unsafe {
-        File::from_raw_fd(fd);
-    }
+pub fn close_fd(fd: c_int) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("close");
+    fd::close(fd)
 }
 
 pub fn copy_file(src: &str, dest: &str, flag: c_uint) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("copyFile");
     crate::copy_file::copy_file(Path::new(src), Path::new(dest), flag)
 }
 
-pub fn cp(_src: &str, _dest: &str, flag: u32) {
-    todo!()
-    // let src = Path::new(src);
-    // let dest = Path::new(dest);
-    // for entry in fs::read_dir(src)? {
-    //     let entry = entry?;
-    // }
+/// `fs.cpSync` is not implemented yet and always fails with `ENOTSUP`, the `fs.cp`
+/// capability reports it as missing.
+pub fn cp(_src: &str, _dest: &str, _flag: u32) -> io::Result<()> {
+    let _span = node_core::trace::fs_span("cp");
+    Err(io::Error::from_raw_os_error(libc::ENOTSUP))
 }
 
 pub fn create_read_stream(_path: &str) {
@@ -255,59 +255,54 @@ pub fn create_write_stream(_path: &str) {
 }
 
 pub fn exists(path: &str) -> bool {
+    let _span = node_core::trace::fs_span("exists");
     Path::new(path).exists()
 }
 
 pub fn fchmod(fd: c_int, mode: c_ushort) -> io::Result<()> {
-    let ret = unsafe { libc::fchmod(fd, mode.into()) };
-    if ret == -1 {
-        let last_error = std::io::Error::last_os_error();
-        return Err(last_error);
-    }
-    Ok(())
+    let _span = node_core::trace::fs_span("fchmod");
+    let file = unsafe { BorrowedFile::new(fd) };
+    file.set_permissions(Permissions::from_mode(mode.into()))
 }
 
 pub fn fchown(fd: c_int, uid: c_uint, gid: c_uint) -> io::Result<()> {
-    let ret = unsafe { libc::fchown(fd, uid, gid) };
-    if ret == -1 {
-        let last_error = std::io::Error::last_os_error();
-        return Err(last_error);
-    }
-    Ok(())
+    let _span = node_core::trace::fs_span("fchown");
+    let file = unsafe { BorrowedFile::new(fd) };
+    std::os::unix::fs::fchown(&*file, Some(uid), Some(gid))
 }
 
 pub fn fdatasync(fd: c_int) -> std::io::Result<()> {
-    let file = unsafe { File::from_raw_fd(fd) };
+    let _span = node_core::trace::fs_span("fdatasync");
+    let file = unsafe { BorrowedFile::new(fd) };
     let ret = file.sync_data();
-    let _ = file.into_raw_fd();
     ret
 }
 
 pub fn fstat(fd: c_int) -> io::Result<fs::Metadata> {
-    let file = unsafe { File::from_raw_fd(fd) };
+    let _span = node_core::trace::fs_span("fstat");
+    let file = unsafe { BorrowedFile::new(fd) };
     let metadata = file.metadata();
-    let _ = file.into_raw_fd();
     metadata
 }
 
 pub fn fsync(fd: c_int) -> std::io::Result<()> {
-    let file = unsafe { File::from_raw_fd(fd) };
+    let _span = node_core::trace::fs_span("fsync");
+    let file = unsafe { BorrowedFile::new(fd) };
     let ret = file.sync_all();
-    let _ = file.into_raw_fd();
     ret
 }
 
 pub fn ftruncate(fd: c_int, len: c_long) -> std::io::Result<()> {
-    let ret = unsafe { libc::ftruncate(fd, len) };
-    if ret == -1 {
-        let last_error = std::io::Error::last_os_error();
-        return Err(last_error);
-    }
-    Ok(())
+    let _span = node_core::trace::fs_span("ftruncate");
+    let file = unsafe { BorrowedFile::new(fd) };
+    // Node clamps a negative length to 0
+    file.set_len(len.max(0) as u64)
 }
 
 #[cfg(any(target_os = "android", target_os = "linux"))]
 pub fn futimes(fd: c_int, atime: c_long, mtime: c_long) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("futimes");
+    let file = unsafe { BorrowedFile::new(fd) };
     let times = [
         libc::timespec {
             tv_sec: atime,
@@ -319,7 +314,7 @@ pub fn futimes(fd: c_int, atime: c_long, mtime: c_long) -> std::io::Result<()> {
         },
     ];
 
-    let ret = unsafe { libc::futimens(fd, times.as_ptr()) };
+    let ret = unsafe { libc::futimens(file.as_raw_fd(), times.as_ptr()) };
 
     if ret == -1 {
         let last_error = std::io::Error::last_os_error();
@@ -330,6 +325,8 @@ pub fn futimes(fd: c_int, atime: c_long, mtime: c_long) -> std::io::Result<()> {
 
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 pub fn futimes(fd: c_int, atime: c_long, mtime: c_long) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("futimes");
+    let file = unsafe { BorrowedFile::new(fd) };
     let times = [
         libc::timeval {
             tv_sec: atime,
@@ -341,7 +338,7 @@ pub fn futimes(fd: c_int, atime: c_long, mtime: c_long) -> std::io::Result<()> {
         },
     ];
 
-    let ret = unsafe { libc::futimes(fd, times.as_ptr()) };
+    let ret = unsafe { libc::futimes(file.as_raw_fd(), times.as_ptr()) };
 
     if ret == -1 {
         let last_error = std::io::Error::last_os_error();
@@ -352,6 +349,7 @@ pub fn futimes(fd: c_int, atime: c_long, mtime: c_long) -> std::io::Result<()> {
 
 #[cfg(any(target_os = "android", target_os = "linux"))]
 pub fn lchmod(path: &str, mode: c_ushort) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("lchmod");
     let mut options = OpenOptions::new();
     options.write(true);
     let file = options.open(path)?;
@@ -361,6 +359,7 @@ pub fn lchmod(path: &str, mode: c_ushort) -> std::io::Result<()> {
 
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 pub fn lchmod(path: &str, mode: c_ushort) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("lchmod");
     mod internal {
         extern "C" {
             pub fn lchmod(__file: *const libc::c_char, __mode: libc::mode_t) -> libc::c_int;
@@ -377,6 +376,7 @@ pub fn lchmod(path: &str, mode: c_ushort) -> std::io::Result<()> {
 }
 
 pub fn lchown(path: &str, uid: c_uint, gid: c_uint) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("lchown");
     let path = CString::new(path).unwrap();
     let ret = unsafe { libc::lchown(path.as_ptr(), uid, gid) };
 
@@ -389,6 +389,7 @@ pub fn lchown(path: &str, uid: c_uint, gid: c_uint) -> std::io::Result<()> {
 
 #[cfg(any(target_os = "android", target_os = "linux"))]
 pub fn lutimes(path: &str, atime: c_long, mtime: c_long) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("lutimes");
     let file = File::open(path)?;
     let times = [
         libc::timespec {
@@ -411,6 +412,7 @@ pub fn lutimes(path: &str, atime: c_long, mtime: c_long) -> std::io::Result<()>
 
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 pub fn lutimes(path: &str, atime: c_long, mtime: c_long) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("lutimes");
     let file = File::open(path)?;
     let times = [
         libc::timeval {
@@ -432,11 +434,13 @@ pub fn lutimes(path: &str, atime: c_long, mtime: c_long) -> std::io::Result<()>
 }
 
 pub fn link(existing_path: &str, new_path: &str) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("link");
     fs::hard_link(existing_path, new_path)
 }
 
 pub fn lstat(path: &str) -> std::io::Result<std::fs::Metadata> {
-    fs::metadata(path)
+    let _span = node_core::trace::fs_span("lstat");
+    fs::symlink_metadata(path)
 }
 
 
@@ -456,6 +460,7 @@ impl Default for MkDirOptions {
 }
 
 pub fn mkdir(path: &str, options: MkDirOptions) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("mkdir");
     let path = Path::new(&path);
 
     let mut builder = std::fs::DirBuilder::new();
@@ -532,6 +537,7 @@ impl Default for MkdTempOptions {
 }
 
 pub fn mkdtemp(prefix: &str, options: MkdTempOptions) -> std::io::Result<PathBuf> {
+    let _span = node_core::trace::fs_span("mkdtemp");
     make_temp(None, Some(prefix), None, true, options)
 }
 
@@ -558,6 +564,7 @@ impl Default for OpenDirOptions {
 }
 
 pub fn opendir(path: &str, options: OpenDirOptions) -> io::Result<FileDir> {
+    let _span = node_core::trace::fs_span("opendir");
     let c_path = CString::new(path)?;
     let dir = unsafe { libc::opendir(c_path.as_ptr()) };
     if dir.is_null() {
@@ -575,7 +582,8 @@ pub fn read(
     length: usize,
     position: isize,
 ) -> std::io::Result<usize> {
-    let mut file = unsafe { File::from_raw_fd(fd) };
+    let _span = node_core::trace::fs_span("read");
+    let mut file = unsafe { BorrowedFile::new(fd) };
 
     read_file_internal(&mut file, buffer, offset, length, position)
 }
@@ -588,6 +596,7 @@ pub fn read(
     length: usize,
     position: isize,
 ) -> std::io::Result<usize> {
+    let _span = node_core::trace::fs_span("read");
     let mut file = unsafe { File::from_raw_handle(fd) };
     read_file_internal(&mut file, buffer, offset, length, position)
 }
@@ -676,6 +685,7 @@ impl Default for ReaddirOptions {
 }
 
 pub fn readdir(path: &str, options: ReaddirOptions) -> io::Result<Vec<ReaddirResult>> {
+    let _span = node_core::trace::fs_span("readdir");
     let read = fs::read_dir(path)?;
     // todo
     let mut result: Vec<ReaddirResult> = Vec::new();
@@ -728,10 +738,12 @@ pub fn readdir(path: &str, options: ReaddirOptions) -> io::Result<Vec<ReaddirRes
 }
 
 
+#[repr(C)]
 #[derive(Copy, Clone, Debug)]
 pub struct ReadFileOptions {
     flag: i32,
     encoding: FsEncodingType,
+    map: bool,
 }
 
 impl Default for ReadFileOptions {
@@ -739,6 +751,7 @@ impl Default for ReadFileOptions {
         Self {
             flag: FILE_OPEN_OPTIONS_O_RDONLY,
             encoding: FsEncodingType::Buffer,
+            map: false,
         }
     }
 }
@@ -759,12 +772,96 @@ impl ReadFileOptions {
     pub fn flag(&self) -> i32 {
         self.flag
     }
+
+    pub fn map(&self) -> bool {
+        self.map
+    }
+
+    /// Maps regular files copy-on-write instead of reading them. Other files are still read.
+    ///
+    /// # Safety
+    ///
+    /// Same as `mmap::map_file`: truncating a mapped file makes touching the pages past its
+    /// new end raise `SIGBUS`, so only set this for files nothing truncates while the buffer
+    /// is alive.
+    pub unsafe fn set_map(&mut self, map: bool) {
+        self.map = map;
+    }
+}
+
+/// Node's `kIoMaxLength`, the largest file `readFile` accepts by default.
+pub const DEFAULT_READ_FILE_MAX_SIZE: u64 = (1 << 31) - 1;
+
+// Node's `kReadFileBufferLength` and `kReadFileUnknownBufferLength`.
+const READ_FILE_CHUNK_SIZE: usize = 512 * 1024;
+const READ_FILE_UNKNOWN_SIZE_CHUNK_SIZE: usize = 64 * 1024;
+
+static READ_FILE_MAX_SIZE: AtomicU64 = AtomicU64::new(DEFAULT_READ_FILE_MAX_SIZE);
+
+pub fn read_file_max_size() -> u64 {
+    READ_FILE_MAX_SIZE.load(Ordering::Relaxed)
+}
+
+/// Files larger than `size` make `readFile` fail with `ERR_FS_FILE_TOO_LARGE`, whose message
+/// names the limit in bytes unless it is the default.
+pub fn set_read_file_max_size(size: u64) {
+    READ_FILE_MAX_SIZE.store(size, Ordering::Relaxed);
+}
+
+fn check_aborted(signal: Option<&AbortSignal>) -> std::io::Result<()> {
+    match signal {
+        Some(signal) => signal.throw_if_aborted().map_err(node_core::error::into_io_error),
+        None => Ok(())
+    }
+}
+
+fn file_too_large(size: u64, max_size: u64) -> std::io::Error {
+    node_core::error::into_io_error(node_core::error::err_fs_file_too_large(size, max_size))
+}
+
+// Regular files are read into a buffer sized from fstat. Others, e.g. `/proc` files that
+// report a size of 0 or pipes, are read in chunks until EOF. The signal is checked before
+// every chunk.
+fn read_file_contents(file: &mut File, options: ReadFileOptions, signal: Option<&AbortSignal>) -> std::io::Result<Buffer> {
+    let metadata = file.metadata()?;
+    let size = if metadata.is_file() { metadata.len() } else { 0 };
+    let max_size = read_file_max_size();
+    if size > max_size {
+        return Err(file_too_large(size, max_size));
+    }
+    if options.map && size > 0 {
+        // Mapped whole, the buffer starts at the fd's position which moves to the end like
+        // a read to EOF would.
+        let position = file.stream_position()?.min(size);
+        let mapped = unsafe { crate::mmap::map(file, crate::mmap::MapOptions::default()) }?;
+        file.seek(SeekFrom::Start(size))?;
+        return Ok(mapped.subarray(Some(position as isize), None));
+    }
+
+    let mut buf = Vec::with_capacity(size as usize);
+    loop {
+        check_aborted(signal)?;
+        let chunk = if size > 0 {
+            min(size as usize - buf.len(), READ_FILE_CHUNK_SIZE)
+        } else {
+            READ_FILE_UNKNOWN_SIZE_CHUNK_SIZE
+        };
+        if chunk == 0 {
+            break;
+        }
+        // A file that shrank since fstat ends early, one that grew is read up to its old size.
+        if (&mut *file).take(chunk as u64).read_to_end(&mut buf)? == 0 {
+            break;
+        }
+        if buf.len() as u64 > max_size {
+            return Err(file_too_large(buf.len() as u64, max_size));
+        }
+    }
+    Ok(Buffer::from_vec(buf))
 }
 
-fn read_file_with_file(file: &mut File, options: ReadFileOptions) -> std::io::Result<FsEncoding> {
-    let mut buf = Vec::new();
-    file.read_to_end(&mut buf)?;
-    let result = Buffer::from_vec(buf);
+fn read_file_with_file(file: &mut File, options: ReadFileOptions, signal: Option<&AbortSignal>) -> std::io::Result<FsEncoding> {
+    let result = read_file_contents(file, options, signal)?;
     let result = match options.encoding {
         FsEncodingType::Ascii => {
             FsEncoding::String(
@@ -808,14 +905,38 @@ fn read_file_with_file(file: &mut File, options: ReadFileOptions) -> std::io::Re
     Ok(result)
 }
 
-pub fn read_file(path: &str, options: ReadFileOptions) -> std::io::Result<FsEncoding> {
+fn read_file_from_path(path: &str, options: ReadFileOptions, signal: Option<&AbortSignal>) -> std::io::Result<FsEncoding> {
+    let _span = node_core::trace::fs_span("readFile");
+    check_aborted(signal)?;
     let mut file = file_from_path(path, options.flag, 0)?;
-    read_file_with_file(&mut file, options)
+    read_file_with_file(&mut file, options, signal)
+}
+
+// The fd belongs to the caller and is left open.
+fn read_file_from_fd(fd: c_int, options: ReadFileOptions, signal: Option<&AbortSignal>) -> std::io::Result<FsEncoding> {
+    let _span = node_core::trace::fs_span("readFile");
+    check_aborted(signal)?;
+    let mut file = unsafe { BorrowedFile::new(fd) };
+    read_file_with_file(&mut file, options, signal)
+}
+
+pub fn read_file(path: &str, options: ReadFileOptions) -> std::io::Result<FsEncoding> {
+    read_file_from_path(path, options, None)
 }
 
+/// Fails with an `AbortError` once `signal` is aborted, checked before opening and between
+/// chunks.
+pub fn read_file_with_signal(path: &str, options: ReadFileOptions, signal: &AbortSignal) -> std::io::Result<FsEncoding> {
+    read_file_from_path(path, options, Some(signal))
+}
+
+/// Reads from the fd's current position, the fd stays open.
 pub fn read_file_with_fd(fd: c_int, options: ReadFileOptions) -> std::io::Result<FsEncoding> {
-    let mut file = unsafe { File::from_raw_fd(fd) };
-    read_file_with_file(&mut file, options)
+    read_file_from_fd(fd, options, None)
+}
+
+pub fn read_file_with_fd_and_signal(fd: c_int, options: ReadFileOptions, signal: &AbortSignal) -> std::io::Result<FsEncoding> {
+    read_file_from_fd(fd, options, Some(signal))
 }
 
 
@@ -831,6 +952,7 @@ impl Default for ReadLinkOptions {
 }
 
 pub fn read_link(path: &str, options: ReadLinkOptions) -> std::io::Result<FsEncoding> {
+    let _span = node_core::trace::fs_span("readlink");
     let result = fs::read_link(path)?;
 
     #[cfg(unix)]
@@ -869,7 +991,8 @@ pub fn read_link(path: &str, options: ReadLinkOptions) -> std::io::Result<FsEnco
 }
 
 pub fn readv(fd: c_int, buffers: &mut [Buffer], position: c_long) -> std::io::Result<usize> {
-    let mut file = unsafe { File::from_raw_fd(fd) };
+    let _span = node_core::trace::fs_span("readv");
+    let mut file = unsafe { BorrowedFile::new(fd) };
 
     if position != -1 {
         match file.seek(SeekFrom::Start(position as u64)) {
@@ -878,14 +1001,18 @@ pub fn readv(fd: c_int, buffers: &mut [Buffer], position: c_long) -> std::io::Re
         }
     }
 
-    let mut buffers: Vec<IoSliceMut> = buffers
-        .iter_mut()
-        .map(|mut b| {
-            IoSliceMut::new(b.buffer_mut())
-        })
-        .collect();
-
-    file.read_vectored(buffers.as_mut_slice())
+    // One read per buffer, so buffers sharing memory see each other's bytes in order. Stops at
+    // the first short read like readv does.
+    let mut total = 0;
+    for buffer in buffers.iter_mut() {
+        let wanted = buffer.length();
+        let read = buffer.with_slice_mut(|buffer| file.read(buffer))?;
+        total += read;
+        if read < wanted {
+            break;
+        }
+    }
+    Ok(total)
 }
 
 pub fn readv_raw(
@@ -920,6 +1047,7 @@ impl Default for RealPathOptions {
 }
 
 pub fn real_path(path: &str, options: RealPathOptions) -> std::io::Result<std::path::PathBuf> {
+    let _span = node_core::trace::fs_span("realpath");
     match options.encoding {
         StringEncoding::Utf8 => {
             std::fs::canonicalize(path)
@@ -933,6 +1061,7 @@ pub fn real_path(path: &str, options: RealPathOptions) -> std::io::Result<std::p
 }
 
 pub fn rename(old_path: &str, new_path: &str) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("rename");
     fs::rename(Path::new(old_path), Path::new(new_path))
 }
 
@@ -958,6 +1087,7 @@ pub fn rmdir(
     path: &str,
     options: RmDirOptions,
 ) -> Result<(), node_core::error::AnyError> {
+    let _span = node_core::trace::fs_span("rmdir");
     if !options.recursive {
         fs::remove_dir(path).map_err(|err| node_core::error::custom_error("", err.to_string()))
     } else {
@@ -1019,6 +1149,7 @@ pub fn rm(
     path: &str,
     options: RmOptions,
 ) -> Result<(), node_core::error::AnyError> {
+    let _span = node_core::trace::fs_span("rm");
     if !options.recursive {
         fs::remove_file(path).map_err(|err| node_core::error::custom_error("", err.to_string()))
     } else {
@@ -1058,15 +1189,18 @@ pub fn rm(
 }
 
 pub fn stat(path: &str) -> std::io::Result<std::fs::Metadata> {
+    let _span = node_core::trace::fs_span("stat");
     fs::metadata(path)
 }
 
 pub fn symlink(target: &str, path: &str, _type_: &str) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("symlink");
     // todo handle type
     std::os::unix::fs::symlink(target, path)
 }
 
 pub fn truncate(path: &str, len: c_ulonglong) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("truncate");
     OpenOptions::new()
         .truncate(true)
         .write(true)
@@ -1075,12 +1209,14 @@ pub fn truncate(path: &str, len: c_ulonglong) -> std::io::Result<()> {
 }
 
 pub fn unlink(path: &str) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("unlink");
     fs::remove_file(path)
 }
 
 // pub fn unwatchFile(filename){}
 
 pub fn utimes(path: &str, atime: c_long, mtime: c_long) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("utimes");
     let path = CString::new(path)?;
     let times = [
         libc::timeval {
@@ -1118,7 +1254,8 @@ pub fn write(
     buffer: &[u8],
     options: WriteOptions,
 ) -> std::io::Result<usize> {
-    let mut file = unsafe { File::from_raw_fd(fd) };
+    let _span = node_core::trace::fs_span("write");
+    let mut file = unsafe { BorrowedFile::new(fd) };
     let new_position = file.stream_position().unwrap_or_default();
     let buffer_len = buffer.len();
     let result = if options.length < buffer_len {
@@ -1145,7 +1282,6 @@ pub fn write(
         Err(error) => std::io::Result::Err(error),
     };
 
-    let _ = file.into_raw_fd();
 
     ret
 }
@@ -1156,9 +1292,33 @@ pub fn write_string(
     encoding: StringEncoding,
     position: isize,
 ) -> std::io::Result<usize> {
-    let mut file = unsafe { File::from_raw_fd(fd) };
+    write_encoded(fd, get_bytes(string, encoding), position)
+}
+
+/// `write_string` for a length delimited UTF-8 string, embedded NULs are written as is.
+pub fn write_utf8_string(
+    fd: c_int,
+    string: &[u8],
+    encoding: StringEncoding,
+    position: isize,
+) -> std::io::Result<usize> {
+    write_encoded(fd, get_bytes_utf8(string, encoding), position)
+}
+
+/// `write_string` for a string given as UTF-16 code units.
+pub fn write_utf16_string(
+    fd: c_int,
+    string: &[u16],
+    encoding: StringEncoding,
+    position: isize,
+) -> std::io::Result<usize> {
+    write_encoded(fd, get_bytes_utf16(string, encoding), position)
+}
+
+fn write_encoded(fd: c_int, buffer: Vec<u8>, position: isize) -> std::io::Result<usize> {
+    let _span = node_core::trace::fs_span("write");
+    let mut file = unsafe { BorrowedFile::new(fd) };
     let new_position = file.stream_position().unwrap_or_default();
-    let buffer = get_bytes(string, encoding);
     let result = if position == -1 {
         file.write(buffer.as_slice())
     } else {
@@ -1175,7 +1335,6 @@ pub fn write_string(
         Err(error) => Err(error),
     };
 
-    let _ = file.into_raw_fd();
 
     ret
 }
@@ -1199,17 +1358,17 @@ impl Default for WriteFileOptions {
 }
 
 pub fn write_file_with_str(fd: c_int, data: &str, options: WriteFileOptions) -> std::io::Result<()> {
-    let mut file = unsafe { File::from_raw_fd(fd) };
+    let _span = node_core::trace::fs_span("writeFile");
+    let mut file = unsafe { BorrowedFile::new(fd) };
     let data = get_bytes(data, options.encoding);
     let ret = file.write(data.as_slice());
-    let _ = file.into_raw_fd();
     ret.map(|_| ())
 }
 
 pub fn write_file_with_bytes(fd: c_int, data: &[u8], options: WriteFileOptions) -> std::io::Result<()> {
-    let mut file = unsafe { File::from_raw_fd(fd) };
+    let _span = node_core::trace::fs_span("writeFile");
+    let mut file = unsafe { BorrowedFile::new(fd) };
     let ret = file.write(data);
-    let _ = file.into_raw_fd();
     ret.map(|_| ())
 }
 
@@ -1218,6 +1377,7 @@ pub fn write_file_with_str_from_path(
     data: &str,
     options: WriteFileOptions,
 ) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("writeFile");
     let mut opts = OpenOptions::new();
     if (options.flag & FILE_OPEN_OPTIONS_O_CREAT) == FILE_OPEN_OPTIONS_O_CREAT {
         opts.create(true);
@@ -1257,6 +1417,7 @@ pub fn write_file_with_bytes_from_path(
     data: &[u8],
     options: WriteFileOptions,
 ) -> std::io::Result<()> {
+    let _span = node_core::trace::fs_span("writeFile");
     let mut opts = OpenOptions::new();
     if (options.flag & FILE_OPEN_OPTIONS_O_CREAT) == FILE_OPEN_OPTIONS_O_CREAT {
         opts.create(true);
@@ -1296,11 +1457,25 @@ pub fn write_file_with_buffer_from_path(
     data: &Buffer,
     options: WriteFileOptions,
 ) -> std::io::Result<()> {
-    write_file_with_bytes_from_path(path, data.buffer(), options)
+    data.with_slice(|data| write_file_with_bytes_from_path(path, data, options))
 }
 
-pub fn writev(fd: c_int, mut buffers: Vec<Buffer>, position: c_long) -> std::io::Result<usize> {
-    let mut file = unsafe { File::from_raw_fd(fd) };
+// Runs `f` with every buffer's bytes while holding all of their shared locks.
+fn with_io_slices<R>(buffers: &[Buffer], gathered: &[IoSlice], f: impl FnOnce(&[IoSlice]) -> R) -> R {
+    match buffers.split_first() {
+        None => f(gathered),
+        Some((first, rest)) => first.with_slice(|bytes| {
+            let mut next: Vec<IoSlice> = Vec::with_capacity(gathered.len() + 1);
+            next.extend_from_slice(gathered);
+            next.push(IoSlice::new(bytes));
+            with_io_slices(rest, &next, f)
+        }),
+    }
+}
+
+pub fn writev(fd: c_int, buffers: Vec<Buffer>, position: c_long) -> std::io::Result<usize> {
+    let _span = node_core::trace::fs_span("writev");
+    let mut file = unsafe { BorrowedFile::new(fd) };
 
     if position != -1 {
         match file.seek(SeekFrom::Start(position as u64)) {
@@ -1309,14 +1484,7 @@ pub fn writev(fd: c_int, mut buffers: Vec<Buffer>, position: c_long) -> std::io:
         }
     }
 
-    let buffers: Vec<IoSlice> = buffers
-        .iter_mut()
-        .map(|b| {
-            IoSlice::new(b.buffer())
-        })
-        .collect();
-
-    file.write_vectored(buffers.as_slice())
+    with_io_slices(&buffers, &[], |buffers| file.write_vectored(buffers))
 }
 
 pub fn writev_slice(
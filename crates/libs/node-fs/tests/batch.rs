@@ -0,0 +1,100 @@
+//! Batched fs operations, run in order on the caller or concurrently on the I/O pool.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use node_fs::a_sync::AsyncClosure;
+use node_fs::batch::{batch, batch_each, batch_sync, BatchOperation, BatchResult};
+use node_fs::sync::{ReadFileOptions, ReaddirOptions};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("node-fs-batch-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir(&path).unwrap();
+    std::fs::write(path.join("a.txt"), "hello").unwrap();
+    std::fs::write(path.join("b.txt"), "world!").unwrap();
+    std::os::unix::fs::symlink(path.join("a.txt"), path.join("link")).unwrap();
+    path
+}
+
+fn operations(dir: &PathBuf) -> Vec<BatchOperation> {
+    let path = |name: &str| dir.join(name).to_str().unwrap().to_string();
+    vec![
+        BatchOperation::Stat(path("a.txt")),
+        BatchOperation::ReadFile(path("b.txt"), ReadFileOptions::default()),
+        BatchOperation::Exists(path("missing")),
+        BatchOperation::Access(path("missing"), libc::F_OK),
+        BatchOperation::Readdir(path(""), ReaddirOptions::default()),
+        BatchOperation::Lstat(path("missing")),
+        BatchOperation::Lstat(path("link")),
+        BatchOperation::Stat(path("link")),
+    ]
+}
+
+fn check(index: usize, result: &BatchResult) {
+    match (index, result) {
+        (0, BatchResult::Stat(stat)) => assert!(stat.isFile && stat.size == 5),
+        (1, BatchResult::ReadFile(contents)) => {
+            assert_eq!(contents.get_buffer_value().unwrap().as_string(None, None, None), "world!")
+        }
+        (2, BatchResult::Exists(exists)) => assert!(!exists),
+        (3 | 5, BatchResult::Error(error)) => assert_eq!(error.raw_os_error(), Some(libc::ENOENT)),
+        (4, BatchResult::Readdir(entries)) => assert_eq!(entries.len(), 3),
+        (6, BatchResult::Stat(stat)) => assert!(stat.isSymbolicLink && !stat.isFile),
+        (7, BatchResult::Stat(stat)) => assert!(stat.isFile && !stat.isSymbolicLink && stat.size == 5),
+        (index, result) => panic!("unexpected result {index}: {result:?}"),
+    }
+}
+
+#[test]
+fn batch_sync_runs_every_operation_in_order() {
+    let dir = temp_dir("sync");
+    let results = batch_sync(&operations(&dir));
+    assert_eq!(results.len(), 8);
+    results.iter().enumerate().for_each(|(index, result)| check(index, result));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn batch_delivers_results_in_submission_order() {
+    let dir = temp_dir("batch");
+    for concurrency in [0, 1, 4] {
+        let (sender, receiver) = mpsc::channel();
+        batch(
+            operations(&dir),
+            concurrency,
+            Arc::new(AsyncClosure::new(Box::new(move |results, _| sender.send(results.unwrap()).unwrap()))),
+        );
+        let results = receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(results.len(), 8);
+        results.iter().enumerate().for_each(|(index, result)| check(index, result));
+    }
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn batch_each_reports_every_index_then_completes() {
+    let dir = temp_dir("each");
+    let (sender, receiver) = mpsc::channel();
+    let done = sender.clone();
+    batch_each(
+        operations(&dir),
+        2,
+        Arc::new(AsyncClosure::new(Box::new(move |item, _| sender.send(item).unwrap()))),
+        Arc::new(AsyncClosure::new(Box::new(move |_, _| done.send(None).unwrap()))),
+    );
+    let mut seen = Vec::new();
+    while let Some((index, result)) = receiver.recv_timeout(Duration::from_secs(10)).unwrap() {
+        check(index, &result);
+        seen.push(index);
+    }
+    seen.sort();
+    assert_eq!(seen, [0, 1, 2, 3, 4, 5, 6, 7]);
+
+    let (sender, receiver) = mpsc::channel();
+    batch(Vec::new(), 0, Arc::new(AsyncClosure::new(Box::new(move |results, _| sender.send(results.unwrap()).unwrap()))));
+    assert!(receiver.recv_timeout(Duration::from_secs(10)).unwrap().is_empty());
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,43 @@
+//! `fs.openAsBlob`, a `Blob` that reads its file lazily.
+
+use std::path::PathBuf;
+
+use node_core::error::get_custom_error_class;
+use node_fs::blob::open_as_blob;
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("node-fs-blob-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn reads_the_file_when_the_blob_is_read() {
+    let path = temp_file("read", b"hello world");
+    let blob = open_as_blob(path.to_str().unwrap(), "Text/Plain").unwrap();
+    assert_eq!(blob.size(), 11);
+    assert_eq!(blob.content_type(), "text/plain");
+    assert_eq!(blob.text().unwrap(), "hello world");
+    assert_eq!(blob.slice(Some(6), None, None).bytes().unwrap(), b"world");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn reads_fail_with_not_readable_error_once_the_file_changed() {
+    let path = temp_file("changed", b"hello");
+    let blob = open_as_blob(path.to_str().unwrap(), "").unwrap();
+    std::fs::write(&path, b"hello world").unwrap();
+    assert_eq!(get_custom_error_class(&blob.text().unwrap_err()), Some("NotReadableError"));
+
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(get_custom_error_class(&blob.bytes().unwrap_err()), Some("NotReadableError"));
+}
+
+#[test]
+fn directories_and_missing_files_fail_to_open() {
+    let error = open_as_blob(std::env::temp_dir().to_str().unwrap(), "").unwrap_err();
+    assert_eq!(error.raw_os_error(), Some(libc::EISDIR));
+    let missing = std::env::temp_dir().join(format!("node-fs-blob-{}-missing", std::process::id()));
+    let error = open_as_blob(missing.to_str().unwrap(), "").unwrap_err();
+    assert_eq!(error.raw_os_error(), Some(libc::ENOENT));
+}
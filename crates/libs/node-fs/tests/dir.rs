@@ -0,0 +1,19 @@
+//! `Dir` handles from `opendir`.
+
+use node_core::error::{error_from_io_error, get_custom_error_code};
+use node_fs::sync::{opendir, OpenDirOptions};
+
+fn code(error: std::io::Error) -> Option<&'static str> {
+    get_custom_error_code(&error_from_io_error(error))
+}
+
+#[test]
+fn reading_or_closing_a_closed_dir_fails_with_err_dir_closed() {
+    let dir = opendir(std::env::temp_dir().to_str().unwrap(), OpenDirOptions::default()).unwrap();
+    let clone = dir.clone();
+    dir.read().unwrap();
+    dir.close().unwrap();
+    assert_eq!(code(dir.close().unwrap_err()), Some("ERR_DIR_CLOSED"));
+    assert_eq!(code(clone.read().err().unwrap()), Some("ERR_DIR_CLOSED"));
+    assert_eq!(dir.path().to_string(), std::env::temp_dir().to_str().unwrap());
+}
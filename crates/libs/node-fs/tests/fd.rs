@@ -0,0 +1,62 @@
+//! Who owns an fd: functions given one borrow it, only `close_fd` closes it.
+
+use std::os::fd::IntoRawFd;
+use std::path::PathBuf;
+
+use parking_lot::Mutex;
+
+use node_fs::fd::{report_leaks, set_tracking};
+use node_fs::sync::{
+    append_file_with_bytes, close_fd, fstat, open, read, read_file_with_fd, write_file_with_bytes, AppendFileOptions,
+    ReadFileOptions, WriteFileOptions,
+};
+
+// Closing an fd twice could close one another test just opened under the same number.
+static FDS: Mutex<()> = Mutex::new(());
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("node-fs-fd-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn is_open(fd: i32) -> bool {
+    unsafe { libc::fcntl(fd, libc::F_GETFD) != -1 }
+}
+
+#[test]
+fn functions_taking_an_fd_leave_it_open() {
+    let _fds = FDS.lock();
+    let path = temp_file("borrowed", b"hello");
+    let fd = std::fs::OpenOptions::new().read(true).write(true).open(&path).unwrap().into_raw_fd();
+
+    let mut buffer = [0_u8; 5];
+    assert_eq!(read(fd, &mut buffer, 0, 5, 0).unwrap(), 5);
+    assert!(is_open(fd));
+    assert_eq!(fstat(fd).unwrap().len(), 5);
+    assert!(is_open(fd));
+    read_file_with_fd(fd, ReadFileOptions::default()).unwrap();
+    assert!(is_open(fd));
+    write_file_with_bytes(fd, b" world", WriteFileOptions::default()).unwrap();
+    assert!(is_open(fd));
+    append_file_with_bytes(fd, b"!", AppendFileOptions::default()).unwrap();
+    assert!(is_open(fd));
+
+    close_fd(fd).unwrap();
+    assert!(!is_open(fd));
+    assert_eq!(close_fd(fd).unwrap_err().raw_os_error(), Some(libc::EBADF));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn the_tracker_reports_fds_left_open() {
+    let _fds = FDS.lock();
+    let path = temp_file("tracked", b"");
+    set_tracking(true);
+    let fd = open(path.to_str().unwrap(), libc::O_RDONLY, 0).unwrap();
+    assert_eq!(report_leaks(), 1);
+    close_fd(fd).unwrap();
+    assert_eq!(report_leaks(), 0);
+    set_tracking(false);
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,139 @@
+//! Memory-mapped files. Truncating a mapped file raises `SIGBUS`, those cases run in a child
+//! process so the signal can't take down the test runner.
+
+use std::io::{Seek, SeekFrom};
+use std::os::fd::AsRawFd;
+use std::os::unix::process::ExitStatusExt;
+use std::path::PathBuf;
+use std::process::Command;
+
+use node_fs::mmap::{map_file, map_file_with_fd, MapOptions};
+use node_fs::sync::{read_file, read_file_with_fd, ReadFileOptions};
+
+/// Set in the child to `path:page:write`, see `truncated`.
+const CHILD: &str = "NODE_FS_MMAP_TRUNCATE_CHILD";
+
+fn page_size() -> usize {
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("node-fs-mmap-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn contents(length: usize) -> Vec<u8> {
+    (0..length).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn maps_the_whole_file() {
+    let data = contents(3 * page_size() + 10);
+    let path = temp_file("whole", &data);
+    let buffer = unsafe { map_file(path.to_str().unwrap(), MapOptions::default()) }.unwrap();
+    assert_eq!(buffer.length(), data.len());
+    assert!(buffer.with_slice(|bytes| bytes == data.as_slice()));
+
+    let empty = temp_file("empty", &[]);
+    assert_eq!(unsafe { map_file(empty.to_str().unwrap(), MapOptions::default()) }.unwrap().length(), 0);
+    std::fs::remove_file(path).unwrap();
+    std::fs::remove_file(empty).unwrap();
+}
+
+#[test]
+fn writes_never_reach_the_file() {
+    let path = temp_file("private", &contents(100));
+    let mut buffer = unsafe { map_file(path.to_str().unwrap(), MapOptions::default()) }.unwrap();
+    buffer.write_uint8(255, Some(0)).unwrap();
+    assert_eq!(buffer.read_uint8(Some(0)).unwrap(), 255);
+    drop(buffer);
+    assert_eq!(std::fs::read(&path).unwrap()[0], 0);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn the_fd_stays_open_and_keeps_its_position() {
+    let data = contents(page_size() + 1);
+    let path = temp_file("fd", &data);
+    let mut file = std::fs::File::open(&path).unwrap();
+    file.seek(SeekFrom::Start(7)).unwrap();
+    let buffer = unsafe { map_file_with_fd(file.as_raw_fd(), MapOptions::default()) }.unwrap();
+    assert_eq!(buffer.length(), data.len());
+    assert_eq!(file.stream_position().unwrap(), 7);
+    drop(file);
+    assert_eq!(buffer.read_uint8(Some(page_size())).unwrap(), data[page_size()]);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn read_file_maps_from_the_fd_position() {
+    let data = contents(2 * page_size());
+    let path = temp_file("read_file", &data);
+    let mut options = ReadFileOptions::default();
+    unsafe { options.set_map(true) };
+
+    let whole = read_file(path.to_str().unwrap(), options).unwrap().get_buffer_value().unwrap();
+    assert!(whole.with_slice(|bytes| bytes == data.as_slice()));
+
+    let mut file = std::fs::File::open(&path).unwrap();
+    file.seek(SeekFrom::Start(100)).unwrap();
+    let rest = read_file_with_fd(file.as_raw_fd(), options).unwrap().get_buffer_value().unwrap();
+    assert!(rest.with_slice(|bytes| bytes == &data[100..]));
+    assert_eq!(file.stream_position().unwrap(), data.len() as u64);
+
+    let end = read_file_with_fd(file.as_raw_fd(), options).unwrap().get_buffer_value().unwrap();
+    assert_eq!(end.length(), 0);
+    std::fs::remove_file(path).unwrap();
+}
+
+// In the child: maps a three page file, optionally writes to `page`, truncates the file to one
+// page and reads `page`. Exits normally only if that read didn't fault.
+fn truncated(page: usize, write: bool) -> std::process::ExitStatus {
+    let path = temp_file(&format!("truncate-{}-{}", page, write), &contents(3 * page_size()));
+    let test = if write { "written_pages_past_a_truncated_end_raise_sigbus" } else { "pages_past_a_truncated_end_raise_sigbus" };
+    let status = Command::new(std::env::current_exe().unwrap())
+        .args([test, "--exact", "--nocapture", "--test-threads=1"])
+        .env(CHILD, format!("{}:{}:{}", path.display(), page, write))
+        .status()
+        .unwrap();
+    std::fs::remove_file(path).unwrap();
+    status
+}
+
+fn run_child() -> bool {
+    let Ok(value) = std::env::var(CHILD) else {
+        return false;
+    };
+    let mut parts = value.rsplitn(3, ':');
+    let write = parts.next().unwrap() == "true";
+    let page = parts.next().unwrap().parse::<usize>().unwrap();
+    let path = parts.next().unwrap();
+
+    let mut buffer = unsafe { map_file(path, MapOptions::default()) }.unwrap();
+    if write {
+        buffer.write_uint8(1, Some(page * page_size())).unwrap();
+    }
+    std::fs::OpenOptions::new().write(true).open(path).unwrap().set_len(page_size() as u64).unwrap();
+    // still inside the file
+    buffer.read_uint8(Some(0)).unwrap();
+    buffer.read_uint8(Some(page * page_size())).unwrap();
+    std::process::exit(0);
+}
+
+#[test]
+fn pages_past_a_truncated_end_raise_sigbus() {
+    if run_child() {
+        return;
+    }
+    assert_eq!(truncated(2, false).signal(), Some(libc::SIGBUS));
+    assert!(truncated(0, false).success());
+}
+
+#[test]
+fn written_pages_past_a_truncated_end_raise_sigbus() {
+    if run_child() {
+        return;
+    }
+    assert_eq!(truncated(1, true).signal(), Some(libc::SIGBUS));
+}
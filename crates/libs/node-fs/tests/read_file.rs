@@ -0,0 +1,72 @@
+//! `readFile` size limits and files that don't report their size.
+
+use std::io::Write;
+use std::os::fd::AsRawFd;
+use std::path::PathBuf;
+
+use parking_lot::Mutex;
+
+use node_core::error::{error_from_io_error, get_custom_error_class, get_custom_error_code};
+use node_fs::sync::{read_file, read_file_max_size, read_file_with_fd, set_read_file_max_size, ReadFileOptions, DEFAULT_READ_FILE_MAX_SIZE};
+
+// The limit is process wide, every test holds this while reading.
+static LIMIT: Mutex<()> = Mutex::new(());
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("node-fs-read-file-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn read(path: &str) -> std::io::Result<Vec<u8>> {
+    let contents = read_file(path, ReadFileOptions::default())?;
+    Ok(contents.get_buffer_value().unwrap().with_slice(|bytes| bytes.to_vec()))
+}
+
+#[test]
+fn files_over_the_limit_fail_with_err_fs_file_too_large() {
+    let _limit = LIMIT.lock();
+    assert_eq!(read_file_max_size(), DEFAULT_READ_FILE_MAX_SIZE);
+    let path = temp_file("limit", b"hello world");
+    let path = path.to_str().unwrap();
+
+    set_read_file_max_size(10);
+    let error = error_from_io_error(read(path).unwrap_err());
+    set_read_file_max_size(DEFAULT_READ_FILE_MAX_SIZE);
+    assert_eq!(get_custom_error_code(&error), Some("ERR_FS_FILE_TOO_LARGE"));
+    assert_eq!(get_custom_error_class(&error), Some("RangeError"));
+    assert_eq!(error.to_string(), "File size (11) is greater than 10 bytes");
+
+    set_read_file_max_size(11);
+    let contents = read(path);
+    set_read_file_max_size(DEFAULT_READ_FILE_MAX_SIZE);
+    assert_eq!(contents.unwrap(), b"hello world");
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn files_reporting_no_size_are_read_to_the_end() {
+    let _limit = LIMIT.lock();
+    let status = read("/proc/self/status").unwrap();
+    assert!(String::from_utf8(status).unwrap().starts_with("Name:"));
+
+    // larger than one chunk of an unknown size read
+    let data = (0..200_000).map(|i| (i % 251) as u8).collect::<Vec<u8>>();
+    let (reader, mut writer) = std::io::pipe().unwrap();
+    let written = data.clone();
+    let writing = std::thread::spawn(move || writer.write_all(&written).unwrap());
+    let contents = read_file_with_fd(reader.as_raw_fd(), ReadFileOptions::default()).unwrap();
+    writing.join().unwrap();
+    assert!(contents.get_buffer_value().unwrap().with_slice(|bytes| bytes == data.as_slice()));
+}
+
+#[test]
+fn files_are_read_from_the_fd_position() {
+    let _limit = LIMIT.lock();
+    let path = temp_file("position", b"hello world");
+    let mut file = std::fs::File::open(&path).unwrap();
+    std::io::Seek::seek(&mut file, std::io::SeekFrom::Start(6)).unwrap();
+    let contents = read_file_with_fd(file.as_raw_fd(), ReadFileOptions::default()).unwrap();
+    assert_eq!(contents.get_buffer_value().unwrap().as_string(None, None, None), "world");
+    std::fs::remove_file(path).unwrap();
+}
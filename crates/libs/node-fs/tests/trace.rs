@@ -0,0 +1,33 @@
+//! Each public fs call records exactly one `node.fs.sync` event, even when it delegates to
+//! another traced function.
+
+use node_buffer::Buffer;
+use node_fs::sync::{
+    append_file_with_buffer, append_file_with_path_buffer, close_fd, open, write_file_with_buffer_from_path, writev_slice,
+    AppendFileOptions, WriteFileOptions,
+};
+
+fn names(trace: &str) -> Vec<&str> {
+    trace.split("{\"name\":\"").skip(1).map(|event| &event[..event.find('"').unwrap()]).collect()
+}
+
+#[test]
+fn delegating_calls_record_one_event() {
+    let path = std::env::temp_dir().join(format!("node-fs-trace-{}", std::process::id()));
+    let path = path.to_str().unwrap();
+    std::fs::write(path, b"").unwrap();
+    let data = Buffer::from_slice(b"hello");
+
+    node_core::trace::enable(node_core::trace::CATEGORY_FS_SYNC);
+    let _ = node_core::trace::flush();
+    let fd = open(path, libc::O_WRONLY | libc::O_APPEND, 0).unwrap();
+    append_file_with_buffer(fd, &data, AppendFileOptions::default()).unwrap();
+    writev_slice(fd, &[b"a", b"b"], -1).unwrap();
+    close_fd(fd).unwrap();
+    append_file_with_path_buffer(path, &data, AppendFileOptions::default()).unwrap();
+    write_file_with_buffer_from_path(path, &data, WriteFileOptions::default()).unwrap();
+    node_core::trace::disable();
+
+    assert_eq!(names(&node_core::trace::flush()), ["open", "appendFile", "writev", "close", "appendFile", "writeFile"]);
+    std::fs::remove_file(path).unwrap();
+}
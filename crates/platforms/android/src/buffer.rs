@@ -1,6 +1,6 @@
 use std::ffi::CString;
 use jni::JNIEnv;
-use jni::objects::{JByteArray, JByteBuffer, JClass, JObject, JObjectArray, JPrimitiveArray, JString, ReleaseMode};
+use jni::objects::{JByteArray, JByteBuffer, JClass, JObject, JObjectArray, JPrimitiveArray, JString, JThrowable, ReleaseMode};
 use jni::sys::{jbyte, jbyteArray, jdouble, jfloat, jint, jlong, jobject, jobjectArray, jshort, jstring};
 use node_buffer::{Buffer, StringEncoding};
 
@@ -11,6 +11,51 @@ fn get_offset(offset: jlong) -> Option<usize> {
     Some(offset as usize)
 }
 
+fn get_index(index: jlong) -> isize {
+    index.clamp(isize::MIN as jlong, isize::MAX as jlong) as isize
+}
+
+// Reads the UTF-16 code units as is, `get_string` goes through modified UTF-8 which
+// can't round trip NULs or lone surrogates.
+fn get_string_utf16(env: &JNIEnv, text: &JString) -> Vec<u16> {
+    let env = env.get_raw();
+    unsafe {
+        let (Some(get_length), Some(get_region)) = ((**env).GetStringLength, (**env).GetStringRegion) else {
+            return Vec::new();
+        };
+        let length = get_length(env, text.as_raw());
+        let mut units = vec![0_u16; length.max(0) as usize];
+        get_region(env, text.as_raw(), 0, length, units.as_mut_ptr());
+        units
+    }
+}
+
+const NODE_EXCEPTION_CLASS: &str = "org/nativescript/node_compat/NodeException";
+
+// Throws a `NodeException` carrying the error's Node `code` and class, e.g.
+// `ERR_OUT_OF_RANGE` and `RangeError`, falling back to a `RuntimeException` with the message.
+fn throw_error(env: &mut JNIEnv, error: &node_core::error::AnyError) {
+    if new_node_exception(env, error).and_then(|exception| env.throw(exception)).is_err() {
+        let _ = env.exception_clear();
+        let _ = env.throw(error.to_string());
+    }
+}
+
+fn new_node_exception<'local>(env: &mut JNIEnv<'local>, error: &node_core::error::AnyError) -> jni::errors::Result<JThrowable<'local>> {
+    let message = env.new_string(error.to_string())?;
+    let code = match node_core::error::get_custom_error_code(error) {
+        Some(code) => JObject::from(env.new_string(code)?),
+        None => JObject::null(),
+    };
+    let class = env.new_string(node_core::error::get_custom_error_class(error).unwrap_or("Error"))?;
+    let exception = env.new_object(
+        NODE_EXCEPTION_CLASS,
+        "(Ljava/lang/String;Ljava/lang/String;Ljava/lang/String;)V",
+        &[(&message).into(), (&code).into(), (&class).into()],
+    )?;
+    Ok(JThrowable::from(exception))
+}
+
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeAlloc(
     mut env: JNIEnv,
@@ -24,24 +69,16 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeAl
             .size(size as usize)
             .build()
     } else {
-        match env.get_string(&text) {
-            Ok(text) => {
-                match StringEncoding::try_from(encoding) {
-                    Ok(encoding) => {
-                        let text = CString::new(text.to_string_lossy().to_string()).unwrap();
-                        Buffer::builder()
-                            .size(size as usize)
-                            .fill_text(text, encoding)
-                            .build()
-                    }
-                    Err(error) => {
-                        let _ = env.throw(error);
-                        Buffer::default()
-                    }
-                }
+        match StringEncoding::try_from(encoding) {
+            Ok(encoding) => {
+                let text = get_string_utf16(&env, &text);
+                Buffer::builder()
+                    .size(size as usize)
+                    .fill_text_utf16(&text, encoding)
+                    .build()
             }
             Err(error) => {
-                let _ = env.throw(error.to_string());
+                let _ = env.throw(error);
                 Buffer::default()
             }
         }
@@ -115,21 +152,13 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeFr
     text: JString,
     encoding: jint,
 ) -> jlong {
-    let buffer = match env.get_string(&text) {
-        Ok(text) => {
-            match StringEncoding::try_from(encoding) {
-                Ok(encoding) => {
-                    let text = CString::new(text.to_string_lossy().to_string()).unwrap();
-                    Buffer::from_string(text, encoding)
-                }
-                Err(error) => {
-                    let _ = env.throw(error);
-                    Buffer::default()
-                }
-            }
+    let buffer = match StringEncoding::try_from(encoding) {
+        Ok(encoding) => {
+            let text = get_string_utf16(&env, &text);
+            Buffer::from_utf16_string(&text, encoding)
         }
         Err(error) => {
-            let _ = env.throw(error.to_string());
+            let _ = env.throw(error);
             Buffer::default()
         }
     };
@@ -208,9 +237,11 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeFr
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeFromBuffer(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
+    offset: jlong,
+    length: jlong,
 ) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
@@ -224,9 +255,50 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeFr
 
     let mut buffer = unsafe { &mut *buffer };
 
+    // Buffer.from(buffer) copies, Buffer.from(arrayBuffer, byteOffset, length) shares memory
+    let buffer = match get_offset(offset) {
+        None => Buffer::from_buffer(&buffer),
+        Some(offset) => {
+            match Buffer::from_buffer_view(&buffer, offset, get_offset(length)) {
+                Ok(view) => view,
+                Err(error) => {
+                    throw_error(&mut env, &error);
+                    Buffer::default()
+                }
+            }
+        }
+    };
+
+    Box::into_raw(
+        Box::new(
+            buffer
+        )
+    ) as jlong
+}
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeSubarray(
+    _env: JNIEnv,
+    _: JClass,
+    buffer: jlong,
+    start: jlong,
+    end: jlong,
+) -> jlong {
+    let buffer = unsafe { Buffer::get_ptr(buffer) };
+
+    if buffer.is_null() {
+        return Box::into_raw(
+            Box::new(
+                Buffer::default()
+            )
+        ) as jlong;
+    }
+
+    let buffer = unsafe { &*buffer };
+
     Box::into_raw(
         Box::new(
-            Buffer::from_buffer(&buffer)
+            buffer.subarray(Some(get_index(start)), Some(get_index(end)))
         )
     ) as jlong
 }
@@ -284,19 +356,76 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeFi
 
     let mut buffer = unsafe { &mut *buffer };
 
-    if let Ok(text) = env.get_string(&text) {
-        match StringEncoding::try_from(encoding) {
-            Ok(encoding) => {
-                let text = CString::new(text.to_string_lossy().to_string()).unwrap();
-                buffer.fill(text, Some(encoding));
+    match StringEncoding::try_from(encoding) {
+        Ok(encoding) => {
+            let text = get_string_utf16(&env, &text);
+            if let Err(error) = buffer.fill_utf16_string(&text, Some(encoding)) {
+                throw_error(&mut env, &error);
             }
-            Err(error) => {
-                let _ = env.throw(error);
+        }
+        Err(error) => {
+            let _ = env.throw(error);
+        }
+    }
+}
+
+
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteString(
+    mut env: JNIEnv,
+    _: JClass,
+    buffer: jlong,
+    text: JString,
+    offset: jlong,
+    length: jlong,
+    encoding: jint,
+) -> jlong {
+    let buffer = unsafe { Buffer::get_ptr(buffer) };
+
+    if buffer.is_null() {
+        return 0;
+    }
+
+    let buffer = unsafe { &mut *buffer };
+
+    match StringEncoding::try_from(encoding) {
+        Ok(encoding) => {
+            let text = get_string_utf16(&env, &text);
+            let offset = (offset >= 0).then_some(offset as usize);
+            let length = (length >= 0).then_some(length as usize);
+            match buffer.write_utf16_string(&text, offset, length, Some(encoding)) {
+                Ok(written) => written as jlong,
+                Err(error) => {
+                    throw_error(&mut env, &error);
+                    0
+                }
             }
         }
+        Err(error) => {
+            let _ = env.throw(error);
+            0
+        }
     }
 }
 
+#[no_mangle]
+pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeByteLength(
+    mut env: JNIEnv,
+    _: JClass,
+    text: JString,
+    encoding: jint,
+) -> jlong {
+    match StringEncoding::try_from(encoding) {
+        Ok(encoding) => {
+            let text = get_string_utf16(&env, &text);
+            node_buffer::byte_length_utf16(&text, encoding) as jlong
+        }
+        Err(error) => {
+            let _ = env.throw(error);
+            0
+        }
+    }
+}
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeToString(
@@ -375,11 +504,10 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeBu
 
     let mut buffer = unsafe { &mut *buffer };
 
-    let buffer = buffer.buffer_mut();
-
+    // The direct ByteBuffer reads and writes the memory in place, not atomically.
     let data = buffer.as_mut_ptr();
 
-    let size = buffer.len();
+    let size = buffer.length();
 
     unsafe {
         env.new_direct_byte_buffer(data, size)
@@ -391,269 +519,353 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeBu
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteInt8(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jbyte,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_int8(value, get_offset(offset));
+    match buffer.write_int8(value, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteUInt8(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jbyte,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_uint8(value as u8, get_offset(offset));
+    match buffer.write_uint8(value as u8, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteUInt16BE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jshort,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_uint16be(value as u16, get_offset(offset));
+    match buffer.write_uint16be(value as u16, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteUInt16LE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jshort,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_uint16le(value as u16, get_offset(offset));
+    match buffer.write_uint16le(value as u16, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteInt16BE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jshort,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_int16be(value, get_offset(offset));
+    match buffer.write_int16be(value, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteInt16LE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jshort,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_int16le(value, get_offset(offset));
+    match buffer.write_int16le(value, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteUInt32BE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jint,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_uint32be(value as u32, get_offset(offset));
+    match buffer.write_uint32be(value as u32, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteUInt32LE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jint,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_uint32le(value as u32, get_offset(offset));
+    match buffer.write_uint32le(value as u32, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteInt32BE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jint,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_int32be(value, get_offset(offset));
+    match buffer.write_int32be(value, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteInt32LE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jint,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_int32le(value, get_offset(offset));
+    match buffer.write_int32le(value, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteFloatBE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jfloat,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_float_be(value, get_offset(offset));
+    match buffer.write_float_be(value, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteFloatLE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jfloat,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_float_le(value, get_offset(offset));
+    match buffer.write_float_le(value, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteDoubleBE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jdouble,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_double_be(value, get_offset(offset));
+    match buffer.write_double_be(value, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWriteDoubleLE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     value: jdouble,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.write_double_le(value, get_offset(offset));
+    match buffer.write_double_le(value, get_offset(offset)) {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 
@@ -664,23 +876,34 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWr
     buffer: jlong,
     value: jbyteArray,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
     let value = unsafe { JByteArray::from_raw(value) };
-    match unsafe { env.get_array_elements_critical(&value, ReleaseMode::NoCopyBack) } {
+    let result = match unsafe { env.get_array_elements_critical(&value, ReleaseMode::NoCopyBack) } {
         Ok(array) => {
             let value = unsafe { std::slice::from_raw_parts_mut(std::mem::transmute::<*mut jbyte, *mut u8>(array.as_ptr()), array.len()) };
-            buffer.write_big_uint64be_bytes(value, get_offset(offset));
+            buffer.write_big_uint64be_bytes(value, get_offset(offset))
+        }
+        Err(error) => {
+            throw_error(&mut env, &error);
+            return 0;
         }
-        Err(_) => {}
     };
+
+    match result {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
@@ -690,23 +913,34 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWr
     buffer: jlong,
     value: jbyteArray,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
     let value = unsafe { JByteArray::from_raw(value) };
-    match unsafe { env.get_array_elements_critical(&value, ReleaseMode::NoCopyBack) } {
+    let result = match unsafe { env.get_array_elements_critical(&value, ReleaseMode::NoCopyBack) } {
         Ok(array) => {
             let value = unsafe { std::slice::from_raw_parts_mut(std::mem::transmute::<*mut jbyte, *mut u8>(array.as_ptr()), array.len()) };
-            buffer.write_big_uint64le_bytes(value, get_offset(offset));
+            buffer.write_big_uint64le_bytes(value, get_offset(offset))
+        }
+        Err(error) => {
+            throw_error(&mut env, &error);
+            return 0;
         }
-        Err(_) => {}
     };
+
+    match result {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
@@ -716,23 +950,34 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWr
     buffer: jlong,
     value: jbyteArray,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
     let value = unsafe { JByteArray::from_raw(value) };
-    match unsafe { env.get_array_elements_critical(&value, ReleaseMode::NoCopyBack) } {
+    let result = match unsafe { env.get_array_elements_critical(&value, ReleaseMode::NoCopyBack) } {
         Ok(array) => {
             let value = unsafe { std::slice::from_raw_parts_mut(std::mem::transmute::<*mut jbyte, *mut u8>(array.as_ptr()), array.len()) };
-            buffer.write_big_int64be_bytes(value, get_offset(offset));
+            buffer.write_big_int64be_bytes(value, get_offset(offset))
+        }
+        Err(error) => {
+            throw_error(&mut env, &error);
+            return 0;
         }
-        Err(_) => {}
     };
+
+    match result {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
@@ -742,29 +987,40 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeWr
     buffer: jlong,
     value: jbyteArray,
     offset: jlong,
-) {
+) -> jlong {
     let mut buffer = unsafe { Buffer::get_ptr(buffer) };
 
     if buffer.is_null() {
-        return;
+        return 0;
     }
 
     let mut buffer = unsafe { &mut *buffer };
 
     let value = unsafe { JByteArray::from_raw(value) };
-    match unsafe { env.get_array_elements_critical(&value, ReleaseMode::NoCopyBack) } {
+    let result = match unsafe { env.get_array_elements_critical(&value, ReleaseMode::NoCopyBack) } {
         Ok(array) => {
             let value = unsafe { std::slice::from_raw_parts_mut(std::mem::transmute::<*mut jbyte, *mut u8>(array.as_ptr()), array.len()) };
-            buffer.write_big_int64le_bytes(value, get_offset(offset));
+            buffer.write_big_int64le_bytes(value, get_offset(offset))
+        }
+        Err(error) => {
+            throw_error(&mut env, &error);
+            return 0;
         }
-        Err(_) => {}
     };
+
+    match result {
+        Ok(offset) => offset as jlong,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadInt8(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -778,12 +1034,18 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_int8(get_offset(offset))
+    match buffer.read_int8(get_offset(offset)) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadUInt8(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -797,12 +1059,18 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_uint8(get_offset(offset)) as jbyte
+    match buffer.read_uint8(get_offset(offset)) {
+        Ok(value) => value as jbyte,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadUInt16BE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -816,12 +1084,18 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_uint16be(get_offset(offset)) as jshort
+    match buffer.read_uint16be(get_offset(offset)) {
+        Ok(value) => value as jshort,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadUInt16LE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -835,13 +1109,19 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_uint16le(get_offset(offset)) as jshort
+    match buffer.read_uint16le(get_offset(offset)) {
+        Ok(value) => value as jshort,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadInt16BE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -855,12 +1135,18 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_int16be(get_offset(offset))
+    match buffer.read_int16be(get_offset(offset)) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadInt16LE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -874,12 +1160,18 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_int16le(get_offset(offset))
+    match buffer.read_int16le(get_offset(offset)) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadUInt32BE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -893,12 +1185,18 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_uint32be(get_offset(offset)) as jint
+    match buffer.read_uint32be(get_offset(offset)) {
+        Ok(value) => value as jint,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadUInt32LE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -912,13 +1210,19 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_uint32le(get_offset(offset)) as jint
+    match buffer.read_uint32le(get_offset(offset)) {
+        Ok(value) => value as jint,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadInt32BE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -932,12 +1236,18 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_int32be(get_offset(offset))
+    match buffer.read_int32be(get_offset(offset)) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadInt32LE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -951,12 +1261,18 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_int32le(get_offset(offset))
+    match buffer.read_int32le(get_offset(offset)) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadFloatBE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -970,12 +1286,18 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_float_be(get_offset(offset))
+    match buffer.read_float_be(get_offset(offset)) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0.
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadFloatLE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -989,13 +1311,19 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_float_le(get_offset(offset))
+    match buffer.read_float_le(get_offset(offset)) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0.
+        }
+    }
 }
 
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadDoubleBE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -1009,12 +1337,18 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_double_be(get_offset(offset))
+    match buffer.read_double_be(get_offset(offset)) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0.
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadDoubleLE(
-    _env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -1028,12 +1362,18 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    buffer.read_double_le(get_offset(offset))
+    match buffer.read_double_le(get_offset(offset)) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            0.
+        }
+    }
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadUInt64BE(
-    env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -1047,14 +1387,20 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    let value = buffer.read_big_uint64be_bytes(get_offset(offset));
+    let value = match buffer.read_big_uint64be_bytes(get_offset(offset)) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            return env.new_byte_array(0).unwrap().into_raw();
+        }
+    };
 
     env.byte_array_from_slice(&value).unwrap().into_raw()
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadUInt64LE(
-    env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -1068,7 +1414,13 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    let value = buffer.read_big_uint64le_bytes(get_offset(offset));
+    let value = match buffer.read_big_uint64le_bytes(get_offset(offset)) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            return env.new_byte_array(0).unwrap().into_raw();
+        }
+    };
 
     env.byte_array_from_slice(&value).unwrap().into_raw()
 }
@@ -1076,7 +1428,7 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadInt64BE(
-    env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -1090,14 +1442,20 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    let value = buffer.read_big_int64be_bytes(get_offset(offset));
+    let value = match buffer.read_big_int64be_bytes(get_offset(offset)) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            return env.new_byte_array(0).unwrap().into_raw();
+        }
+    };
 
     env.byte_array_from_slice(&value).unwrap().into_raw()
 }
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeReadInt64LE(
-    env: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     buffer: jlong,
     offset: jlong,
@@ -1111,7 +1469,13 @@ pub extern "system" fn Java_org_nativescript_node_1compat_buffer_Buffer_nativeRe
 
     let mut buffer = unsafe { &mut *buffer };
 
-    let value = buffer.read_big_int64le_bytes(get_offset(offset));
+    let value = match buffer.read_big_int64le_bytes(get_offset(offset)) {
+        Ok(value) => value,
+        Err(error) => {
+            throw_error(&mut env, &error);
+            return env.new_byte_array(0).unwrap().into_raw();
+        }
+    };
 
     env.byte_array_from_slice(&value).unwrap().into_raw()
 }
\ No newline at end of file
@@ -164,17 +164,11 @@ pub extern "system" fn Java_org_nativescript_node_1compat_fs_FileSystem_nativeAp
         let jvm = JVM.get().unwrap();
         let _ = jvm.attach_current_thread().unwrap();
 
-        let bytes = buffer.buffer();
-
-        match node_fs::sync::append_file_with_bytes(fd, bytes) {
+        match buffer.with_slice(|bytes| node_fs::sync::append_file_with_bytes(fd, bytes)) {
             Ok(_) => {
-                // force drop of array to enable jni usage
-                drop(data);
                 callback.on_success(JObject::null().into())
             }
             Err(error) => {
-                // force drop of array to enable jni usage
-                drop(data);
                 callback.on_error(JValue::Object(
                     error_to_jstring(error).as_obj(),
                 ))
@@ -284,23 +278,17 @@ pub extern "system" fn Java_org_nativescript_node_1compat_fs_FileSystem_nativeAp
         let jvm = JVM.get().unwrap();
         let mut env = jvm.attach_current_thread().unwrap();
 
-        let data = buffer.buffer();
-
         let path = unsafe { JString::from_raw(path.as_raw()) };
-        match node_fs::sync::append_file_with_path_bytes(
+        match buffer.with_slice(|data| node_fs::sync::append_file_with_path_bytes(
             unsafe { get_str(&mut env, &path, "").as_ref() },
             data,
             mode,
             flags,
-        ) {
+        )) {
             Ok(_) => {
-                // force drop of array to enable jni usage
-                drop(data);
                 callback.on_success(JObject::null().into())
             }
             Err(error) => {
-                // force drop of array to enable jni usage
-                drop(data);
                 callback.on_error(jni::objects::JValue::Object(
                     error_to_jstring(error).as_obj(),
                 ))
@@ -900,14 +888,13 @@ pub extern "system" fn Java_org_nativescript_node_1compat_fs_FileSystem_nativeRe
     let _ = node_core::thread::spawn(move || {
         let jvm = JVM.get().unwrap();
         let mut env = jvm.attach_current_thread().unwrap();
-        let bytes = buffer.buffer_mut();
-        match node_fs::sync::read(
+        match buffer.with_slice_mut(|bytes| node_fs::sync::read(
             fd,
             bytes,
             offset.try_into().unwrap(),
             length.try_into().unwrap(),
             position.try_into().unwrap(),
-        ) {
+        )) {
             Ok(read) => callback.on_success(to_long(&mut env, read.try_into().unwrap()).into()),
             Err(error) => callback.on_error(JValue::Object(
                 error_to_jstring(error).as_obj(),
@@ -2034,7 +2021,7 @@ pub extern "system" fn Java_org_nativescript_node_1compat_fs_FileSystem_nativeWr
         let jvm = JVM.get().unwrap();
         let _ = jvm.attach_current_thread().unwrap();
 
-        match node_fs::sync::write_file_with_bytes_from_path(&path, data.buffer(), mode, flag) {
+        match data.with_slice(|data| node_fs::sync::write_file_with_bytes_from_path(&path, data, mode, flag)) {
             Ok(_) => callback.on_success(JObject::null().into()),
             Err(error) => callback.on_error(JValue::Object(
                 error_to_jstring(error).as_obj(),
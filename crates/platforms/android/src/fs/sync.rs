@@ -163,11 +163,13 @@ pub extern "system" fn Java_org_nativescript_node_1compat_fs_FileSystem_nativeCh
 
 #[no_mangle]
 pub extern "system" fn Java_org_nativescript_node_1compat_fs_FileSystem_nativeCloseSync(
-    _: JNIEnv,
+    mut env: JNIEnv,
     _: JClass,
     fd: jint,
 ) {
-    node_fs::sync::close_fd(fd);
+    if let Err(error) = node_fs::sync::close_fd(fd) {
+        let _ = env.throw(error.to_string());
+    }
 }
 
 #[no_mangle]
@@ -455,13 +457,13 @@ pub extern "system" fn Java_org_nativescript_node_1compat_fs_FileSystem_nativeRe
     position: jlong,
 ) -> jlong {
     let buffer = unsafe { &mut *(buffer as *mut Buffer) };
-    return match node_fs::sync::read(
+    return match buffer.with_slice_mut(|buffer| node_fs::sync::read(
         fd,
-        buffer.buffer_mut(),
+        buffer,
         offset.try_into().unwrap(),
         length.try_into().unwrap(),
         position.try_into().unwrap(),
-    ) {
+    )) {
         Ok(read) => (read as jlong).into(),
         Err(error) => {
             let _ = env.throw(error.to_string());
@@ -961,13 +963,13 @@ pub extern "system" fn Java_org_nativescript_node_1compat_fs_FileSystem_nativeWr
     position: jlong,
 ) -> jlong {
     let buffer = unsafe { &mut *(buffer as *mut Buffer) };
-    match node_fs::sync::write(
+    match buffer.with_slice(|buffer| node_fs::sync::write(
         fd,
-        buffer.buffer_mut(),
+        buffer,
         offset.try_into().unwrap(),
         length.try_into().unwrap(),
         position.try_into().unwrap(),
-    ) {
+    )) {
         Ok(wrote) => wrote as jlong,
         Err(error) => {
             let _ = env.throw(error.to_string());
@@ -1091,9 +1093,7 @@ pub extern "system" fn Java_org_nativescript_node_1compat_fs_FileSystem_nativeWr
 ) {
     let data = unsafe { &*(buffer as *mut Buffer) };
 
-    let bytes = data.buffer();
-
-    let result = node_fs::sync::write_file_with_bytes(fd, bytes);
+    let result = data.with_slice(|bytes| node_fs::sync::write_file_with_bytes(fd, bytes));
 
     if let Err(error) = result {
         let _ = env.throw(error.to_string());
@@ -38,11 +38,11 @@ pub fn run() {
         .size(26)
         .build();
 
-    let buf = buffer.buffer_mut();
-
-    for i in 0..26 as usize {
-        buf[i] = (i + 97) as u8;
-    }
+    buffer.with_slice_mut(|buf| {
+        for i in 0..26 as usize {
+            buf[i] = (i + 97) as u8;
+        }
+    });
 
 
     println!("{}", buffer.as_string(Some(StringEncoding::Utf8), None, None));
@@ -53,7 +53,7 @@ pub fn run() {
 
     let buffer = Buffer::from_slice(&[0x12, 0x34, 0x56, 0x78]);
 
-    println!("{:x}", buffer.read_uint32le(Some(0)));
+    println!("{:x}", buffer.read_uint32le(Some(0)).unwrap());
 // Prints: 78563412
     // println!("{:x}",buffer.read_uint32le(Some(1)).unwrap());
 // Throws ERR_OUT_OF_RANGE.
@@ -63,7 +63,7 @@ pub fn run() {
         .size(8)
         .build();
 
-    buffer.write_big_int64be(0x0102030405060708, Some(0));
+    buffer.write_big_int64be(0x0102030405060708, Some(0)).unwrap();
 
     println!("{:}", buffer);
 
@@ -72,7 +72,7 @@ pub fn run() {
         .size(8)
         .build();
 
-    buffer.write_big_int64le(0x0102030405060708, Some(0));
+    buffer.write_big_int64le(0x0102030405060708, Some(0)).unwrap();
 
     println!("{}", buffer);
 
@@ -81,7 +81,7 @@ pub fn run() {
         .size(8)
         .build();
 
-    buffer.write_big_uint64be(0xdecafafecacefade, Some(0));
+    buffer.write_big_uint64be(0xdecafafecacefade, Some(0)).unwrap();
 
     println!("{}", buffer);
 
@@ -90,7 +90,7 @@ pub fn run() {
         .size(8)
         .build();
 
-    buffer.write_big_uint64le(0xdecafafecacefade, Some(0));
+    buffer.write_big_uint64le(0xdecafafecacefade, Some(0)).unwrap();
 
     println!("{}", buffer);
 
@@ -99,10 +99,10 @@ pub fn run() {
         .size(4)
         .build();
 
-    buffer.write_uint8(0x3, Some(0));
-    buffer.write_uint8(0x4, Some(1));
-    buffer.write_uint8(0x23, Some(2));
-    buffer.write_uint8(0x42, Some(3));
+    buffer.write_uint8(0x3, Some(0)).unwrap();
+    buffer.write_uint8(0x4, Some(1)).unwrap();
+    buffer.write_uint8(0x23, Some(2)).unwrap();
+    buffer.write_uint8(0x42, Some(3)).unwrap();
 
     println!("{}", buffer);
 // Prints: <Buffer 03 04 23 42>
@@ -112,7 +112,7 @@ pub fn run() {
         .size(2)
         .build();
 
-    buffer.write_int16be(0x0102, None);
+    buffer.write_int16be(0x0102, None).unwrap();
 
     println!("{}", buffer);
 // Prints: <Buffer 01 02>
@@ -121,8 +121,8 @@ pub fn run() {
     let mut buffer = Buffer::builder()
         .size(4)
         .build();
-    buffer.write_uint16be(0xdead, Some(0));
-    buffer.write_uint16be(0xbeef, Some(2));
+    buffer.write_uint16be(0xdead, Some(0)).unwrap();
+    buffer.write_uint16be(0xbeef, Some(2)).unwrap();
 
     println!("{}", buffer);
 // Prints: <Buffer de ad be ef>
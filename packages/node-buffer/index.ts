@@ -132,75 +132,75 @@ export class Buffer {
     return this._native.toString(encoding ?? undefined, start ?? 0, end ?? -1);
   }
 
-  writeInt8(value: number, offset: number) {
+  writeInt8(value: number, offset: number): number {
     return this._native.writeInt8(value, offset);
   }
 
-  writeUInt8(value: number, offset: number) {
+  writeUInt8(value: number, offset: number): number {
     return this._native.writeUInt8(value, offset);
   }
 
-  writeInt16LE(value: number, offset: number) {
+  writeInt16LE(value: number, offset: number): number {
     return this._native.writeInt16LE(value, offset);
   }
 
-  writeInt16BE(value: number, offset: number) {
+  writeInt16BE(value: number, offset: number): number {
     return this._native.writeInt16BE(value, offset);
   }
 
-  writeUInt16LE(value: number, offset: number) {
+  writeUInt16LE(value: number, offset: number): number {
     return this._native.writeUInt16LE(value, offset);
   }
 
-  writeUInt16BE(value: number, offset: number) {
+  writeUInt16BE(value: number, offset: number): number {
     return this._native.writeUInt16BE(value, offset);
   }
 
-  writeInt32LE(value: number, offset: number) {
+  writeInt32LE(value: number, offset: number): number {
     return this._native.writeInt32LE(value, offset);
   }
 
-  writeInt32BE(value: number, offset: number) {
+  writeInt32BE(value: number, offset: number): number {
     return this._native.writeInt32BE(value, offset);
   }
 
-  writeUInt32LE(value: number, offset: number) {
+  writeUInt32LE(value: number, offset: number): number {
     return this._native.writeUInt32LE(value, offset);
   }
 
-  writeUInt32BE(value: number, offset: number) {
+  writeUInt32BE(value: number, offset: number): number {
     return this._native.writeUInt32BE(value, offset);
   }
 
-  writeFloatLE(value: number, offset: number) {
+  writeFloatLE(value: number, offset: number): number {
     return this._native.writeFloatLE(value, offset);
   }
 
-  writeFloatBE(value: number, offset: number) {
+  writeFloatBE(value: number, offset: number): number {
     return this._native.writeFloatBE(value, offset);
   }
 
-  writeDoubleLE(value: number, offset: number) {
+  writeDoubleLE(value: number, offset: number): number {
     return this._native.writeDoubleLE(value, offset);
   }
 
-  writeDoubleBE(value: number, offset: number) {
+  writeDoubleBE(value: number, offset: number): number {
     return this._native.writeDoubleBE(value, offset);
   }
 
-  writeBigInt64LE(value: bigint, offset: number) {
+  writeBigInt64LE(value: bigint, offset: number): number {
     return this._native.writeBigInt64LE(value, offset);
   }
 
-  writeBigInt64BE(value: bigint, offset: number) {
+  writeBigInt64BE(value: bigint, offset: number): number {
     return this._native.writeBigInt64BE(value, offset);
   }
 
-  writeBigUInt64LE(value: bigint, offset: number) {
+  writeBigUInt64LE(value: bigint, offset: number): number {
     return this._native.writeBigUInt64LE(value, offset);
   }
 
-  writeBigUInt64BE(value: bigint, offset: number) {
+  writeBigUInt64BE(value: bigint, offset: number): number {
     return this._native.writeBigUInt64BE(value, offset);
   }
 
@@ -21,41 +21,41 @@ declare class NSCBuffer {
 
   toString(encoding?: string, start?: number, end?: number): string;
 
-  writeInt8(value: number, offset: number);
+  writeInt8(value: number, offset: number): number;
 
-  writeUInt8(value: number, offset: number);
+  writeUInt8(value: number, offset: number): number;
 
-  writeInt16LE(value: number, offset: number);
+  writeInt16LE(value: number, offset: number): number;
 
-  writeInt16BE(value: number, offset: number);
+  writeInt16BE(value: number, offset: number): number;
 
-  writeUInt16LE(value: number, offset: number);
+  writeUInt16LE(value: number, offset: number): number;
 
-  writeUInt16BE(value: number, offset: number);
+  writeUInt16BE(value: number, offset: number): number;
 
-  writeInt32LE(value: number, offset: number);
+  writeInt32LE(value: number, offset: number): number;
 
-  writeInt32BE(value: number, offset: number);
+  writeInt32BE(value: number, offset: number): number;
 
-  writeUInt32LE(value: number, offset: number);
+  writeUInt32LE(value: number, offset: number): number;
 
-  writeUInt32BE(value: number, offset: number);
+  writeUInt32BE(value: number, offset: number): number;
 
-  writeFloatLE(value: number, offset: number);
+  writeFloatLE(value: number, offset: number): number;
 
-  writeFloatBE(value: number, offset: number);
+  writeFloatBE(value: number, offset: number): number;
 
-  writeDoubleLE(value: number, offset: number);
+  writeDoubleLE(value: number, offset: number): number;
 
-  writeDoubleBE(value: number, offset: number);
+  writeDoubleBE(value: number, offset: number): number;
 
-  writeBigInt64LE(value: bigint, offset: number);
+  writeBigInt64LE(value: bigint, offset: number): number;
 
-  writeBigInt64BE(value: bigint, offset: number);
+  writeBigInt64BE(value: bigint, offset: number): number;
 
-  writeBigUInt64LE(value: bigint, offset: number);
+  writeBigUInt64LE(value: bigint, offset: number): number;
 
-  writeBigUInt64BE(value: bigInt, offset: number);
+  writeBigUInt64BE(value: bigint, offset: number): number;
 
   readInt8(offset: number): number;
 
@@ -0,0 +1,7 @@
+package org.nativescript.node_compat
+
+/**
+ * Thrown by the native bindings for errors Node would throw. [code] is Node's `code`, e.g.
+ * `ERR_OUT_OF_RANGE`, and [errorClass] the JS error class, e.g. `RangeError`.
+ */
+class NodeException(message: String, val code: String?, val errorClass: String) : RuntimeException(message)
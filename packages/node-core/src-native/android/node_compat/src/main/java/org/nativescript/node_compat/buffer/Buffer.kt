@@ -24,6 +24,11 @@ class Buffer private constructor(var native: Long) {
     nativeFillString(native, value, encoding.value)
   }
 
+  @JvmOverloads
+  fun write(value: String, offset: Long = -1, length: Long = -1, encoding: Encoding = Encoding.Utf8): Long {
+    return nativeWriteString(native, value, offset, length, encoding.value)
+  }
+
   fun buffer(): ByteBuffer? {
     if (buffer == null) {
       buffer = nativeBuffer(native)
@@ -35,100 +40,110 @@ class Buffer private constructor(var native: Long) {
     return nativeLength(native)
   }
 
+  @JvmOverloads
+  fun subarray(start: Long = 0, end: Long = Long.MAX_VALUE): Buffer {
+    return Buffer(nativeSubarray(native, start, end))
+  }
+
+  @JvmOverloads
+  fun slice(start: Long = 0, end: Long = Long.MAX_VALUE): Buffer {
+    return subarray(start, end)
+  }
+
   @JvmOverloads
   fun writeInt8(
     value: Byte,
     offset: Long = -1
-  ) {
-    nativeWriteInt8(native, value, offset)
+  ): Long {
+    return nativeWriteInt8(native, value, offset)
   }
 
   @JvmOverloads
   fun writeUInt8(
     value: Byte,
     offset: Long = -1
-  ) {
-    nativeWriteUInt8(native, value, offset)
+  ): Long {
+    return nativeWriteUInt8(native, value, offset)
   }
 
   @JvmOverloads
   fun writeUInt16BE(
     value: Short,
     offset: Long = -1,
-  ) {
-    nativeWriteUInt16BE(native, value, offset)
+  ): Long {
+    return nativeWriteUInt16BE(native, value, offset)
   }
 
   @JvmOverloads
   fun writeUInt16LE(
     value: Short,
     offset: Long = -1,
-  ) {
-    nativeWriteUInt16LE(native, value, offset)
+  ): Long {
+    return nativeWriteUInt16LE(native, value, offset)
   }
 
   @JvmOverloads
   fun writeInt16BE(
     value: Short,
     offset: Long = -1,
-  ) {
-    nativeWriteInt16BE(native, value, offset)
+  ): Long {
+    return nativeWriteInt16BE(native, value, offset)
   }
 
   @JvmOverloads
   fun writeInt16LE(
     value: Short,
     offset: Long = -1,
-  ) {
-    nativeWriteInt16LE(native, value, offset)
+  ): Long {
+    return nativeWriteInt16LE(native, value, offset)
   }
 
   @JvmOverloads
   fun writeUInt32BE(
     value: Int,
     offset: Long = -1,
-  ) {
-    nativeWriteUInt32BE(native, value, offset)
+  ): Long {
+    return nativeWriteUInt32BE(native, value, offset)
   }
 
   @JvmOverloads
   fun writeUInt32LE(
     value: Int,
     offset: Long = -1,
-  ) {
-    nativeWriteUInt32LE(native, value, offset)
+  ): Long {
+    return nativeWriteUInt32LE(native, value, offset)
   }
 
   @JvmOverloads
   fun writeInt32BE(
     value: Int,
     offset: Long = -1,
-  ) {
-    nativeWriteInt32BE(native, value, offset)
+  ): Long {
+    return nativeWriteInt32BE(native, value, offset)
   }
 
   @JvmOverloads
   fun writeInt32LE(
     value: Int,
     offset: Long = -1,
-  ) {
-    nativeWriteInt32LE(native, value, offset)
+  ): Long {
+    return nativeWriteInt32LE(native, value, offset)
   }
 
   @JvmOverloads
   fun writeFloatBE(
     value: Float,
     offset: Long = -1,
-  ) {
-    nativeWriteFloatBE(native, value, offset)
+  ): Long {
+    return nativeWriteFloatBE(native, value, offset)
   }
 
   @JvmOverloads
   fun writeFloatLE(
     value: Float,
     offset: Long = -1,
-  ) {
-    nativeWriteFloatLE(native, value, offset)
+  ): Long {
+    return nativeWriteFloatLE(native, value, offset)
   }
 
 
@@ -136,48 +151,48 @@ class Buffer private constructor(var native: Long) {
   fun writeDoubleBE(
     value: Double,
     offset: Long = -1,
-  ) {
-    nativeWriteDoubleBE(native, value, offset)
+  ): Long {
+    return nativeWriteDoubleBE(native, value, offset)
   }
 
   @JvmOverloads
   fun writeDoubleLE(
     value: Double,
     offset: Long = -1,
-  ) {
-    nativeWriteDoubleLE(native, value, offset)
+  ): Long {
+    return nativeWriteDoubleLE(native, value, offset)
   }
 
   @JvmOverloads
   fun writeBigUInt64BE(
     value: BigInteger,
     offset: Long = -1,
-  ) {
-    nativeWriteUInt64BE(native, value.toByteArray(), offset)
+  ): Long {
+    return nativeWriteUInt64BE(native, value.toByteArray(), offset)
   }
 
   @JvmOverloads
   fun writeBigUInt64LE(
     value: BigInteger,
     offset: Long = -1,
-  ) {
-    nativeWriteUInt64LE(native, value.toByteArray(), offset)
+  ): Long {
+    return nativeWriteUInt64LE(native, value.toByteArray(), offset)
   }
 
   @JvmOverloads
   fun writeBigInt64BE(
     value: BigInteger,
     offset: Long = -1,
-  ) {
-    nativeWriteInt64BE(native, value.toByteArray(), offset)
+  ): Long {
+    return nativeWriteInt64BE(native, value.toByteArray(), offset)
   }
 
   @JvmOverloads
   fun writeBigInt64LE(
     value: BigInteger,
     offset: Long = -1,
-  ) {
-    nativeWriteInt64LE(native, value.toByteArray(), offset)
+  ): Long {
+    return nativeWriteInt64LE(native, value.toByteArray(), offset)
   }
 
   fun readInt8(
@@ -337,7 +352,13 @@ class Buffer private constructor(var native: Long) {
 
     @JvmStatic
     fun from(value: Buffer): Buffer {
-      return Buffer(nativeFromBuffer(value.native))
+      return Buffer(nativeFromBuffer(value.native, -1, -1))
+    }
+
+    @JvmOverloads
+    @JvmStatic
+    fun from(value: Buffer, byteOffset: Long, length: Long = -1): Buffer {
+      return Buffer(nativeFromBuffer(value.native, byteOffset, length))
     }
 
     @JvmStatic
@@ -361,6 +382,12 @@ class Buffer private constructor(var native: Long) {
       return Buffer(nativeConcat(value, length))
     }
 
+    @JvmOverloads
+    @JvmStatic
+    fun byteLength(value: String, encoding: Encoding = Encoding.Utf8): Long {
+      return nativeByteLength(value, encoding.value)
+    }
+
     @JvmStatic
     fun atob(text: String): String {
       return nativeAtob(text)
@@ -381,7 +408,10 @@ class Buffer private constructor(var native: Long) {
 
 
     @JvmStatic
-    external fun nativeFromBuffer(buffer: Long): Long
+    external fun nativeFromBuffer(buffer: Long, offset: Long, length: Long): Long
+
+    @JvmStatic
+    external fun nativeSubarray(buffer: Long, start: Long, end: Long): Long
 
     @JvmStatic
     external fun nativeFromString(text: String, encoding: Int): Long
@@ -407,6 +437,18 @@ class Buffer private constructor(var native: Long) {
     @JvmStatic
     external fun nativeFillString(buffer: Long, value: String, encoding: Int)
 
+    @JvmStatic
+    external fun nativeWriteString(
+      buffer: Long,
+      value: String,
+      offset: Long,
+      length: Long,
+      encoding: Int
+    ): Long
+
+    @JvmStatic
+    external fun nativeByteLength(value: String, encoding: Int): Long
+
     @JvmStatic
     external fun nativeAtob(text: String): String
 
@@ -431,14 +473,14 @@ class Buffer private constructor(var native: Long) {
       buffer: Long,
       value: Byte,
       offset: Long
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteUInt8(
       buffer: Long,
       value: Byte,
       offset: Long
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteUInt16BE(
@@ -446,21 +488,21 @@ class Buffer private constructor(var native: Long) {
       buffer: Long,
       value: Short,
       offset: Long,
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteUInt16LE(
       buffer: Long,
       value: Short,
       offset: Long,
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteInt16BE(
       buffer: Long,
       value: Short,
       offset: Long,
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteInt16LE(
@@ -468,35 +510,35 @@ class Buffer private constructor(var native: Long) {
       buffer: Long,
       value: Short,
       offset: Long,
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteUInt32BE(
       buffer: Long,
       value: Int,
       offset: Long,
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteUInt32LE(
       buffer: Long,
       value: Int,
       offset: Long,
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteInt32BE(
       buffer: Long,
       value: Int,
       offset: Long,
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteInt32LE(
       buffer: Long,
       value: Int,
       offset: Long,
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteFloatBE(
@@ -504,7 +546,7 @@ class Buffer private constructor(var native: Long) {
       buffer: Long,
       value: Float,
       offset: Long,
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteFloatLE(
@@ -512,7 +554,7 @@ class Buffer private constructor(var native: Long) {
       buffer: Long,
       value: Float,
       offset: Long,
-    )
+    ): Long
 
 
     @JvmStatic
@@ -521,7 +563,7 @@ class Buffer private constructor(var native: Long) {
       buffer: Long,
       value: Double,
       offset: Long,
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteDoubleLE(
@@ -529,7 +571,7 @@ class Buffer private constructor(var native: Long) {
       buffer: Long,
       value: Double,
       offset: Long,
-    )
+    ): Long
 
 
     @JvmStatic
@@ -537,28 +579,28 @@ class Buffer private constructor(var native: Long) {
       buffer: Long,
       value: ByteArray,
       offset: Long,
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteUInt64LE(
       buffer: Long,
       value: ByteArray,
       offset: Long,
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteInt64BE(
       buffer: Long,
       value: ByteArray,
       offset: Long,
-    )
+    ): Long
 
     @JvmStatic
     external fun nativeWriteInt64LE(
       buffer: Long,
       value: ByteArray,
       offset: Long,
-    )
+    ): Long
 
 
     @JvmStatic